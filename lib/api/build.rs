@@ -245,6 +245,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CountPoints.collection_name", "length(min = 1, max = 255)"),
             ("CountPoints.filter", ""),
+            ("CountPoints.group_by", "length(min = 1)"),
+            ("CountPoints.distinct", "length(min = 1)"),
+            ("CountPoints.group_by_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("GeoPolygon.exterior", "custom = \"crate::grpc::validate::validate_geo_polygon_exterior\""),
             ("GeoPolygon.interiors", "custom = \"crate::grpc::validate::validate_geo_polygon_interiors\""),
             ("Filter.should", ""),
@@ -267,6 +270,27 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPoints.query_points", ""),
             ("QueryBatchPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPointGroups.collection_name", "length(min = 1, max = 255)"),
+            ("QueryPointGroups.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPointGroups.group_size", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("QueryPointGroups.group_by", "length(min = 1)"),
+            ("QueryPointGroups.filter", ""),
+            ("QueryPointGroups.params", ""),
+            ("QueryPointGroups.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("FacetCounts.collection_name", "length(min = 1, max = 255)"),
+            ("FacetCounts.key", "length(min = 1)"),
+            ("FacetCounts.filter", ""),
+            ("FacetCounts.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("SearchMatrixPoints.collection_name", "length(min = 1, max = 255)"),
+            ("SearchMatrixPoints.filter", ""),
+            ("SearchMatrixPoints.sample", "custom = \"crate::grpc::validate::validate_u64_range_min_2_max_1000\""),
+            ("SearchMatrixPoints.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1_max_1000\""),
+            ("SearchMatrixPoints.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("AggregatePoints.collection_name", "length(min = 1, max = 255)"),
+            ("AggregatePoints.key", "length(min = 1)"),
+            ("AggregatePoints.filter", ""),
+            ("AggregatePoints.date_histogram", ""),
+            ("DateHistogramParams.facet_limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
         ], &[])
         .type_attribute(".", "#[derive(serde::Serialize)]")
         // Service: points_internal_service.proto
@@ -291,6 +315,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CoreSearchBatchPointsInternal.search_points", ""),
             ("RecoQuery.positives", ""),
             ("RecoQuery.negatives", ""),
+            ("WeightedRecoQuery.positives", ""),
+            ("WeightedRecoQuery.negatives", ""),
             ("ContextPair.positive", ""),
             ("ContextPair.negative", ""),
             ("DiscoveryQuery.target", ""),
@@ -300,6 +326,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
             ("CountPointsInternal.count_points", ""),
+            ("FacetCountsInternal.facet_counts", ""),
+            ("AggregatePointsInternal.collection_name", "length(min = 1, max = 255)"),
+            ("AggregatePointsInternal.filter", ""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
             ("QueryBatchPointsInternal.collection_name", "length(min = 1, max = 255)"),
@@ -4,9 +4,10 @@ use std::time::Instant;
 
 use chrono::{NaiveDateTime, Timelike};
 use itertools::Itertools;
+use segment::data_types::formula as segment_formula;
 use segment::data_types::index::{
-    BoolIndexType, DatetimeIndexType, FloatIndexType, GeoIndexType, IntegerIndexType,
-    KeywordIndexType, TextIndexType,
+    BoolIndexType, DatetimeIndexType, FloatIndexType, GeoIndexType, GeoShapeIndexType,
+    IntegerIndexType, KeywordIndexType, TextIndexType,
 };
 use segment::data_types::vectors as segment_vectors;
 use segment::json_path::JsonPath;
@@ -15,14 +16,24 @@ use segment::vector_storage::query as segment_query;
 use sparse::common::sparse_vector::validate_sparse_vector_impl;
 use tonic::Status;
 use uuid::Uuid;
+use validator::Validate as _;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, BoolIndexParams, CompressionRatio,
-    DatetimeIndexParams, DatetimeRange, Direction, FieldType, FloatIndexParams, GeoIndexParams,
-    GeoLineString, GroupId, KeywordIndexParams, LookupLocation, MultiVectorComparator,
-    MultiVectorConfig, OrderBy, OrderValue, Range, RawVector, RecommendStrategy, SearchPointGroups,
-    SearchPoints, ShardKeySelector, SparseIndices, StartFrom, WithLookup,
+    decay_origin, expression, DecayOrigin, DecayParams, DivExpression, Expression, Formula,
+    GeoDistance, MultExpression, PowExpression, SumExpression,
+};
+use super::qdrant::{
+    geo_shape, raw_query, start_from, then_by, BinaryQuantization, BoolIndexParams,
+    CalendarInterval, CardinalityEstimate, CardinalitySketch, CompoundGroupId, CompressionRatio,
+    DateHistogramBucketing, DateHistogramCounts, DatetimeIndexParams, DatetimeRange, Direction,
+    FacetHit, FacetValue, FieldType, FloatIndexParams, GeoIndexParams, GeoLineString, GeoShape,
+    GeoShapeCondition, GeoShapeIndexParams, GeoShapeRelation, GroupId, GroupOrderBy, GroupScoring,
+    HistogramBucketing, KeywordIndexParams, LookupLocation, MultiVectorComparator,
+    MultiVectorConfig, NumericAggregation, OrderBy, OrderValue, OrderValues, ProfileStage,
+    QueryProfile, Range, RawVector, RecommendStrategy, Sample, ScoreNormalization,
+    SearchPointGroups, SearchPoints, SearchStrategy, SegmentExplanation, ShardExplanation,
+    ShardKeySelector, SparseIndices, StartFrom, ThenBy, WithLookup,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
@@ -34,10 +45,10 @@ use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
-    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
+    Condition, DenseVector, Distance, Document, FieldCondition, Filter, GeoBoundingBox, GeoPoint,
+    GeoPolygon, GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, Image,
+    IntegerIndexParams, IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue,
+    Match, MinShould, MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
     PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
     PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
     QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
@@ -222,9 +233,11 @@ impl From<segment::data_types::index::TokenizerType> for TokenizerType {
 }
 
 impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::KeywordIndexParams) -> Self {
+    fn from(params: segment::data_types::index::KeywordIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {})),
+            index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
+                is_tenant: params.is_tenant,
+            })),
         }
     }
 }
@@ -235,15 +248,18 @@ impl From<segment::data_types::index::IntegerIndexParams> for PayloadIndexParams
             index_params: Some(IndexParams::IntegerIndexParams(IntegerIndexParams {
                 lookup: params.lookup,
                 range: params.range,
+                is_principal: params.is_principal,
             })),
         }
     }
 }
 
 impl From<segment::data_types::index::FloatIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::FloatIndexParams) -> Self {
+    fn from(params: segment::data_types::index::FloatIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {})),
+            index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {
+                is_principal: params.is_principal,
+            })),
         }
     }
 }
@@ -279,9 +295,19 @@ impl From<segment::data_types::index::BoolIndexParams> for PayloadIndexParams {
 }
 
 impl From<segment::data_types::index::DatetimeIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::DatetimeIndexParams) -> Self {
+    fn from(params: segment::data_types::index::DatetimeIndexParams) -> Self {
+        PayloadIndexParams {
+            index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {
+                is_principal: params.is_principal,
+            })),
+        }
+    }
+}
+
+impl From<segment::data_types::index::GeoShapeIndexParams> for PayloadIndexParams {
+    fn from(_params: segment::data_types::index::GeoShapeIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {})),
+            index_params: Some(IndexParams::GeoShapeIndexParams(GeoShapeIndexParams {})),
         }
     }
 }
@@ -306,6 +332,7 @@ impl From<segment::types::PayloadSchemaType> for PayloadSchemaType {
             segment::types::PayloadSchemaType::Text => PayloadSchemaType::Text,
             segment::types::PayloadSchemaType::Bool => PayloadSchemaType::Bool,
             segment::types::PayloadSchemaType::Datetime => PayloadSchemaType::Datetime,
+            segment::types::PayloadSchemaType::GeoShape => PayloadSchemaType::GeoShape,
         }
     }
 }
@@ -320,6 +347,7 @@ impl From<segment::types::PayloadSchemaType> for FieldType {
             segment::types::PayloadSchemaType::Text => FieldType::Text,
             segment::types::PayloadSchemaType::Bool => FieldType::Bool,
             segment::types::PayloadSchemaType::Datetime => FieldType::Datetime,
+            segment::types::PayloadSchemaType::GeoShape => FieldType::GeoShape,
         }
     }
 }
@@ -349,15 +377,17 @@ impl From<segment::types::PayloadSchemaParams> for PayloadIndexParams {
             segment::types::PayloadSchemaParams::Text(p) => p.into(),
             segment::types::PayloadSchemaParams::Bool(p) => p.into(),
             segment::types::PayloadSchemaParams::Datetime(p) => p.into(),
+            segment::types::PayloadSchemaParams::GeoShape(p) => p.into(),
         }
     }
 }
 
 impl TryFrom<KeywordIndexParams> for segment::data_types::index::KeywordIndexParams {
     type Error = Status;
-    fn try_from(_params: KeywordIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: KeywordIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::KeywordIndexParams {
             r#type: KeywordIndexType::Keyword,
+            is_tenant: params.is_tenant,
         })
     }
 }
@@ -369,15 +399,17 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::index::IntegerIndexPar
             r#type: IntegerIndexType::Integer,
             lookup: params.lookup,
             range: params.range,
+            is_principal: params.is_principal,
         })
     }
 }
 
 impl TryFrom<FloatIndexParams> for segment::data_types::index::FloatIndexParams {
     type Error = Status;
-    fn try_from(_params: FloatIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: FloatIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::FloatIndexParams {
             r#type: FloatIndexType::Float,
+            is_principal: params.is_principal,
         })
     }
 }
@@ -417,9 +449,19 @@ impl TryFrom<BoolIndexParams> for segment::data_types::index::BoolIndexParams {
 
 impl TryFrom<DatetimeIndexParams> for segment::data_types::index::DatetimeIndexParams {
     type Error = Status;
-    fn try_from(_params: DatetimeIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: DatetimeIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::DatetimeIndexParams {
             r#type: DatetimeIndexType::Datetime,
+            is_principal: params.is_principal,
+        })
+    }
+}
+
+impl TryFrom<GeoShapeIndexParams> for segment::data_types::index::GeoShapeIndexParams {
+    type Error = Status;
+    fn try_from(_params: GeoShapeIndexParams) -> Result<Self, Self::Error> {
+        Ok(segment::data_types::index::GeoShapeIndexParams {
+            r#type: GeoShapeIndexType::GeoShape,
         })
     }
 }
@@ -450,6 +492,9 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
             IndexParams::DatetimeIndexParams(p) => {
                 segment::types::PayloadSchemaParams::Datetime(p.try_into()?)
             }
+            IndexParams::GeoShapeIndexParams(p) => {
+                segment::types::PayloadSchemaParams::GeoShape(p.try_into()?)
+            }
         })
     }
 }
@@ -472,6 +517,7 @@ impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
                 PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
                 PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
                 PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
+                PayloadSchemaType::GeoShape => segment::types::PayloadSchemaType::GeoShape,
                 PayloadSchemaType::UnknownType => {
                     return Err(Status::invalid_argument(
                         "Malformed payload schema".to_string(),
@@ -629,6 +675,8 @@ impl From<segment_vectors::Vector> for Vector {
                 data: vector,
                 indices: None,
                 vectors_count: None,
+                document: None,
+                image: None,
             },
             segment_vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
@@ -636,6 +684,8 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.indices,
                 }),
                 vectors_count: None,
+                document: None,
+                image: None,
             },
             segment_vectors::Vector::MultiDense(vector) => {
                 let vector_count = vector.multi_vectors().count() as u32;
@@ -643,6 +693,8 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.flattened_vectors,
                     indices: None,
                     vectors_count: Some(vector_count),
+                    document: None,
+                    image: None,
                 }
             }
         }
@@ -653,6 +705,12 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     type Error = Status;
 
     fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        if vector.document.is_some() || vector.image.is_some() {
+            return Err(Status::invalid_argument(
+                "Documents and images are only accepted in point upserts and vector updates",
+            ));
+        }
+
         // sparse vector
         if let Some(indices) = vector.indices {
             return Ok(segment_vectors::Vector::Sparse(
@@ -730,6 +788,10 @@ impl From<segment::data_types::order_by::OrderValue> for OrderValue {
         let variant = match value {
             segment::OrderValue::Float(value) => Variant::Float(value),
             segment::OrderValue::Int(value) => Variant::Int(value),
+            segment::OrderValue::Keyword(value) => Variant::Keyword(value),
+            segment::OrderValue::Compound(values) => Variant::Compound(OrderValues {
+                values: values.into_iter().map(OrderValue::from).collect(),
+            }),
         };
 
         Self {
@@ -753,12 +815,814 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
         let value = match variant {
             Variant::Float(value) => segment::OrderValue::Float(value),
             Variant::Int(value) => segment::OrderValue::Int(value),
+            Variant::Keyword(value) => segment::OrderValue::Keyword(value),
+            Variant::Compound(OrderValues { values }) => segment::OrderValue::Compound(
+                values
+                    .into_iter()
+                    .map(segment::OrderValue::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
         };
 
         Ok(value)
     }
 }
 
+impl From<segment::data_types::facets::FacetValue> for FacetValue {
+    fn from(value: segment::data_types::facets::FacetValue) -> Self {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = match value {
+            segment::FacetValue::Keyword(value) => Variant::StringValue(value),
+            segment::FacetValue::Int(value) => Variant::IntegerValue(value),
+            segment::FacetValue::Bool(value) => Variant::BoolValue(value),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<FacetValue> for segment::data_types::facets::FacetValue {
+    type Error = Status;
+
+    fn try_from(value: FacetValue) -> Result<Self, Self::Error> {
+        use segment::data_types::facets as segment;
+
+        use crate::grpc::qdrant::facet_value::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("FacetValue should have a variant"))?;
+
+        let value = match variant {
+            Variant::StringValue(value) => segment::FacetValue::Keyword(value),
+            Variant::IntegerValue(value) => segment::FacetValue::Int(value),
+            Variant::BoolValue(value) => segment::FacetValue::Bool(value),
+        };
+
+        Ok(value)
+    }
+}
+
+impl From<segment::data_types::facets::FacetValueHit> for FacetHit {
+    fn from(hit: segment::data_types::facets::FacetValueHit) -> Self {
+        Self {
+            value: Some(hit.value.into()),
+            count: hit.count as u64,
+        }
+    }
+}
+
+impl TryFrom<FacetHit> for segment::data_types::facets::FacetValueHit {
+    type Error = Status;
+
+    fn try_from(hit: FacetHit) -> Result<Self, Self::Error> {
+        let value = hit
+            .value
+            .ok_or_else(|| Status::invalid_argument("FacetHit should have a value"))?;
+
+        Ok(Self {
+            value: value.try_into()?,
+            count: hit.count as usize,
+        })
+    }
+}
+
+impl From<segment::data_types::distinct::CardinalitySketch> for CardinalitySketch {
+    fn from(sketch: segment::data_types::distinct::CardinalitySketch) -> Self {
+        match sketch {
+            segment::data_types::distinct::CardinalitySketch::Exact(hashes) => Self {
+                hashes: hashes.into_iter().collect(),
+                registers: Vec::new(),
+            },
+            segment::data_types::distinct::CardinalitySketch::HyperLogLog(registers) => Self {
+                hashes: Vec::new(),
+                registers,
+            },
+        }
+    }
+}
+
+impl TryFrom<CardinalitySketch> for segment::data_types::distinct::CardinalitySketch {
+    type Error = Status;
+
+    fn try_from(sketch: CardinalitySketch) -> Result<Self, Self::Error> {
+        let CardinalitySketch { hashes, registers } = sketch;
+
+        if registers.is_empty() {
+            return Ok(Self::Exact(hashes.into_iter().collect()));
+        }
+
+        if registers.len() != Self::REGISTERS {
+            return Err(Status::invalid_argument(format!(
+                "CardinalitySketch should have {} registers, got {}",
+                Self::REGISTERS,
+                registers.len(),
+            )));
+        }
+
+        Ok(Self::HyperLogLog(registers))
+    }
+}
+
+impl From<segment::data_types::aggregations::HistogramBucketing> for HistogramBucketing {
+    fn from(value: segment::data_types::aggregations::HistogramBucketing) -> Self {
+        let segment::data_types::aggregations::HistogramBucketing {
+            interval,
+            offset,
+            buckets,
+        } = value;
+
+        Self {
+            interval,
+            offset,
+            buckets: buckets.map(|buckets| buckets as u64),
+        }
+    }
+}
+
+impl From<HistogramBucketing> for segment::data_types::aggregations::HistogramBucketing {
+    fn from(value: HistogramBucketing) -> Self {
+        let HistogramBucketing {
+            interval,
+            offset,
+            buckets,
+        } = value;
+
+        Self {
+            interval,
+            offset,
+            buckets: buckets.map(|buckets| buckets as usize),
+        }
+    }
+}
+
+impl From<segment::data_types::aggregations::CalendarInterval> for CalendarInterval {
+    fn from(value: segment::data_types::aggregations::CalendarInterval) -> Self {
+        use segment::data_types::aggregations::CalendarInterval as Interval;
+        match value {
+            Interval::Minute => CalendarInterval::Minute,
+            Interval::Hour => CalendarInterval::Hour,
+            Interval::Day => CalendarInterval::Day,
+            Interval::Week => CalendarInterval::Week,
+            Interval::Month => CalendarInterval::Month,
+            Interval::Quarter => CalendarInterval::Quarter,
+            Interval::Year => CalendarInterval::Year,
+        }
+    }
+}
+
+impl From<CalendarInterval> for segment::data_types::aggregations::CalendarInterval {
+    fn from(value: CalendarInterval) -> Self {
+        match value {
+            CalendarInterval::Minute => Self::Minute,
+            CalendarInterval::Hour => Self::Hour,
+            CalendarInterval::Day => Self::Day,
+            CalendarInterval::Week => Self::Week,
+            CalendarInterval::Month => Self::Month,
+            CalendarInterval::Quarter => Self::Quarter,
+            CalendarInterval::Year => Self::Year,
+        }
+    }
+}
+
+impl From<segment::data_types::aggregations::DateHistogramBucketing> for DateHistogramBucketing {
+    fn from(value: segment::data_types::aggregations::DateHistogramBucketing) -> Self {
+        let segment::data_types::aggregations::DateHistogramBucketing {
+            interval,
+            time_zone,
+            facet,
+        } = value;
+
+        Self {
+            interval: CalendarInterval::from(interval) as i32,
+            time_zone: time_zone.name().to_string(),
+            facet_key: facet.map(|key| key.to_string()),
+        }
+    }
+}
+
+impl TryFrom<DateHistogramBucketing> for segment::data_types::aggregations::DateHistogramBucketing {
+    type Error = Status;
+
+    fn try_from(value: DateHistogramBucketing) -> Result<Self, Self::Error> {
+        let DateHistogramBucketing {
+            interval,
+            time_zone,
+            facet_key,
+        } = value;
+
+        let interval = CalendarInterval::from_i32(interval)
+            .ok_or_else(|| Status::invalid_argument("Unknown CalendarInterval"))?;
+        let time_zone = time_zone
+            .parse::<chrono_tz::Tz>()
+            .map_err(|err| Status::invalid_argument(format!("Invalid time zone: {err}")))?;
+
+        Ok(Self {
+            interval: interval.into(),
+            time_zone,
+            facet: facet_key
+                .map(|key| json_path_from_proto(&key))
+                .transpose()?,
+        })
+    }
+}
+
+impl From<segment::data_types::aggregations::NumericAggregation> for NumericAggregation {
+    fn from(value: segment::data_types::aggregations::NumericAggregation) -> Self {
+        let segment::data_types::aggregations::NumericAggregation {
+            count,
+            min,
+            max,
+            sum,
+            histogram,
+            date_histogram,
+        } = value;
+
+        Self {
+            count: count as u64,
+            min,
+            max,
+            sum,
+            histogram: histogram
+                .into_iter()
+                .map(|(bucket, count)| (bucket, count as u64))
+                .collect(),
+            date_histogram: date_histogram
+                .into_iter()
+                .map(|(bucket, counts)| DateHistogramCounts {
+                    bucket,
+                    count: counts.count as u64,
+                    facet: counts
+                        .facet
+                        .into_iter()
+                        .map(|(value, count)| FacetHit {
+                            value: Some(value.into()),
+                            count: count as u64,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<NumericAggregation> for segment::data_types::aggregations::NumericAggregation {
+    type Error = Status;
+
+    fn try_from(value: NumericAggregation) -> Result<Self, Self::Error> {
+        let NumericAggregation {
+            count,
+            min,
+            max,
+            sum,
+            histogram,
+            date_histogram,
+        } = value;
+
+        let date_histogram = date_histogram
+            .into_iter()
+            .map(|counts| {
+                let DateHistogramCounts {
+                    bucket,
+                    count,
+                    facet,
+                } = counts;
+                let facet = facet
+                    .into_iter()
+                    .map(|hit| {
+                        let hit = segment::data_types::facets::FacetValueHit::try_from(hit)?;
+                        Ok((hit.value, hit.count))
+                    })
+                    .collect::<Result<_, Status>>()?;
+                let counts = segment::data_types::aggregations::DateHistogramCounts {
+                    count: count as usize,
+                    facet,
+                };
+                Ok((bucket, counts))
+            })
+            .collect::<Result<_, Status>>()?;
+
+        Ok(Self {
+            count: count as usize,
+            min,
+            max,
+            sum,
+            histogram: histogram
+                .into_iter()
+                .map(|(bucket, count)| (bucket, count as usize))
+                .collect(),
+            date_histogram,
+        })
+    }
+}
+
+impl From<segment::data_types::explain::SearchStrategy> for SearchStrategy {
+    fn from(value: segment::data_types::explain::SearchStrategy) -> Self {
+        match value {
+            segment::data_types::explain::SearchStrategy::FullScan => SearchStrategy::FullScan,
+            segment::data_types::explain::SearchStrategy::PayloadIndex => {
+                SearchStrategy::PayloadIndex
+            }
+            segment::data_types::explain::SearchStrategy::Graph => SearchStrategy::Graph,
+            segment::data_types::explain::SearchStrategy::InvertedIndex => {
+                SearchStrategy::InvertedIndex
+            }
+        }
+    }
+}
+
+impl From<SearchStrategy> for segment::data_types::explain::SearchStrategy {
+    fn from(value: SearchStrategy) -> Self {
+        match value {
+            SearchStrategy::FullScan => segment::data_types::explain::SearchStrategy::FullScan,
+            SearchStrategy::PayloadIndex => {
+                segment::data_types::explain::SearchStrategy::PayloadIndex
+            }
+            SearchStrategy::Graph => segment::data_types::explain::SearchStrategy::Graph,
+            SearchStrategy::InvertedIndex => {
+                segment::data_types::explain::SearchStrategy::InvertedIndex
+            }
+        }
+    }
+}
+
+impl From<segment::data_types::explain::SegmentExplanation> for SegmentExplanation {
+    fn from(value: segment::data_types::explain::SegmentExplanation) -> Self {
+        let segment::data_types::explain::SegmentExplanation {
+            segment,
+            segment_type,
+            points_count,
+            strategy,
+            estimated_cardinality,
+            actual_cardinality,
+            indexes_used,
+            estimation_time,
+            filtering_time,
+            vector,
+            time,
+        } = value;
+
+        let segment_type = match segment_type {
+            segment::types::SegmentType::Plain => "plain",
+            segment::types::SegmentType::Indexed => "indexed",
+            segment::types::SegmentType::Special => "special",
+        };
+
+        Self {
+            segment,
+            segment_type: segment_type.to_string(),
+            points_count: points_count as u64,
+            strategy: SearchStrategy::from(strategy) as i32,
+            estimated_cardinality: estimated_cardinality.map(|cardinality| CardinalityEstimate {
+                min: cardinality.min as u64,
+                exp: cardinality.exp as u64,
+                max: cardinality.max as u64,
+            }),
+            actual_cardinality: actual_cardinality.map(|cardinality| cardinality as u64),
+            indexes_used,
+            estimation_time,
+            filtering_time,
+            vector,
+            time,
+        }
+    }
+}
+
+impl TryFrom<SegmentExplanation> for segment::data_types::explain::SegmentExplanation {
+    type Error = Status;
+
+    fn try_from(value: SegmentExplanation) -> Result<Self, Self::Error> {
+        let SegmentExplanation {
+            segment,
+            segment_type,
+            points_count,
+            strategy,
+            estimated_cardinality,
+            actual_cardinality,
+            indexes_used,
+            estimation_time,
+            filtering_time,
+            vector,
+            time,
+        } = value;
+
+        let segment_type = match segment_type.as_str() {
+            "plain" => segment::types::SegmentType::Plain,
+            "indexed" => segment::types::SegmentType::Indexed,
+            "special" => segment::types::SegmentType::Special,
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown segment type: {segment_type}"
+                )))
+            }
+        };
+        let strategy = SearchStrategy::from_i32(strategy).ok_or_else(|| {
+            Status::invalid_argument(format!("Unknown search strategy: {strategy}"))
+        })?;
+
+        Ok(Self {
+            segment,
+            segment_type,
+            points_count: points_count as usize,
+            strategy: strategy.into(),
+            estimated_cardinality: estimated_cardinality.map(|cardinality| {
+                segment::data_types::explain::CardinalityInfo {
+                    min: cardinality.min as usize,
+                    exp: cardinality.exp as usize,
+                    max: cardinality.max as usize,
+                }
+            }),
+            actual_cardinality: actual_cardinality.map(|cardinality| cardinality as usize),
+            indexes_used,
+            estimation_time,
+            filtering_time,
+            vector,
+            time,
+        })
+    }
+}
+
+impl From<segment::data_types::explain::ShardExplanation> for ShardExplanation {
+    fn from(value: segment::data_types::explain::ShardExplanation) -> Self {
+        Self {
+            shard_id: value.shard_id,
+            segments: value.segments.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl From<segment::data_types::profile::ProfileStage> for ProfileStage {
+    fn from(value: segment::data_types::profile::ProfileStage) -> Self {
+        match value {
+            segment::data_types::profile::ProfileStage::Query => ProfileStage::Query,
+            segment::data_types::profile::ProfileStage::Lookup => ProfileStage::Lookup,
+            segment::data_types::profile::ProfileStage::Shard => ProfileStage::Shard,
+            segment::data_types::profile::ProfileStage::Local => ProfileStage::Local,
+            segment::data_types::profile::ProfileStage::Remote => ProfileStage::Remote,
+            segment::data_types::profile::ProfileStage::Prefetch => ProfileStage::Prefetch,
+            segment::data_types::profile::ProfileStage::Scoring => ProfileStage::Scoring,
+            segment::data_types::profile::ProfileStage::Filtering => ProfileStage::Filtering,
+            segment::data_types::profile::ProfileStage::Rescoring => ProfileStage::Rescoring,
+            segment::data_types::profile::ProfileStage::Fusion => ProfileStage::Fusion,
+            segment::data_types::profile::ProfileStage::Fetching => ProfileStage::Fetching,
+            segment::data_types::profile::ProfileStage::Merging => ProfileStage::Merging,
+        }
+    }
+}
+
+impl From<ProfileStage> for segment::data_types::profile::ProfileStage {
+    fn from(value: ProfileStage) -> Self {
+        match value {
+            ProfileStage::Query => segment::data_types::profile::ProfileStage::Query,
+            ProfileStage::Lookup => segment::data_types::profile::ProfileStage::Lookup,
+            ProfileStage::Shard => segment::data_types::profile::ProfileStage::Shard,
+            ProfileStage::Local => segment::data_types::profile::ProfileStage::Local,
+            ProfileStage::Remote => segment::data_types::profile::ProfileStage::Remote,
+            ProfileStage::Prefetch => segment::data_types::profile::ProfileStage::Prefetch,
+            ProfileStage::Scoring => segment::data_types::profile::ProfileStage::Scoring,
+            ProfileStage::Filtering => segment::data_types::profile::ProfileStage::Filtering,
+            ProfileStage::Rescoring => segment::data_types::profile::ProfileStage::Rescoring,
+            ProfileStage::Fusion => segment::data_types::profile::ProfileStage::Fusion,
+            ProfileStage::Fetching => segment::data_types::profile::ProfileStage::Fetching,
+            ProfileStage::Merging => segment::data_types::profile::ProfileStage::Merging,
+        }
+    }
+}
+
+impl From<segment::data_types::profile::QueryProfile> for QueryProfile {
+    fn from(value: segment::data_types::profile::QueryProfile) -> Self {
+        let segment::data_types::profile::QueryProfile {
+            stage,
+            shard_id,
+            time,
+            children,
+            segments,
+        } = value;
+
+        Self {
+            stage: ProfileStage::from(stage) as i32,
+            shard_id,
+            time,
+            children: children.into_iter().map(From::from).collect(),
+            segments: segments.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<QueryProfile> for segment::data_types::profile::QueryProfile {
+    type Error = Status;
+
+    fn try_from(value: QueryProfile) -> Result<Self, Self::Error> {
+        let QueryProfile {
+            stage,
+            shard_id,
+            time,
+            children,
+            segments,
+        } = value;
+
+        let stage = ProfileStage::from_i32(stage)
+            .ok_or_else(|| Status::invalid_argument(format!("Unknown profile stage: {stage}")))?;
+
+        Ok(Self {
+            stage: stage.into(),
+            shard_id,
+            time,
+            children: children
+                .into_iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+            segments: segments
+                .into_iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<segment::common::score_fusion::Normalization> for ScoreNormalization {
+    fn from(value: segment::common::score_fusion::Normalization) -> Self {
+        match value {
+            segment::common::score_fusion::Normalization::Distr => ScoreNormalization::Distribution,
+            segment::common::score_fusion::Normalization::MinMax => ScoreNormalization::MinMax,
+        }
+    }
+}
+
+impl From<ScoreNormalization> for segment::common::score_fusion::Normalization {
+    fn from(value: ScoreNormalization) -> Self {
+        match value {
+            ScoreNormalization::Distribution => segment::common::score_fusion::Normalization::Distr,
+            ScoreNormalization::MinMax => segment::common::score_fusion::Normalization::MinMax,
+        }
+    }
+}
+
+impl TryFrom<Expression> for segment_formula::Expression {
+    type Error = Status;
+
+    fn try_from(value: Expression) -> Result<Self, Self::Error> {
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("Expression variant is missing"))?;
+
+        let boxed = |expression: Box<Expression>| {
+            segment_formula::Expression::try_from(*expression).map(Box::new)
+        };
+        let required = |expression: Option<Box<Expression>>, field: &str| {
+            expression
+                .ok_or_else(|| Status::invalid_argument(format!("Expression {field} is missing")))
+                .and_then(boxed)
+        };
+        let all = |expressions: Vec<Expression>| {
+            expressions
+                .into_iter()
+                .map(segment_formula::Expression::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let expression = match variant {
+            expression::Variant::Constant(value) => segment_formula::Expression::Constant(value),
+            expression::Variant::Variable(variable) => segment_formula::Expression::Variable(
+                variable.parse().map_err(Status::invalid_argument)?,
+            ),
+            expression::Variant::Mult(mult) => segment_formula::Expression::Mult(all(mult.mult)?),
+            expression::Variant::Sum(sum) => segment_formula::Expression::Sum(all(sum.sum)?),
+            expression::Variant::Div(div) => {
+                let DivExpression {
+                    left,
+                    right,
+                    by_zero_default,
+                } = *div;
+                segment_formula::Expression::Div {
+                    left: required(left, "left")?,
+                    right: required(right, "right")?,
+                    by_zero_default,
+                }
+            }
+            expression::Variant::Neg(neg) => segment_formula::Expression::Neg(boxed(neg)?),
+            expression::Variant::Abs(abs) => segment_formula::Expression::Abs(boxed(abs)?),
+            expression::Variant::Sqrt(sqrt) => segment_formula::Expression::Sqrt(boxed(sqrt)?),
+            expression::Variant::Pow(pow) => {
+                let PowExpression { base, exponent } = *pow;
+                segment_formula::Expression::Pow {
+                    base: required(base, "base")?,
+                    exponent: required(exponent, "exponent")?,
+                }
+            }
+            expression::Variant::Exp(exp) => segment_formula::Expression::Exp(boxed(exp)?),
+            expression::Variant::Log10(log10) => segment_formula::Expression::Log10(boxed(log10)?),
+            expression::Variant::Ln(ln) => segment_formula::Expression::Ln(boxed(ln)?),
+            expression::Variant::LinDecay(params) => segment_formula::Expression::Decay {
+                kind: segment_formula::DecayKind::Lin,
+                params: segment_formula::DecayParams::try_from(params)?,
+            },
+            expression::Variant::ExpDecay(params) => segment_formula::Expression::Decay {
+                kind: segment_formula::DecayKind::Exp,
+                params: segment_formula::DecayParams::try_from(params)?,
+            },
+            expression::Variant::GaussDecay(params) => segment_formula::Expression::Decay {
+                kind: segment_formula::DecayKind::Gauss,
+                params: segment_formula::DecayParams::try_from(params)?,
+            },
+            expression::Variant::GeoDistance(GeoDistance { key, origin }) => {
+                segment_formula::Expression::GeoDistance {
+                    key: key.parse().map_err(|_| {
+                        Status::invalid_argument(format!("Invalid geo distance key: {key}"))
+                    })?,
+                    origin: origin.map(segment::types::GeoPoint::from).ok_or_else(|| {
+                        Status::invalid_argument("Geo distance origin is missing")
+                    })?,
+                }
+            }
+        };
+
+        Ok(expression)
+    }
+}
+
+impl From<segment_formula::Expression> for Expression {
+    fn from(value: segment_formula::Expression) -> Self {
+        let boxed =
+            |expression: Box<segment_formula::Expression>| Box::new(Expression::from(*expression));
+        let all = |expressions: Vec<segment_formula::Expression>| -> Vec<Expression> {
+            expressions.into_iter().map(Expression::from).collect()
+        };
+
+        let variant = match value {
+            segment_formula::Expression::Constant(value) => expression::Variant::Constant(value),
+            segment_formula::Expression::Variable(variable) => {
+                expression::Variant::Variable(variable.to_string())
+            }
+            segment_formula::Expression::Sum(sum) => {
+                expression::Variant::Sum(Box::new(SumExpression { sum: all(sum) }))
+            }
+            segment_formula::Expression::Mult(mult) => {
+                expression::Variant::Mult(Box::new(MultExpression { mult: all(mult) }))
+            }
+            segment_formula::Expression::Neg(neg) => expression::Variant::Neg(boxed(neg)),
+            segment_formula::Expression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => expression::Variant::Div(Box::new(DivExpression {
+                left: Some(boxed(left)),
+                right: Some(boxed(right)),
+                by_zero_default,
+            })),
+            segment_formula::Expression::Abs(abs) => expression::Variant::Abs(boxed(abs)),
+            segment_formula::Expression::Sqrt(sqrt) => expression::Variant::Sqrt(boxed(sqrt)),
+            segment_formula::Expression::Pow { base, exponent } => {
+                expression::Variant::Pow(Box::new(PowExpression {
+                    base: Some(boxed(base)),
+                    exponent: Some(boxed(exponent)),
+                }))
+            }
+            segment_formula::Expression::Exp(exp) => expression::Variant::Exp(boxed(exp)),
+            segment_formula::Expression::Log10(log10) => expression::Variant::Log10(boxed(log10)),
+            segment_formula::Expression::Ln(ln) => expression::Variant::Ln(boxed(ln)),
+            segment_formula::Expression::Decay { kind, params } => {
+                let params = DecayParams::from(params);
+                match kind {
+                    segment_formula::DecayKind::Lin => expression::Variant::LinDecay(params),
+                    segment_formula::DecayKind::Exp => expression::Variant::ExpDecay(params),
+                    segment_formula::DecayKind::Gauss => expression::Variant::GaussDecay(params),
+                }
+            }
+            segment_formula::Expression::GeoDistance { key, origin } => {
+                expression::Variant::GeoDistance(GeoDistance {
+                    key: key.to_string(),
+                    origin: Some(origin.into()),
+                })
+            }
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl TryFrom<DecayParams> for segment_formula::DecayParams {
+    type Error = Status;
+
+    fn try_from(value: DecayParams) -> Result<Self, Self::Error> {
+        let DecayParams {
+            key,
+            origin,
+            scale,
+            offset,
+            decay,
+        } = value;
+
+        let origin = match origin.and_then(|origin| origin.variant) {
+            Some(decay_origin::Variant::Number(number)) => {
+                segment_formula::DecayOrigin::Number(number)
+            }
+            Some(decay_origin::Variant::Geo(geo)) => {
+                segment_formula::DecayOrigin::Geo(segment::types::GeoPoint::from(geo))
+            }
+            Some(decay_origin::Variant::Datetime(datetime)) => {
+                segment_formula::DecayOrigin::DateTime(
+                    DateTimePayloadType::from_str(&datetime).map_err(|err| {
+                        Status::invalid_argument(format!(
+                            "Unable to parse decay origin datetime {datetime}: {err}"
+                        ))
+                    })?,
+                )
+            }
+            None => return Err(Status::invalid_argument("Decay origin is missing")),
+        };
+
+        let params = Self {
+            key: key
+                .parse()
+                .map_err(|_| Status::invalid_argument(format!("Invalid decay key: {key}")))?,
+            origin,
+            scale,
+            offset: offset.unwrap_or(Self::DEFAULT_OFFSET),
+            decay: decay.unwrap_or(Self::DEFAULT_DECAY),
+        };
+        params.validate().map_err(Status::invalid_argument)?;
+
+        Ok(params)
+    }
+}
+
+impl From<segment_formula::DecayParams> for DecayParams {
+    fn from(value: segment_formula::DecayParams) -> Self {
+        let segment_formula::DecayParams {
+            key,
+            origin,
+            scale,
+            offset,
+            decay,
+        } = value;
+
+        let origin = match origin {
+            segment_formula::DecayOrigin::Number(number) => decay_origin::Variant::Number(number),
+            segment_formula::DecayOrigin::Geo(geo) => decay_origin::Variant::Geo(geo.into()),
+            segment_formula::DecayOrigin::DateTime(datetime) => {
+                decay_origin::Variant::Datetime(datetime.0.to_rfc3339())
+            }
+        };
+
+        Self {
+            key: key.to_string(),
+            origin: Some(DecayOrigin {
+                variant: Some(origin),
+            }),
+            scale,
+            offset: Some(offset),
+            decay: Some(decay),
+        }
+    }
+}
+
+impl TryFrom<Formula> for segment_formula::FormulaQuery {
+    type Error = Status;
+
+    fn try_from(value: Formula) -> Result<Self, Self::Error> {
+        let Formula {
+            expression,
+            defaults,
+        } = value;
+
+        let formula = expression
+            .ok_or_else(|| Status::invalid_argument("Formula expression is missing"))?
+            .try_into()?;
+        let defaults = defaults
+            .into_iter()
+            .map(|(key, value)| {
+                let key = key.parse::<JsonPath>().map_err(|_| {
+                    Status::invalid_argument(format!("Invalid formula default key: {key}"))
+                })?;
+                Ok((key, value))
+            })
+            .collect::<Result<_, Status>>()?;
+
+        Ok(Self { formula, defaults })
+    }
+}
+
+impl From<segment_formula::FormulaQuery> for Formula {
+    fn from(value: segment_formula::FormulaQuery) -> Self {
+        let segment_formula::FormulaQuery { formula, defaults } = value;
+        Self {
+            expression: Some(Expression::from(formula)),
+            defaults: defaults
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        }
+    }
+}
+
 impl From<segment::types::ScoredPoint> for ScoredPoint {
     fn from(point: segment::types::ScoredPoint) -> Self {
         Self {
@@ -785,10 +1649,49 @@ impl From<segment::data_types::groups::GroupId> for GroupId {
             segment::data_types::groups::GroupId::NumberI64(n) => Self {
                 kind: Some(crate::grpc::qdrant::group_id::Kind::IntegerValue(n)),
             },
+            segment::data_types::groups::GroupId::Compound(ids) => Self {
+                kind: Some(crate::grpc::qdrant::group_id::Kind::CompoundValue(
+                    CompoundGroupId {
+                        values: ids.into_iter().map(GroupId::from).collect(),
+                    },
+                )),
+            },
+        }
+    }
+}
+
+impl From<GroupScoring> for segment::data_types::groups::GroupScoring {
+    fn from(value: GroupScoring) -> Self {
+        match value {
+            GroupScoring::Best => segment::data_types::groups::GroupScoring::Best,
+            GroupScoring::Sum => segment::data_types::groups::GroupScoring::Sum,
+            GroupScoring::Avg => segment::data_types::groups::GroupScoring::Avg,
+            GroupScoring::Count => segment::data_types::groups::GroupScoring::Count,
         }
     }
 }
 
+impl TryFrom<GroupOrderBy> for segment::data_types::groups::GroupOrderBy {
+    type Error = Status;
+
+    fn try_from(value: GroupOrderBy) -> Result<Self, Self::Error> {
+        let GroupOrderBy { key, direction } = value;
+
+        let direction = direction
+            .map(|direction| {
+                Direction::from_i32(direction)
+                    .map(segment::data_types::order_by::Direction::from)
+                    .ok_or_else(|| Status::invalid_argument("Unknown direction"))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            key: json_path_from_proto(&key)?,
+            direction,
+        })
+    }
+}
+
 impl TryFrom<NamedVectors> for HashMap<String, segment_vectors::Vector> {
     type Error = Status;
 
@@ -817,8 +1720,15 @@ impl TryFrom<Vectors> for segment_vectors::VectorStructInternal {
                         data,
                         indices,
                         vectors_count,
+                        document,
+                        image,
                     } = vector;
 
+                    if document.is_some() || image.is_some() {
+                        return Err(Status::invalid_argument(
+                            "Documents and images are only accepted in point upserts and vector updates",
+                        ));
+                    }
                     if indices.is_some() {
                         return Err(Status::invalid_argument(
                             "Sparse vector must be named".to_string(),
@@ -848,6 +1758,53 @@ impl TryFrom<Vectors> for segment_vectors::VectorStructInternal {
     }
 }
 
+impl TryFrom<Vector> for rest::Vector {
+    type Error = Status;
+
+    fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        match vector {
+            Vector {
+                document: Some(Document { text }),
+                ..
+            } => Ok(rest::Vector::Document(rest::Document { text })),
+            Vector {
+                image: Some(Image { image }),
+                ..
+            } => Ok(rest::Vector::Image(rest::Image { image })),
+            vector => segment_vectors::Vector::try_from(vector).map(rest::Vector::from),
+        }
+    }
+}
+
+/// Vectors of the point upserts and vector updates, which may have raw documents and images to embed
+impl TryFrom<Vectors> for rest::VectorStruct {
+    type Error = Status;
+
+    fn try_from(vectors: Vectors) -> Result<Self, Self::Error> {
+        match vectors.vectors_options {
+            // Documents and images are always named in the REST vectors, the default vector has an empty name
+            Some(VectorsOptions::Vector(vector))
+                if vector.document.is_some() || vector.image.is_some() =>
+            {
+                Ok(rest::VectorStruct::Named(HashMap::from([(
+                    segment_vectors::DEFAULT_VECTOR_NAME.to_string(),
+                    vector.try_into()?,
+                )])))
+            }
+            Some(VectorsOptions::Vectors(vectors)) => vectors
+                .vectors
+                .into_iter()
+                .map(|(name, vector)| Ok((name, vector.try_into()?)))
+                .collect::<Result<_, Status>>()
+                .map(rest::VectorStruct::Named),
+            vectors_options => {
+                segment_vectors::VectorStructInternal::try_from(Vectors { vectors_options })
+                    .map(rest::VectorStruct::from)
+            }
+        }
+    }
+}
+
 impl From<segment::types::WithVector> for WithVectorsSelector {
     fn from(with_vectors: segment::types::WithVector) -> Self {
         let selector_options = match with_vectors {
@@ -1082,17 +2039,19 @@ fn conditions_helper_from_grpc(
     }
 }
 
-fn conditions_helper_to_grpc(conditions: Option<Vec<segment::types::Condition>>) -> Vec<Condition> {
+fn conditions_helper_to_grpc(
+    conditions: Option<Vec<segment::types::Condition>>,
+) -> Result<Vec<Condition>, Status> {
     match conditions {
-        None => vec![],
+        None => Ok(vec![]),
         Some(conditions) => {
             if conditions.is_empty() {
-                vec![]
+                Ok(vec![])
             } else {
                 conditions
                     .into_iter()
                     .filter(|c| !c.is_local_only()) // TODO(resharding)!?
-                    .map(|c| c.into())
+                    .map(|c| c.try_into())
                     .collect()
             }
         }
@@ -1124,10 +2083,12 @@ impl TryFrom<Filter> for segment::types::Filter {
     }
 }
 
-impl From<segment::types::Filter> for Filter {
-    fn from(value: segment::types::Filter) -> Self {
-        Self {
-            should: conditions_helper_to_grpc(value.should),
+impl TryFrom<segment::types::Filter> for Filter {
+    type Error = Status;
+
+    fn try_from(value: segment::types::Filter) -> Result<Self, Self::Error> {
+        Ok(Self {
+            should: conditions_helper_to_grpc(value.should)?,
             min_should: {
                 if let Some(segment::types::MinShould {
                     conditions,
@@ -1135,16 +2096,16 @@ impl From<segment::types::Filter> for Filter {
                 }) = value.min_should
                 {
                     Some(MinShould {
-                        conditions: conditions_helper_to_grpc(Some(conditions)),
+                        conditions: conditions_helper_to_grpc(Some(conditions))?,
                         min_count: min_count as u64,
                     })
                 } else {
                     None
                 }
             },
-            must: conditions_helper_to_grpc(value.must),
-            must_not: conditions_helper_to_grpc(value.must_not),
-        }
+            must: conditions_helper_to_grpc(value.must)?,
+            must_not: conditions_helper_to_grpc(value.must_not)?,
+        })
     }
 }
 
@@ -1178,18 +2139,20 @@ impl TryFrom<Condition> for segment::types::Condition {
     }
 }
 
-impl From<segment::types::Condition> for Condition {
-    fn from(value: segment::types::Condition) -> Self {
+impl TryFrom<segment::types::Condition> for Condition {
+    type Error = Status;
+
+    fn try_from(value: segment::types::Condition) -> Result<Self, Self::Error> {
         let condition_one_of = match value {
-            segment::types::Condition::Field(field) => ConditionOneOf::Field(field.into()),
+            segment::types::Condition::Field(field) => ConditionOneOf::Field(field.try_into()?),
             segment::types::Condition::IsEmpty(is_empty) => {
                 ConditionOneOf::IsEmpty(is_empty.into())
             }
             segment::types::Condition::IsNull(is_null) => ConditionOneOf::IsNull(is_null.into()),
             segment::types::Condition::HasId(has_id) => ConditionOneOf::HasId(has_id.into()),
-            segment::types::Condition::Filter(filter) => ConditionOneOf::Filter(filter.into()),
+            segment::types::Condition::Filter(filter) => ConditionOneOf::Filter(filter.try_into()?),
             segment::types::Condition::Nested(nested) => {
-                ConditionOneOf::Nested(nested.nested.into())
+                ConditionOneOf::Nested(nested.nested.try_into()?)
             }
 
             segment::types::Condition::Resharding(_) => {
@@ -1197,9 +2160,9 @@ impl From<segment::types::Condition> for Condition {
             }
         };
 
-        Self {
+        Ok(Self {
             condition_one_of: Some(condition_one_of),
-        }
+        })
     }
 }
 
@@ -1219,12 +2182,14 @@ impl TryFrom<NestedCondition> for segment::types::Nested {
     }
 }
 
-impl From<segment::types::Nested> for NestedCondition {
-    fn from(value: segment::types::Nested) -> Self {
-        Self {
+impl TryFrom<segment::types::Nested> for NestedCondition {
+    type Error = Status;
+
+    fn try_from(value: segment::types::Nested) -> Result<Self, Self::Error> {
+        Ok(Self {
             key: value.key.to_string(),
-            filter: Some(value.filter.into()),
-        }
+            filter: Some(value.filter.try_into()?),
+        })
     }
 }
 
@@ -1283,7 +2248,10 @@ impl TryFrom<HasIdCondition> for segment::types::HasIdCondition {
 
 impl From<segment::types::HasIdCondition> for HasIdCondition {
     fn from(value: segment::types::HasIdCondition) -> Self {
-        let set: Vec<PointId> = value.has_id.into_iter().map(|p| p.into()).collect();
+        // Sorted, so that the same condition is always encoded the same way
+        let mut ids: Vec<_> = value.has_id.into_iter().collect();
+        ids.sort_unstable();
+        let set: Vec<PointId> = ids.into_iter().map(|p| p.into()).collect();
         Self { has_id: set }
     }
 }
@@ -1301,12 +2269,14 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             values_count,
             geo_polygon,
             datetime_range,
+            geo_shape,
         } = value;
 
         let geo_bounding_box =
             geo_bounding_box.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
         let geo_radius = geo_radius.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
         let geo_polygon = geo_polygon.map_or_else(|| Ok(None), |g| g.try_into().map(Some))?;
+        let geo_shape = geo_shape.map(TryInto::try_into).transpose()?;
 
         let range = range.map(Into::into);
         let datetime_range = datetime_range
@@ -1320,13 +2290,16 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             geo_bounding_box,
             geo_radius,
             geo_polygon,
+            geo_shape,
             values_count: values_count.map(Into::into),
         })
     }
 }
 
-impl From<segment::types::FieldCondition> for FieldCondition {
-    fn from(value: segment::types::FieldCondition) -> Self {
+impl TryFrom<segment::types::FieldCondition> for FieldCondition {
+    type Error = Status;
+
+    fn try_from(value: segment::types::FieldCondition) -> Result<Self, Self::Error> {
         let segment::types::FieldCondition {
             key,
             r#match,
@@ -1334,16 +2307,24 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_bounding_box,
             geo_radius,
             geo_polygon,
+            geo_shape,
             values_count,
         } = value;
 
         let (range, datetime_range) = match range {
             Some(segment::types::RangeInterface::Float(range)) => (Some(range.into()), None),
             Some(segment::types::RangeInterface::DateTime(range)) => (None, Some(range.into())),
+            // Relative bounds are resolved once on the node which received the request,
+            // so that all shards compare against the same instant
+            Some(segment::types::RangeInterface::RelativeDateTime(_)) => {
+                return Err(Status::internal(format!(
+                    "Relative datetime range of {key} must be resolved before it is sent to other peers",
+                )));
+            }
             None => (None, None),
         };
 
-        Self {
+        Ok(Self {
             key: key.to_string(),
             r#match: r#match.map(Into::into),
             range,
@@ -1352,7 +2333,8 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_polygon: geo_polygon.map(Into::into),
             values_count: values_count.map(Into::into),
             datetime_range,
-        }
+            geo_shape: geo_shape.map(Into::into),
+        })
     }
 }
 
@@ -1439,6 +2421,72 @@ impl From<segment::types::GeoPolygon> for GeoPolygon {
     }
 }
 
+impl TryFrom<GeoShape> for segment::types::GeoShape {
+    type Error = Status;
+
+    fn try_from(value: GeoShape) -> Result<Self, Self::Error> {
+        match value.shape {
+            Some(geo_shape::Shape::Point(point)) => Ok(Self::Point(point.into())),
+            Some(geo_shape::Shape::Polygon(polygon)) => Ok(Self::Polygon(polygon.try_into()?)),
+            None => Err(Status::invalid_argument("Malformed GeoShape type")),
+        }
+    }
+}
+
+impl From<segment::types::GeoShape> for GeoShape {
+    fn from(value: segment::types::GeoShape) -> Self {
+        let shape = match value {
+            segment::types::GeoShape::Point(point) => geo_shape::Shape::Point(point.into()),
+            segment::types::GeoShape::Polygon(polygon) => geo_shape::Shape::Polygon(polygon.into()),
+        };
+        Self { shape: Some(shape) }
+    }
+}
+
+impl From<GeoShapeRelation> for segment::types::GeoShapeRelation {
+    fn from(value: GeoShapeRelation) -> Self {
+        match value {
+            GeoShapeRelation::Intersects => Self::Intersects,
+            GeoShapeRelation::Within => Self::Within,
+            GeoShapeRelation::Contains => Self::Contains,
+        }
+    }
+}
+
+impl From<segment::types::GeoShapeRelation> for GeoShapeRelation {
+    fn from(value: segment::types::GeoShapeRelation) -> Self {
+        match value {
+            segment::types::GeoShapeRelation::Intersects => Self::Intersects,
+            segment::types::GeoShapeRelation::Within => Self::Within,
+            segment::types::GeoShapeRelation::Contains => Self::Contains,
+        }
+    }
+}
+
+impl TryFrom<GeoShapeCondition> for segment::types::GeoShapeCondition {
+    type Error = Status;
+
+    fn try_from(value: GeoShapeCondition) -> Result<Self, Self::Error> {
+        let GeoShapeCondition { relation, shape } = value;
+        let relation = GeoShapeRelation::from_i32(relation)
+            .ok_or_else(|| Status::invalid_argument("Unknown GeoShapeRelation"))?;
+        let shape = shape.ok_or_else(|| Status::invalid_argument("Malformed GeoShapeCondition"))?;
+        Ok(Self {
+            relation: relation.into(),
+            shape: shape.try_into()?,
+        })
+    }
+}
+
+impl From<segment::types::GeoShapeCondition> for GeoShapeCondition {
+    fn from(value: segment::types::GeoShapeCondition) -> Self {
+        Self {
+            relation: GeoShapeRelation::from(value.relation) as i32,
+            shape: Some(value.shape.into()),
+        }
+    }
+}
+
 impl From<GeoPoint> for segment::types::GeoPoint {
     fn from(value: GeoPoint) -> Self {
         Self {
@@ -1645,15 +2693,63 @@ impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
                             Status::invalid_argument(format!("Malformed datetime: {e}"))
                         })?,
                     )),
+                    Value::Keyword(keyword) => Ok(StartFrom::Keyword(keyword)),
                 }
             })
             .transpose()?;
 
-        Ok(Self {
+        let order_by = Self {
             key: json_path_from_proto(&value.key)?,
             direction,
             start_from,
-        })
+            geo_origin: value.geo_origin.map(Into::into),
+            then_by: value
+                .then_by
+                .into_iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+        order_by
+            .validate()
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        Ok(order_by)
+    }
+}
+
+impl TryFrom<ThenBy> for segment::data_types::order_by::ThenBy {
+    type Error = Status;
+
+    fn try_from(value: ThenBy) -> Result<Self, Self::Error> {
+        use segment::data_types::order_by::IdOrder;
+
+        match value.variant {
+            Some(then_by::Variant::Key(key)) => Ok(Self::Key(key.try_into()?)),
+            Some(then_by::Variant::Id(direction)) => {
+                let direction = Direction::from_i32(direction)
+                    .ok_or_else(|| Status::invalid_argument("Unknown id direction"))?;
+                Ok(Self::Id(IdOrder {
+                    id: direction.into(),
+                }))
+            }
+            None => Err(Status::invalid_argument("Missing then_by variant")),
+        }
+    }
+}
+
+impl From<segment::data_types::order_by::ThenBy> for ThenBy {
+    fn from(value: segment::data_types::order_by::ThenBy) -> Self {
+        use segment::data_types::order_by::IdOrder;
+
+        let variant = match value {
+            segment::data_types::order_by::ThenBy::Key(key) => then_by::Variant::Key(key.into()),
+            segment::data_types::order_by::ThenBy::Id(IdOrder { id }) => {
+                then_by::Variant::Id(Direction::from(id) as i32)
+            }
+        };
+        Self {
+            variant: Some(variant),
+        }
     }
 }
 
@@ -1663,6 +2759,8 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
             key: value.key.to_string(),
             direction: value.direction.map(|d| Direction::from(d) as i32),
             start_from: value.start_from.map(|start_from| start_from.into()),
+            geo_origin: value.geo_origin.map(Into::into),
+            then_by: value.then_by.into_iter().map(From::from).collect(),
         }
     }
 }
@@ -1680,6 +2778,9 @@ impl From<segment::data_types::order_by::StartFrom> for StartFrom {
                 segment::data_types::order_by::StartFrom::Datetime(datetime) => {
                     start_from::Value::Timestamp(date_time_to_proto(datetime))
                 }
+                segment::data_types::order_by::StartFrom::Keyword(keyword) => {
+                    start_from::Value::Keyword(keyword)
+                }
             }),
         }
     }
@@ -1924,6 +3025,8 @@ impl From<RecommendStrategy> for crate::rest::RecommendStrategy {
         match value {
             RecommendStrategy::AverageVector => crate::rest::RecommendStrategy::AverageVector,
             RecommendStrategy::BestScore => crate::rest::RecommendStrategy::BestScore,
+            RecommendStrategy::SumScores => crate::rest::RecommendStrategy::SumScores,
+            RecommendStrategy::Contrastive => crate::rest::RecommendStrategy::Contrastive,
         }
     }
 }
@@ -1939,6 +3042,32 @@ impl TryFrom<i32> for crate::rest::RecommendStrategy {
     }
 }
 
+impl From<Sample> for crate::rest::Sample {
+    fn from(value: Sample) -> Self {
+        match value {
+            Sample::Random => crate::rest::Sample::Random,
+        }
+    }
+}
+
+impl From<crate::rest::Sample> for Sample {
+    fn from(value: crate::rest::Sample) -> Self {
+        match value {
+            crate::rest::Sample::Random => Sample::Random,
+        }
+    }
+}
+
+impl TryFrom<i32> for crate::rest::Sample {
+    type Error = Status;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        let sample = Sample::from_i32(value)
+            .ok_or_else(|| Status::invalid_argument(format!("Unknown sample: {value}")))?;
+        Ok(sample.into())
+    }
+}
+
 impl From<segment_query::RecoQuery<segment_vectors::Vector>> for raw_query::Recommend {
     fn from(value: segment_query::RecoQuery<segment_vectors::Vector>) -> Self {
         Self {
@@ -1966,6 +3095,87 @@ impl TryFrom<raw_query::Recommend> for segment_query::RecoQuery<segment_vectors:
     }
 }
 
+impl From<segment_query::RecoSumQuery<segment_vectors::Vector>> for raw_query::Recommend {
+    fn from(value: segment_query::RecoSumQuery<segment_vectors::Vector>) -> Self {
+        Self {
+            positives: value.positives.into_iter().map(RawVector::from).collect(),
+            negatives: value.negatives.into_iter().map(RawVector::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<raw_query::Recommend> for segment_query::RecoSumQuery<segment_vectors::Vector> {
+    type Error = Status;
+    fn try_from(value: raw_query::Recommend) -> Result<Self, Self::Error> {
+        Ok(Self {
+            positives: value
+                .positives
+                .into_iter()
+                .map(segment_vectors::Vector::try_from)
+                .try_collect()?,
+            negatives: value
+                .negatives
+                .into_iter()
+                .map(segment_vectors::Vector::try_from)
+                .try_collect()?,
+        })
+    }
+}
+
+impl From<segment_query::ContrastiveQuery<segment_vectors::Vector>>
+    for raw_query::WeightedRecommend
+{
+    fn from(value: segment_query::ContrastiveQuery<segment_vectors::Vector>) -> Self {
+        let (positives, positive_weights) = value
+            .positives
+            .into_iter()
+            .map(|(vector, weight)| (RawVector::from(vector), weight))
+            .unzip();
+        let (negatives, negative_weights) = value
+            .negatives
+            .into_iter()
+            .map(|(vector, weight)| (RawVector::from(vector), weight))
+            .unzip();
+        Self {
+            positives,
+            negatives,
+            positive_weights,
+            negative_weights,
+        }
+    }
+}
+
+impl TryFrom<raw_query::WeightedRecommend>
+    for segment_query::ContrastiveQuery<segment_vectors::Vector>
+{
+    type Error = Status;
+    fn try_from(value: raw_query::WeightedRecommend) -> Result<Self, Self::Error> {
+        let raw_query::WeightedRecommend {
+            positives,
+            negatives,
+            positive_weights,
+            negative_weights,
+        } = value;
+        if positives.len() != positive_weights.len() || negatives.len() != negative_weights.len() {
+            return Err(Status::invalid_argument(
+                "Each weighted example must have exactly one weight",
+            ));
+        }
+        Ok(Self {
+            positives: positives
+                .into_iter()
+                .zip(positive_weights)
+                .map(|(vector, weight)| Ok((segment_vectors::Vector::try_from(vector)?, weight)))
+                .collect::<Result<_, Status>>()?,
+            negatives: negatives
+                .into_iter()
+                .zip(negative_weights)
+                .map(|(vector, weight)| Ok((segment_vectors::Vector::try_from(vector)?, weight)))
+                .collect::<Result<_, Status>>()?,
+        })
+    }
+}
+
 impl From<segment_query::ContextPair<segment_vectors::Vector>> for raw_query::RawContextPair {
     fn from(value: segment_query::ContextPair<segment_vectors::Vector>) -> Self {
         Self {
@@ -2101,6 +3311,8 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             timeout: None,
             shard_key_selector: None,
             sparse_indices: value.sparse_indices,
+            explain: None,
+            page_token: None,
         };
 
         if let Some(sparse_indices) = &search_points.sparse_indices {
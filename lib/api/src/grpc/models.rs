@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use schemars::JsonSchema;
+use segment::data_types::explain::ShardExplanation;
 use serde;
 use serde::Serialize;
 
@@ -43,6 +44,12 @@ pub struct ApiResponse<D> {
     pub result: Option<D>,
     pub status: ApiStatus,
     pub time: f64,
+    /// How each segment executed the request, if requested with `explain`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<Vec<ShardExplanation>>,
+    /// Token to get the next page of the results with, if there can be more results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
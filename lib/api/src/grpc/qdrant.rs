@@ -27,6 +27,9 @@ pub struct VectorParams {
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Name of the embedding provider of the nodes, which embeds raw documents and images of this vector
+    #[prost(string, optional, tag = "8")]
+    pub embedding: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -111,6 +114,23 @@ pub mod vectors_config_diff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseDocumentParams {
+    /// Tokenizer to split the documents with
+    #[prost(enumeration = "TokenizerType", tag = "1")]
+    pub tokenizer: i32,
+    /// If true - all tokens will be lowercase
+    #[prost(bool, optional, tag = "2")]
+    pub lowercase: ::core::option::Option<bool>,
+    /// Minimal token length
+    #[prost(uint64, optional, tag = "3")]
+    pub min_token_len: ::core::option::Option<u64>,
+    /// Maximal token length
+    #[prost(uint64, optional, tag = "4")]
+    pub max_token_len: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SparseVectorParams {
     /// Configuration of sparse index
     #[prost(message, optional, tag = "1")]
@@ -118,6 +138,9 @@ pub struct SparseVectorParams {
     /// If set - apply modifier to the vector values
     #[prost(enumeration = "Modifier", optional, tag = "2")]
     pub modifier: ::core::option::Option<i32>,
+    /// If set - accept raw text documents for this vector
+    #[prost(message, optional, tag = "3")]
+    pub document: ::core::option::Option<SparseDocumentParams>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -638,7 +661,11 @@ pub struct CollectionConfig {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct KeywordIndexParams {}
+pub struct KeywordIndexParams {
+    /// If true - used for tenant optimization.
+    #[prost(bool, optional, tag = "1")]
+    pub is_tenant: ::core::option::Option<bool>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -649,11 +676,18 @@ pub struct IntegerIndexParams {
     /// If true - support ranges filters.
     #[prost(bool, tag = "2")]
     pub range: bool,
+    /// If true - use this index to order points within segments.
+    #[prost(bool, optional, tag = "3")]
+    pub is_principal: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct FloatIndexParams {}
+pub struct FloatIndexParams {
+    /// If true - use this index to order points within segments.
+    #[prost(bool, optional, tag = "1")]
+    pub is_principal: ::core::option::Option<bool>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -682,12 +716,20 @@ pub struct BoolIndexParams {}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct DatetimeIndexParams {}
+pub struct DatetimeIndexParams {
+    /// If true - use this index to order points within segments.
+    #[prost(bool, optional, tag = "1")]
+    pub is_principal: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoShapeIndexParams {}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PayloadIndexParams {
-    #[prost(oneof = "payload_index_params::IndexParams", tags = "3, 2, 4, 5, 1, 6, 7")]
+    #[prost(oneof = "payload_index_params::IndexParams", tags = "3, 2, 4, 5, 1, 6, 7, 8")]
     pub index_params: ::core::option::Option<payload_index_params::IndexParams>,
 }
 /// Nested message and enum types in `PayloadIndexParams`.
@@ -717,6 +759,9 @@ pub mod payload_index_params {
         /// Parameters for datetime index
         #[prost(message, tag = "7")]
         DatetimeIndexParams(super::DatetimeIndexParams),
+        /// Parameters for geo shape index
+        #[prost(message, tag = "8")]
+        GeoShapeIndexParams(super::GeoShapeIndexParams),
     }
 }
 #[derive(serde::Serialize)]
@@ -1339,6 +1384,7 @@ pub enum PayloadSchemaType {
     Text = 5,
     Bool = 6,
     Datetime = 7,
+    GeoShape = 8,
 }
 impl PayloadSchemaType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1355,6 +1401,7 @@ impl PayloadSchemaType {
             PayloadSchemaType::Text => "Text",
             PayloadSchemaType::Bool => "Bool",
             PayloadSchemaType::Datetime => "Datetime",
+            PayloadSchemaType::GeoShape => "GeoShape",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1368,6 +1415,7 @@ impl PayloadSchemaType {
             "Text" => Some(Self::Text),
             "Bool" => Some(Self::Bool),
             "Datetime" => Some(Self::Datetime),
+            "GeoShape" => Some(Self::GeoShape),
             _ => None,
         }
     }
@@ -3800,6 +3848,12 @@ pub struct Vector {
     /// Number of vectors per multi vector
     #[prost(uint32, optional, tag = "3")]
     pub vectors_count: ::core::option::Option<u32>,
+    /// Raw text, which the server encodes into the vector. Only accepted in upserts and vector updates
+    #[prost(message, optional, tag = "4")]
+    pub document: ::core::option::Option<Document>,
+    /// Image, which the server embeds into the vector. Only accepted in upserts and vector updates
+    #[prost(message, optional, tag = "5")]
+    pub image: ::core::option::Option<Image>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3824,12 +3878,30 @@ pub struct MultiDenseVector {
     #[prost(message, repeated, tag = "1")]
     pub vectors: ::prost::alloc::vec::Vec<DenseVector>,
 }
+/// Raw text, which is encoded into a sparse vector by the server
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Document {
+    /// Text of the document
+    #[prost(string, tag = "1")]
+    pub text: ::prost::alloc::string::String,
+}
+/// Image, which is embedded into a dense vector by the server
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Image {
+    /// URL or base64 encoded data of the image
+    #[prost(string, tag = "1")]
+    pub image: ::prost::alloc::string::String,
+}
 /// Vector type to be used in queries. Ids will be substituted with their corresponding vectors from the collection.
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VectorInput {
-    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4, 5, 6")]
     pub variant: ::core::option::Option<vector_input::Variant>,
 }
 /// Nested message and enum types in `VectorInput`.
@@ -3846,6 +3918,10 @@ pub mod vector_input {
         Sparse(super::SparseVector),
         #[prost(message, tag = "4")]
         MultiDense(super::MultiDenseVector),
+        #[prost(message, tag = "5")]
+        Document(super::Document),
+        #[prost(message, tag = "6")]
+        Image(super::Image),
     }
 }
 #[derive(serde::Serialize)]
@@ -4303,6 +4379,12 @@ pub struct SearchPoints {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "15")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// If `true` - describe how each segment of each shard replica executes the search. Not supported in batch requests
+    #[prost(bool, optional, tag = "16")]
+    pub explain: ::core::option::Option<bool>,
+    /// Continue right after the last point of the page, which returned this token
+    #[prost(string, optional, tag = "17")]
+    pub page_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4402,7 +4484,7 @@ pub struct SearchPointGroups {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StartFrom {
-    #[prost(oneof = "start_from::Value", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "start_from::Value", tags = "1, 2, 3, 4, 5")]
     pub value: ::core::option::Option<start_from::Value>,
 }
 /// Nested message and enum types in `StartFrom`.
@@ -4419,6 +4501,8 @@ pub mod start_from {
         Timestamp(::prost_wkt_types::Timestamp),
         #[prost(string, tag = "4")]
         Datetime(::prost::alloc::string::String),
+        #[prost(string, tag = "5")]
+        Keyword(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -4434,6 +4518,33 @@ pub struct OrderBy {
     /// Start from this value
     #[prost(message, optional, tag = "3")]
     pub start_from: ::core::option::Option<StartFrom>,
+    /// Order by distance from this location to the geo values of the key
+    #[prost(message, optional, tag = "4")]
+    pub geo_origin: ::core::option::Option<GeoPoint>,
+    /// Keys to order by, when the values of the previous keys are equal
+    #[prost(message, repeated, tag = "5")]
+    pub then_by: ::prost::alloc::vec::Vec<ThenBy>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ThenBy {
+    #[prost(oneof = "then_by::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<then_by::Variant>,
+}
+/// Nested message and enum types in `ThenBy`.
+pub mod then_by {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Order by payload key
+        #[prost(message, tag = "1")]
+        Key(super::OrderBy),
+        /// Order by point id, only as the last key
+        #[prost(enumeration = "super::Direction", tag = "2")]
+        Id(i32),
+    }
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4548,6 +4659,12 @@ pub struct RecommendPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "20")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Weights of the positive examples, ids first and then vectors. Only used by the `Contrastive` strategy, 1.0 by default
+    #[prost(float, repeated, tag = "21")]
+    pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+    /// Weights of the negative examples, ids first and then vectors. Only used by the `Contrastive` strategy, 1.0 by default
+    #[prost(float, repeated, tag = "22")]
+    pub negative_weights: ::prost::alloc::vec::Vec<f32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4643,6 +4760,12 @@ pub struct RecommendPointGroups {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "21")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Weights of the positive examples, ids first and then vectors. Only used by the `Contrastive` strategy, 1.0 by default
+    #[prost(float, repeated, tag = "22")]
+    pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+    /// Weights of the negative examples, ids first and then vectors. Only used by the `Contrastive` strategy, 1.0 by default
+    #[prost(float, repeated, tag = "23")]
+    pub negative_weights: ::prost::alloc::vec::Vec<f32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4784,6 +4907,310 @@ pub struct CountPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If `true` - describe how each segment of each shard replica counts the points
+    #[prost(bool, optional, tag = "6")]
+    pub explain: ::core::option::Option<bool>,
+    /// If set, also count points for each value of this payload key. Must have a keyword, integer or bool index
+    #[prost(string, optional, tag = "7")]
+    #[validate(length(min = 1))]
+    pub group_by: ::core::option::Option<::prost::alloc::string::String>,
+    /// If set, also estimate the number of distinct values of this payload key among the counted points
+    #[prost(string, optional, tag = "8")]
+    #[validate(length(min = 1))]
+    pub distinct: ::core::option::Option<::prost::alloc::string::String>,
+    /// Max number of `group_by` values to return. Default is 10. If `exact` is false, counts of values close to the limit might be underestimated
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub group_by_limit: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCounts {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key of the facet
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - only count points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of hits to return. Default is 10
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// If `true` - return exact counts, if `false` - return approximate counts. Default is `false`
+    #[prost(bool, optional, tag = "5")]
+    pub exact: ::core::option::Option<bool>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "6")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "7")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetValue {
+    #[prost(oneof = "facet_value::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<facet_value::Variant>,
+}
+/// Nested message and enum types in `FacetValue`.
+pub mod facet_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// String value from the facet
+        #[prost(string, tag = "1")]
+        StringValue(::prost::alloc::string::String),
+        /// Integer value from the facet
+        #[prost(int64, tag = "2")]
+        IntegerValue(i64),
+        /// Boolean value from the facet
+        #[prost(bool, tag = "3")]
+        BoolValue(bool),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetHit {
+    /// Value from the facet
+    #[prost(message, optional, tag = "1")]
+    pub value: ::core::option::Option<FacetValue>,
+    /// Number of points with this value
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Payload key to aggregate values of
+    #[prost(string, tag = "2")]
+    #[validate(length(min = 1))]
+    pub key: ::prost::alloc::string::String,
+    /// Filter conditions - only aggregate values of points that satisfy the specified conditions
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// If set, also count values per histogram bucket
+    #[prost(message, optional, tag = "4")]
+    pub histogram: ::core::option::Option<HistogramParams>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "5")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If set, also count points per calendar bucket. Requires a datetime index on the key
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub date_histogram: ::core::option::Option<DateHistogramParams>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramParams {
+    #[prost(oneof = "histogram_params::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<histogram_params::Variant>,
+}
+/// Nested message and enum types in `HistogramParams`.
+pub mod histogram_params {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Buckets of a fixed width, starting from zero. For datetime keys the interval is in seconds
+        #[prost(double, tag = "1")]
+        Interval(f64),
+        /// Given number of buckets of equal width, which span from the min to the max value
+        #[prost(uint64, tag = "2")]
+        Buckets(u64),
+    }
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DateHistogramParams {
+    /// Calendar unit of the buckets
+    #[prost(enumeration = "CalendarInterval", tag = "1")]
+    pub interval: i32,
+    /// IANA name of the time zone of bucket edges, for example `Europe/Berlin`. Default is UTC
+    #[prost(string, optional, tag = "2")]
+    pub time_zone: ::core::option::Option<::prost::alloc::string::String>,
+    /// If set, also count the values of this keyword, integer or bool key within each bucket
+    #[prost(string, optional, tag = "3")]
+    pub facet_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Max number of facet values to return per bucket. Default is 10
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub facet_limit: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregationValue {
+    #[prost(oneof = "aggregation_value::Variant", tags = "1, 2")]
+    pub variant: ::core::option::Option<aggregation_value::Variant>,
+}
+/// Nested message and enum types in `AggregationValue`.
+pub mod aggregation_value {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Value of an integer or float key
+        #[prost(double, tag = "1")]
+        Number(f64),
+        /// Value of a datetime key
+        #[prost(message, tag = "2")]
+        Datetime(::prost_wkt_types::Timestamp),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramBucket {
+    /// Lower bound of the bucket, inclusive
+    #[prost(message, optional, tag = "1")]
+    pub from: ::core::option::Option<AggregationValue>,
+    /// Upper bound of the bucket, exclusive
+    #[prost(message, optional, tag = "2")]
+    pub to: ::core::option::Option<AggregationValue>,
+    /// Number of values in the bucket
+    #[prost(uint64, tag = "3")]
+    pub count: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResult {
+    /// Number of aggregated values
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    #[prost(message, optional, tag = "2")]
+    pub min: ::core::option::Option<AggregationValue>,
+    #[prost(message, optional, tag = "3")]
+    pub max: ::core::option::Option<AggregationValue>,
+    /// Sum of the values. Not available for datetime keys
+    #[prost(double, optional, tag = "4")]
+    pub sum: ::core::option::Option<f64>,
+    #[prost(message, optional, tag = "5")]
+    pub avg: ::core::option::Option<AggregationValue>,
+    /// Histogram buckets, in ascending order
+    #[prost(message, repeated, tag = "6")]
+    pub histogram: ::prost::alloc::vec::Vec<HistogramBucket>,
+    /// Non-empty date histogram buckets, in ascending order
+    #[prost(message, repeated, tag = "7")]
+    pub date_histogram: ::prost::alloc::vec::Vec<DateHistogramBucket>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CardinalityEstimate {
+    #[prost(uint64, tag = "1")]
+    pub min: u64,
+    #[prost(uint64, tag = "2")]
+    pub exp: u64,
+    #[prost(uint64, tag = "3")]
+    pub max: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SegmentExplanation {
+    /// Name of the segment directory
+    #[prost(string, tag = "1")]
+    pub segment: ::prost::alloc::string::String,
+    /// Type of the segment: plain, indexed or special
+    #[prost(string, tag = "2")]
+    pub segment_type: ::prost::alloc::string::String,
+    /// Number of available points in the segment
+    #[prost(uint64, tag = "3")]
+    pub points_count: u64,
+    /// The way points are found in the segment
+    #[prost(enumeration = "SearchStrategy", tag = "4")]
+    pub strategy: i32,
+    /// Cardinality of the filter, as estimated by the payload index
+    #[prost(message, optional, tag = "5")]
+    pub estimated_cardinality: ::core::option::Option<CardinalityEstimate>,
+    /// Number of points, which actually match the filter. Absent if the strategy hasn't selected all of the matching points
+    #[prost(uint64, optional, tag = "6")]
+    pub actual_cardinality: ::core::option::Option<u64>,
+    /// Payload indexes, selected to find matching points
+    #[prost(string, repeated, tag = "7")]
+    pub indexes_used: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Time spent on cardinality estimation, in seconds
+    #[prost(double, tag = "8")]
+    pub estimation_time: f64,
+    /// Time spent on finding all points matching the filter, in seconds
+    #[prost(double, tag = "9")]
+    pub filtering_time: f64,
+    /// Searched vector. Absent if the request doesn't search vectors
+    #[prost(string, optional, tag = "10")]
+    pub vector: ::core::option::Option<::prost::alloc::string::String>,
+    /// Time spent on the request in the segment, in seconds
+    #[prost(double, tag = "11")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardExplanation {
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub segments: ::prost::alloc::vec::Vec<SegmentExplanation>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryProfile {
+    #[prost(enumeration = "ProfileStage", tag = "1")]
+    pub stage: i32,
+    /// Shard, the stage was executed on
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    /// Time spent on the stage, including nested stages, in seconds
+    #[prost(double, tag = "3")]
+    pub time: f64,
+    /// Nested stages, which may run concurrently
+    #[prost(message, repeated, tag = "4")]
+    pub children: ::prost::alloc::vec::Vec<QueryProfile>,
+    /// How segments have executed the stage. Moved to `explain` of the response, if requested
+    #[prost(message, repeated, tag = "5")]
+    pub segments: ::prost::alloc::vec::Vec<SegmentExplanation>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DateHistogramBucket {
+    /// Start of the bucket, inclusive
+    #[prost(message, optional, tag = "1")]
+    pub from: ::core::option::Option<::prost_wkt_types::Timestamp>,
+    /// End of the bucket, exclusive
+    #[prost(message, optional, tag = "2")]
+    pub to: ::core::option::Option<::prost_wkt_types::Timestamp>,
+    /// Number of points with a value in the bucket
+    #[prost(uint64, tag = "3")]
+    pub count: u64,
+    /// Most frequent values of the facet key among the points of the bucket
+    #[prost(message, repeated, tag = "4")]
+    pub facet: ::prost::alloc::vec::Vec<FacetHit>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4798,6 +5225,12 @@ pub struct RecommendInput {
     /// How to use the provided vectors to find the results
     #[prost(enumeration = "RecommendStrategy", optional, tag = "3")]
     pub strategy: ::core::option::Option<i32>,
+    /// Weights of the positive examples, in the same order. Only used by the `Contrastive` strategy, 1.0 by default
+    #[prost(float, repeated, tag = "4")]
+    pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+    /// Weights of the negative examples, in the same order. Only used by the `Contrastive` strategy, 1.0 by default
+    #[prost(float, repeated, tag = "5")]
+    pub negative_weights: ::prost::alloc::vec::Vec<f32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4832,34 +5265,241 @@ pub struct ContextInput {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6")]
-    pub variant: ::core::option::Option<query::Variant>,
+pub struct FusionParams {
+    /// Fusion method
+    #[prost(enumeration = "Fusion", tag = "1")]
+    pub fusion: i32,
+    /// Multipliers of the scores of each prefetch, in the order of prefetches. Default is 1.0 for each prefetch
+    #[prost(float, repeated, tag = "2")]
+    pub weights: ::prost::alloc::vec::Vec<f32>,
+    /// How to normalize the scores of each prefetch. Only for DBSF. Default is Distribution
+    #[prost(enumeration = "ScoreNormalization", optional, tag = "3")]
+    pub normalization: ::core::option::Option<i32>,
+    /// Constant, added to the rank of each point. Only for RRF. Default is 2
+    #[prost(uint32, optional, tag = "4")]
+    pub k: ::core::option::Option<u32>,
 }
-/// Nested message and enum types in `Query`.
-pub mod query {
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Expression {
+    #[prost(
+        oneof = "expression::Variant",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16"
+    )]
+    pub variant: ::core::option::Option<expression::Variant>,
+}
+/// Nested message and enum types in `Expression`.
+pub mod expression {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Oneof)]
     pub enum Variant {
-        /// Find the nearest neighbors to this vector.
-        #[prost(message, tag = "1")]
-        Nearest(super::VectorInput),
-        /// Use multiple positive and negative vectors to find the results.
-        #[prost(message, tag = "2")]
-        Recommend(super::RecommendInput),
-        /// Search for nearest points, but constrain the search space with context
+        /// Constant value
+        #[prost(double, tag = "1")]
+        Constant(f64),
+        /// Score of a prefetch, `$score` or `$score\[<index>\]`, or a payload key of a numeric or datetime value
+        #[prost(string, tag = "2")]
+        Variable(::prost::alloc::string::String),
+        /// Product of the expressions
         #[prost(message, tag = "3")]
-        Discover(super::DiscoverInput),
-        /// Return points that live in positive areas.
+        Mult(::prost::alloc::boxed::Box<super::MultExpression>),
+        /// Sum of the expressions
         #[prost(message, tag = "4")]
-        Context(super::ContextInput),
-        /// Order the points by a payload field.
+        Sum(::prost::alloc::boxed::Box<super::SumExpression>),
+        /// Division of the expressions
         #[prost(message, tag = "5")]
-        OrderBy(super::OrderBy),
+        Div(::prost::alloc::boxed::Box<super::DivExpression>),
+        /// Negation of the expression
+        #[prost(message, tag = "6")]
+        Neg(::prost::alloc::boxed::Box<super::Expression>),
+        /// Absolute value of the expression
+        #[prost(message, tag = "7")]
+        Abs(::prost::alloc::boxed::Box<super::Expression>),
+        /// Square root of the expression
+        #[prost(message, tag = "8")]
+        Sqrt(::prost::alloc::boxed::Box<super::Expression>),
+        /// Power of the expression
+        #[prost(message, tag = "9")]
+        Pow(::prost::alloc::boxed::Box<super::PowExpression>),
+        /// Exponent of the expression
+        #[prost(message, tag = "10")]
+        Exp(::prost::alloc::boxed::Box<super::Expression>),
+        /// Base-10 logarithm of the expression
+        #[prost(message, tag = "11")]
+        Log10(::prost::alloc::boxed::Box<super::Expression>),
+        /// Natural logarithm of the expression
+        #[prost(message, tag = "12")]
+        Ln(::prost::alloc::boxed::Box<super::Expression>),
+        /// Linear decay of the distance from the origin
+        #[prost(message, tag = "13")]
+        LinDecay(super::DecayParams),
+        /// Exponential decay of the distance from the origin
+        #[prost(message, tag = "14")]
+        ExpDecay(super::DecayParams),
+        /// Gaussian decay of the distance from the origin
+        #[prost(message, tag = "15")]
+        GaussDecay(super::DecayParams),
+        /// Distance in meters from the origin to the geo value of the payload field
+        #[prost(message, tag = "16")]
+        GeoDistance(super::GeoDistance),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MultExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub mult: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SumExpression {
+    #[prost(message, repeated, tag = "1")]
+    pub sum: ::prost::alloc::vec::Vec<Expression>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DivExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    /// Result of the division by zero. If not set, division by zero fails the query
+    #[prost(double, optional, tag = "3")]
+    pub by_zero_default: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PowExpression {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub base: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub exponent: ::core::option::Option<::prost::alloc::boxed::Box<Expression>>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayOrigin {
+    #[prost(oneof = "decay_origin::Variant", tags = "1, 2, 3")]
+    pub variant: ::core::option::Option<decay_origin::Variant>,
+}
+/// Nested message and enum types in `DecayOrigin`.
+pub mod decay_origin {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Numeric value, the distance is the absolute difference
+        #[prost(double, tag = "1")]
+        Number(f64),
+        /// Geo point, the distance is in meters
+        #[prost(message, tag = "2")]
+        Geo(super::GeoPoint),
+        /// Datetime, the distance is in seconds
+        #[prost(string, tag = "3")]
+        Datetime(::prost::alloc::string::String),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecayParams {
+    /// Payload key of the numeric, datetime or geo value
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Origin of the decay, its type defines how the payload value is interpreted
+    #[prost(message, optional, tag = "2")]
+    pub origin: ::core::option::Option<DecayOrigin>,
+    /// Distance from the origin plus offset, at which the decay reaches `decay`
+    #[prost(double, tag = "3")]
+    pub scale: f64,
+    /// Distance from the origin, within which there is no decay. Default is 0.0
+    #[prost(double, optional, tag = "4")]
+    pub offset: ::core::option::Option<f64>,
+    /// Value of the decay at the distance scale plus offset, between 0.0 and 1.0. Default is 0.5
+    #[prost(double, optional, tag = "5")]
+    pub decay: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoDistance {
+    /// Payload key of the geo value
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Origin, from which the distance is measured
+    #[prost(message, optional, tag = "2")]
+    pub origin: ::core::option::Option<GeoPoint>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Mmr {
+    /// Vector, to which the relevance of the candidates is measured
+    #[prost(message, optional, tag = "1")]
+    pub vector: ::core::option::Option<VectorInput>,
+    /// Weight of the relevance, from 0.0 for only diversity to 1.0 for only relevance
+    #[prost(float, tag = "2")]
+    pub lambda: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Formula {
+    /// Expression, which computes the score of a point
+    #[prost(message, optional, tag = "1")]
+    pub expression: ::core::option::Option<Expression>,
+    /// Values of payload variables, which are missing in a point
+    #[prost(map = "string, double", tag = "2")]
+    pub defaults: ::std::collections::HashMap<::prost::alloc::string::String, f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Query {
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
+    pub variant: ::core::option::Option<query::Variant>,
+}
+/// Nested message and enum types in `Query`.
+pub mod query {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Variant {
+        /// Find the nearest neighbors to this vector.
+        #[prost(message, tag = "1")]
+        Nearest(super::VectorInput),
+        /// Use multiple positive and negative vectors to find the results.
+        #[prost(message, tag = "2")]
+        Recommend(super::RecommendInput),
+        /// Search for nearest points, but constrain the search space with context
+        #[prost(message, tag = "3")]
+        Discover(super::DiscoverInput),
+        /// Return points that live in positive areas.
+        #[prost(message, tag = "4")]
+        Context(super::ContextInput),
+        /// Order the points by a payload field.
+        #[prost(message, tag = "5")]
+        OrderBy(super::OrderBy),
         /// Fuse the results of multiple prefetches.
         #[prost(enumeration = "super::Fusion", tag = "6")]
         Fusion(i32),
+        /// Fuse the results of multiple prefetches, with parameters of the fusion method.
+        #[prost(message, tag = "7")]
+        FusionParams(super::FusionParams),
+        /// Score the results of prefetches with a formula.
+        #[prost(message, tag = "8")]
+        Formula(super::Formula),
+        /// Select diverse results of prefetches with maximal marginal relevance.
+        #[prost(message, tag = "9")]
+        Mmr(super::Mmr),
+        /// Sample points which satisfy the filter.
+        #[prost(enumeration = "super::Sample", tag = "10")]
+        Sample(i32),
     }
 }
 #[derive(serde::Serialize)]
@@ -4946,6 +5586,15 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// If `true` - describe how each segment of each shard replica executes the query. Not supported in batch requests
+    #[prost(bool, optional, tag = "16")]
+    pub explain: ::core::option::Option<bool>,
+    /// If `true` - measure time spent on each stage of the query
+    #[prost(bool, optional, tag = "17")]
+    pub profile: ::core::option::Option<bool>,
+    /// Continue right after the last point of the page, which returned this token
+    #[prost(string, optional, tag = "18")]
+    pub page_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4969,6 +5618,125 @@ pub struct QueryBatchPoints {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GroupOrderBy {
+    /// Payload key to order groups by. The value is taken from the best hit of each group
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Direction of ordering. Default is ascending
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryPointGroups {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Sub-requests to perform first. If present, the query will be performed on the results of the prefetches.
+    #[prost(message, repeated, tag = "2")]
+    pub prefetch: ::prost::alloc::vec::Vec<PrefetchQuery>,
+    /// Query to perform. If missing, returns points ordered by their IDs.
+    #[prost(message, optional, tag = "3")]
+    pub query: ::core::option::Option<Query>,
+    /// Define which vector to use for querying. If missing, the default vector is used.
+    #[prost(string, optional, tag = "4")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// Filter conditions - return only those points that satisfy the specified conditions.
+    #[prost(message, optional, tag = "5")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// Search params for when there is no prefetch.
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub params: ::core::option::Option<SearchParams>,
+    /// Return points with scores better than this threshold.
+    #[prost(float, optional, tag = "7")]
+    pub score_threshold: ::core::option::Option<f32>,
+    /// Options for specifying which payload to include or not
+    #[prost(message, optional, tag = "8")]
+    pub with_payload: ::core::option::Option<WithPayloadSelector>,
+    /// Options for specifying which vectors to include into response
+    #[prost(message, optional, tag = "9")]
+    pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// The location to use for IDs lookup, if not specified - use the current collection and the 'using' vector
+    #[prost(message, optional, tag = "10")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Max number of groups. Default is 10.
+    #[prost(uint64, optional, tag = "11")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Maximum amount of points to return per group. Default is 3.
+    #[prost(uint64, optional, tag = "12")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub group_size: ::core::option::Option<u64>,
+    /// Payload field to group by, must be a string or number field. If there are multiple values for the field, all of them will be used. One point can be in multiple groups.
+    #[prost(string, tag = "13")]
+    #[validate(length(min = 1))]
+    pub group_by: ::prost::alloc::string::String,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "14")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Options for specifying how to use the group id to lookup points in another collection
+    #[prost(message, optional, tag = "15")]
+    pub with_lookup: ::core::option::Option<WithLookup>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "16")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "17")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// More payload fields to group by. Groups are formed by each combination of values of `group_by` and these fields, and their id is compound. Can't be used together with `with_lookup`
+    #[prost(string, repeated, tag = "18")]
+    pub then_group_by: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// How to compute the score of a group from its hits. Default is the best hit. Sum, Avg and Count are approximate, as only the hits collected for the returned groups are taken into account
+    #[prost(enumeration = "GroupScoring", optional, tag = "19")]
+    pub group_scoring: ::core::option::Option<i32>,
+    /// Rank groups by this payload field of their best hit first, then by their score. Groups without the field go last
+    #[prost(message, optional, tag = "20")]
+    pub group_order_by: ::core::option::Option<GroupOrderBy>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPoints {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Filter conditions - only sample points that satisfy the specified conditions
+    #[prost(message, optional, tag = "2")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    /// How many points to sample, at most 1000. Default is 10
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_2_max_1000")]
+    pub sample: ::core::option::Option<u64>,
+    /// How many neighbours per sampled point to return, at most 1000. Default is all the other sampled points
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1_max_1000")]
+    pub limit: ::core::option::Option<u64>,
+    /// Name of the vector to compare. Default is the unnamed vector
+    #[prost(string, optional, tag = "5")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    /// If set, overrides global timeout setting for this request. Unit is seconds.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub timeout: ::core::option::Option<u64>,
+    /// Options for specifying read consistency guarantees
+    #[prost(message, optional, tag = "7")]
+    pub read_consistency: ::core::option::Option<ReadConsistency>,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[prost(message, optional, tag = "8")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointsUpdateOperation {
     #[prost(
         oneof = "points_update_operation::Operation",
@@ -5157,7 +5925,7 @@ pub struct UpdateResult {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OrderValue {
-    #[prost(oneof = "order_value::Variant", tags = "1, 2")]
+    #[prost(oneof = "order_value::Variant", tags = "1, 2, 3, 4")]
     pub variant: ::core::option::Option<order_value::Variant>,
 }
 /// Nested message and enum types in `OrderValue`.
@@ -5170,11 +5938,23 @@ pub mod order_value {
         Int(i64),
         #[prost(double, tag = "2")]
         Float(f64),
+        #[prost(string, tag = "3")]
+        Keyword(::prost::alloc::string::String),
+        /// Values of each key of a compound order
+        #[prost(message, tag = "4")]
+        Compound(super::OrderValues),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderValues {
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<OrderValue>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ScoredPoint {
     /// Point id
     #[prost(message, optional, tag = "1")]
@@ -5202,7 +5982,7 @@ pub struct ScoredPoint {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GroupId {
-    #[prost(oneof = "group_id::Kind", tags = "1, 2, 3")]
+    #[prost(oneof = "group_id::Kind", tags = "1, 2, 3, 4")]
     pub kind: ::core::option::Option<group_id::Kind>,
 }
 /// Nested message and enum types in `GroupId`.
@@ -5220,11 +6000,21 @@ pub mod group_id {
         /// Represents a string value.
         #[prost(string, tag = "3")]
         StringValue(::prost::alloc::string::String),
+        /// Represents the values of each field, when grouping by several fields
+        #[prost(message, tag = "4")]
+        CompoundValue(super::CompoundGroupId),
     }
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CompoundGroupId {
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<GroupId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointGroup {
     /// Group id
     #[prost(message, optional, tag = "1")]
@@ -5253,6 +6043,12 @@ pub struct SearchResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Execution details per shard, if requested
+    #[prost(message, repeated, tag = "3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplanation>,
+    /// Token of the next page, if there can be more results
+    #[prost(string, optional, tag = "4")]
+    pub next_page_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5263,6 +6059,15 @@ pub struct QueryResponse {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Execution details per shard, if requested
+    #[prost(message, repeated, tag = "3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplanation>,
+    /// Time spent on each stage of the query, if requested
+    #[prost(message, optional, tag = "4")]
+    pub profile: ::core::option::Option<QueryProfile>,
+    /// Token of the next page, if there can be more results
+    #[prost(string, optional, tag = "5")]
+    pub next_page_token: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5304,12 +6109,104 @@ pub struct SearchGroupsResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryGroupsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<GroupsResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CountResponse {
     #[prost(message, optional, tag = "1")]
     pub result: ::core::option::Option<CountResult>,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Execution details per shard, if requested
+    #[prost(message, repeated, tag = "3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplanation>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<AggregateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPair {
+    /// Sampled point
+    #[prost(message, optional, tag = "1")]
+    pub a: ::core::option::Option<PointId>,
+    /// Neighbour of the sampled point
+    #[prost(message, optional, tag = "2")]
+    pub b: ::core::option::Option<PointId>,
+    /// Score of the neighbour
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairs {
+    /// Neighbour pairs, grouped by the sampled point
+    #[prost(message, repeated, tag = "1")]
+    pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsets {
+    /// Row offsets of the scores in the sparse matrix, indexes of the sampled points in `ids`
+    #[prost(uint64, repeated, tag = "1")]
+    pub offsets_row: ::prost::alloc::vec::Vec<u64>,
+    /// Column offsets of the scores in the sparse matrix, indexes of the neighbours in `ids`
+    #[prost(uint64, repeated, tag = "2")]
+    pub offsets_col: ::prost::alloc::vec::Vec<u64>,
+    /// Scores of the neighbours
+    #[prost(float, repeated, tag = "3")]
+    pub scores: ::prost::alloc::vec::Vec<f32>,
+    /// Ids of the sampled points
+    #[prost(message, repeated, tag = "4")]
+    pub ids: ::prost::alloc::vec::Vec<PointId>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixPairs>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsetsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixOffsets>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5330,6 +6227,12 @@ pub struct ScrollResponse {
 pub struct CountResult {
     #[prost(uint64, tag = "1")]
     pub count: u64,
+    /// Number of points for each value of the `group_by` key, most frequent first
+    #[prost(message, repeated, tag = "2")]
+    pub groups: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Estimated number of distinct values of the `distinct` key
+    #[prost(uint64, optional, tag = "3")]
+    pub distinct: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5538,6 +6441,9 @@ pub struct FieldCondition {
     /// Check if datetime is within a given range
     #[prost(message, optional, tag = "8")]
     pub datetime_range: ::core::option::Option<DatetimeRange>,
+    /// Check spatial relation of the stored geo shape with a given shape
+    #[prost(message, optional, tag = "9")]
+    pub geo_shape: ::core::option::Option<GeoShapeCondition>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5672,6 +6578,36 @@ pub struct GeoPolygon {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoShape {
+    #[prost(oneof = "geo_shape::Shape", tags = "1, 2")]
+    pub shape: ::core::option::Option<geo_shape::Shape>,
+}
+/// Nested message and enum types in `GeoShape`.
+pub mod geo_shape {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Shape {
+        #[prost(message, tag = "1")]
+        Point(super::GeoPoint),
+        #[prost(message, tag = "2")]
+        Polygon(super::GeoPolygon),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoShapeCondition {
+    /// How stored shapes should relate to the given shape
+    #[prost(enumeration = "GeoShapeRelation", tag = "1")]
+    pub relation: i32,
+    /// Shape to compare stored shapes with
+    #[prost(message, optional, tag = "2")]
+    pub shape: ::core::option::Option<GeoShape>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ValuesCount {
     #[prost(uint64, optional, tag = "1")]
     pub lt: ::core::option::Option<u64>,
@@ -5809,6 +6745,7 @@ pub enum FieldType {
     Text = 4,
     Bool = 5,
     Datetime = 6,
+    GeoShape = 7,
 }
 impl FieldType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5824,6 +6761,7 @@ impl FieldType {
             FieldType::Text => "FieldTypeText",
             FieldType::Bool => "FieldTypeBool",
             FieldType::Datetime => "FieldTypeDatetime",
+            FieldType::GeoShape => "FieldTypeGeoShape",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5836,6 +6774,7 @@ impl FieldType {
             "FieldTypeText" => Some(Self::Text),
             "FieldTypeBool" => Some(Self::Bool),
             "FieldTypeDatetime" => Some(Self::Datetime),
+            "FieldTypeGeoShape" => Some(Self::GeoShape),
             _ => None,
         }
     }
@@ -5867,6 +6806,112 @@ impl Direction {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SearchStrategy {
+    /// Check every point of the segment
+    FullScan = 0,
+    /// Select points with the payload index, then check them
+    PayloadIndex = 1,
+    /// Traverse the HNSW graph, checking the filter on visited points
+    Graph = 2,
+    /// Traverse the inverted index of sparse vectors, checking the filter on visited points
+    InvertedIndex = 3,
+}
+impl SearchStrategy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SearchStrategy::FullScan => "FullScan",
+            SearchStrategy::PayloadIndex => "PayloadIndex",
+            SearchStrategy::Graph => "Graph",
+            SearchStrategy::InvertedIndex => "InvertedIndex",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "FullScan" => Some(Self::FullScan),
+            "PayloadIndex" => Some(Self::PayloadIndex),
+            "Graph" => Some(Self::Graph),
+            "InvertedIndex" => Some(Self::InvertedIndex),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ProfileStage {
+    /// Whole query, as executed by the collection
+    Query = 0,
+    /// Fetching vectors of points, referenced by id in the query
+    Lookup = 1,
+    /// Query on a single shard, including all of its replicas
+    Shard = 2,
+    /// Query on the replica of a shard, located on this peer
+    Local = 3,
+    /// Round trip to the replica of a shard, located on another peer
+    Remote = 4,
+    /// Nested prefetch, including its own prefetches
+    Prefetch = 5,
+    /// Scoring points against vectors
+    Scoring = 6,
+    /// Selecting points by filter and payload order, without vector scoring
+    Filtering = 7,
+    /// Scoring results of prefetches with the query
+    Rescoring = 8,
+    /// Fusion of results of prefetches
+    Fusion = 9,
+    /// Fetching payload and vectors of the selected points
+    Fetching = 10,
+    /// Merging results of shards
+    Merging = 11,
+}
+impl ProfileStage {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ProfileStage::Query => "Query",
+            ProfileStage::Lookup => "Lookup",
+            ProfileStage::Shard => "Shard",
+            ProfileStage::Local => "Local",
+            ProfileStage::Remote => "Remote",
+            ProfileStage::Prefetch => "Prefetch",
+            ProfileStage::Scoring => "Scoring",
+            ProfileStage::Filtering => "Filtering",
+            ProfileStage::Rescoring => "Rescoring",
+            ProfileStage::Fusion => "Fusion",
+            ProfileStage::Fetching => "Fetching",
+            ProfileStage::Merging => "Merging",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Query" => Some(Self::Query),
+            "Lookup" => Some(Self::Lookup),
+            "Shard" => Some(Self::Shard),
+            "Local" => Some(Self::Local),
+            "Remote" => Some(Self::Remote),
+            "Prefetch" => Some(Self::Prefetch),
+            "Scoring" => Some(Self::Scoring),
+            "Filtering" => Some(Self::Filtering),
+            "Rescoring" => Some(Self::Rescoring),
+            "Fusion" => Some(Self::Fusion),
+            "Fetching" => Some(Self::Fetching),
+            "Merging" => Some(Self::Merging),
+            _ => None,
+        }
+    }
+}
 /// How to use positive and negative vectors to find the results, default is `AverageVector`.
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -5879,6 +6924,14 @@ pub enum RecommendStrategy {
     /// examples, its score is then chosen from the `max(max_pos_score, max_neg_score)`.
     /// If the `max_neg_score` is chosen then it is squared and negated.
     BestScore = 1,
+    /// Uses custom search objective. Each candidate is compared against all
+    /// examples, its score is the sum of the similarities to the positives
+    /// minus the sum of the similarities to the negatives.
+    SumScores = 2,
+    /// Uses custom search objective. Similarities of each candidate to the weighted
+    /// examples are folded into `ln(sum(w_pos * exp(pos_score))) - ln(sum(w_neg * exp(neg_score)))`,
+    /// so that the closest examples dominate the score.
+    Contrastive = 3,
 }
 impl RecommendStrategy {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5889,6 +6942,8 @@ impl RecommendStrategy {
         match self {
             RecommendStrategy::AverageVector => "AverageVector",
             RecommendStrategy::BestScore => "BestScore",
+            RecommendStrategy::SumScores => "SumScores",
+            RecommendStrategy::Contrastive => "Contrastive",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5896,6 +6951,51 @@ impl RecommendStrategy {
         match value {
             "AverageVector" => Some(Self::AverageVector),
             "BestScore" => Some(Self::BestScore),
+            "SumScores" => Some(Self::SumScores),
+            "Contrastive" => Some(Self::Contrastive),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CalendarInterval {
+    Minute = 0,
+    Hour = 1,
+    Day = 2,
+    /// Weeks start on Monday
+    Week = 3,
+    Month = 4,
+    Quarter = 5,
+    Year = 6,
+}
+impl CalendarInterval {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CalendarInterval::Minute => "Minute",
+            CalendarInterval::Hour => "Hour",
+            CalendarInterval::Day => "Day",
+            CalendarInterval::Week => "Week",
+            CalendarInterval::Month => "Month",
+            CalendarInterval::Quarter => "Quarter",
+            CalendarInterval::Year => "Year",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Minute" => Some(Self::Minute),
+            "Hour" => Some(Self::Hour),
+            "Day" => Some(Self::Day),
+            "Week" => Some(Self::Week),
+            "Month" => Some(Self::Month),
+            "Quarter" => Some(Self::Quarter),
+            "Year" => Some(Self::Year),
             _ => None,
         }
     }
@@ -5906,6 +7006,8 @@ impl RecommendStrategy {
 pub enum Fusion {
     /// Reciprocal Rank Fusion
     Rrf = 0,
+    /// Distribution-Based Score Fusion
+    Dbsf = 1,
 }
 impl Fusion {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5915,12 +7017,105 @@ impl Fusion {
     pub fn as_str_name(&self) -> &'static str {
         match self {
             Fusion::Rrf => "RRF",
+            Fusion::Dbsf => "DBSF",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "RRF" => Some(Self::Rrf),
+            "DBSF" => Some(Self::Dbsf),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Sample {
+    /// Uniformly random sample of points
+    Random = 0,
+}
+impl Sample {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Sample::Random => "Random",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Random" => Some(Self::Random),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ScoreNormalization {
+    /// Use the mean and the 3rd standard deviation of the scores as extremes
+    Distribution = 0,
+    /// Use the minimum and maximum scores as extremes
+    MinMax = 1,
+}
+impl ScoreNormalization {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ScoreNormalization::Distribution => "Distribution",
+            ScoreNormalization::MinMax => "MinMax",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Distribution" => Some(Self::Distribution),
+            "MinMax" => Some(Self::MinMax),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum GroupScoring {
+    /// Score of the best hit in the group
+    Best = 0,
+    /// Sum of the scores of the returned hits in the group
+    Sum = 1,
+    /// Average score of the returned hits in the group
+    Avg = 2,
+    /// Number of the returned hits in the group, more hits rank higher
+    Count = 3,
+}
+impl GroupScoring {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            GroupScoring::Best => "Best",
+            GroupScoring::Sum => "Sum",
+            GroupScoring::Avg => "Avg",
+            GroupScoring::Count => "Count",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Best" => Some(Self::Best),
+            "Sum" => Some(Self::Sum),
+            "Avg" => Some(Self::Avg),
+            "Count" => Some(Self::Count),
             _ => None,
         }
     }
@@ -5961,6 +7156,39 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum GeoShapeRelation {
+    /// Stored shape and condition shape have at least one common point
+    Intersects = 0,
+    /// Stored shape lies completely inside the condition shape
+    Within = 1,
+    /// Stored shape completely covers the condition shape
+    Contains = 2,
+}
+impl GeoShapeRelation {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            GeoShapeRelation::Intersects => "Intersects",
+            GeoShapeRelation::Within => "Within",
+            GeoShapeRelation::Contains => "Contains",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Intersects" => Some(Self::Intersects),
+            "Within" => Some(Self::Within),
+            "Contains" => Some(Self::Contains),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -6562,16 +7790,135 @@ pub mod points_client {
                 "/qdrant.Points/DiscoverBatch",
             );
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.Points", "DiscoverBatch"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "DiscoverBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Count points in collection with given filtering conditions
+        pub async fn count(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CountPoints>,
+        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Count");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Count"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Perform multiple update operations in one request
+        pub async fn update_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/UpdateBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPoints>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Query");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Query"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Universally query points in a batch fashion. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryBatchPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/QueryBatch");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "QueryBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Universally query points and group results by a payload field. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        pub async fn query_groups(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryPointGroups>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryGroupsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/QueryGroups");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "QueryGroups"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Count points in collection with given filtering conditions
-        pub async fn count(
+        /// Count points per value of the given payload key
+        pub async fn facet(
             &mut self,
-            request: impl tonic::IntoRequest<super::CountPoints>,
-        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::FacetCounts>,
+        ) -> std::result::Result<
+            tonic::Response<super::FacetResponse>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -6582,18 +7929,18 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Count");
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Facet");
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Count"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Facet"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Perform multiple update operations in one request
-        pub async fn update_batch(
+        /// Compute statistics and histogram of the values of the given payload key
+        pub async fn aggregate(
             &mut self,
-            request: impl tonic::IntoRequest<super::UpdateBatchPoints>,
+            request: impl tonic::IntoRequest<super::AggregatePoints>,
         ) -> std::result::Result<
-            tonic::Response<super::UpdateBatchResponse>,
+            tonic::Response<super::AggregateResponse>,
             tonic::Status,
         > {
             self.inner
@@ -6606,19 +7953,20 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.Points/UpdateBatch",
-            );
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Aggregate");
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Aggregate"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Universally query points. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
-        pub async fn query(
+        /// Compute distance matrix for sampled points with a pair based output format
+        pub async fn search_matrix_pairs(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryPoints>,
-        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -6629,18 +7977,21 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Query");
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixPairs",
+            );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Query"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixPairs"));
             self.inner.unary(req, path, codec).await
         }
         ///
-        /// Universally query points in a batch fashion. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
-        pub async fn query_batch(
+        /// Compute distance matrix for sampled points with an offset based output format
+        pub async fn search_matrix_offsets(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryBatchPoints>,
+            request: impl tonic::IntoRequest<super::SearchMatrixPoints>,
         ) -> std::result::Result<
-            tonic::Response<super::QueryBatchResponse>,
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
             tonic::Status,
         > {
             self.inner
@@ -6653,9 +8004,12 @@ pub mod points_client {
                     )
                 })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/QueryBatch");
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SearchMatrixOffsets",
+            );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "QueryBatch"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
             self.inner.unary(req, path, codec).await
         }
     }
@@ -6882,6 +8236,51 @@ pub mod points_server {
             tonic::Response<super::QueryBatchResponse>,
             tonic::Status,
         >;
+        ///
+        /// Universally query points and group results by a payload field. This endpoint covers all capabilities of search, recommend, discover, filters. But also enables hybrid and multi-stage queries.
+        async fn query_groups(
+            &self,
+            request: tonic::Request<super::QueryPointGroups>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryGroupsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Count points per value of the given payload key
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCounts>,
+        ) -> std::result::Result<
+            tonic::Response<super::FacetResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Compute statistics and histogram of the values of the given payload key
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Compute distance matrix for sampled points with a pair based output format
+        async fn search_matrix_pairs(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixPairsResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Compute distance matrix for sampled points with an offset based output format
+        async fn search_matrix_offsets(
+            &self,
+            request: tonic::Request<super::SearchMatrixPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchMatrixOffsetsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -7994,21 +9393,247 @@ pub mod points_server {
                 }
                 "/qdrant.Points/QueryBatch" => {
                     #[allow(non_camel_case_types)]
-                    struct QueryBatchSvc<T: Points>(pub Arc<T>);
-                    impl<T: Points> tonic::server::UnaryService<super::QueryBatchPoints>
-                    for QueryBatchSvc<T> {
-                        type Response = super::QueryBatchResponse;
+                    struct QueryBatchSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryBatchPoints>
+                    for QueryBatchSvc<T> {
+                        type Response = super::QueryBatchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryBatchPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::query_batch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QueryBatchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/QueryGroups" => {
+                    #[allow(non_camel_case_types)]
+                    struct QueryGroupsSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::QueryPointGroups>
+                    for QueryGroupsSvc<T> {
+                        type Response = super::QueryGroupsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryPointGroups>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::query_groups(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QueryGroupsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::FacetCounts>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCounts>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Aggregate" => {
+                    #[allow(non_camel_case_types)]
+                    struct AggregateSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::AggregatePoints>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AggregatePoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::aggregate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AggregateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchMatrixPairs" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMatrixPairsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixPairsSvc<T> {
+                        type Response = super::SearchMatrixPairsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SearchMatrixPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::search_matrix_pairs(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SearchMatrixPairsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SearchMatrixOffsets" => {
+                    #[allow(non_camel_case_types)]
+                    struct SearchMatrixOffsetsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::SearchMatrixPoints>
+                    for SearchMatrixOffsetsSvc<T> {
+                        type Response = super::SearchMatrixOffsetsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QueryBatchPoints>,
+                            request: tonic::Request<super::SearchMatrixPoints>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as Points>::query_batch(&inner, request).await
+                                <T as Points>::search_matrix_offsets(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -8020,7 +9645,7 @@ pub mod points_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = QueryBatchSvc(inner);
+                        let method = SearchMatrixOffsetsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -8317,6 +9942,22 @@ pub struct RecoQuery {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedRecoQuery {
+    #[prost(message, repeated, tag = "1")]
+    #[validate]
+    pub positives: ::prost::alloc::vec::Vec<Vector>,
+    #[prost(message, repeated, tag = "2")]
+    #[validate]
+    pub negatives: ::prost::alloc::vec::Vec<Vector>,
+    #[prost(float, repeated, tag = "3")]
+    pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+    #[prost(float, repeated, tag = "4")]
+    pub negative_weights: ::prost::alloc::vec::Vec<f32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ContextPair {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -8350,7 +9991,7 @@ pub struct ContextQuery {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QueryEnum {
-    #[prost(oneof = "query_enum::Query", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "query_enum::Query", tags = "1, 2, 3, 4, 5, 6")]
     pub query: ::core::option::Option<query_enum::Query>,
 }
 /// Nested message and enum types in `QueryEnum`.
@@ -8371,6 +10012,12 @@ pub mod query_enum {
         /// Use only the context to find points that minimize loss against negative examples
         #[prost(message, tag = "4")]
         Context(super::ContextQuery),
+        /// Recommend points by the sum of similarities to positive examples minus negative examples
+        #[prost(message, tag = "5")]
+        RecommendSumScores(super::RecoQuery),
+        /// Recommend points by contrasting the weighted positive examples against the weighted negative ones
+        #[prost(message, tag = "6")]
+        RecommendContrastive(super::WeightedRecoQuery),
     }
 }
 /// This is only used internally, so it makes more sense to add it here rather than in points.proto
@@ -8466,6 +10113,138 @@ pub struct CountPointsInternal {
     #[prost(uint32, optional, tag = "2")]
     pub shard_id: ::core::option::Option<u32>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountResultInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<CountResult>,
+    /// Sketch of the distinct values, to merge results of shards
+    #[prost(message, optional, tag = "2")]
+    pub distinct_sketch: ::core::option::Option<CardinalitySketch>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CardinalitySketch {
+    /// Hashes of all the values, while there are few of them
+    #[prost(fixed64, repeated, tag = "1")]
+    pub hashes: ::prost::alloc::vec::Vec<u64>,
+    /// HyperLogLog registers, once there are many values
+    #[prost(bytes = "vec", tag = "2")]
+    pub registers: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<CountResultInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    /// Execution details of the requested shard, if requested
+    #[prost(message, repeated, tag = "3")]
+    pub explain: ::prost::alloc::vec::Vec<ShardExplanation>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FacetCountsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub facet_counts: ::core::option::Option<FacetCounts>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistogramBucketing {
+    /// Width of a bucket
+    #[prost(double, tag = "1")]
+    pub interval: f64,
+    /// Start of the bucket number zero
+    #[prost(double, tag = "2")]
+    pub offset: f64,
+    /// Number of buckets, if limited
+    #[prost(uint64, optional, tag = "3")]
+    pub buckets: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DateHistogramBucketing {
+    #[prost(enumeration = "CalendarInterval", tag = "1")]
+    pub interval: i32,
+    /// IANA name of the time zone
+    #[prost(string, tag = "2")]
+    pub time_zone: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub facet_key: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregatePointsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    #[validate]
+    pub filter: ::core::option::Option<Filter>,
+    #[prost(message, optional, tag = "4")]
+    pub histogram: ::core::option::Option<HistogramBucketing>,
+    #[prost(uint32, optional, tag = "5")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "6")]
+    pub date_histogram: ::core::option::Option<DateHistogramBucketing>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NumericAggregation {
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+    #[prost(double, optional, tag = "2")]
+    pub min: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "3")]
+    pub max: ::core::option::Option<f64>,
+    #[prost(double, tag = "4")]
+    pub sum: f64,
+    /// Number of values per bucket
+    #[prost(map = "int64, uint64", tag = "5")]
+    pub histogram: ::std::collections::HashMap<i64, u64>,
+    /// Points per date histogram bucket
+    #[prost(message, repeated, tag = "6")]
+    pub date_histogram: ::prost::alloc::vec::Vec<DateHistogramCounts>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DateHistogramCounts {
+    /// Start of the bucket, timestamp in microseconds
+    #[prost(int64, tag = "1")]
+    pub bucket: i64,
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub facet: ::prost::alloc::vec::Vec<FacetHit>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AggregateResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<NumericAggregation>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 /// A bare vector. No id reference here.
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8493,7 +10272,7 @@ pub mod raw_vector {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RawQuery {
-    #[prost(oneof = "raw_query::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "raw_query::Variant", tags = "1, 2, 3, 4, 5, 6")]
     pub variant: ::core::option::Option<raw_query::Variant>,
 }
 /// Nested message and enum types in `RawQuery`.
@@ -8510,6 +10289,19 @@ pub mod raw_query {
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct WeightedRecommend {
+        #[prost(message, repeated, tag = "1")]
+        pub positives: ::prost::alloc::vec::Vec<super::RawVector>,
+        #[prost(message, repeated, tag = "2")]
+        pub negatives: ::prost::alloc::vec::Vec<super::RawVector>,
+        #[prost(float, repeated, tag = "3")]
+        pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+        #[prost(float, repeated, tag = "4")]
+        pub negative_weights: ::prost::alloc::vec::Vec<f32>,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct RawContextPair {
         #[prost(message, optional, tag = "1")]
         pub positive: ::core::option::Option<super::RawVector>,
@@ -8548,6 +10340,12 @@ pub mod raw_query {
         /// Use only the context to find points that minimize loss against negative examples
         #[prost(message, tag = "4")]
         Context(Context),
+        /// Recommend points by the sum of similarities to positive examples minus negative examples
+        #[prost(message, tag = "5")]
+        RecommendSumScores(Recommend),
+        /// Recommend points by contrasting the weighted positive examples against the weighted negative ones
+        #[prost(message, tag = "6")]
+        RecommendContrastive(WeightedRecommend),
     }
 }
 #[derive(serde::Serialize)]
@@ -8581,7 +10379,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4, 5, 6, 7")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -8599,11 +10397,34 @@ pub mod query_shard_points {
             /// Order by a field
             #[prost(message, tag = "3")]
             OrderBy(super::super::OrderBy),
+            /// One of the fusion methods, with its parameters
+            #[prost(message, tag = "4")]
+            FusionParams(super::super::FusionParams),
+            /// Score with a formula
+            #[prost(message, tag = "5")]
+            Formula(super::super::Formula),
+            /// Diversify with maximal marginal relevance
+            #[prost(message, tag = "6")]
+            Mmr(super::Mmr),
+            /// Sample points randomly
+            #[prost(enumeration = "super::super::Sample", tag = "7")]
+            Sample(i32),
         }
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Mmr {
+        /// Vector, to which the relevance of the candidates is measured
+        #[prost(message, optional, tag = "1")]
+        pub vector: ::core::option::Option<super::RawVector>,
+        /// Weight of the relevance, from 0.0 for only diversity to 1.0 for only relevance
+        #[prost(float, tag = "2")]
+        pub lambda: f32,
+    }
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Prefetch {
         #[prost(message, repeated, tag = "1")]
         pub prefetch: ::prost::alloc::vec::Vec<Prefetch>,
@@ -8636,6 +10457,12 @@ pub struct QueryBatchPointsInternal {
     #[prost(uint64, optional, tag = "4")]
     #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
     pub timeout: ::core::option::Option<u64>,
+    /// If `true` - measure time spent on each stage of the query
+    #[prost(bool, optional, tag = "5")]
+    pub profile: ::core::option::Option<bool>,
+    /// If `true` - describe how each segment executes the query, as a part of the profile
+    #[prost(bool, optional, tag = "6")]
+    pub explain: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -8660,6 +10487,9 @@ pub struct QueryBatchResponseInternal {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Time spent on each stage of the query, if requested
+    #[prost(message, optional, tag = "3")]
+    pub profile: ::core::option::Option<QueryProfile>,
 }
 /// Generated client implementations.
 pub mod points_internal_client {
@@ -9071,7 +10901,10 @@ pub mod points_internal_client {
         pub async fn count(
             &mut self,
             request: impl tonic::IntoRequest<super::CountPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
+        ) -> std::result::Result<
+            tonic::Response<super::CountResponseInternal>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -9161,6 +10994,56 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "QueryBatch"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn facet(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FacetCountsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::FacetResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Facet",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn aggregate(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AggregatePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Aggregate",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Aggregate"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -9261,7 +11144,10 @@ pub mod points_internal_server {
         async fn count(
             &self,
             request: tonic::Request<super::CountPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status>;
+        ) -> std::result::Result<
+            tonic::Response<super::CountResponseInternal>,
+            tonic::Status,
+        >;
         async fn recommend(
             &self,
             request: tonic::Request<super::RecommendPointsInternal>,
@@ -9280,6 +11166,20 @@ pub mod points_internal_server {
             tonic::Response<super::QueryBatchResponseInternal>,
             tonic::Status,
         >;
+        async fn facet(
+            &self,
+            request: tonic::Request<super::FacetCountsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::FacetResponse>,
+            tonic::Status,
+        >;
+        async fn aggregate(
+            &self,
+            request: tonic::Request<super::AggregatePointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::AggregateResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -9975,7 +11875,7 @@ pub mod points_internal_server {
                         T: PointsInternal,
                     > tonic::server::UnaryService<super::CountPointsInternal>
                     for CountSvc<T> {
-                        type Response = super::CountResponse;
+                        type Response = super::CountResponseInternal;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
@@ -10152,6 +12052,98 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/Facet" => {
+                    #[allow(non_camel_case_types)]
+                    struct FacetSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::FacetCountsInternal>
+                    for FacetSvc<T> {
+                        type Response = super::FacetResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FacetCountsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::facet(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FacetSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/Aggregate" => {
+                    #[allow(non_camel_case_types)]
+                    struct AggregateSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::AggregatePointsInternal>
+                    for AggregateSvc<T> {
+                        type Response = super::AggregateResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AggregatePointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::aggregate(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = AggregateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -203,6 +203,7 @@ impl Validate for grpc::FieldCondition {
             geo_radius,
             geo_polygon,
             values_count,
+            geo_shape,
         } = self;
 
         let all_fields_none = r#match.is_none()
@@ -211,6 +212,7 @@ impl Validate for grpc::FieldCondition {
             && geo_bounding_box.is_none()
             && geo_radius.is_none()
             && geo_polygon.is_none()
+            && geo_shape.is_none()
             && values_count.is_none();
 
         if all_fields_none {
@@ -228,6 +230,28 @@ impl Validate for grpc::FieldCondition {
 
 impl Validate for grpc::Vector {
     fn validate(&self) -> Result<(), ValidationErrors> {
+        let has_data = !self.data.is_empty() || self.indices.is_some();
+        if self.document.is_some() && (has_data || self.vectors_count.is_some()) {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "document",
+                ValidationError::new("`document` cannot be specified together with vector data"),
+            );
+            return Err(errors);
+        }
+        if self.image.is_some()
+            && (has_data || self.vectors_count.is_some() || self.document.is_some())
+        {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "image",
+                ValidationError::new(
+                    "`image` cannot be specified together with vector data or `document`",
+                ),
+            );
+            return Err(errors);
+        }
+
         match (&self.indices, self.vectors_count) {
             (Some(_), Some(_)) => {
                 let mut errors = ValidationErrors::new();
@@ -263,6 +287,8 @@ impl Validate for super::qdrant::query_enum::Query {
         match self {
             super::qdrant::query_enum::Query::NearestNeighbors(q) => q.validate(),
             super::qdrant::query_enum::Query::RecommendBestScore(q) => q.validate(),
+            super::qdrant::query_enum::Query::RecommendSumScores(q) => q.validate(),
+            super::qdrant::query_enum::Query::RecommendContrastive(q) => q.validate(),
             super::qdrant::query_enum::Query::Discover(q) => q.validate(),
             super::qdrant::query_enum::Query::Context(q) => q.validate(),
         }
@@ -279,6 +305,16 @@ pub fn validate_u32_range_min_1(value: &Option<u32>) -> Result<(), ValidationErr
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), None))
 }
 
+/// Validate the value is in `[1, 1000]` or `None`.
+pub fn validate_u64_range_min_1_max_1000(value: &Option<u64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(1), Some(1000)))
+}
+
+/// Validate the value is in `[2, 1000]` or `None`.
+pub fn validate_u64_range_min_2_max_1000(value: &Option<u64>) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |v| validate_range_generic(v, Some(2), Some(1000)))
+}
+
 /// Validate the value is in `[100, ]` or `None`.
 pub fn validate_u64_range_min_100(value: &Option<u64>) -> Result<(), ValidationError> {
     value.map_or(Ok(()), |v| validate_range_generic(v, Some(100), None))
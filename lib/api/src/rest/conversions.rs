@@ -1,7 +1,16 @@
+use segment::common::score_fusion::Normalization;
+use segment::data_types::formula as segment_formula;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use sparse::common::sparse_vector::SparseVector;
 
-use super::schema::{BatchVectorStruct, ScoredPoint, Vector, VectorStruct};
+use super::schema::{
+    AbsExpression, BatchVectorStruct, DecayParams, DivExpression, DivParams, ExpDecayExpression,
+    ExpExpression, Expression, FormulaQuery, GaussDecayExpression, GeoDistanceExpression,
+    GeoDistanceParams, LinDecayExpression, LnExpression, Log10Expression, MultExpression,
+    NegExpression, PowExpression, PowParams, ScoreNormalization, ScoredPoint, SqrtExpression,
+    SumExpression, Vector, VectorStruct,
+};
 use super::{NearestQuery, OrderByInterface, Query, QueryInterface};
 use crate::rest::{DenseVector, NamedVectorStruct};
 
@@ -29,6 +38,13 @@ impl From<Vector> for segment::data_types::vectors::Vector {
                     segment::data_types::vectors::MultiDenseVectorInternal::new_unchecked(vector),
                 )
             }
+            // Documents are encoded by the collection before the operation is applied,
+            // an unencoded document has no terms
+            Vector::Document(_) => {
+                segment::data_types::vectors::Vector::Sparse(SparseVector::default())
+            }
+            // Images are embedded by the collection before the operation is applied as well
+            Vector::Image(_) => segment::data_types::vectors::Vector::Dense(Vec::new()),
         }
     }
 }
@@ -217,6 +233,8 @@ impl From<OrderByInterface> for OrderBy {
                 key,
                 direction: None,
                 start_from: None,
+                geo_origin: None,
+                then_by: vec![],
             },
             OrderByInterface::Struct(order_by) => order_by,
         }
@@ -231,3 +249,107 @@ impl From<QueryInterface> for Query {
         }
     }
 }
+
+impl From<ScoreNormalization> for Normalization {
+    fn from(normalization: ScoreNormalization) -> Self {
+        match normalization {
+            ScoreNormalization::Distribution => Normalization::Distr,
+            ScoreNormalization::MinMax => Normalization::MinMax,
+        }
+    }
+}
+
+impl TryFrom<Expression> for segment_formula::Expression {
+    type Error = String;
+
+    fn try_from(expression: Expression) -> Result<Self, Self::Error> {
+        let boxed = |expression: Box<Expression>| Self::try_from(*expression).map(Box::new);
+        let all = |expressions: Vec<Expression>| {
+            expressions
+                .into_iter()
+                .map(Self::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let expression = match expression {
+            Expression::Constant(value) => Self::Constant(value),
+            Expression::Variable(variable) => Self::Variable(variable),
+            Expression::Sum(SumExpression { sum }) => Self::Sum(all(sum)?),
+            Expression::Mult(MultExpression { mult }) => Self::Mult(all(mult)?),
+            Expression::Neg(NegExpression { neg }) => Self::Neg(boxed(neg)?),
+            Expression::Div(DivExpression {
+                div:
+                    DivParams {
+                        left,
+                        right,
+                        by_zero_default,
+                    },
+            }) => Self::Div {
+                left: boxed(left)?,
+                right: boxed(right)?,
+                by_zero_default,
+            },
+            Expression::Abs(AbsExpression { abs }) => Self::Abs(boxed(abs)?),
+            Expression::Sqrt(SqrtExpression { sqrt }) => Self::Sqrt(boxed(sqrt)?),
+            Expression::Pow(PowExpression {
+                pow: PowParams { base, exponent },
+            }) => Self::Pow {
+                base: boxed(base)?,
+                exponent: boxed(exponent)?,
+            },
+            Expression::Exp(ExpExpression { exp }) => Self::Exp(boxed(exp)?),
+            Expression::Log10(Log10Expression { log10 }) => Self::Log10(boxed(log10)?),
+            Expression::Ln(LnExpression { ln }) => Self::Ln(boxed(ln)?),
+            Expression::LinDecay(LinDecayExpression { lin_decay }) => Self::Decay {
+                kind: segment_formula::DecayKind::Lin,
+                params: lin_decay.try_into()?,
+            },
+            Expression::ExpDecay(ExpDecayExpression { exp_decay }) => Self::Decay {
+                kind: segment_formula::DecayKind::Exp,
+                params: exp_decay.try_into()?,
+            },
+            Expression::GaussDecay(GaussDecayExpression { gauss_decay }) => Self::Decay {
+                kind: segment_formula::DecayKind::Gauss,
+                params: gauss_decay.try_into()?,
+            },
+            Expression::GeoDistance(GeoDistanceExpression {
+                geo_distance: GeoDistanceParams { key, origin },
+            }) => Self::GeoDistance { key, origin },
+        };
+        Ok(expression)
+    }
+}
+
+impl TryFrom<DecayParams> for segment_formula::DecayParams {
+    type Error = String;
+
+    fn try_from(value: DecayParams) -> Result<Self, Self::Error> {
+        let DecayParams {
+            key,
+            origin,
+            scale,
+            offset,
+            decay,
+        } = value;
+        let params = Self {
+            key,
+            origin,
+            scale,
+            offset: offset.unwrap_or(Self::DEFAULT_OFFSET),
+            decay: decay.unwrap_or(Self::DEFAULT_DECAY),
+        };
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+impl TryFrom<FormulaQuery> for segment_formula::FormulaQuery {
+    type Error = String;
+
+    fn try_from(value: FormulaQuery) -> Result<Self, Self::Error> {
+        let FormulaQuery { formula, defaults } = value;
+        Ok(Self {
+            formula: formula.try_into()?,
+            defaults,
+        })
+    }
+}
@@ -4,10 +4,13 @@ use std::str::FromStr;
 use common::types::ScoreType;
 use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
+use segment::data_types::formula::{DecayOrigin, Variable};
+use segment::data_types::groups::{GroupOrderBy, GroupScoring};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Condition, FieldCondition, Filter, Match, Payload, SearchParams, ShardKey,
+    Condition, FieldCondition, Filter, GeoPoint, Match, Payload, SearchParams, ShardKey,
     WithPayloadInterface, WithVector,
 };
 use serde::{Deserialize, Serialize};
@@ -26,6 +29,25 @@ pub enum Vector {
     Dense(DenseVector),
     Sparse(sparse::common::sparse_vector::SparseVector),
     MultiDense(MultiDenseVector),
+    Document(Document),
+    Image(Image),
+}
+
+/// Raw text, which the server encodes into a vector.
+/// Accepted for sparse vectors with configured `document` params,
+/// and for vectors with a configured embedding provider.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct Document {
+    /// Text of the document
+    pub text: String,
+}
+
+/// Image, which the server embeds into a dense vector.
+/// Only accepted for vectors with a configured embedding provider.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct Image {
+    /// URL or base64 encoded data of the image, passed to the embedding provider as is
+    pub image: String,
 }
 
 /// Full vector data per point separator with single and multiple vector modes
@@ -47,6 +69,8 @@ impl VectorStruct {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
                 Vector::MultiDense(vector) => vector.is_empty(),
+                Vector::Document(document) => document.text.is_empty(),
+                Vector::Image(image) => image.image.is_empty(),
             }),
         }
     }
@@ -194,11 +218,31 @@ fn fusion_example() -> Fusion {
 /// Fusion algorithm allows to combine results of multiple prefetches.
 /// Available fusion algorithms:
 /// * `rrf` - Rank Reciprocal Fusion
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+/// * `dbsf` - Distribution-Based Score Fusion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[schemars(example = "fusion_example")]
 pub enum Fusion {
     Rrf,
+    Dbsf,
+}
+
+/// How to normalize the scores of each prefetch before fusing them.
+/// * `distribution` - use the mean and the 3rd standard deviation of the scores as extremes
+/// * `min_max` - use the minimum and maximum scores as extremes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreNormalization {
+    Distribution,
+    MinMax,
+}
+
+/// Sampling method of the points.
+/// * `random` - uniformly random sample of the points, which satisfy the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Sample {
+    Random,
 }
 
 fn multi_dense_vector_example() -> MultiDenseVector {
@@ -218,6 +262,8 @@ pub enum VectorInput {
     #[schemars(example = "multi_dense_vector_example")]
     MultiDenseVector(MultiDenseVector),
     Id(segment::types::PointIdType),
+    Document(Document),
+    Image(Image),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -293,6 +339,9 @@ fn query_request_example() -> QueryRequest {
             lookup_from: Some(lookup_location_example()),
         },
         shard_key: Some(ShardKeySelector::ShardKey(ShardKey::from("region_1"))),
+        explain: None,
+        profile: None,
+        page_token: None,
     }
 }
 
@@ -303,6 +352,20 @@ pub struct QueryRequest {
     #[serde(flatten)]
     pub internal: QueryRequestInternal,
     pub shard_key: Option<ShardKeySelector>,
+    /// If true, describe how each segment of each shard replica has executed the searches and
+    /// filters of the query: the chosen strategy, estimated and actual filter cardinality, and the
+    /// time spent. Not supported in batch requests.
+    pub explain: Option<bool>,
+    /// If true, return the time spent on each stage of the query: prefetches, scoring,
+    /// filtering, rescoring, fusion, fetching and round trips to remote shards.
+    /// Ignored in batch requests.
+    pub profile: Option<bool>,
+    /// Continue the results right after the last point of the previous page, by passing the
+    /// `next_page_token` of its response. Pages never repeat points of the previous pages.
+    /// Changes made in between the requests only affect the points after the last one returned.
+    /// Must be used with the same query, and without offset.
+    /// Not supported for fusion, MMR and sample queries. Ignored in batch requests.
+    pub page_token: Option<String>,
 }
 
 fn query_request_batch_example() -> QueryRequestBatch {
@@ -336,6 +399,9 @@ fn query_request_batch_example() -> QueryRequestBatch {
                     lookup_from: Some(lookup_location_example()),
                 },
                 shard_key: Some(ShardKeySelector::ShardKey(ShardKey::from("region_1"))),
+                explain: None,
+                profile: None,
+                page_token: None,
             },
         ],
     }
@@ -380,6 +446,7 @@ fn query_response_example() -> QueryResponse {
                 order_value: Some(segment::data_types::order_by::OrderValue::from(1)),
             },
         ],
+        profile: None,
     }
 }
 
@@ -387,6 +454,9 @@ fn query_response_example() -> QueryResponse {
 #[schemars(example = "query_response_example")]
 pub struct QueryResponse {
     pub points: Vec<ScoredPoint>,
+    /// Time spent on each stage of the query, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<QueryProfile>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -416,6 +486,15 @@ pub enum Query {
 
     /// Fuse the results of multiple prefetches.
     Fusion(FusionQuery),
+
+    /// Score the results of multiple prefetches with a formula.
+    Formula(FormulaQuery),
+
+    /// Select diverse results of prefetches with maximal marginal relevance.
+    Mmr(MmrQuery),
+
+    /// Sample points which satisfy the filter.
+    Sample(SampleQuery),
 }
 
 fn nearest_query_example() -> NearestQuery {
@@ -470,6 +549,37 @@ pub struct ContextQuery {
     pub context: ContextInput,
 }
 
+fn mmr_query_example() -> MmrQuery {
+    MmrQuery {
+        mmr: MmrInput {
+            vector: VectorInput::DenseVector(vec![0.875, 0.140625, -0.15625, 0.96875]),
+            lambda: 0.7,
+        },
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(example = "mmr_query_example")]
+pub struct MmrQuery {
+    pub mmr: MmrInput,
+}
+
+/// Maximal marginal relevance: select the candidates from the prefetches one by one,
+/// balancing their similarity to the vector against their similarity to the already selected ones.
+///
+/// Scores of the results are their marginal relevance at the moment of the selection.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct MmrInput {
+    /// Vector, to which the relevance of the candidates is measured
+    #[validate]
+    pub vector: VectorInput,
+    /// Weight of the relevance, from 0.0 for only diversity to 1.0 for only relevance
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub lambda: f32,
+}
+
 fn order_by_query_example() -> OrderByQuery {
     OrderByQuery {
         order_by: OrderByInterface::Key(JsonPath::from_str("timestamp").unwrap()),
@@ -486,14 +596,206 @@ pub struct OrderByQuery {
 fn fusion_query_example() -> FusionQuery {
     FusionQuery {
         fusion: Fusion::Rrf,
+        weights: None,
+        normalization: None,
+        k: None,
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SampleQuery {
+    pub sample: Sample,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[schemars(example = "fusion_query_example")]
 pub struct FusionQuery {
     pub fusion: Fusion,
+    /// Multipliers of the scores of each prefetch, in the order of prefetches.
+    /// Default is 1.0 for each prefetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f32>>,
+    /// How to normalize the scores of each prefetch. Only for `dbsf`. Default is `distribution`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalization: Option<ScoreNormalization>,
+    /// Constant, added to the rank of each point. Larger values reduce the advantage of top ranks.
+    /// Only for `rrf`. Default is 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k: Option<u32>,
+}
+
+fn formula_query_example() -> FormulaQuery {
+    FormulaQuery {
+        formula: Expression::Sum(SumExpression {
+            sum: vec![
+                Expression::Variable(Variable::Score(0)),
+                Expression::Mult(MultExpression {
+                    mult: vec![
+                        Expression::Constant(0.1),
+                        Expression::Ln(LnExpression {
+                            ln: Box::new(Expression::Variable(Variable::Payload(JsonPath::new(
+                                "popularity",
+                            )))),
+                        }),
+                    ],
+                }),
+            ],
+        }),
+        defaults: HashMap::from([(JsonPath::new("popularity"), 1.0)]),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[schemars(example = "formula_query_example")]
+pub struct FormulaQuery {
+    /// Expression, which computes the score of each point returned by the prefetches
+    pub formula: Expression,
+    /// Values of payload variables, which are missing in a point.
+    /// Points without a value nor a default fail the query.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub defaults: HashMap<JsonPath, f64>,
+}
+
+/// Expression over the scores of prefetches, payload values and constants.
+///
+/// Scores are written as `$score` for the first prefetch, or `$score[<index>]` for any prefetch.
+/// A point, which is missing in a prefetch, has the score 0 there.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Expression {
+    Constant(f64),
+    Variable(Variable),
+    Sum(SumExpression),
+    Mult(MultExpression),
+    Neg(NegExpression),
+    Div(DivExpression),
+    Abs(AbsExpression),
+    Sqrt(SqrtExpression),
+    Pow(PowExpression),
+    Exp(ExpExpression),
+    Log10(Log10Expression),
+    Ln(LnExpression),
+    LinDecay(LinDecayExpression),
+    ExpDecay(ExpDecayExpression),
+    GaussDecay(GaussDecayExpression),
+    GeoDistance(GeoDistanceExpression),
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SumExpression {
+    pub sum: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MultExpression {
+    pub mult: Vec<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct NegExpression {
+    pub neg: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DivExpression {
+    pub div: DivParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DivParams {
+    pub left: Box<Expression>,
+    pub right: Box<Expression>,
+    /// Result of the division by zero. If not set, division by zero fails the query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by_zero_default: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AbsExpression {
+    pub abs: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SqrtExpression {
+    pub sqrt: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PowExpression {
+    pub pow: PowParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct PowParams {
+    pub base: Box<Expression>,
+    pub exponent: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExpExpression {
+    pub exp: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Log10Expression {
+    pub log10: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LnExpression {
+    pub ln: Box<Expression>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct LinDecayExpression {
+    pub lin_decay: DecayParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ExpDecayExpression {
+    pub exp_decay: DecayParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GaussDecayExpression {
+    pub gauss_decay: DecayParams,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeoDistanceExpression {
+    pub geo_distance: GeoDistanceParams,
+}
+
+/// Distance in meters from the origin to the geo value of the payload field.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GeoDistanceParams {
+    /// Payload key of the geo value
+    pub key: JsonPath,
+    /// Origin, from which the distance is measured
+    pub origin: GeoPoint,
+}
+
+/// Decay of the distance between a payload value and the origin, from 1.0 at the origin down to 0.0.
+///
+/// Points without the payload value get 0.0.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DecayParams {
+    /// Payload key of a numeric, datetime or geo value
+    pub key: JsonPath,
+    /// Origin of the decay: a number, a datetime or a geo point.
+    /// Distances are absolute differences for numbers, seconds for datetimes and meters for geo points.
+    pub origin: DecayOrigin,
+    /// Distance from the origin plus `offset`, at which the decay reaches `decay`. Must be positive.
+    pub scale: f64,
+    /// Distance from the origin, within which there is no decay. Default is 0.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<f64>,
+    /// Value of the decay at the distance `scale` plus `offset`, between 0.0 and 1.0 exclusively. Default is 0.5
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decay: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -541,6 +843,15 @@ pub struct Prefetch {
 ///   examples, its score is then chosen from the `max(max_pos_score, max_neg_score)`.
 ///   If the `max_neg_score` is chosen then it is squared and negated, otherwise it is just
 ///   the `max_pos_score`.
+///
+/// * `sum_scores` - Uses custom search objective. Each candidate is compared against all
+///   examples, its score is the sum of the similarities to the positives minus the sum of the
+///   similarities to the negatives.
+///
+/// * `contrastive` - Uses custom search objective. Similarities of each candidate to the
+///   weighted examples are folded into
+///   `ln(sum(w_pos * exp(pos_score))) - ln(sum(w_neg * exp(neg_score)))`,
+///   so that the closest examples dominate the score. Weights are 1.0 by default.
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Default, PartialEq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
@@ -548,6 +859,8 @@ pub enum RecommendStrategy {
     #[default]
     AverageVector,
     BestScore,
+    SumScores,
+    Contrastive,
 }
 
 fn recommend_input_example() -> RecommendInput {
@@ -559,6 +872,8 @@ fn recommend_input_example() -> RecommendInput {
             0.475, 0.440625, -0.25625, 0.36875,
         ])]),
         strategy: Some(RecommendStrategy::AverageVector),
+        positive_weights: None,
+        negative_weights: None,
     }
 }
 
@@ -573,6 +888,16 @@ pub struct RecommendInput {
 
     /// How to use the provided vectors to find the results
     pub strategy: Option<RecommendStrategy>,
+
+    /// Weights of the positive examples, in the same order.
+    /// Only used by the `contrastive` strategy, 1.0 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positive_weights: Option<Vec<f32>>,
+
+    /// Weights of the negative examples, in the same order.
+    /// Only used by the `contrastive` strategy, 1.0 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_weights: Option<Vec<f32>>,
 }
 
 impl RecommendInput {
@@ -794,6 +1119,22 @@ pub struct QueryBaseGroupRequest {
     #[schemars(length(min = 1))]
     pub group_by: JsonPath,
 
+    /// More payload fields to group by. Groups are formed by each combination of values
+    /// of `group_by` and these fields, and their id is the list of those values.
+    /// Can't be used together with `with_lookup`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub then_group_by: Vec<JsonPath>,
+
+    /// How to compute the score of a group from its hits: `best` hit, `sum` or `avg` of the
+    /// scores of the returned hits, or `count` of them. Default is `best`.
+    /// `sum`, `avg` and `count` are approximate: only the hits collected for the returned groups,
+    /// at most `group_size` per group, are taken into account.
+    pub group_scoring: Option<GroupScoring>,
+
+    /// Rank groups by this payload field of their best hit first, then by their score.
+    /// Groups without the field go last.
+    pub group_order_by: Option<GroupOrderBy>,
+
     /// Maximum amount of points to return per group. Default is 3.
     #[validate(range(min = 1))]
     pub group_size: Option<usize>,
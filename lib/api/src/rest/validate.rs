@@ -3,7 +3,8 @@ use validator::{Validate, ValidationError};
 
 use super::schema::{BatchVectorStruct, Vector, VectorStruct};
 use super::{
-    ContextInput, Fusion, OrderByInterface, Query, QueryInterface, RecommendInput, VectorInput,
+    ContextInput, Fusion, FusionQuery, OrderByInterface, Query, QueryInterface, RecommendInput,
+    RecommendStrategy, VectorInput,
 };
 use crate::rest::NamedVectorStruct;
 
@@ -40,6 +41,7 @@ impl Validate for Vector {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
+            Vector::Document(_) | Vector::Image(_) => Ok(()),
         }
     }
 }
@@ -70,8 +72,11 @@ impl Validate for Query {
             Query::Recommend(recommend) => recommend.recommend.validate(),
             Query::Discover(discover) => discover.discover.validate(),
             Query::Context(context) => context.context.validate(),
-            Query::Fusion(fusion) => fusion.fusion.validate(),
+            Query::Fusion(fusion) => fusion.validate(),
             Query::OrderBy(order_by) => order_by.order_by.validate(),
+            Query::Formula(_) => Ok(()),
+            Query::Mmr(mmr) => mmr.mmr.validate(),
+            Query::Sample(_) => Ok(()),
         }
     }
 }
@@ -83,6 +88,7 @@ impl Validate for VectorInput {
             VectorInput::DenseVector(_dense) => Ok(()),
             VectorInput::SparseVector(sparse) => sparse.validate(),
             VectorInput::MultiDenseVector(multi) => validate_multi_vector(multi),
+            VectorInput::Document(_) | VectorInput::Image(_) => Ok(()),
         }
     }
 }
@@ -103,6 +109,20 @@ impl Validate for RecommendInput {
             return Err(errors);
         }
 
+        if let Err(message) = self.strategy.unwrap_or_default().validate_weights(
+            self.positive.as_ref().map_or(0, Vec::len),
+            self.negative.as_ref().map_or(0, Vec::len),
+            self.positive_weights.as_deref(),
+            self.negative_weights.as_deref(),
+        ) {
+            let mut errors = validator::ValidationErrors::new();
+            errors.add(
+                "positive_weights, negative_weights",
+                ValidationError::new(message),
+            );
+            return Err(errors);
+        }
+
         for item in self.iter() {
             item.validate()?;
         }
@@ -111,6 +131,39 @@ impl Validate for RecommendInput {
     }
 }
 
+impl RecommendStrategy {
+    /// Check the weights of the examples, only the `contrastive` strategy accepts them
+    pub fn validate_weights(
+        self,
+        positives: usize,
+        negatives: usize,
+        positive_weights: Option<&[f32]>,
+        negative_weights: Option<&[f32]>,
+    ) -> Result<(), &'static str> {
+        if positive_weights.is_none() && negative_weights.is_none() {
+            return Ok(());
+        }
+        if self != RecommendStrategy::Contrastive {
+            return Err("weights are only supported by the contrastive strategy");
+        }
+        if positive_weights.is_some_and(|weights| weights.len() != positives) {
+            return Err("number of positive weights must match the number of positive examples");
+        }
+        if negative_weights.is_some_and(|weights| weights.len() != negatives) {
+            return Err("number of negative weights must match the number of negative examples");
+        }
+        if positive_weights
+            .into_iter()
+            .chain(negative_weights)
+            .flatten()
+            .any(|weight| !weight.is_finite() || *weight <= 0.0)
+        {
+            return Err("weights must be finite positive numbers");
+        }
+        Ok(())
+    }
+}
+
 impl Validate for ContextInput {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         for item in self.0.iter().flatten().flat_map(|item| item.iter()) {
@@ -121,10 +174,52 @@ impl Validate for ContextInput {
     }
 }
 
-impl Validate for Fusion {
+impl Validate for FusionQuery {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
-        match self {
-            Fusion::Rrf => Ok(()),
+        let FusionQuery {
+            fusion,
+            weights,
+            normalization,
+            k,
+        } = self;
+
+        let mut errors = validator::ValidationErrors::new();
+        match fusion {
+            Fusion::Rrf => {
+                if normalization.is_some() {
+                    errors.add(
+                        "normalization",
+                        ValidationError::new("normalization is only supported by dbsf fusion"),
+                    );
+                }
+                if *k == Some(0) {
+                    errors.add("k", ValidationError::new("k must be at least 1"));
+                }
+            }
+            Fusion::Dbsf => {
+                if k.is_some() {
+                    errors.add(
+                        "k",
+                        ValidationError::new("k is only supported by rrf fusion"),
+                    );
+                }
+            }
+        }
+        if weights
+            .iter()
+            .flatten()
+            .any(|weight| !weight.is_finite() || *weight < 0.0)
+        {
+            errors.add(
+                "weights",
+                ValidationError::new("weights must be finite non-negative numbers"),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 }
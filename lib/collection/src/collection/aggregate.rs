@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use futures::TryStreamExt as _;
+use segment::data_types::aggregations::{
+    AggregationParams, HistogramBucketing, NumericAggregation,
+};
+use segment::types::PayloadSchemaType;
+
+use super::point_ops::merge_filters;
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::*;
+
+/// Datetime values are aggregated as timestamps in microseconds, while intervals are in seconds
+const DATETIME_INTERVAL_SCALE: f64 = 1_000_000.0;
+
+impl Collection {
+    pub async fn aggregate(
+        &self,
+        mut request: AggregateRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<AggregateResult> {
+        if let Some(filter) = &mut request.filter {
+            filter.resolve_relative_datetime(chrono::Utc::now())?;
+        }
+        merge_filters(
+            &mut request.filter,
+            self.shards_holder.read().await.resharding_filter(),
+        );
+
+        let is_datetime = self
+            .payload_index_schema
+            .read()
+            .schema
+            .get(&request.key)
+            .is_some_and(|schema| schema.expand().kind() == PayloadSchemaType::Datetime);
+
+        let date_histogram = match &request.date_histogram {
+            Some(_) if !is_datetime => {
+                return Err(CollectionError::bad_request(format!(
+                    "Date histogram requires a datetime index on key `{}`",
+                    request.key,
+                )));
+            }
+            Some(date_histogram) => Some(date_histogram.bucketing()?),
+            None => None,
+        };
+        let facet_limit = request
+            .date_histogram
+            .as_ref()
+            .and_then(|date_histogram| date_histogram.facet.as_ref())
+            .and_then(|facet| facet.limit);
+
+        let mut params = AggregationParams {
+            key: request.key,
+            filter: request.filter,
+            histogram: None,
+            date_histogram: None,
+        };
+
+        let bucketing = match request.histogram {
+            None => None,
+            Some(HistogramRequest::Interval { interval }) => Some(HistogramBucketing {
+                interval: if is_datetime {
+                    interval * DATETIME_INTERVAL_SCALE
+                } else {
+                    interval
+                },
+                offset: 0.0,
+                buckets: None,
+            }),
+            Some(HistogramRequest::Buckets { buckets }) => {
+                // Bounds of the buckets depend on the range of values, which has to be found first
+                let stats = self
+                    .aggregate_shards(params.clone(), read_consistency, shard_selection)
+                    .await?;
+                let (Some(min), Some(max)) = (stats.min, stats.max) else {
+                    return Ok(AggregateResult::from_aggregation(
+                        stats,
+                        Some(&HistogramBucketing {
+                            interval: 1.0,
+                            offset: 0.0,
+                            buckets: Some(buckets),
+                        }),
+                        date_histogram.as_ref(),
+                        facet_limit,
+                        is_datetime,
+                    ));
+                };
+                let interval = if max > min {
+                    (max - min) / buckets as f64
+                } else {
+                    1.0
+                };
+                Some(HistogramBucketing {
+                    interval,
+                    offset: min,
+                    buckets: Some(buckets),
+                })
+            }
+        };
+        params.histogram = bucketing;
+        params.date_histogram.clone_from(&date_histogram);
+
+        let aggregation = self
+            .aggregate_shards(params, read_consistency, shard_selection)
+            .await?;
+
+        Ok(AggregateResult::from_aggregation(
+            aggregation,
+            bucketing.as_ref(),
+            date_histogram.as_ref(),
+            facet_limit,
+            is_datetime,
+        ))
+    }
+
+    /// Aggregate values of the selected shards, with the bucketing already resolved
+    pub async fn aggregate_internal(
+        &self,
+        params: AggregationParams,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<NumericAggregation> {
+        self.aggregate_shards(params, None, shard_selection).await
+    }
+
+    async fn aggregate_shards(
+        &self,
+        params: AggregationParams,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<NumericAggregation> {
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let params = Arc::new(params);
+        let mut requests: futures::stream::FuturesUnordered<_> = shards
+            .into_iter()
+            // `aggregate` requests received through internal gRPC *always* have `shard_selection`
+            .map(|(shard, _shard_key)| {
+                shard.aggregate(
+                    params.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                )
+            })
+            .collect();
+
+        let mut aggregation = NumericAggregation::default();
+        while let Some(response) = requests.try_next().await? {
+            aggregation.merge(response);
+        }
+
+        Ok(aggregation)
+    }
+}
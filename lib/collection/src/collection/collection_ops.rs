@@ -294,6 +294,10 @@ impl Collection {
         let count_request = Arc::new(CountRequestInternal {
             filter: None,
             exact: false, // Don't need exact count of unique ids here, only size estimation
+            group_by: None,
+            group_by_limit: None,
+            distinct: None,
+            explain: None,
         });
         let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
 
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use api::rest::Sample;
+use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{
+    Condition, Filter, HasIdCondition, PointIdType, WithPayloadInterface, WithVector,
+};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, SearchMatrixRequestInternal, SearchMatrixResult,
+};
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
+
+/// Max number of the neighbour searches sent to the shards at once
+const SEARCH_MATRIX_BATCH_SIZE: usize = 64;
+
+impl Collection {
+    /// Sample random points which satisfy the filter, and find the nearest neighbours of each of
+    /// them among the sampled points.
+    ///
+    /// Neighbours are found with batches of searches, restricted to the sampled ids.
+    pub async fn search_points_matrix(
+        &self,
+        request: SearchMatrixRequestInternal,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<SearchMatrixResult> {
+        let SearchMatrixRequestInternal {
+            filter,
+            sample,
+            limit,
+            using,
+        } = request;
+
+        let start = Instant::now();
+        let sample = sample.unwrap_or(SearchMatrixRequestInternal::DEFAULT_SAMPLE);
+        let using = using.unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_string());
+
+        // Sample the points along with their vectors
+        let sample_request = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(Sample::Random)),
+            filter,
+            score_threshold: None,
+            limit: sample,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Selector(vec![using.clone()]),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+        let sampled = self
+            .query(
+                sample_request,
+                read_consistency,
+                shard_selection.clone(),
+                timeout,
+            )
+            .await?;
+
+        // Points without the vector can't be compared
+        let sampled: Vec<_> = sampled
+            .into_iter()
+            .filter_map(|point| {
+                let vector = point.vector?.get(&using)?.to_owned();
+                Some((point.id, vector))
+            })
+            .collect();
+
+        if sampled.len() < 2 {
+            return Ok(SearchMatrixResult::default());
+        }
+
+        let sample_ids: Vec<PointIdType> = sampled.iter().map(|(id, _)| *id).collect();
+        let sample_filter = Filter::new_must(Condition::HasId(HasIdCondition::from(
+            sample_ids.iter().copied().collect::<HashSet<_>>(),
+        )));
+
+        // By default, all the other sampled points are neighbours
+        let limit = limit.unwrap_or(sample_ids.len() - 1);
+
+        // One more, as each point finds itself
+        let mut searches = sampled.into_iter().map(|(_, vector)| ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+                NamedVectorStruct::new_from_vector(vector, using.clone()),
+            ))),
+            filter: Some(sample_filter.clone()),
+            score_threshold: None,
+            limit: limit + 1,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        });
+
+        // Search in bounded batches, so a big sample doesn't flood the shards at once
+        let mut nearests = Vec::with_capacity(sample_ids.len());
+        loop {
+            let batch: Vec<_> = searches.by_ref().take(SEARCH_MATRIX_BATCH_SIZE).collect();
+            if batch.is_empty() {
+                break;
+            }
+            let timeout = timeout
+                .map(|timeout| {
+                    timeout.checked_sub(start.elapsed()).ok_or_else(|| {
+                        CollectionError::timeout(timeout.as_secs() as usize, "search matrix")
+                    })
+                })
+                .transpose()?;
+            let (batch_nearests, _profiles) = self
+                .do_query_batch(
+                    batch,
+                    read_consistency,
+                    shard_selection.clone(),
+                    timeout,
+                    false,
+                )
+                .await?;
+            nearests.extend(batch_nearests);
+        }
+
+        if nearests.len() != sample_ids.len() {
+            return Err(CollectionError::service_error(format!(
+                "Expected {} nearest neighbours results, got {}",
+                sample_ids.len(),
+                nearests.len(),
+            )));
+        }
+
+        let nearests = sample_ids
+            .iter()
+            .zip(nearests)
+            .map(|(id, nearest)| {
+                nearest
+                    .into_iter()
+                    .filter(|point| point.id != *id)
+                    .take(limit)
+                    .collect()
+            })
+            .collect();
+
+        Ok(SearchMatrixResult {
+            sample_ids,
+            nearests,
+        })
+    }
+}
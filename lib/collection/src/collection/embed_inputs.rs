@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use api::rest::{Document, Image};
+use segment::data_types::vectors::Vector;
+
+use super::sparse_documents::{collect_query_inputs, named_vectors_mut};
+use crate::collection::Collection;
+use crate::embeddings::EmbeddingInput;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::collection_query::{CollectionQueryRequest, VectorInput};
+use crate::operations::CollectionUpdateOperations;
+
+impl Collection {
+    /// Names of the embedding providers of the vectors, which have one
+    async fn vector_embedding_providers(&self) -> HashMap<String, String> {
+        self.collection_config
+            .read()
+            .await
+            .params
+            .vectors
+            .params_iter()
+            .filter_map(|(vector_name, params)| {
+                Some((vector_name.to_string(), params.embedding.clone()?))
+            })
+            .collect()
+    }
+
+    /// Replaces raw documents and images of the update operation with their embeddings,
+    /// for the vectors with an embedding provider.
+    ///
+    /// Documents of the other vectors are left to be encoded into sparse vectors.
+    pub(crate) async fn embed_update_inputs(
+        &self,
+        operation: &mut CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let embeddings = &self.shared_storage_config.embeddings;
+        let providers = self.vector_embedding_providers().await;
+
+        let mut targets = Vec::new();
+        let mut inputs = Vec::new();
+        for (vector_name, vector) in named_vectors_mut(operation) {
+            let input = match &*vector {
+                api::rest::Vector::Document(Document { text }) => {
+                    EmbeddingInput::Text(text.clone())
+                }
+                api::rest::Vector::Image(Image { image }) => EmbeddingInput::Image(image.clone()),
+                api::rest::Vector::Dense(_)
+                | api::rest::Vector::Sparse(_)
+                | api::rest::Vector::MultiDense(_) => continue,
+            };
+            let Some(provider_name) = providers.get(vector_name) else {
+                check_embeddable(vector_name, &input)?;
+                continue;
+            };
+            inputs.push((provider_name.as_str(), input));
+            targets.push(vector);
+        }
+        if inputs.is_empty() {
+            return Ok(());
+        }
+
+        let embedded = embeddings.embed(inputs).await?;
+        for (target, embedding) in targets.into_iter().zip(embedded) {
+            *target = api::rest::Vector::Dense(embedding);
+        }
+
+        Ok(())
+    }
+
+    /// Replaces raw documents and images of the queries with their embeddings,
+    /// for the vectors with an embedding provider.
+    ///
+    /// Inputs of all the queries are embedded together, to make the most of the batching.
+    pub(crate) async fn embed_query_inputs<'a>(
+        &self,
+        requests: impl IntoIterator<Item = &'a mut CollectionQueryRequest>,
+    ) -> CollectionResult<()> {
+        let embeddings = &self.shared_storage_config.embeddings;
+        let providers = self.vector_embedding_providers().await;
+
+        let mut query_inputs = Vec::new();
+        for request in requests {
+            collect_query_inputs(
+                &request.using,
+                request.query.as_mut(),
+                &mut request.prefetch,
+                &mut query_inputs,
+            );
+        }
+
+        let mut targets = Vec::new();
+        let mut inputs = Vec::new();
+        for (vector_name, vector_input) in query_inputs {
+            let input = match &*vector_input {
+                VectorInput::Document(Document { text }) => EmbeddingInput::Text(text.clone()),
+                VectorInput::Image(Image { image }) => EmbeddingInput::Image(image.clone()),
+                VectorInput::Id(_) | VectorInput::Vector(_) => continue,
+            };
+            let Some(provider_name) = providers.get(vector_name) else {
+                check_embeddable(vector_name, &input)?;
+                continue;
+            };
+            inputs.push((provider_name.as_str(), input));
+            targets.push(vector_input);
+        }
+        if inputs.is_empty() {
+            return Ok(());
+        }
+
+        let embedded = embeddings.embed(inputs).await?;
+        for (target, embedding) in targets.into_iter().zip(embedded) {
+            *target = VectorInput::Vector(Vector::Dense(embedding));
+        }
+
+        Ok(())
+    }
+}
+
+/// Text can still be encoded into a sparse vector without a provider, images can not
+fn check_embeddable(vector_name: &str, input: &EmbeddingInput) -> CollectionResult<()> {
+    match input {
+        EmbeddingInput::Text(_) => Ok(()),
+        EmbeddingInput::Image(_) => Err(CollectionError::bad_request(format!(
+            "Vector {vector_name} does not accept images, \
+             only vectors with an embedding provider do",
+        ))),
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use futures::TryStreamExt as _;
+use segment::data_types::facets::merge_facet_counts;
+
+use super::point_ops::merge_filters;
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::*;
+
+impl Collection {
+    pub async fn facet(
+        &self,
+        mut request: FacetRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<FacetResult> {
+        if let Some(filter) = &mut request.filter {
+            filter.resolve_relative_datetime(chrono::Utc::now())?;
+        }
+        merge_filters(
+            &mut request.filter,
+            self.shards_holder.read().await.resharding_filter(),
+        );
+
+        let limit = request.facet_params().limit;
+
+        // Internal requests in exact mode must return counts of all values,
+        // otherwise the merged counts of the remote side would be incomplete
+        let is_internal_exact = shard_selection.is_shard_id() && request.exact;
+
+        let shards_holder = self.shards_holder.read().await;
+        let shards = shards_holder.select_shards(shard_selection)?;
+
+        let request = Arc::new(request);
+        let mut requests: futures::stream::FuturesUnordered<_> = shards
+            .into_iter()
+            // `facet` requests received through internal gRPC *always* have `shard_selection`
+            .map(|(shard, _shard_key)| {
+                shard.facet(
+                    request.clone(),
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                )
+            })
+            .collect();
+
+        let mut counts = Vec::new();
+        while let Some(response) = requests.try_next().await? {
+            counts.extend(response.hits.into_iter().map(|hit| (hit.value, hit.count)));
+        }
+
+        Ok(FacetResult {
+            hits: merge_facet_counts(counts, (!is_internal_exact).then_some(limit)),
+        })
+    }
+}
@@ -1,12 +1,18 @@
+mod aggregate;
 mod collection_ops;
+mod distance_matrix;
+mod embed_inputs;
+mod facet;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
+mod query_page;
 mod resharding;
 mod search;
 mod shard_transfer;
 mod sharding_keys;
 mod snapshots;
+mod sparse_documents;
 mod state_management;
 
 use std::collections::{HashMap, HashSet};
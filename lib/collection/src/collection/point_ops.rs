@@ -4,7 +4,7 @@ use std::sync::Arc;
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::OrderBy;
 use segment::types::{Filter, ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
 
@@ -131,12 +131,15 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
+        self.embed_update_inputs(&mut operation).await?;
+        self.encode_update_documents(&mut operation).await?;
         operation.validate()?;
+        operation.resolve_relative_datetime(chrono::Utc::now())?;
 
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
@@ -211,6 +214,9 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
     ) -> CollectionResult<ScrollResult> {
+        if let Some(filter) = &mut request.filter {
+            filter.resolve_relative_datetime(chrono::Utc::now())?;
+        }
         merge_filters(
             &mut request.filter,
             self.shards_holder.read().await.resharding_filter(),
@@ -298,11 +304,11 @@ impl Collection {
                         records.into_iter().map(|mut record| {
                             let value;
                             if local_only {
-                                value = record.order_value.unwrap_or_else(|| {
+                                value = record.order_value.clone().unwrap_or_else(|| {
                                     order_by.get_order_value_from_payload(record.payload.as_ref())
                                 });
                             } else {
-                                value = if let Some(order_value) = record.order_value {
+                                value = if let Some(order_value) = record.order_value.clone() {
                                     order_by
                                         .remove_order_value_from_payload(record.payload.as_mut());
                                     order_value
@@ -320,10 +326,9 @@ impl Collection {
                     })
                     // Get top results
                     .kmerge_by(|(value_a, record_a), (value_b, record_b)| {
-                        match order_by.direction() {
-                            Direction::Asc => (value_a, record_a.id) < (value_b, record_b.id),
-                            Direction::Desc => (value_a, record_a.id) > (value_b, record_b.id),
-                        }
+                        order_by
+                            .cmp_points((value_a, record_a.id), (value_b, record_b.id))
+                            .is_lt()
                     })
                     // Only keep the point with the most "valuable" order value
                     .dedup_by(|(_, record_a), (_, record_b)| record_a.id == record_b.id)
@@ -352,11 +357,22 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
     ) -> CollectionResult<CountResult> {
+        if let Some(filter) = &mut request.filter {
+            filter.resolve_relative_datetime(chrono::Utc::now())?;
+        }
         merge_filters(
             &mut request.filter,
             self.shards_holder.read().await.resharding_filter(),
         );
 
+        // Internal requests in exact mode must return counts of all `group_by` values,
+        // otherwise the merged counts of the remote side would be incomplete
+        let is_internal_exact = shard_selection.is_shard_id() && request.exact;
+        let group_by_limit = request
+            .group_by_params()
+            .map(|params| params.limit)
+            .filter(|_| !is_internal_exact);
+
         let shards_holder = self.shards_holder.read().await;
         let shards = shards_holder.select_shards(shard_selection)?;
 
@@ -373,13 +389,26 @@ impl Collection {
             })
             .collect();
 
-        let mut count = 0;
+        let mut result = CountResult::default();
 
         while let Some(response) = requests.try_next().await? {
-            count += response.count;
+            result.merge(response);
         }
 
-        Ok(CountResult { count })
+        // Shards without any values of the key may have returned no groups
+        if request.group_by.is_some() {
+            let groups = result.groups.get_or_insert_with(Vec::new);
+            if let Some(limit) = group_by_limit {
+                groups.truncate(limit);
+            }
+        }
+
+        // Stable sort keeps the order of replicas of each shard
+        result
+            .explanations
+            .sort_by_key(|explanation| explanation.shard_id);
+
+        Ok(result)
     }
 
     pub async fn retrieve(
@@ -446,7 +475,7 @@ impl Collection {
     }
 }
 
-fn merge_filters(filter: &mut Option<Filter>, resharding_filter: Option<Filter>) {
+pub(super) fn merge_filters(filter: &mut Option<Filter>, resharding_filter: Option<Filter>) {
     if let Some(resharding_filter) = resharding_filter {
         *filter = Some(match filter.take() {
             Some(filter) => filter.merge_owned(resharding_filter),
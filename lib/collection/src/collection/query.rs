@@ -2,10 +2,11 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
-use futures::{future, TryFutureExt};
+use futures::future;
 use itertools::{Either, Itertools};
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Order, ScoredPoint};
+use segment::data_types::profile::{ProfileStage, QueryProfile};
+use segment::data_types::vectors::{VectorStructInternal, DEFAULT_VECTOR_NAME};
+use segment::types::{Order, ScoredPoint, WithVector};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::sync::RwLockReadGuard;
 use tokio::time::Instant;
@@ -13,7 +14,7 @@ use tokio::time::Instant;
 use super::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
-    build_vector_resolver_queries, resolve_referenced_vectors_batch,
+    build_vector_resolver_queries, resolve_referenced_vectors_batch, ReferencedVectors,
 };
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::common::transpose_iterator::transposed_iter;
@@ -21,9 +22,11 @@ use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::page_token::{rank_cmp, Ranking};
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+    ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
+use crate::shards::shard::ShardId;
 
 struct IntermediateQueryInfo<'a> {
     scoring_query: Option<&'a ScoringQuery>,
@@ -43,89 +46,180 @@ impl Collection {
         if request.limit == 0 {
             return Ok(vec![]);
         }
-        let results = self
-            .do_query_batch(vec![(request)], read_consistency, shard_selection, timeout)
+        let (results, _profiles) = self
+            .do_query_batch(
+                vec![(request)],
+                read_consistency,
+                shard_selection,
+                timeout,
+                false,
+                false,
+            )
             .await?;
         Ok(results.into_iter().next().unwrap())
     }
 
-    /// Returns a shape of [shard_id, batch_id, intermediate_response, points]
-    async fn batch_query_shards_concurrently(
+    /// Returns the ids of the queried shards, and a shape of [shard_id, batch_id, intermediate_response, points]
+    ///
+    /// If `profile` or `explain` is set, also returns the profile of each shard. If `explain` is
+    /// set, the profiles also hold how each segment has executed the batch.
+    pub(super) async fn batch_query_shards_concurrently(
         &self,
         batch_request: Arc<Vec<ShardQueryRequest>>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ShardQueryResponse>>> {
+        profile: bool,
+        explain: bool,
+    ) -> CollectionResult<(
+        Vec<ShardId>,
+        Vec<Vec<ShardQueryResponse>>,
+        Vec<QueryProfile>,
+    )> {
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
         let target_shards = shard_holder.select_shards(shard_selection)?;
         let all_searches = target_shards.iter().map(|(shard, shard_key)| {
             let shard_key = shard_key.cloned();
-            shard
-                .query_batch(
-                    Arc::clone(&batch_request),
-                    read_consistency,
-                    shard_selection.is_shard_id(),
-                    timeout,
-                )
-                .and_then(move |mut shard_responses| async move {
-                    if shard_key.is_none() {
-                        return Ok(shard_responses);
-                    }
+            let batch_request = Arc::clone(&batch_request);
+            async move {
+                let local_only = shard_selection.is_shard_id();
+                let (mut shard_responses, shard_profile) = if profile || explain {
+                    let (responses, shard_profile) = shard
+                        .query_batch_profiled(
+                            batch_request,
+                            read_consistency,
+                            local_only,
+                            timeout,
+                            explain,
+                        )
+                        .await?;
+                    (responses, Some(shard_profile))
+                } else {
+                    let responses = shard
+                        .query_batch(batch_request, read_consistency, local_only, timeout)
+                        .await?;
+                    (responses, None)
+                };
+
+                if shard_key.is_some() {
                     shard_responses
                         .iter_mut()
                         .flatten()
                         .flatten()
                         .for_each(|point| point.shard_key.clone_from(&shard_key));
+                }
 
-                    Ok(shard_responses)
-                })
+                Ok::<_, CollectionError>((shard.shard_id, shard_responses, shard_profile))
+            }
         });
-        future::try_join_all(all_searches).await
+        let (shard_ids, all_shards_responses, shard_profiles): (Vec<_>, Vec<_>, Vec<_>) =
+            future::try_join_all(all_searches)
+                .await?
+                .into_iter()
+                .multiunzip();
+
+        Ok((
+            shard_ids,
+            all_shards_responses,
+            shard_profiles.into_iter().flatten().collect(),
+        ))
     }
 
     /// This function is used to query the collection. It will return a list of scored points.
-    async fn do_query_batch(
+    ///
+    /// If `profile` is set, also returns the profiles of all stages of the batch.
+    /// If `explain` is set, also returns the profiles of the shards, which hold how each segment
+    /// has executed the batch.
+    pub(super) async fn do_query_batch(
         &self,
-        requests_batch: Vec<ShardQueryRequest>,
+        mut requests_batch: Vec<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        profile: bool,
+        explain: bool,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<QueryProfile>)> {
         let instant = Instant::now();
 
+        // Relative datetime ranges are resolved once here, so all shards and replicas see the same values
+        let now = chrono::Utc::now();
+        for request in &mut requests_batch {
+            request.resolve_relative_datetime(now)?;
+        }
+
         let requests_batch = Arc::new(requests_batch);
 
-        let all_shards_results = self
+        let (_shard_ids, all_shards_results, mut profiles) = self
             .batch_query_shards_concurrently(
                 requests_batch.clone(),
                 read_consistency,
                 &shard_selection,
                 timeout,
+                profile,
+                explain,
             )
             .await?;
 
         let results_f = transposed_iter(all_shards_results)
             .zip(requests_batch.iter())
             .map(|(shards_results, request)| async {
+                let mut request_profiles = Vec::new();
+
                 // shards_results shape: [num_shards, num_intermediate_results, num_points]
+                let merge_timer = Instant::now();
                 let mut merged_intermediates = self
                     .merge_intermediate_results_from_shards(request, shards_results)
                     .await?;
+                request_profiles.push(QueryProfile::new(
+                    ProfileStage::Merging,
+                    merge_timer.elapsed(),
+                ));
 
                 let result = if let Some(ScoringQuery::Fusion(fusion)) = &request.query {
                     // If the root query is a Fusion, the returned results correspond to each the prefetches.
-                    let mut fused = match fusion {
-                        Fusion::Rrf => rrf_scoring(merged_intermediates),
-                    };
+                    let fusion_timer = Instant::now();
+                    let collection_params = self.collection_config.read().await.params.clone();
+                    let sources_order: Vec<_> = request
+                        .prefetches
+                        .iter()
+                        .map(|prefetch| {
+                            ScoringQuery::order(prefetch.query.as_ref(), &collection_params)
+                        })
+                        .collect::<CollectionResult<_>>()?;
+                    let mut fused = fusion.fuse(merged_intermediates, &sources_order);
                     if let Some(score_threshold) = request.score_threshold {
                         fused = fused
                             .into_iter()
                             .take_while(|point| point.score >= score_threshold)
                             .collect();
                     }
+                    request_profiles.push(QueryProfile::new(
+                        ProfileStage::Fusion,
+                        fusion_timer.elapsed(),
+                    ));
                     fused
+                } else if let Some(ScoringQuery::Mmr(mmr)) = &request.query {
+                    // If the root query is MMR, the candidates come from all of the prefetches.
+                    let mmr_timer = Instant::now();
+                    let distance = self
+                        .collection_config
+                        .read()
+                        .await
+                        .params
+                        .get_distance(mmr.get_vector_name())?;
+                    let candidates = merged_intermediates.into_iter().flatten().collect();
+                    let mut selected =
+                        mmr.select(candidates, distance, request.offset + request.limit)?;
+                    // The vector of the query was only fetched for the selection
+                    selected
+                        .iter_mut()
+                        .for_each(|point| retain_requested_vectors(point, &request.with_vector));
+                    request_profiles.push(QueryProfile::new(
+                        ProfileStage::Rescoring,
+                        mmr_timer.elapsed(),
+                    ));
+                    selected
                 } else {
                     // Otherwise, it will be a list with a single list of scored points.
                     debug_assert_eq!(merged_intermediates.len(), 1);
@@ -136,38 +230,45 @@ impl Collection {
                     })?
                 };
 
-                let result: Vec<ScoredPoint> = result
+                let mut result: Vec<ScoredPoint> = result
                     .into_iter()
                     .skip(request.offset)
                     .take(request.limit)
                     .collect();
 
+                // Sampling keys are only meaningful for merging the samples of the shards
+                if let Some(ScoringQuery::Sample(_)) = &request.query {
+                    result.iter_mut().for_each(|point| point.score = 0.0);
+                }
+
                 let filter_refs = request.filter_refs();
                 self.post_process_if_slow_request(instant.elapsed(), filter_refs);
 
-                Ok::<_, CollectionError>(result)
+                Ok::<_, CollectionError>((result, request_profiles))
             });
-        let results = future::try_join_all(results_f).await?;
+        let (results, request_profiles): (Vec<_>, Vec<_>) =
+            future::try_join_all(results_f).await?.into_iter().unzip();
 
-        Ok(results)
+        if profile {
+            profiles.extend(request_profiles.into_iter().flatten());
+        }
+
+        Ok((results, profiles))
     }
 
-    /// To be called on the user-responding instance. Resolves ids into vectors, and merges the results from local and remote shards.
-    ///
-    /// This function is used to query the collection. It will return a list of scored points.
-    pub async fn query_batch<'a, F, Fut>(
+    /// Resolves ids, referenced in the requests, into vectors
+    pub(super) async fn resolve_referenced_vectors<'a, F, Fut>(
         &self,
-        requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        requests_batch: &Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
         collection_by_name: F,
         read_consistency: Option<ReadConsistency>,
-        timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    ) -> CollectionResult<ReferencedVectors>
     where
         F: Fn(String) -> Fut,
         Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
     {
         // Lift nested prefetches to root queries for vector resolution
-        let resolver_requests = build_vector_resolver_queries(&requests_batch);
+        let resolver_requests = build_vector_resolver_queries(requests_batch);
 
         // Build referenced vectors
         let ids_to_vectors = resolve_referenced_vectors_batch(
@@ -190,6 +291,33 @@ impl Collection {
             }
         }
 
+        Ok(ids_to_vectors)
+    }
+
+    /// To be called on the user-responding instance. Resolves ids into vectors, and merges the results from local and remote shards.
+    ///
+    /// This function is used to query the collection. It will return a list of scored points.
+    pub async fn query_batch<'a, F, Fut>(
+        &self,
+        mut requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        self.embed_query_inputs(requests_batch.iter_mut().map(|(request, _)| request))
+            .await?;
+        for (request, _) in &mut requests_batch {
+            self.encode_query_documents(request).await?;
+        }
+
+        let ids_to_vectors = self
+            .resolve_referenced_vectors(&requests_batch, collection_by_name, read_consistency)
+            .await?;
+
         let futures = batch_requests::<
             (CollectionQueryRequest, ShardSelectorInternal),
             ShardSelectorInternal,
@@ -214,6 +342,8 @@ impl Collection {
                     read_consistency,
                     shard_selection,
                     timeout,
+                    false,
+                    false,
                 ));
 
                 Ok(())
@@ -223,35 +353,95 @@ impl Collection {
         let results = future::try_join_all(futures)
             .await?
             .into_iter()
-            .flatten()
+            .flat_map(|(results, _profiles)| results)
             .collect();
 
         Ok(results)
     }
 
+    /// Same as `query_batch` for a single request, but also measures the time spent on each stage.
+    ///
+    /// If `explain` is set, the profile also holds how each segment has executed the query.
+    pub async fn query_profiled<'a, F, Fut>(
+        &self,
+        mut request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ScoredPoint>, QueryProfile)>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        let start_time = Instant::now();
+
+        self.embed_query_inputs([&mut request]).await?;
+        self.encode_query_documents(&mut request).await?;
+
+        let requests_batch = vec![(request, shard_selection)];
+        let ids_to_vectors = self
+            .resolve_referenced_vectors(&requests_batch, collection_by_name, read_consistency)
+            .await?;
+        let mut profiles = vec![QueryProfile::new(
+            ProfileStage::Lookup,
+            start_time.elapsed(),
+        )];
+
+        let (request, shard_selection) = requests_batch.into_iter().next().unwrap();
+        let shard_request = request.try_into_shard_request(&self.id, &ids_to_vectors)?;
+
+        let (mut results, batch_profiles) = self
+            .do_query_batch(
+                vec![shard_request],
+                read_consistency,
+                shard_selection,
+                timeout,
+                true,
+                explain,
+            )
+            .await?;
+        profiles.extend(batch_profiles);
+
+        let profile =
+            QueryProfile::new(ProfileStage::Query, start_time.elapsed()).with_children(profiles);
+
+        Ok((results.pop().unwrap_or_default(), profile))
+    }
+
     /// To be called on the remote instance. Only used for the internal service.
     ///
     /// If the root query is a Fusion, the returned results correspond to each the prefetches.
     /// Otherwise, it will be a list with a single list of scored points.
+    ///
+    /// If `profile` is set, also returns the time spent on each stage of the batch.
+    /// If `explain` is set, the profile also holds how each segment has executed the batch.
     pub async fn query_batch_internal(
         &self,
         requests: Vec<ShardQueryRequest>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        profile: bool,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, Option<QueryProfile>)> {
+        let start_time = Instant::now();
         let requests_arc = Arc::new(requests);
 
         // Results from all shards
         // Shape: [num_shards, batch_size, num_intermediate_results, num_points]
-        let all_shards_results = self
+        let (_shard_ids, all_shards_results, mut profiles) = self
             .batch_query_shards_concurrently(
                 Arc::clone(&requests_arc),
                 None,
                 shard_selection,
                 timeout,
+                profile,
+                explain,
             )
             .await?;
 
+        let merge_timer = Instant::now();
         let merged_f = transposed_iter(all_shards_results)
             .zip(requests_arc.iter())
             .map(|(shards_results, request)| async {
@@ -260,7 +450,16 @@ impl Collection {
                     .await
             });
         let merged = futures::future::try_join_all(merged_f).await?;
-        Ok(merged)
+
+        let profile = (profile || explain).then(|| {
+            profiles.push(QueryProfile::new(
+                ProfileStage::Merging,
+                merge_timer.elapsed(),
+            ));
+            QueryProfile::new(ProfileStage::Query, start_time.elapsed()).with_children(profiles)
+        });
+
+        Ok((merged, profile))
     }
 
     /// Merges the results in each shard for each intermediate query.
@@ -294,6 +493,21 @@ impl Collection {
             // `shards_results` shape: [num_shards, num_scored_points]
             let order = ScoringQuery::order(query_info.scoring_query, &collection_params)?;
 
+            // Keys of a compound order may go in different directions
+            if let Some(ScoringQuery::OrderBy(order_by)) = query_info.scoring_query {
+                if order_by.is_compound() {
+                    let ranking = Ranking::OrderBy(order_by);
+                    let intermediate_result = shards_results
+                        .into_iter()
+                        .kmerge_by(|a, b| rank_cmp(a, b, ranking).is_lt())
+                        .dedup()
+                        .take(query_info.take)
+                        .collect();
+                    results.push(intermediate_result);
+                    continue;
+                }
+            }
+
             // Equivalent to:
             //
             // shards_results
@@ -329,6 +543,27 @@ impl Collection {
     }
 }
 
+/// Drop the vectors of the point, which were not requested by `with_vector`
+fn retain_requested_vectors(point: &mut ScoredPoint, with_vector: &WithVector) {
+    let names = match with_vector {
+        WithVector::Bool(true) => return,
+        WithVector::Bool(false) => {
+            point.vector = None;
+            return;
+        }
+        WithVector::Selector(names) => names,
+    };
+
+    point.vector = match point.vector.take() {
+        Some(VectorStructInternal::Named(mut vectors)) => {
+            vectors.retain(|name, _| names.contains(name));
+            Some(VectorStructInternal::Named(vectors))
+        }
+        Some(vector) if names.iter().any(|name| name == DEFAULT_VECTOR_NAME) => Some(vector),
+        Some(_) | None => None,
+    };
+}
+
 /// Returns a list of the query that corresponds to each of the results in each shard.
 ///
 /// Example: `[info1, info2, info3]` corresponds to `[result1, result2, result3]` of each shard
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use itertools::Itertools;
+use segment::data_types::explain::ShardExplanation;
+use segment::data_types::profile::QueryProfile;
+use segment::types::ScoredPoint;
+use tokio::sync::RwLockReadGuard;
+use tokio::time::Instant;
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequest};
+use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::page_token::{rank_cmp, request_hash, PageCursor, Ranking};
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
+use crate::shards::shard::ShardId;
+
+impl Collection {
+    /// Query a single page of results, continuing right after the point of the page token.
+    ///
+    /// Also returns the token of the next page, if the query supports tokens and the page is full.
+    /// If `explain` is set, also returns how the segments of each shard replica have executed the
+    /// query of this page.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_page<'a, F, Fut>(
+        &self,
+        mut request: CollectionQueryRequest,
+        page_token: Option<&str>,
+        shard_selection: ShardSelectorInternal,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Option<String>, Vec<ShardExplanation>)>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        self.embed_query_inputs([&mut request]).await?;
+        self.encode_query_documents(&mut request).await?;
+
+        let requests_batch = vec![(request, shard_selection)];
+        let ids_to_vectors = self
+            .resolve_referenced_vectors(&requests_batch, collection_by_name, read_consistency)
+            .await?;
+
+        let (request, shard_selection) = requests_batch.into_iter().next().unwrap();
+        let shard_request = request.try_into_shard_request(&self.id, &ids_to_vectors)?;
+
+        self.query_shard_page(
+            shard_request,
+            page_token,
+            read_consistency,
+            shard_selection,
+            timeout,
+            explain,
+        )
+        .await
+    }
+
+    /// Same as [`Collection::query_page`], for a single search request
+    pub async fn search_page(
+        &self,
+        request: CoreSearchRequest,
+        page_token: Option<&str>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Option<String>, Vec<ShardExplanation>)> {
+        self.query_shard_page(
+            ShardQueryRequest::from(request),
+            page_token,
+            read_consistency,
+            shard_selection,
+            timeout,
+            explain,
+        )
+        .await
+    }
+
+    /// Shards are asked only for the points from the cursor on, by setting the score threshold or
+    /// the start of the ordered read to the cursor, and excluding the cursor point itself.
+    /// Requests without such a bound, like the ones ordered by id, ask every shard for as many
+    /// points as the furthest shard position of the cursor, plus the page.
+    ///
+    /// Points up to the cursor are dropped, so the page never repeats the points of the previous
+    /// pages, even if they have changed since. Pages are not a snapshot of the collection: points
+    /// changed in between the requests may be skipped or returned again, if their rank moves past
+    /// the cursor. Relative datetime ranges of all pages are resolved against the time of the
+    /// first page.
+    ///
+    /// If some shard might have more points before the end of the page, than it has returned,
+    /// the shards are asked again for more points. Only the last request to the shards, which
+    /// has completed the page, is explained.
+    async fn query_shard_page(
+        &self,
+        mut request: ShardQueryRequest,
+        page_token: Option<&str>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Option<String>, Vec<ShardExplanation>)> {
+        let cursor = page_token.map(PageCursor::from_token).transpose()?;
+        let cursor = cursor.as_ref();
+
+        // Only the results of the shards, merged by score, can be continued from a point
+        let supports_tokens = request.query.as_ref().map_or(true, |query| {
+            !query.needs_intermediate_results() && !matches!(query, ScoringQuery::Sample(_))
+        });
+        if !supports_tokens {
+            if cursor.is_some() {
+                return Err(CollectionError::bad_input(
+                    "Page token can't be used with fusion, MMR and sample queries",
+                ));
+            }
+            if request.limit == 0 {
+                return Ok((vec![], None, vec![]));
+            }
+            let (mut results, mut profiles) = self
+                .do_query_batch(
+                    vec![request],
+                    read_consistency,
+                    shard_selection,
+                    timeout,
+                    false,
+                    explain,
+                )
+                .await?;
+            let points = results.pop().unwrap_or_default();
+            return Ok((points, None, take_explanations(&mut profiles)));
+        }
+
+        if cursor.is_some() && request.offset > 0 {
+            return Err(CollectionError::bad_input(
+                "Page token can't be used together with offset",
+            ));
+        }
+
+        if request.limit == 0 {
+            return Ok((vec![], None, vec![]));
+        }
+
+        let instant = Instant::now();
+
+        let now = cursor.map_or_else(chrono::Utc::now, PageCursor::now);
+        request.resolve_relative_datetime(now)?;
+
+        let request_hash = request_hash(&self.id, &request)?;
+        if let Some(cursor) = cursor {
+            cursor.check_request_hash(request_hash)?;
+        }
+
+        let collection_params = self.collection_config.read().await.params.clone();
+        let order = ScoringQuery::order(request.query.as_ref(), &collection_params)?;
+
+        let mut shard_request = request.clone();
+        let is_bounded = match cursor {
+            Some(cursor) => cursor.bound_request(&mut shard_request, order, &collection_params)?,
+            None => false,
+        };
+
+        let ranking = match &request.query {
+            Some(ScoringQuery::OrderBy(order_by)) => Ranking::OrderBy(order_by),
+            _ => Ranking::Order(order),
+        };
+
+        let offset = request.offset;
+        let page_size = request.offset.saturating_add(request.limit);
+        let mut take = match cursor {
+            Some(cursor) if !is_bounded => cursor.max_shard_position().saturating_add(page_size),
+            _ => page_size,
+        };
+
+        let (page, shard_positions, explanations) = loop {
+            let shard_request = ShardQueryRequest {
+                offset: 0,
+                limit: take,
+                ..shard_request.clone()
+            };
+
+            let (shard_ids, all_shards_results, mut profiles) = self
+                .batch_query_shards_concurrently(
+                    Arc::new(vec![shard_request]),
+                    read_consistency,
+                    &shard_selection,
+                    timeout,
+                    false,
+                    explain,
+                )
+                .await?;
+
+            // Shards which may have more points, after the last one they have returned
+            let mut shard_bounds = Vec::new();
+
+            let shards_points = shard_ids
+                .into_iter()
+                .zip(all_shards_results)
+                .map(|(shard_id, shard_results)| {
+                    // Single request with the single merged result
+                    let points = shard_results
+                        .into_iter()
+                        .flatten()
+                        .next()
+                        .unwrap_or_default();
+
+                    if points.len() >= take {
+                        shard_bounds.extend(points.last().cloned());
+                    }
+
+                    points
+                        .into_iter()
+                        .filter(move |point| {
+                            cursor.map_or(true, |cursor| cursor.precedes(point, ranking))
+                        })
+                        .map(move |point| (shard_id, point))
+                })
+                .collect_vec();
+
+            // Bounded requests don't need the positions, shards skip the previous pages anyway
+            let mut shard_positions: BTreeMap<ShardId, usize> = cursor
+                .filter(|_| !is_bounded)
+                .map(|cursor| cursor.shard_positions().clone())
+                .unwrap_or_default();
+
+            let mut page: Vec<ScoredPoint> = Vec::with_capacity(page_size);
+            for (shard_id, point) in shards_points
+                .into_iter()
+                .kmerge_by(|(_, a), (_, b)| rank_cmp(a, b, ranking).is_lt())
+            {
+                // Same point can come from different shards, e.g. during resharding
+                let is_duplicate = page.last().map_or(false, |last| last.id == point.id);
+                if page.len() == page_size && !is_duplicate {
+                    break;
+                }
+                if !is_bounded {
+                    *shard_positions.entry(shard_id).or_default() += 1;
+                }
+                if !is_duplicate {
+                    page.push(point);
+                }
+            }
+
+            // All of the points up to the end of the page must have been returned by the shards
+            let is_complete = shard_bounds.iter().all(|bound| match page.last() {
+                Some(last) if page.len() == page_size => rank_cmp(bound, last, ranking).is_ge(),
+                _ => false,
+            });
+
+            if is_complete {
+                break (page, shard_positions, take_explanations(&mut profiles));
+            }
+
+            take = take.saturating_mul(2);
+        };
+
+        let next_page_token = page
+            .last()
+            .filter(|_| page.len() == page_size)
+            .map(|last| PageCursor::new(last, request_hash, now, shard_positions).to_token());
+
+        let points = page.into_iter().skip(offset).collect();
+
+        self.post_process_if_slow_request(instant.elapsed(), request.filter_refs());
+
+        Ok((points, next_page_token, explanations))
+    }
+}
+
+/// Explanations of the segments of each shard replica, held by the profiles of the shards
+fn take_explanations(profiles: &mut [QueryProfile]) -> Vec<ShardExplanation> {
+    let mut explanations = profiles
+        .iter_mut()
+        .flat_map(QueryProfile::take_explanations)
+        .collect_vec();
+    explanations.sort_by_key(|explanation| explanation.shard_id);
+    explanations
+}
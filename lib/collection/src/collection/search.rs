@@ -130,6 +130,14 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Relative datetime ranges are resolved once here, so all shards and replicas see the same values
+        let now = chrono::Utc::now();
+        for search in &mut request.searches {
+            if let Some(filter) = &mut search.filter {
+                filter.resolve_relative_datetime(now)?;
+            }
+        }
+
         if let Some(resharding_filter) = self.shards_holder.read().await.resharding_filter() {
             for search in &mut request.searches {
                 match &mut search.filter {
@@ -0,0 +1,185 @@
+use api::rest::{BatchVectorStruct, Document, VectorStruct};
+use segment::data_types::document::{
+    document_term_frequencies, hashed_dim_id, query_term_frequencies, term_frequency_vector,
+};
+use segment::data_types::vectors::Vector;
+use sparse::common::sparse_vector::SparseVector;
+
+use crate::collection::Collection;
+use crate::config::CollectionParams;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::types::{CollectionError, CollectionResult, SparseDocumentParams};
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query, VectorInput,
+};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+
+impl Collection {
+    /// Replaces raw text documents of the update operation with the encoded sparse vectors.
+    pub(crate) async fn encode_update_documents(
+        &self,
+        operation: &mut CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let documents: Vec<_> = named_vectors_mut(operation)
+            .filter(|(_, vector)| matches!(vector, api::rest::Vector::Document(_)))
+            .collect();
+        if documents.is_empty() {
+            return Ok(());
+        }
+
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        for (vector_name, vector) in documents {
+            let api::rest::Vector::Document(document) = &*vector else {
+                continue;
+            };
+            let params = document_params(&collection_params, vector_name)?;
+            let frequencies =
+                document_term_frequencies(&document.text, &params.text_index_params());
+            *vector = api::rest::Vector::Sparse(encode_frequencies(&frequencies));
+        }
+
+        Ok(())
+    }
+
+    /// Replaces raw text documents of the query with the encoded sparse vectors.
+    pub(crate) async fn encode_query_documents(
+        &self,
+        request: &mut CollectionQueryRequest,
+    ) -> CollectionResult<()> {
+        let mut inputs = Vec::new();
+        collect_query_inputs(
+            &request.using,
+            request.query.as_mut(),
+            &mut request.prefetch,
+            &mut inputs,
+        );
+        if inputs.is_empty() {
+            return Ok(());
+        }
+
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        for (vector_name, input) in inputs {
+            let VectorInput::Document(Document { text }) = &*input else {
+                continue;
+            };
+            let params = document_params(&collection_params, vector_name)?;
+            let frequencies = query_term_frequencies(text, &params.text_index_params());
+            let sparse = encode_frequencies(&frequencies);
+            *input = VectorInput::Vector(Vector::Sparse(sparse));
+        }
+
+        Ok(())
+    }
+}
+
+fn document_params(
+    collection_params: &CollectionParams,
+    vector_name: &str,
+) -> CollectionResult<SparseDocumentParams> {
+    collection_params
+        .get_sparse_vector_params_opt(vector_name)
+        .and_then(|params| params.document.clone())
+        .ok_or_else(|| {
+            CollectionError::bad_request(format!(
+                "Vector {vector_name} does not accept documents, \
+                 only sparse vectors with configured document params do",
+            ))
+        })
+}
+
+fn encode_frequencies(frequencies: &[(String, usize)]) -> SparseVector {
+    term_frequency_vector(
+        frequencies
+            .iter()
+            .map(|(token, count)| (hashed_dim_id(token), *count)),
+    )
+}
+
+/// All named vectors of the points, which are inserted or updated by the operation
+pub(super) fn named_vectors_mut(
+    operation: &mut CollectionUpdateOperations,
+) -> Box<dyn Iterator<Item = (&str, &mut api::rest::Vector)> + '_> {
+    fn of_struct(
+        vector: &mut VectorStruct,
+    ) -> Box<dyn Iterator<Item = (&str, &mut api::rest::Vector)> + '_> {
+        match vector {
+            VectorStruct::Named(vectors) => Box::new(
+                vectors
+                    .iter_mut()
+                    .map(|(name, vector)| (name.as_str(), vector)),
+            ),
+            VectorStruct::Single(_) | VectorStruct::MultiDense(_) => Box::new(std::iter::empty()),
+        }
+    }
+
+    match operation {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(insert)) => {
+            match insert {
+                PointInsertOperationsInternal::PointsList(points) => Box::new(
+                    points
+                        .iter_mut()
+                        .flat_map(|point| of_struct(&mut point.vector)),
+                ),
+                PointInsertOperationsInternal::PointsBatch(batch) => match &mut batch.vectors {
+                    BatchVectorStruct::Named(vectors) => {
+                        Box::new(vectors.iter_mut().flat_map(|(name, vectors)| {
+                            vectors
+                                .iter_mut()
+                                .map(move |vector| (name.as_str(), vector))
+                        }))
+                    }
+                    BatchVectorStruct::Single(_) | BatchVectorStruct::MultiDense(_) => {
+                        Box::new(std::iter::empty())
+                    }
+                },
+            }
+        }
+        CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(sync)) => Box::new(
+            sync.points
+                .iter_mut()
+                .flat_map(|point| of_struct(&mut point.vector)),
+        ),
+        CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(update)) => {
+            Box::new(
+                update
+                    .points
+                    .iter_mut()
+                    .flat_map(|point| of_struct(&mut point.vector)),
+            )
+        }
+        CollectionUpdateOperations::PointOperation(_)
+        | CollectionUpdateOperations::VectorOperation(_)
+        | CollectionUpdateOperations::PayloadOperation(_)
+        | CollectionUpdateOperations::FieldIndexOperation(_) => Box::new(std::iter::empty()),
+    }
+}
+
+/// Raw documents and images of the query and of all its prefetches,
+/// along with the vector they are searched in
+pub(super) fn collect_query_inputs<'a>(
+    using: &'a str,
+    query: Option<&'a mut Query>,
+    prefetches: &'a mut [CollectionPrefetch],
+    inputs: &mut Vec<(&'a str, &'a mut VectorInput)>,
+) {
+    if let Some(vector_query) = query.and_then(|query| query.vector_query_mut()) {
+        inputs.extend(
+            vector_query
+                .flat_iter_mut()
+                .filter(|input| matches!(input, VectorInput::Document(_) | VectorInput::Image(_)))
+                .map(|input| (using, input)),
+        );
+    }
+
+    for prefetch in prefetches {
+        collect_query_inputs(
+            &prefetch.using,
+            prefetch.query.as_mut(),
+            &mut prefetch.prefetch,
+            inputs,
+        );
+    }
+}
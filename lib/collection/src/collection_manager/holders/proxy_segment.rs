@@ -4,9 +4,14 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use bitvec::prelude::BitVec;
-use common::types::{PointOffsetType, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, TelemetryDetail};
+use itertools::Itertools;
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
+use segment::data_types::distinct::{CardinalitySketch, DistinctParams};
+use segment::data_types::facets::{FacetParams, FacetValue};
+use segment::data_types::formula::FormulaQuery;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
 use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
@@ -607,29 +612,61 @@ impl SegmentEntry for ProxySegment {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
-    ) -> Vec<PointIdType> {
+    ) -> OperationResult<Vec<PointIdType>> {
         let deleted_points = self.deleted_points.read();
         let mut read_points = if deleted_points.is_empty() {
             self.wrapped_segment
                 .get()
                 .read()
-                .read_filtered(offset, limit, filter)
+                .read_filtered(offset, limit, filter)?
         } else {
             let wrapped_filter =
                 self.add_deleted_points_condition_to_filter(filter, &deleted_points);
             self.wrapped_segment
                 .get()
                 .read()
-                .read_filtered(offset, limit, Some(&wrapped_filter))
+                .read_filtered(offset, limit, Some(&wrapped_filter))?
         };
         let mut write_segment_points = self
             .write_segment
             .get()
             .read()
-            .read_filtered(offset, limit, filter);
+            .read_filtered(offset, limit, filter)?;
         read_points.append(&mut write_segment_points);
         read_points.sort_unstable();
-        read_points
+        Ok(read_points)
+    }
+
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> OperationResult<Vec<(ScoreType, PointIdType)>> {
+        let deleted_points = self.deleted_points.read();
+        let read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_random_filtered(limit, filter)?
+        } else {
+            let wrapped_filter =
+                self.add_deleted_points_condition_to_filter(filter, &deleted_points);
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_random_filtered(limit, Some(&wrapped_filter))?
+        };
+        let write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_random_filtered(limit, filter)?;
+        // Both samples are ordered by their keys, so the largest keys are a sample of both
+        Ok(read_points
+            .into_iter()
+            .merge_by(write_segment_points, |a, b| a.0 >= b.0)
+            .take(limit)
+            .collect())
     }
 
     fn read_ordered_filtered<'a>(
@@ -659,10 +696,97 @@ impl SegmentEntry for ProxySegment {
             .read()
             .read_ordered_filtered(limit, filter, order_by)?;
         read_points.append(&mut write_segment_points);
-        read_points.sort_unstable();
+        read_points.sort_unstable_by(|(value_a, id_a), (value_b, id_b)| {
+            order_by.cmp_points((value_a, *id_a), (value_b, *id_b))
+        });
         Ok(read_points)
     }
 
+    fn facet(&self, request: &FacetParams) -> OperationResult<HashMap<FacetValue, usize>> {
+        let deleted_points = self.deleted_points.read();
+        let mut counts = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().facet(request)?
+        } else {
+            let wrapped_filter = self
+                .add_deleted_points_condition_to_filter(request.filter.as_ref(), &deleted_points);
+            let wrapped_request = FacetParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment.get().read().facet(&wrapped_request)?
+        };
+        let write_segment_counts = self.write_segment.get().read().facet(request)?;
+        for (value, count) in write_segment_counts {
+            *counts.entry(value).or_default() += count;
+        }
+        Ok(counts)
+    }
+
+    fn aggregate(&self, request: &AggregationParams) -> OperationResult<NumericAggregation> {
+        let deleted_points = self.deleted_points.read();
+        let mut aggregation = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().aggregate(request)?
+        } else {
+            let wrapped_filter = self
+                .add_deleted_points_condition_to_filter(request.filter.as_ref(), &deleted_points);
+            let wrapped_request = AggregationParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .aggregate(&wrapped_request)?
+        };
+        aggregation.merge(self.write_segment.get().read().aggregate(request)?);
+        Ok(aggregation)
+    }
+
+    fn distinct(&self, request: &DistinctParams) -> OperationResult<CardinalitySketch> {
+        let deleted_points = self.deleted_points.read();
+        let mut sketch = if deleted_points.is_empty() {
+            self.wrapped_segment.get().read().distinct(request)?
+        } else {
+            let wrapped_filter = self
+                .add_deleted_points_condition_to_filter(request.filter.as_ref(), &deleted_points);
+            let wrapped_request = DistinctParams {
+                filter: Some(wrapped_filter),
+                ..request.clone()
+            };
+            self.wrapped_segment
+                .get()
+                .read()
+                .distinct(&wrapped_request)?
+        };
+        sketch.merge(self.write_segment.get().read().distinct(request)?);
+        Ok(sketch)
+    }
+
+    fn rescore_with_formula(
+        &self,
+        formula: &FormulaQuery,
+        prefetches_scores: &[HashMap<PointIdType, ScoreType>],
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let mut scored_points = self
+            .wrapped_segment
+            .get()
+            .read()
+            .rescore_with_formula(formula, prefetches_scores)?;
+        {
+            let deleted_points = self.deleted_points.read();
+            if !deleted_points.is_empty() {
+                scored_points.retain(|point| !deleted_points.contains(&point.id));
+            }
+        }
+        scored_points.extend(
+            self.write_segment
+                .get()
+                .read()
+                .rescore_with_formula(formula, prefetches_scores)?,
+        );
+        Ok(scored_points)
+    }
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let deleted_points = self.deleted_points.read();
@@ -723,19 +847,26 @@ impl SegmentEntry for ProxySegment {
         }
     }
 
-    fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation {
+    fn estimate_point_count<'a>(
+        &'a self,
+        filter: Option<&'a Filter>,
+    ) -> OperationResult<CardinalityEstimation> {
         let deleted_point_count = self.deleted_points.read().len();
 
         let (wrapped_segment_est, total_wrapped_size) = {
             let wrapped_segment = self.wrapped_segment.get();
             let wrapped_segment_guard = wrapped_segment.read();
             (
-                wrapped_segment_guard.estimate_point_count(filter),
+                wrapped_segment_guard.estimate_point_count(filter)?,
                 wrapped_segment_guard.available_point_count(),
             )
         };
 
-        let write_segment_est = self.write_segment.get().read().estimate_point_count(filter);
+        let write_segment_est = self
+            .write_segment
+            .get()
+            .read()
+            .estimate_point_count(filter)?;
 
         let expected_deleted_count = if total_wrapped_size > 0 {
             (wrapped_segment_est.exp as f64
@@ -751,14 +882,14 @@ impl SegmentEntry for ProxySegment {
                 vec![]
             };
 
-        CardinalityEstimation {
+        Ok(CardinalityEstimation {
             primary_clauses,
             min: wrapped_segment_est.min.saturating_sub(deleted_point_count)
                 + write_segment_est.min,
             exp: (wrapped_segment_est.exp + write_segment_est.exp)
                 .saturating_sub(expected_deleted_count),
             max: wrapped_segment_est.max + write_segment_est.max,
-        }
+        })
     }
 
     fn segment_type(&self) -> SegmentType {
@@ -919,7 +1050,7 @@ impl SegmentEntry for ProxySegment {
             self.wrapped_segment
                 .get()
                 .read()
-                .read_filtered(None, None, Some(filter));
+                .read_filtered(None, None, Some(filter))?;
         let points_offsets_to_delete = match &self.wrapped_segment {
             LockedSegment::Original(raw_segment) => {
                 let raw_segment_read = raw_segment.read();
@@ -1288,20 +1419,23 @@ mod tests {
         let original_points = original_segment
             .get()
             .read()
-            .read_filtered(None, Some(100), None);
+            .read_filtered(None, Some(100), None)
+            .unwrap();
 
-        let original_points_filtered =
-            original_segment
-                .get()
-                .read()
-                .read_filtered(None, Some(100), Some(&filter));
+        let original_points_filtered = original_segment
+            .get()
+            .read()
+            .read_filtered(None, Some(100), Some(&filter))
+            .unwrap();
 
         let mut proxy_segment = wrap_proxy(&dir, original_segment);
 
         proxy_segment.delete_point(100, 2.into()).unwrap();
 
-        let proxy_res = proxy_segment.read_filtered(None, Some(100), None);
-        let proxy_res_filtered = proxy_segment.read_filtered(None, Some(100), Some(&filter));
+        let proxy_res = proxy_segment.read_filtered(None, Some(100), None).unwrap();
+        let proxy_res_filtered = proxy_segment
+            .read_filtered(None, Some(100), Some(&filter))
+            .unwrap();
 
         assert_eq!(original_points_filtered.len() - 1, proxy_res_filtered.len());
         assert_eq!(original_points.len() - 1, proxy_res.len());
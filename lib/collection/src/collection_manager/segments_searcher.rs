@@ -226,9 +226,9 @@ impl SegmentsSearcher {
         batch_request: Arc<CoreSearchRequestBatch>,
         runtime_handle: &Handle,
         sampling_enabled: bool,
-        query_context: QueryContext,
+        query_context: Arc<QueryContext>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        let query_context_acr = Arc::new(query_context);
+        let query_context_acr = query_context;
 
         // Using block to ensure `segments` variable is dropped in the end of it
         let (locked_segments, searches): (Vec<_>, Vec<_>) = {
@@ -404,6 +404,8 @@ pub enum SearchType {
     #[default]
     Nearest,
     RecommendBestScore,
+    RecommendSumScores,
+    RecommendContrastive,
     Discover,
     Context,
 }
@@ -413,6 +415,8 @@ impl From<&QueryEnum> for SearchType {
         match query {
             QueryEnum::Nearest(_) => Self::Nearest,
             QueryEnum::RecommendBestScore(_) => Self::RecommendBestScore,
+            QueryEnum::RecommendSumScores(_) => Self::RecommendSumScores,
+            QueryEnum::RecommendContrastive(_) => Self::RecommendContrastive,
             QueryEnum::Discover(_) => Self::Discover,
             QueryEnum::Context(_) => Self::Context,
         }
@@ -683,7 +687,7 @@ mod tests {
             Arc::new(batch_request),
             &Handle::current(),
             true,
-            QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+            Arc::new(QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB)),
         )
         .await
         .unwrap()
@@ -748,7 +752,7 @@ mod tests {
                 batch_request.clone(),
                 &Handle::current(),
                 false,
-                QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+                Arc::new(QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB)),
             )
             .await
             .unwrap();
@@ -760,7 +764,7 @@ mod tests {
                 batch_request,
                 &Handle::current(),
                 true,
-                QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+                Arc::new(QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB)),
             )
             .await
             .unwrap();
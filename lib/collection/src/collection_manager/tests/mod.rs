@@ -84,7 +84,13 @@ fn test_update_proxy_segments() {
     let all_ids = segments
         .read()
         .iter()
-        .flat_map(|(_id, segment)| segment.get().read().read_filtered(None, Some(100), None))
+        .flat_map(|(_id, segment)| {
+            segment
+                .get()
+                .read()
+                .read_filtered(None, Some(100), None)
+                .unwrap()
+        })
         .sorted()
         .collect_vec();
 
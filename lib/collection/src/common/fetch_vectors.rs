@@ -152,6 +152,8 @@ impl ReferencedVectors {
                 let rec = self.get(&collection_name, vid)?;
                 rec.get_vector_by_name(vector_name).map(|v| v.to_owned())
             }
+            // Documents and images are turned into vectors before the references are resolved
+            VectorInput::Document(_) | VectorInput::Image(_) => None,
         }
     }
 }
@@ -380,7 +382,11 @@ pub fn build_vector_resolver_query<'a>(
 ) -> Vec<(CollectionQueryResolveRequest<'a>, ShardSelectorInternal)> {
     let mut resolve_prefetches = vec![];
     // resolve query for root query
-    if let Some(collection_query::Query::Vector(vector_query)) = &request.query {
+    if let Some(vector_query) = request
+        .query
+        .as_ref()
+        .and_then(collection_query::Query::vector_query)
+    {
         let resolve_root = CollectionQueryResolveRequest {
             vector_query,
             lookup_from: request.lookup_from.clone(),
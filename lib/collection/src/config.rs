@@ -318,7 +318,18 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index, modifier } = update_params.clone();
+            let SparseVectorParams {
+                index,
+                modifier,
+                document,
+            } = update_params.clone();
+
+            // Existing vectors were encoded with the current params, changing them breaks ranking
+            if document.is_some() && document != sparse_vector_params.document {
+                return Err(CollectionError::bad_request(format!(
+                    "Document params of sparse vector {vector_name} can't be changed",
+                )));
+            }
 
             if let Some(modifier) = modifier {
                 sparse_vector_params.modifier = Some(modifier);
@@ -0,0 +1,323 @@
+//! Embedding providers, which turn raw text and images into dense vectors on the server
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::{stream, StreamExt as _, TryStreamExt as _};
+use parking_lot::Mutex;
+use segment::data_types::vectors::DenseVector;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Max number of provider calls of a single request, which run at the same time
+const MAX_CONCURRENT_EMBEDDING_BATCHES: usize = 4;
+
+/// Raw input, which is embedded by a provider
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EmbeddingInput {
+    Text(String),
+    /// URL or base64 encoded data of the image, passed to the provider as is
+    Image(String),
+}
+
+#[async_trait]
+pub trait EmbeddingProvider: fmt::Debug + Send + Sync {
+    /// Max number of inputs to embed in a single call
+    fn batch_size(&self) -> usize;
+
+    /// Embeddings of the inputs, in the same order
+    async fn embed(&self, inputs: &[EmbeddingInput]) -> CollectionResult<Vec<DenseVector>>;
+}
+
+/// Deterministic provider, which does not need any model.
+///
+/// The same input always gets the same normalized vector, different inputs get unrelated ones.
+#[derive(Debug, Clone)]
+pub struct StubEmbeddingProvider {
+    pub dimension: usize,
+}
+
+#[async_trait]
+impl EmbeddingProvider for StubEmbeddingProvider {
+    fn batch_size(&self) -> usize {
+        32
+    }
+
+    async fn embed(&self, inputs: &[EmbeddingInput]) -> CollectionResult<Vec<DenseVector>> {
+        Ok(inputs.iter().map(|input| self.embed_one(input)).collect())
+    }
+}
+
+impl StubEmbeddingProvider {
+    fn embed_one(&self, input: &EmbeddingInput) -> DenseVector {
+        let vector: DenseVector = (0..self.dimension)
+            .map(|i| {
+                let mut hasher = DefaultHasher::new();
+                (input, i).hash(&mut hasher);
+                // Map the hash into [-1, 1]
+                (hasher.finish() as f64 / u64::MAX as f64 * 2.0 - 1.0) as f32
+            })
+            .collect();
+        let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            return vector;
+        }
+        vector.into_iter().map(|x| x / norm).collect()
+    }
+}
+
+type CacheKey = (String, EmbeddingInput);
+
+/// Recently computed embeddings, the oldest ones are evicted first
+#[derive(Default)]
+struct EmbeddingCache {
+    /// Max total size of the cached embeddings and their inputs, in bytes
+    max_bytes: usize,
+    bytes: usize,
+    entries: HashMap<CacheKey, DenseVector>,
+    order: VecDeque<CacheKey>,
+}
+
+impl EmbeddingCache {
+    /// Approximate memory, taken by the entry
+    fn entry_bytes((provider_name, input): &CacheKey, vector: &DenseVector) -> usize {
+        let input_bytes = match input {
+            EmbeddingInput::Text(text) => text.len(),
+            EmbeddingInput::Image(image) => image.len(),
+        };
+        provider_name.len() + input_bytes + std::mem::size_of_val(vector.as_slice())
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<DenseVector> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: CacheKey, vector: DenseVector) {
+        let entry_bytes = Self::entry_bytes(&key, &vector);
+        if entry_bytes > self.max_bytes || self.entries.contains_key(&key) {
+            return;
+        }
+        self.order.push_back(key.clone());
+        self.entries.insert(key, vector);
+        self.bytes += entry_bytes;
+        while self.bytes > self.max_bytes {
+            let Some(evicted) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(vector) = self.entries.remove(&evicted) {
+                self.bytes -= Self::entry_bytes(&evicted, &vector);
+            }
+        }
+    }
+}
+
+/// Embedding providers of the node, shared by all collections.
+///
+/// Collections refer to the providers by name in the params of their vectors.
+#[derive(Default)]
+pub struct EmbeddingProviders {
+    /// Providers by their names
+    providers: HashMap<String, Arc<dyn EmbeddingProvider>>,
+    cache: Mutex<EmbeddingCache>,
+}
+
+impl fmt::Debug for EmbeddingProviders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmbeddingProviders")
+            .field("providers", &self.providers)
+            .field("cache_bytes", &self.cache.lock().max_bytes)
+            .finish()
+    }
+}
+
+impl EmbeddingProviders {
+    pub fn new(providers: HashMap<String, Arc<dyn EmbeddingProvider>>, cache_bytes: usize) -> Self {
+        Self {
+            providers,
+            cache: Mutex::new(EmbeddingCache {
+                max_bytes: cache_bytes,
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn has_provider(&self, provider_name: &str) -> bool {
+        self.providers.contains_key(provider_name)
+    }
+
+    /// Embeddings of the inputs by the named providers, in the same order.
+    ///
+    /// Cached embeddings are reused, and each distinct input is sent to its provider only once,
+    /// in batches of the provider's size. At most [`MAX_CONCURRENT_EMBEDDING_BATCHES`] batches
+    /// are embedded at the same time.
+    pub async fn embed(
+        &self,
+        inputs: Vec<(&str, EmbeddingInput)>,
+    ) -> CollectionResult<Vec<DenseVector>> {
+        let mut embeddings: Vec<Option<DenseVector>> = vec![None; inputs.len()];
+
+        // Positions of the inputs to embed, per provider
+        let mut missing: BTreeMap<&str, HashMap<EmbeddingInput, Vec<usize>>> = BTreeMap::new();
+        {
+            let cache = self.cache.lock();
+            for (position, (provider_name, input)) in inputs.into_iter().enumerate() {
+                let (provider_name, _) =
+                    self.providers.get_key_value(provider_name).ok_or_else(|| {
+                        CollectionError::bad_request(format!(
+                            "Embedding provider {provider_name} is not configured on this node",
+                        ))
+                    })?;
+                let key = (provider_name.clone(), input);
+                match cache.get(&key) {
+                    Some(embedding) => embeddings[position] = Some(embedding),
+                    None => missing
+                        .entry(provider_name.as_str())
+                        .or_default()
+                        .entry(key.1)
+                        .or_default()
+                        .push(position),
+                }
+            }
+        }
+
+        let mut batches = Vec::new();
+        for (provider_name, positions) in &missing {
+            let provider = &self.providers[*provider_name];
+            let unique_inputs: Vec<_> = positions.keys().cloned().collect();
+            for chunk in unique_inputs.chunks(provider.batch_size().max(1)) {
+                let chunk = chunk.to_vec();
+                batches.push(async move {
+                    let batch_embeddings = provider.embed(&chunk).await?;
+                    if batch_embeddings.len() != chunk.len() {
+                        return Err(CollectionError::service_error(format!(
+                            "Embedding provider {provider_name} returned {} embeddings for {} inputs",
+                            batch_embeddings.len(),
+                            chunk.len(),
+                        )));
+                    }
+                    Ok((*provider_name, chunk, batch_embeddings))
+                });
+            }
+        }
+        let batches: Vec<_> = stream::iter(batches)
+            .buffer_unordered(MAX_CONCURRENT_EMBEDDING_BATCHES)
+            .try_collect()
+            .await?;
+
+        let mut cache = self.cache.lock();
+        for (provider_name, chunk, batch_embeddings) in batches {
+            for (input, embedding) in chunk.into_iter().zip(batch_embeddings) {
+                for &position in &missing[provider_name][&input] {
+                    embeddings[position] = Some(embedding.clone());
+                }
+                cache.insert((provider_name.to_string(), input), embedding);
+            }
+        }
+
+        Ok(embeddings
+            .into_iter()
+            .map(|embedding| embedding.expect("every input is either cached or embedded"))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct CountingProvider {
+        stub: StubEmbeddingProvider,
+        batches: AtomicUsize,
+        inputs: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for CountingProvider {
+        fn batch_size(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, inputs: &[EmbeddingInput]) -> CollectionResult<Vec<DenseVector>> {
+            assert!(inputs.len() <= self.batch_size());
+            self.batches.fetch_add(1, Ordering::Relaxed);
+            self.inputs.fetch_add(inputs.len(), Ordering::Relaxed);
+            self.stub.embed(inputs).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embeddings_are_batched_and_cached() {
+        let provider = Arc::new(CountingProvider {
+            stub: StubEmbeddingProvider { dimension: 4 },
+            batches: AtomicUsize::new(0),
+            inputs: AtomicUsize::new(0),
+        });
+        let providers = EmbeddingProviders::new(
+            HashMap::from([(
+                "stub".to_string(),
+                provider.clone() as Arc<dyn EmbeddingProvider>,
+            )]),
+            1024,
+        );
+
+        let text = |text: &str| EmbeddingInput::Text(text.to_string());
+        let embeddings = providers
+            .embed(vec![
+                ("stub", text("cat")),
+                ("stub", text("cat")),
+                ("stub", text("hat")),
+                ("stub", EmbeddingInput::Image("cat.png".to_string())),
+            ])
+            .await
+            .unwrap();
+
+        // The same input is embedded once
+        assert_eq!(provider.inputs.load(Ordering::Relaxed), 3);
+        assert_eq!(provider.batches.load(Ordering::Relaxed), 2);
+        assert_eq!(embeddings[0], embeddings[1]);
+        assert_ne!(embeddings[0], embeddings[2]);
+        assert_ne!(embeddings[0], embeddings[3]);
+        let norm: f32 = embeddings[0].iter().map(|x| x * x).sum();
+        assert!((norm - 1.0).abs() < 1e-5);
+
+        let cached = providers
+            .embed(vec![("stub", text("hat")), ("stub", text("bat"))])
+            .await
+            .unwrap();
+        assert_eq!(provider.inputs.load(Ordering::Relaxed), 4);
+        assert_eq!(cached[0], embeddings[2]);
+
+        assert!(providers.embed(vec![("other", text("cat"))]).await.is_err());
+    }
+
+    #[test]
+    fn test_embedding_cache_is_bounded_by_bytes() {
+        let key = |text: &str| ("stub".to_string(), EmbeddingInput::Text(text.to_string()));
+        // 4 bytes of the provider name, 3 of the text and 16 of the vector
+        let entry_bytes = EmbeddingCache::entry_bytes(&key("cat"), &vec![0.0; 4]);
+        assert_eq!(entry_bytes, 23);
+
+        let mut cache = EmbeddingCache {
+            max_bytes: entry_bytes * 2,
+            ..Default::default()
+        };
+        cache.insert(key("cat"), vec![0.0; 4]);
+        cache.insert(key("hat"), vec![1.0; 4]);
+        cache.insert(key("bat"), vec![2.0; 4]);
+        assert_eq!(cache.bytes, entry_bytes * 2);
+        assert!(cache.get(&key("cat")).is_none());
+        assert_eq!(cache.get(&key("bat")), Some(vec![2.0; 4]));
+
+        // Entries larger than the whole cache are not kept
+        cache.insert(key("dog"), vec![0.0; 16]);
+        assert!(cache.get(&key("dog")).is_none());
+        assert_eq!(cache.bytes, entry_bytes * 2);
+    }
+}
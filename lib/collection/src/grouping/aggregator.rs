@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::iter;
 
+use common::types::ScoreType;
 use itertools::Itertools;
-use segment::data_types::groups::GroupId;
+use segment::data_types::groups::{GroupId, GroupOrderBy, GroupScoring};
+use segment::data_types::order_by::{Direction, OrderValue};
 use segment::json_path::JsonPath;
 use segment::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use segment::types::{ExtendedPointId, Order, PayloadContainer, PointIdType, ScoredPoint};
@@ -17,11 +20,21 @@ pub(super) struct GroupsAggregator {
     groups: HashMap<GroupId, Hits>,
     max_group_size: usize,
     grouped_by: JsonPath,
+    then_grouped_by: Vec<JsonPath>,
     max_groups: usize,
     full_groups: HashSet<GroupId>,
     group_best_scores: HashMap<GroupId, ScoredPoint>,
     all_ids: HashSet<ExtendedPointId>,
     order: Order,
+    scoring: GroupScoring,
+    order_by: Option<GroupOrderBy>,
+}
+
+/// What groups are ranked by, better groups compare as smaller
+struct GroupRank<'a> {
+    order_value: Option<OrderValue>,
+    score: ScoreType,
+    best_hit: &'a ScoredPoint,
 }
 
 impl GroupsAggregator {
@@ -35,40 +48,74 @@ impl GroupsAggregator {
             groups: HashMap::with_capacity(groups),
             max_group_size: group_size,
             grouped_by,
+            then_grouped_by: Vec::new(),
             max_groups: groups,
             full_groups: HashSet::with_capacity(groups),
             group_best_scores: HashMap::with_capacity(groups),
             all_ids: HashSet::with_capacity(groups * group_size),
             order,
+            scoring: GroupScoring::default(),
+            order_by: None,
         }
     }
 
-    /// Adds a point to the group that corresponds based on the group_by field, assumes that the point has the group_by field
-    fn add_point(&mut self, point: ScoredPoint) -> Result<(), AggregatorError> {
-        // extract all values from the group_by field
-        let payload_values: Vec<_> = point
-            .payload
-            .as_ref()
-            .map(|p| {
-                p.get_value(&self.grouped_by)
+    /// Group by the values of these fields too, keying groups by the values of all the fields
+    pub(super) fn with_then_grouped_by(mut self, then_grouped_by: Vec<JsonPath>) -> Self {
+        self.then_grouped_by = then_grouped_by;
+        self
+    }
+
+    /// Rank groups by the aggregated score of their hits
+    pub(super) fn with_scoring(mut self, scoring: GroupScoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Rank groups by a payload value of their best hit, before their score
+    pub(super) fn with_order_by(mut self, order_by: Option<GroupOrderBy>) -> Self {
+        self.order_by = order_by;
+        self
+    }
+
+    /// Extracts the keys of all the groups the point belongs to
+    fn group_keys(&self, point: &ScoredPoint) -> Result<Vec<GroupId>, AggregatorError> {
+        let payload = point.payload.as_ref().ok_or(KeyNotFound)?;
+
+        // extract all values from each group_by field
+        let mut keys_per_field = iter::once(&self.grouped_by)
+            .chain(&self.then_grouped_by)
+            .map(|path| {
+                payload
+                    .get_value(path)
                     .into_iter()
                     .flat_map(|v| match v {
                         Value::Array(arr) => arr.iter().collect(),
                         _ => vec![v],
                     })
-                    .collect()
+                    .map(GroupId::try_from)
+                    .collect::<Result<Vec<GroupId>, ()>>()
+                    .map(|keys| keys.into_iter().unique().collect_vec())
+                    .map_err(|_| BadKeyType)
             })
-            .ok_or(KeyNotFound)?;
+            .collect::<Result<Vec<_>, _>>()?;
 
-        let group_keys = payload_values
+        if keys_per_field.len() == 1 {
+            return Ok(keys_per_field.swap_remove(0));
+        }
+
+        // one group per combination of values
+        Ok(keys_per_field
             .into_iter()
-            .map(GroupId::try_from)
-            .collect::<Result<Vec<GroupId>, ()>>()
-            .map_err(|_| BadKeyType)?;
+            .multi_cartesian_product()
+            .map(GroupId::Compound)
+            .collect())
+    }
 
-        let unique_group_keys: Vec<_> = group_keys.into_iter().unique().collect();
+    /// Adds a point to the group that corresponds based on the group_by field, assumes that the point has the group_by field
+    fn add_point(&mut self, point: ScoredPoint) -> Result<(), AggregatorError> {
+        let group_keys = self.group_keys(&point)?;
 
-        for group_key in unique_group_keys {
+        for group_key in group_keys {
             let group = self
                 .groups
                 .entry(group_key.clone())
@@ -124,16 +171,94 @@ impl GroupsAggregator {
         self.groups.len()
     }
 
-    /// Return `max_groups` number of keys of the groups with the best score
+    /// Aggregates the scores of the hits that would be returned for the group
+    fn group_score(&self, group_key: &GroupId, best_hit: &ScoredPoint) -> ScoreType {
+        if self.scoring == GroupScoring::Best {
+            return best_hit.score;
+        }
+
+        let mut scores = self.groups[group_key]
+            .values()
+            .map(|hit| hit.score)
+            .collect_vec();
+        match self.order {
+            Order::LargeBetter => scores.sort_unstable_by(|a, b| b.total_cmp(a)),
+            Order::SmallBetter => scores.sort_unstable_by(|a, b| a.total_cmp(b)),
+        }
+        scores.truncate(self.max_group_size);
+
+        match self.scoring {
+            GroupScoring::Best => best_hit.score,
+            GroupScoring::Sum => scores.iter().sum(),
+            GroupScoring::Avg => scores.iter().sum::<ScoreType>() / scores.len() as ScoreType,
+            GroupScoring::Count => scores.len() as ScoreType,
+        }
+    }
+
+    /// Value of the `order_by` field in the payload of the best hit.
+    /// If there are several values, the first one in the ordering direction is used.
+    fn group_order_value(&self, best_hit: &ScoredPoint) -> Option<OrderValue> {
+        let order_by = self.order_by.as_ref()?;
+        let values = best_hit
+            .payload
+            .as_ref()?
+            .get_value(&order_by.key)
+            .into_iter()
+            .flat_map(|v| match v {
+                Value::Array(arr) => arr.iter().collect(),
+                _ => vec![v],
+            })
+            .filter_map(|v| OrderValue::try_from(v.clone()).ok());
+
+        match order_by.direction() {
+            Direction::Asc => values.min(),
+            Direction::Desc => values.max(),
+        }
+    }
+
+    fn cmp_ranks(&self, a: &GroupRank, b: &GroupRank) -> Ordering {
+        // groups without the order_by value go last
+        let by_order_value = match (&a.order_value, &b.order_value) {
+            (Some(a_value), Some(b_value)) => match self.order_by.as_ref().map(|o| o.direction()) {
+                Some(Direction::Desc) => b_value.cmp(a_value),
+                _ => a_value.cmp(b_value),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+
+        let by_score = match (self.scoring, self.order) {
+            (GroupScoring::Count, _) | (_, Order::LargeBetter) => b.score.total_cmp(&a.score),
+            (_, Order::SmallBetter) => a.score.total_cmp(&b.score),
+        };
+
+        let by_best_hit = match self.order {
+            Order::LargeBetter => b.best_hit.cmp(a.best_hit),
+            Order::SmallBetter => a.best_hit.cmp(b.best_hit),
+        };
+
+        by_order_value.then(by_score).then(by_best_hit)
+    }
+
+    /// Return `max_groups` number of keys of the best ranked groups
     fn best_group_keys(&self) -> Vec<GroupId> {
-        let mut pairs: Vec<_> = self.group_best_scores.iter().collect();
+        let mut ranks: Vec<_> = self
+            .group_best_scores
+            .iter()
+            .map(|(key, best_hit)| {
+                let rank = GroupRank {
+                    order_value: self.group_order_value(best_hit),
+                    score: self.group_score(key, best_hit),
+                    best_hit,
+                };
+                (key, rank)
+            })
+            .collect();
 
-        pairs.sort_unstable_by(|(_, score1), (_, score2)| match self.order {
-            Order::LargeBetter => score2.cmp(score1),
-            Order::SmallBetter => score1.cmp(score2),
-        });
+        ranks.sort_unstable_by(|(_, rank1), (_, rank2)| self.cmp_ranks(rank1, rank2));
 
-        pairs
+        ranks
             .iter()
             .take(self.max_groups)
             .map(|(k, _)| (*k).clone())
@@ -166,7 +291,7 @@ impl GroupsAggregator {
         &self.all_ids
     }
 
-    /// Returns the best groups sorted by their best hit. The hits are sorted too.
+    /// Returns the best groups sorted by their rank. The hits are sorted too.
     pub(super) fn distill(mut self) -> Vec<Group> {
         let best_groups = self.best_group_keys();
         let mut groups = Vec::with_capacity(best_groups.len());
@@ -225,6 +350,105 @@ mod unit_tests {
         }
     }
 
+    fn product(idx: u64, score: ScoreType, payload: Value) -> ScoredPoint {
+        ScoredPoint {
+            payload: Some(Payload::from(payload)),
+            ..empty_point(idx, score)
+        }
+    }
+
+    fn distilled_keys(aggregator: GroupsAggregator) -> Vec<GroupId> {
+        aggregator
+            .distill()
+            .into_iter()
+            .map(|group| group.key)
+            .collect()
+    }
+
+    #[test]
+    fn test_group_scoring_and_ordering() {
+        let products = [
+            product(1, 0.9, json!({"brand": "a", "rank": 1})),
+            product(2, 0.3, json!({"brand": "a"})),
+            product(3, 0.8, json!({"brand": "b"})),
+            product(4, 0.7, json!({"brand": "b"})),
+            product(5, 0.5, json!({"brand": "c", "rank": [5, 2]})),
+            product(6, 0.45, json!({"brand": "c"})),
+            product(7, 0.4, json!({"brand": "c"})),
+            product(8, 0.1, json!({"brand": "c"})), // beyond the group size
+        ];
+
+        let aggregate = |scoring, order_by| {
+            let mut aggregator =
+                GroupsAggregator::new(3, 3, "brand".parse().unwrap(), Order::LargeBetter)
+                    .with_scoring(scoring)
+                    .with_order_by(order_by);
+            aggregator.add_points(&products);
+            distilled_keys(aggregator)
+        };
+
+        let keys = |ids: [&str; 3]| ids.map(GroupId::from);
+
+        // best: 0.9, 0.8, 0.5
+        assert_eq!(aggregate(GroupScoring::Best, None), keys(["a", "b", "c"]));
+        // sum: 1.5, 1.35, 1.2
+        assert_eq!(aggregate(GroupScoring::Sum, None), keys(["b", "c", "a"]));
+        // avg: 0.75, 0.6, 0.45
+        assert_eq!(aggregate(GroupScoring::Avg, None), keys(["b", "a", "c"]));
+        // count: 3, then ties broken by the best hit
+        assert_eq!(aggregate(GroupScoring::Count, None), keys(["c", "a", "b"]));
+
+        // groups without the value go last
+        let by_rank = |direction| {
+            Some(GroupOrderBy {
+                key: "rank".parse().unwrap(),
+                direction: Some(direction),
+            })
+        };
+        assert_eq!(
+            aggregate(GroupScoring::Best, by_rank(Direction::Desc)),
+            keys(["c", "a", "b"])
+        );
+        assert_eq!(
+            aggregate(GroupScoring::Sum, by_rank(Direction::Asc)),
+            keys(["a", "c", "b"])
+        );
+    }
+
+    #[test]
+    fn test_group_by_multiple_fields() {
+        let mut aggregator =
+            GroupsAggregator::new(3, 2, "brand".parse().unwrap(), Order::LargeBetter)
+                .with_then_grouped_by(vec!["color".parse().unwrap()])
+                .with_scoring(GroupScoring::Sum);
+
+        let products = [
+            product(1, 0.9, json!({"brand": "a", "color": ["red", "blue"]})),
+            product(2, 0.8, json!({"brand": "a", "color": "red"})),
+            product(3, 0.7, json!({"brand": 1, "color": "red"})),
+            product(4, 0.6, json!({"brand": "c"})), // has no color
+        ];
+        for product in products {
+            aggregator.add_point(product).unwrap();
+        }
+
+        assert_eq!(aggregator.len(), 3);
+        assert_eq!(
+            aggregator.keys_of_filled_groups(),
+            vec![json!(["a", "red"])]
+        );
+
+        let compound = |brand: GroupId, color: &str| GroupId::Compound(vec![brand, color.into()]);
+        assert_eq!(
+            distilled_keys(aggregator),
+            [
+                compound("a".into(), "red"),
+                compound("a".into(), "blue"),
+                compound(1u64.into(), "red"),
+            ]
+        );
+    }
+
     #[test]
     fn test_group_with_multiple_payload_values() {
         let scored_points = vec![
@@ -77,6 +77,13 @@ where
     async fn run(self) -> CollectionResult<Vec<PointGroup>> {
         let with_lookup = self.group_by.with_lookup.clone();
 
+        // Compound group ids can't be used as point ids in the lookup collection
+        if with_lookup.is_some() && !self.group_by.then_group_by.is_empty() {
+            return Err(CollectionError::bad_request(
+                "`with_lookup` is not supported when grouping by multiple fields",
+            ));
+        }
+
         let core_group_by = self
             .group_by
             .into_query_group_request(
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::future::Future;
+use std::iter;
 use std::time::Duration;
 
 use api::rest::{
@@ -8,6 +9,7 @@ use api::rest::{
 };
 use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
+use segment::data_types::groups::{GroupOrderBy, GroupScoring};
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::json_path::JsonPath;
 use segment::types::{
@@ -26,7 +28,8 @@ use crate::lookup::WithLookup;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    CollectionResult, PointGroup, RecommendGroupsRequestInternal, RecommendRequestInternal,
+    CollectionError, CollectionResult, PointGroup, RecommendGroupsRequestInternal,
+    RecommendRequestInternal,
 };
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
 use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
@@ -50,6 +53,15 @@ pub struct GroupRequest {
     /// Path to the field to group by
     pub group_by: JsonPath,
 
+    /// Paths to more fields to group by, groups are keyed by the values of all the fields
+    pub then_group_by: Vec<JsonPath>,
+
+    /// How to aggregate the scores of the hits into the score of the group
+    pub group_scoring: GroupScoring,
+
+    /// Payload field to rank groups by, before their score
+    pub group_order_by: Option<GroupOrderBy>,
+
     /// Limit of points to return per group
     pub group_size: usize,
 
@@ -74,6 +86,9 @@ impl GroupRequest {
         Self {
             source,
             group_by,
+            then_group_by: Vec::new(),
+            group_scoring: GroupScoring::default(),
+            group_order_by: None,
             group_size,
             limit,
             with_lookup: None,
@@ -106,7 +121,10 @@ impl GroupRequest {
                     recommend_into_core_search(&collection.id, recommend_req, &referenced_vectors)?;
                 ShardQueryRequest::from(core_search)
             }
-            SourceRequest::Query(query_req) => {
+            SourceRequest::Query(mut query_req) => {
+                collection.embed_query_inputs([&mut query_req]).await?;
+                collection.encode_query_documents(&mut query_req).await?;
+
                 // Lift nested prefetches to root queries for vector resolution
                 let resolver_requests = build_vector_resolver_query(&query_req, &shard_selection);
 
@@ -124,6 +142,9 @@ impl GroupRequest {
         Ok(QueryGroupRequest {
             source: query_search,
             group_by: self.group_by,
+            then_group_by: self.then_group_by,
+            group_scoring: self.group_scoring,
+            group_order_by: self.group_order_by,
             group_size: self.group_size,
             limit: self.limit,
             with_lookup: self.with_lookup,
@@ -132,9 +153,77 @@ impl GroupRequest {
 }
 
 impl QueryGroupRequest {
-    /// Make `group_by` field selector work with as `with_payload`.
-    fn group_by_to_payload_selector(&self, group_by: &JsonPath) -> WithPayloadInterface {
-        WithPayloadInterface::Fields(vec![group_by.strip_wildcard_suffix()])
+    /// Paths to all the fields to group by
+    fn grouped_by(&self) -> impl Iterator<Item = &JsonPath> + '_ {
+        iter::once(&self.group_by).chain(&self.then_group_by)
+    }
+
+    /// Make `group_by` and `group_order_by` field selectors work with as `with_payload`.
+    fn group_by_to_payload_selector(&self) -> WithPayloadInterface {
+        let order_by_key = self.group_order_by.as_ref().map(|order_by| &order_by.key);
+        WithPayloadInterface::Fields(
+            self.grouped_by()
+                .chain(order_by_key)
+                .map(JsonPath::strip_wildcard_suffix)
+                .collect(),
+        )
+    }
+
+    /// Conditions excluding the points of the given groups
+    fn except_groups(&self, keys: Vec<Value>) -> Vec<Condition> {
+        if self.then_group_by.is_empty() {
+            return except_on(&self.group_by, keys);
+        }
+        if keys.is_empty() {
+            return Vec::new();
+        }
+
+        let exclude_groups = Filter {
+            must_not: Some(
+                keys.into_iter()
+                    .map(|key| self.match_compound_key(key))
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        vec![Condition::Filter(exclude_groups)]
+    }
+
+    /// Conditions matching only the points of the given groups
+    fn match_groups(&self, keys: Vec<Value>) -> Vec<Condition> {
+        if self.then_group_by.is_empty() {
+            return match_on(&self.group_by, keys);
+        }
+        if keys.is_empty() {
+            return Vec::new();
+        }
+
+        let include_groups = Filter {
+            should: Some(
+                keys.into_iter()
+                    .map(|key| self.match_compound_key(key))
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        vec![Condition::Filter(include_groups)]
+    }
+
+    /// Condition matching the points of the group with the given compound key
+    fn match_compound_key(&self, key: Value) -> Condition {
+        let components = match key {
+            Value::Array(components) => components,
+            key => vec![key],
+        };
+        let match_fields = self
+            .grouped_by()
+            .zip(components)
+            .flat_map(|(path, component)| match_on(path, vec![component]))
+            .collect();
+        Condition::Filter(Filter {
+            must: Some(match_fields),
+            ..Default::default()
+        })
     }
 
     async fn r#do(
@@ -148,10 +237,17 @@ impl QueryGroupRequest {
 
         request.limit = self.limit * self.group_size;
 
-        let key_not_empty = Filter::new_must_not(Condition::IsEmpty(self.group_by.clone().into()));
-        request.filter = Some(request.filter.unwrap_or_default().merge(&key_not_empty));
+        let keys_not_empty = Filter {
+            must_not: Some(
+                self.grouped_by()
+                    .map(|path| Condition::IsEmpty(path.clone().into()))
+                    .collect(),
+            ),
+            ..Default::default()
+        };
+        request.filter = Some(request.filter.unwrap_or_default().merge(&keys_not_empty));
 
-        let with_group_by_payload = self.group_by_to_payload_selector(&self.group_by);
+        let with_group_by_payload = self.group_by_to_payload_selector();
 
         // We're enriching the final results at the end, so we'll keep this minimal
         request.with_payload = with_group_by_payload;
@@ -195,6 +291,9 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
         GroupRequest {
             source: SourceRequest::Search(search),
             group_by,
+            then_group_by: Vec::new(),
+            group_scoring: GroupScoring::default(),
+            group_order_by: None,
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
@@ -208,6 +307,8 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             positive,
             negative,
             strategy,
+            positive_weights,
+            negative_weights,
             filter,
             params,
             with_payload,
@@ -228,6 +329,8 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             positive,
             negative,
             strategy,
+            positive_weights,
+            negative_weights,
             filter,
             params,
             limit: 0,
@@ -242,6 +345,9 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
         GroupRequest {
             source: SourceRequest::Recommend(recommend),
             group_by,
+            then_group_by: Vec::new(),
+            group_scoring: GroupScoring::default(),
+            group_order_by: None,
             group_size: group_size as usize,
             limit: limit as usize,
             with_lookup: with_lookup_interface.map(Into::into),
@@ -249,8 +355,10 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
     }
 }
 
-impl From<QueryGroupsRequestInternal> for GroupRequest {
-    fn from(request: QueryGroupsRequestInternal) -> Self {
+impl TryFrom<QueryGroupsRequestInternal> for GroupRequest {
+    type Error = CollectionError;
+
+    fn try_from(request: QueryGroupsRequestInternal) -> CollectionResult<Self> {
         let QueryGroupsRequestInternal {
             prefetch,
             query,
@@ -263,6 +371,9 @@ impl From<QueryGroupsRequestInternal> for GroupRequest {
             group_request:
                 QueryBaseGroupRequest {
                     group_by,
+                    then_group_by,
+                    group_scoring,
+                    group_order_by,
                     group_size,
                     limit,
                     with_lookup: with_lookup_interface,
@@ -270,8 +381,12 @@ impl From<QueryGroupsRequestInternal> for GroupRequest {
         } = request;
 
         let collection_query_request = CollectionQueryRequest {
-            prefetch: prefetch.into_iter().flatten().map(From::from).collect(),
-            query: query.map(From::from),
+            prefetch: prefetch
+                .into_iter()
+                .flatten()
+                .map(TryFrom::try_from)
+                .collect::<CollectionResult<_>>()?,
+            query: query.map(TryFrom::try_from).transpose()?,
             using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
             filter,
             score_threshold,
@@ -283,13 +398,16 @@ impl From<QueryGroupsRequestInternal> for GroupRequest {
             lookup_from: None,
         };
 
-        GroupRequest {
+        Ok(GroupRequest {
             source: SourceRequest::Query(collection_query_request),
             group_by,
+            then_group_by,
+            group_scoring: group_scoring.unwrap_or_default(),
+            group_order_by,
             group_size: group_size.unwrap_or(CollectionQueryRequest::DEFAULT_GROUP_SIZE),
             limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
             with_lookup: with_lookup_interface.map(Into::into),
-        }
+        })
     }
 }
 
@@ -309,32 +427,34 @@ pub async fn group_by(
         request.group_size,
         request.group_by.clone(),
         score_ordering,
-    );
+    )
+    .with_then_grouped_by(request.then_group_by.clone())
+    .with_scoring(request.group_scoring)
+    .with_order_by(request.group_order_by.clone());
 
     // Try to complete amount of groups
     let mut needs_filling = true;
     for _ in 0..MAX_GET_GROUPS_REQUESTS {
         let mut request = request.clone();
 
-        let source = &mut request.source;
-
         // Construct filter to exclude already found groups
         let full_groups = aggregator.keys_of_filled_groups();
-        if !full_groups.is_empty() {
-            let except_any = except_on(&request.group_by, full_groups);
-            if !except_any.is_empty() {
-                let exclude_groups = Filter {
-                    must: Some(except_any),
-                    ..Default::default()
-                };
-                source.filter = Some(
-                    source
-                        .filter
-                        .as_ref()
-                        .map(|filter| filter.merge(&exclude_groups))
-                        .unwrap_or(exclude_groups),
-                );
-            }
+        let except_any = request.except_groups(full_groups);
+
+        let source = &mut request.source;
+
+        if !except_any.is_empty() {
+            let exclude_groups = Filter {
+                must: Some(except_any),
+                ..Default::default()
+            };
+            source.filter = Some(
+                source
+                    .filter
+                    .as_ref()
+                    .map(|filter| filter.merge(&exclude_groups))
+                    .unwrap_or(exclude_groups),
+            );
         }
 
         // Exclude already aggregated points
@@ -378,11 +498,12 @@ pub async fn group_by(
         for _ in 0..MAX_GROUP_FILLING_REQUESTS {
             let mut request = request.clone();
 
-            let source = &mut request.source;
-
             // Construct filter to only include unsatisfied groups
             let unsatisfied_groups = aggregator.keys_of_unfilled_best_groups();
-            let match_any = match_on(&request.group_by, unsatisfied_groups);
+            let match_any = request.match_groups(unsatisfied_groups);
+
+            let source = &mut request.source;
+
             if !match_any.is_empty() {
                 let include_groups = Filter {
                     must: Some(match_any),
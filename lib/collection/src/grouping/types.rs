@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use segment::data_types::groups::GroupId;
+use segment::data_types::groups::{GroupId, GroupOrderBy, GroupScoring};
 use segment::json_path::JsonPath;
 use segment::types::{PointIdType, ScoredPoint};
 
@@ -52,6 +52,15 @@ pub struct QueryGroupRequest {
     /// Path to the field to group by
     pub group_by: JsonPath,
 
+    /// Paths to more fields to group by, groups are keyed by the values of all the fields
+    pub then_group_by: Vec<JsonPath>,
+
+    /// How to aggregate the scores of the hits into the score of the group
+    pub group_scoring: GroupScoring,
+
+    /// Payload field to rank groups by, before their score
+    pub group_order_by: Option<GroupOrderBy>,
+
     /// Limit of points to return per group
     pub group_size: usize,
 
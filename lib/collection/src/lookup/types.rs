@@ -74,6 +74,10 @@ impl From<GroupId> for PseudoId {
             GroupId::String(s) => Self::String(s),
             GroupId::NumberU64(n) => Self::NumberU64(n),
             GroupId::NumberI64(n) => Self::NumberI64(n),
+            // Lookups by compound keys are rejected upfront, they never match a point id
+            compound @ GroupId::Compound(_) => {
+                Self::String(serde_json::Value::from(compound).to_string())
+            }
         }
     }
 }
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
+    date_time_to_proto, from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -19,16 +19,19 @@ use segment::data_types::vectors::{
     BatchVectorStructInternal, NamedQuery, Vector, VectorStructInternal,
 };
 use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint};
-use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::vector_storage::query::{
+    ContextPair, ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery,
+};
 use sparse::common::sparse_vector::{validate_sparse_vector_impl, SparseVector};
 use tonic::Status;
+use validator::Validate as _;
 
 use super::consistency_params::ReadConsistency;
 use super::types::{
     ContextExamplePair, CoreSearchRequest, Datatype, DiscoverRequestInternal, GroupsResult,
     Modifier, PointGroup, RecommendExample, RecommendGroupsRequestInternal, ReshardingInfo,
-    SparseIndexParams, SparseVectorParams, SparseVectorsConfig, VectorParamsDiff,
-    VectorsConfigDiff,
+    SparseDocumentParams, SparseIndexParams, SparseVectorParams, SparseVectorsConfig,
+    VectorParamsDiff, VectorsConfigDiff,
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
@@ -53,9 +56,11 @@ use crate::operations::point_ops::{
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
+    AggregateResult, AggregationValue, AliasDescription, CollectionClusterInfo, CollectionInfo,
+    CollectionStatus, CountResult, DateHistogramFacet, DateHistogramRequest, HistogramRequest,
     LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
-    ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, ShardTransferInfo, UpdateResult,
+    UpdateStatus, VectorParams, VectorsConfig,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::CollectionCoreSearchRequest;
@@ -555,6 +560,7 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            embedding: vector_params.embedding,
         })
     }
 }
@@ -625,10 +631,54 @@ impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
                 .modifier
                 .and_then(api::grpc::qdrant::Modifier::from_i32)
                 .map(Modifier::from),
+            document: sparse_vector_params
+                .document
+                .map(SparseDocumentParams::try_from)
+                .transpose()?,
         })
     }
 }
 
+impl TryFrom<api::grpc::qdrant::SparseDocumentParams> for SparseDocumentParams {
+    type Error = Status;
+
+    fn try_from(params: api::grpc::qdrant::SparseDocumentParams) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::SparseDocumentParams {
+            tokenizer,
+            lowercase,
+            min_token_len,
+            max_token_len,
+        } = params;
+
+        Ok(Self {
+            tokenizer: api::grpc::qdrant::TokenizerType::from_i32(tokenizer)
+                .map(|tokenizer| tokenizer.try_into())
+                .unwrap_or_else(|| Err(Status::invalid_argument("unknown tokenizer type")))?,
+            min_token_len: min_token_len.map(|len| len as usize),
+            max_token_len: max_token_len.map(|len| len as usize),
+            lowercase,
+        })
+    }
+}
+
+impl From<SparseDocumentParams> for api::grpc::qdrant::SparseDocumentParams {
+    fn from(params: SparseDocumentParams) -> Self {
+        let SparseDocumentParams {
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+        } = params;
+
+        Self {
+            tokenizer: api::grpc::qdrant::TokenizerType::from(tokenizer) as i32,
+            lowercase,
+            min_token_len: min_token_len.map(|len| len as u64),
+            max_token_len: max_token_len.map(|len| len as u64),
+        }
+    }
+}
+
 impl From<Modifier> for api::grpc::qdrant::Modifier {
     fn from(value: Modifier) -> Self {
         match value {
@@ -653,6 +703,9 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
             modifier: sparse_vector_params
                 .modifier
                 .map(|modifier| api::grpc::qdrant::Modifier::from(modifier) as i32),
+            document: sparse_vector_params
+                .document
+                .map(api::grpc::qdrant::SparseDocumentParams::from),
         }
     }
 }
@@ -827,7 +880,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             Some(proto_to_payloads(payload)?)
         };
 
-        let vector_struct: VectorStructInternal = match vectors {
+        let vector_struct: api::rest::VectorStruct = match vectors {
             None => return Err(Status::invalid_argument("Expected some vectors")),
             Some(vectors) => vectors.try_into()?,
         };
@@ -836,7 +889,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             id: id
                 .ok_or_else(|| Status::invalid_argument("Empty ID is not allowed"))?
                 .try_into()?,
-            vector: api::rest::VectorStruct::from(vector_struct),
+            vector: vector_struct,
             payload: converted_payload,
         })
     }
@@ -991,18 +1044,216 @@ impl TryFrom<i32> for UpdateStatus {
     }
 }
 
-impl From<api::grpc::qdrant::CountResult> for CountResult {
-    fn from(value: api::grpc::qdrant::CountResult) -> Self {
+impl TryFrom<api::grpc::qdrant::CountResultInternal> for CountResult {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::CountResultInternal) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::CountResultInternal {
+            result,
+            distinct_sketch,
+        } = value;
+
+        let api::grpc::qdrant::CountResult {
+            count,
+            groups,
+            distinct: _,
+        } = result.ok_or_else(|| Status::invalid_argument("Missing count result"))?;
+
+        let groups = groups
+            .into_iter()
+            .map(TryFrom::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            count: count as usize,
+            groups: (!groups.is_empty()).then_some(groups),
+            distinct: distinct_sketch.map(TryFrom::try_from).transpose()?,
+            segments: None,
+            explanations: Vec::new(),
+        })
+    }
+}
+
+impl From<CountResult> for api::grpc::qdrant::CountResult {
+    fn from(value: CountResult) -> Self {
+        let CountResult {
+            count,
+            groups,
+            distinct,
+            segments: _,
+            explanations: _,
+        } = value;
+
         Self {
-            count: value.count as usize,
+            count: count as u64,
+            groups: groups.into_iter().flatten().map(From::from).collect(),
+            distinct: distinct.as_ref().map(|sketch| sketch.estimate() as u64),
         }
     }
 }
 
-impl From<CountResult> for api::grpc::qdrant::CountResult {
+impl From<CountResult> for api::grpc::qdrant::CountResultInternal {
     fn from(value: CountResult) -> Self {
+        let distinct_sketch = value.distinct.clone().map(From::from);
+
         Self {
-            count: value.count as u64,
+            result: Some(value.into()),
+            distinct_sketch,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::HistogramParams> for HistogramRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::HistogramParams) -> Result<Self, Self::Error> {
+        use api::grpc::qdrant::histogram_params::Variant;
+
+        let variant = value
+            .variant
+            .ok_or_else(|| Status::invalid_argument("HistogramParams should have a variant"))?;
+
+        let histogram = match variant {
+            Variant::Interval(interval) => HistogramRequest::Interval { interval },
+            Variant::Buckets(buckets) => HistogramRequest::Buckets {
+                buckets: buckets as usize,
+            },
+        };
+
+        histogram
+            .validate()
+            .map_err(|err| Status::invalid_argument(format!("Invalid histogram: {err}")))?;
+
+        Ok(histogram)
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::DateHistogramParams> for DateHistogramRequest {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::DateHistogramParams) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::DateHistogramParams {
+            interval,
+            time_zone,
+            facet_key,
+            facet_limit,
+        } = value;
+
+        let interval = api::grpc::qdrant::CalendarInterval::from_i32(interval)
+            .ok_or_else(|| Status::invalid_argument("Unknown CalendarInterval"))?;
+
+        let date_histogram = DateHistogramRequest {
+            interval: interval.into(),
+            time_zone,
+            facet: facet_key
+                .map(|key| -> Result<_, Status> {
+                    Ok(DateHistogramFacet {
+                        key: json_path_from_proto(&key)?,
+                        limit: facet_limit.map(|limit| limit as usize),
+                    })
+                })
+                .transpose()?,
+        };
+
+        date_histogram
+            .validate()
+            .map_err(|err| Status::invalid_argument(format!("Invalid date histogram: {err}")))?;
+
+        Ok(date_histogram)
+    }
+}
+
+impl From<AggregationValue> for api::grpc::qdrant::AggregationValue {
+    fn from(value: AggregationValue) -> Self {
+        use api::grpc::qdrant::aggregation_value::Variant;
+
+        let variant = match value {
+            AggregationValue::Number(number) => Variant::Number(number),
+            AggregationValue::Datetime(datetime) => Variant::Datetime(date_time_to_proto(datetime)),
+        };
+
+        Self {
+            variant: Some(variant),
+        }
+    }
+}
+
+impl From<AggregateResult> for api::grpc::qdrant::AggregateResult {
+    fn from(value: AggregateResult) -> Self {
+        let AggregateResult {
+            count,
+            min,
+            max,
+            sum,
+            avg,
+            histogram,
+            date_histogram,
+        } = value;
+
+        Self {
+            count: count as u64,
+            min: min.map(From::from),
+            max: max.map(From::from),
+            sum,
+            avg: avg.map(From::from),
+            histogram: histogram
+                .unwrap_or_default()
+                .into_iter()
+                .map(|bucket| api::grpc::qdrant::HistogramBucket {
+                    from: Some(bucket.from.into()),
+                    to: Some(bucket.to.into()),
+                    count: bucket.count as u64,
+                })
+                .collect(),
+            date_histogram: date_histogram
+                .unwrap_or_default()
+                .into_iter()
+                .map(|bucket| api::grpc::qdrant::DateHistogramBucket {
+                    from: Some(date_time_to_proto(bucket.from)),
+                    to: Some(date_time_to_proto(bucket.to)),
+                    count: bucket.count as u64,
+                    facet: bucket
+                        .facet
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(From::from)
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<SearchMatrixPairsResponse> for api::grpc::qdrant::SearchMatrixPairs {
+    fn from(response: SearchMatrixPairsResponse) -> Self {
+        Self {
+            pairs: response
+                .pairs
+                .into_iter()
+                .map(|pair| api::grpc::qdrant::SearchMatrixPair {
+                    a: Some(pair.a.into()),
+                    b: Some(pair.b.into()),
+                    score: pair.score,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<SearchMatrixOffsetsResponse> for api::grpc::qdrant::SearchMatrixOffsets {
+    fn from(response: SearchMatrixOffsetsResponse) -> Self {
+        let SearchMatrixOffsetsResponse {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids,
+        } = response;
+
+        Self {
+            offsets_row,
+            offsets_col,
+            scores,
+            ids: ids.into_iter().map(From::from).collect(),
         }
     }
 }
@@ -1025,6 +1276,8 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            explain: _,
+            page_token: _,
         } = value;
 
         if let Some(sparse_indices) = &sparse_indices {
@@ -1065,6 +1318,38 @@ impl From<QueryEnum> for api::grpc::qdrant::QueryEnum {
                     },
                 )),
             },
+            QueryEnum::RecommendSumScores(named) => api::grpc::qdrant::QueryEnum {
+                query: Some(api::grpc::qdrant::query_enum::Query::RecommendSumScores(
+                    api::grpc::qdrant::RecoQuery {
+                        positives: named.query.positives.into_iter().map_into().collect(),
+                        negatives: named.query.negatives.into_iter().map_into().collect(),
+                    },
+                )),
+            },
+            QueryEnum::RecommendContrastive(named) => {
+                let (positives, positive_weights) = named
+                    .query
+                    .positives
+                    .into_iter()
+                    .map(|(vector, weight)| (api::grpc::qdrant::Vector::from(vector), weight))
+                    .unzip();
+                let (negatives, negative_weights) = named
+                    .query
+                    .negatives
+                    .into_iter()
+                    .map(|(vector, weight)| (api::grpc::qdrant::Vector::from(vector), weight))
+                    .unzip();
+                api::grpc::qdrant::QueryEnum {
+                    query: Some(api::grpc::qdrant::query_enum::Query::RecommendContrastive(
+                        api::grpc::qdrant::WeightedRecoQuery {
+                            positives,
+                            negatives,
+                            positive_weights,
+                            negative_weights,
+                        },
+                    )),
+                }
+            }
             QueryEnum::Discover(named) => api::grpc::qdrant::QueryEnum {
                 query: Some(api::grpc::qdrant::query_enum::Query::Discover(
                     api::grpc::qdrant::DiscoveryQuery {
@@ -1100,14 +1385,16 @@ impl From<QueryEnum> for api::grpc::qdrant::QueryEnum {
     }
 }
 
-impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearchPoints {
-    fn from(value: CollectionCoreSearchRequest<'a>) -> Self {
+impl<'a> TryFrom<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearchPoints {
+    type Error = Status;
+
+    fn try_from(value: CollectionCoreSearchRequest<'a>) -> Result<Self, Self::Error> {
         let (collection_id, request) = value.0;
 
-        Self {
+        Ok(Self {
             collection_name: collection_id,
             query: Some(request.query.clone().into()),
-            filter: request.filter.clone().map(|f| f.into()),
+            filter: request.filter.clone().map(|f| f.try_into()).transpose()?,
             limit: request.limit as u64,
             with_vectors: request.with_vector.clone().map(|wv| wv.into()),
             with_payload: request.with_payload.clone().map(|wp| wp.into()),
@@ -1116,7 +1403,7 @@ impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearch
             offset: Some(request.offset as u64),
             vector_name: Some(request.query.get_vector_name().to_owned()),
             read_consistency: None,
-        }
+        })
     }
 }
 
@@ -1175,6 +1462,22 @@ fn try_context_pair_from_grpc(
     }
 }
 
+fn try_weighted_vectors_from_grpc(
+    vectors: Vec<api::grpc::qdrant::Vector>,
+    weights: Vec<f32>,
+) -> Result<Vec<(Vector, ScoreType)>, Status> {
+    if vectors.len() != weights.len() {
+        return Err(Status::invalid_argument(
+            "Each weighted example must have exactly one weight",
+        ));
+    }
+    vectors
+        .into_iter()
+        .zip(weights)
+        .map(|(vector, weight)| Ok((vector.try_into()?, weight)))
+        .collect()
+}
+
 impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
     type Error = Status;
 
@@ -1208,6 +1511,38 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
                             using: value.vector_name,
                         })
                     }
+                    api::grpc::qdrant::query_enum::Query::RecommendSumScores(query) => {
+                        QueryEnum::RecommendSumScores(NamedQuery {
+                            query: RecoSumQuery::new(
+                                query
+                                    .positives
+                                    .into_iter()
+                                    .map(TryInto::try_into)
+                                    .collect::<Result<_, _>>()?,
+                                query
+                                    .negatives
+                                    .into_iter()
+                                    .map(TryInto::try_into)
+                                    .collect::<Result<_, _>>()?,
+                            ),
+                            using: value.vector_name,
+                        })
+                    }
+                    api::grpc::qdrant::query_enum::Query::RecommendContrastive(query) => {
+                        let api::grpc::qdrant::WeightedRecoQuery {
+                            positives,
+                            negatives,
+                            positive_weights,
+                            negative_weights,
+                        } = query;
+                        QueryEnum::RecommendContrastive(NamedQuery {
+                            query: ContrastiveQuery::new(
+                                try_weighted_vectors_from_grpc(positives, positive_weights)?,
+                                try_weighted_vectors_from_grpc(negatives, negative_weights)?,
+                            ),
+                            using: value.vector_name,
+                        })
+                    }
                     api::grpc::qdrant::query_enum::Query::Discover(query) => {
                         let target = match query.target {
                             Some(target) => target,
@@ -1407,10 +1742,16 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             .collect::<Result<_, _>>()?;
         let negative = [negative_ids, negative_vectors].concat();
 
+        // Repeated fields can't be unset, no weights are the same as default weights
+        let positive_weights = Some(value.positive_weights).filter(|weights| !weights.is_empty());
+        let negative_weights = Some(value.negative_weights).filter(|weights| !weights.is_empty());
+
         Ok(RecommendRequestInternal {
             positive,
             negative,
             strategy: value.strategy.map(|s| s.try_into()).transpose()?,
+            positive_weights,
+            negative_weights,
             filter: value.filter.map(|f| f.try_into()).transpose()?,
             params: value.params.map(|p| p.into()),
             limit: value.limit as usize,
@@ -1452,12 +1793,16 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             negative_vectors: value.negative_vectors,
             timeout: None, // Passed as query param
             shard_key_selector: None,
+            positive_weights: value.positive_weights,
+            negative_weights: value.negative_weights,
         };
 
         let RecommendRequestInternal {
             positive,
             negative,
             strategy,
+            positive_weights,
+            negative_weights,
             using,
             lookup_from,
             filter,
@@ -1473,6 +1818,8 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             positive,
             negative,
             strategy,
+            positive_weights,
+            negative_weights,
             using,
             lookup_from,
             filter,
@@ -1518,6 +1865,7 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             multivector_config: value
                 .multivector_config
                 .map(api::grpc::qdrant::MultiVectorConfig::from),
+            embedding: value.embedding,
         }
     }
 }
@@ -19,6 +19,8 @@ pub mod vector_params_builder;
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
+use segment::common::operation_error::OperationResult;
 use segment::json_path::JsonPath;
 use segment::types::{ExtendedPointId, PayloadFieldSchema};
 use serde::{Deserialize, Serialize};
@@ -287,6 +289,38 @@ impl CollectionUpdateOperations {
             }
         }
     }
+
+    /// Resolve relative datetime ranges in the filter of the operation, if it has one.
+    ///
+    /// Operation is replicated with absolute values, so all replicas select the same points.
+    pub fn resolve_relative_datetime(&mut self, now: DateTime<Utc>) -> OperationResult<()> {
+        let filter = match self {
+            CollectionUpdateOperations::PointOperation(
+                point_ops::PointOperations::DeletePointsByFilter(filter),
+            )
+            | CollectionUpdateOperations::VectorOperation(
+                vector_ops::VectorOperations::DeleteVectorsByFilter(filter, _),
+            )
+            | CollectionUpdateOperations::PayloadOperation(
+                payload_ops::PayloadOps::ClearPayloadByFilter(filter),
+            ) => Some(filter),
+            CollectionUpdateOperations::PayloadOperation(
+                payload_ops::PayloadOps::SetPayload(op)
+                | payload_ops::PayloadOps::OverwritePayload(op),
+            ) => op.filter.as_mut(),
+            CollectionUpdateOperations::PayloadOperation(
+                payload_ops::PayloadOps::DeletePayload(op),
+            ) => op.filter.as_mut(),
+            CollectionUpdateOperations::PointOperation(_)
+            | CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => None,
+        };
+        match filter {
+            Some(filter) => filter.resolve_relative_datetime(now),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 use segment::data_types::vectors::{DenseVector, Named, NamedQuery, NamedVectorStruct, Vector};
-use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::vector_storage::query::{
+    ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery,
+};
 use sparse::common::sparse_vector::SparseVector;
 
 impl QueryEnum {
@@ -7,6 +9,8 @@ impl QueryEnum {
         match self {
             QueryEnum::Nearest(vector) => vector.get_name(),
             QueryEnum::RecommendBestScore(reco_query) => reco_query.get_name(),
+            QueryEnum::RecommendSumScores(reco_query) => reco_query.get_name(),
+            QueryEnum::RecommendContrastive(contrastive_query) => contrastive_query.get_name(),
             QueryEnum::Discover(discovery_query) => discovery_query.get_name(),
             QueryEnum::Context(context_query) => context_query.get_name(),
         }
@@ -16,9 +20,11 @@ impl QueryEnum {
     pub fn is_distance_scored(&self) -> bool {
         match self {
             QueryEnum::Nearest(_) => true,
-            QueryEnum::RecommendBestScore(_) | QueryEnum::Discover(_) | QueryEnum::Context(_) => {
-                false
-            }
+            QueryEnum::RecommendBestScore(_)
+            | QueryEnum::RecommendSumScores(_)
+            | QueryEnum::RecommendContrastive(_)
+            | QueryEnum::Discover(_)
+            | QueryEnum::Context(_) => false,
         }
     }
 
@@ -41,6 +47,24 @@ impl QueryEnum {
                     }
                 }
             }
+            QueryEnum::RecommendSumScores(reco_query) => {
+                let name = reco_query.get_name();
+                for vector in reco_query.query.flat_iter() {
+                    match vector {
+                        Vector::Sparse(sparse_vector) => f(name, sparse_vector),
+                        Vector::Dense(_) | Vector::MultiDense(_) => {}
+                    }
+                }
+            }
+            QueryEnum::RecommendContrastive(contrastive_query) => {
+                let name = contrastive_query.get_name();
+                for vector in contrastive_query.query.flat_iter() {
+                    match vector {
+                        Vector::Sparse(sparse_vector) => f(name, sparse_vector),
+                        Vector::Dense(_) | Vector::MultiDense(_) => {}
+                    }
+                }
+            }
             QueryEnum::Discover(discovery_query) => {
                 let name = discovery_query.get_name();
                 for pair in discovery_query.query.flat_iter() {
@@ -67,6 +91,8 @@ impl QueryEnum {
 pub enum QueryEnum {
     Nearest(NamedVectorStruct),
     RecommendBestScore(NamedQuery<RecoQuery<Vector>>),
+    RecommendSumScores(NamedQuery<RecoSumQuery<Vector>>),
+    RecommendContrastive(NamedQuery<ContrastiveQuery<Vector>>),
     Discover(NamedQuery<DiscoveryQuery<Vector>>),
     Context(NamedQuery<ContextQuery<Vector>>),
 }
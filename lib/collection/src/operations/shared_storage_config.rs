@@ -1,8 +1,10 @@
 use std::default;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::common::snapshots_manager::SnapShotsConfig;
+use crate::embeddings::EmbeddingProviders;
 use crate::operations::types::NodeType;
 use crate::shards::transfer::ShardTransferMethod;
 
@@ -31,6 +33,8 @@ pub struct SharedStorageConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: String,
     pub snapshots_config: SnapShotsConfig,
+    /// Providers, which embed raw inputs of the named vectors
+    pub embeddings: Arc<EmbeddingProviders>,
 }
 
 impl Default for SharedStorageConfig {
@@ -48,6 +52,7 @@ impl Default for SharedStorageConfig {
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
+            embeddings: Default::default(),
         }
     }
 }
@@ -67,6 +72,7 @@ impl SharedStorageConfig {
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: String,
         snapshots_config: SnapShotsConfig,
+        embeddings: Arc<EmbeddingProviders>,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -85,6 +91,7 @@ impl SharedStorageConfig {
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            embeddings,
         }
     }
 }
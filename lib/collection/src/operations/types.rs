@@ -1,4 +1,5 @@
 use std::backtrace::Backtrace;
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error as _;
 use std::fmt::Write as _;
@@ -20,15 +21,23 @@ use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
+use segment::data_types::aggregations::{
+    CalendarInterval, DateHistogramBucketing, HistogramBucketing, NumericAggregation,
+};
+use segment::data_types::distinct::CardinalitySketch;
+use segment::data_types::explain::{SegmentExplanation, ShardExplanation};
+use segment::data_types::facets::{merge_facet_counts, FacetParams, FacetValueHit};
 use segment::data_types::groups::GroupId;
-use segment::data_types::order_by::OrderValue;
+use segment::data_types::index::{TextIndexParams, TextIndexType, TokenizerType};
+use segment::data_types::order_by::{OrderBy, OrderValue};
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStructInternal, DEFAULT_VECTOR_NAME,
 };
+use segment::json_path::JsonPath;
 use segment::types::{
-    Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    DateTimePayloadType, Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo,
+    PayloadKeyType, PointIdType, QuantizationConfig, ScoredPoint, SearchParams, SeqNumberType,
+    ShardKey, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use serde;
@@ -339,6 +348,7 @@ pub struct ScrollRequestInternal {
     pub with_vector: WithVector,
 
     /// Order the records by a payload field.
+    #[validate]
     pub order_by: Option<OrderByInterface>,
 }
 
@@ -357,8 +367,20 @@ pub struct QueryScrollRequestInternal {
     /// Options for specifying which vectors to include into response. Default is false.
     pub with_vector: WithVector,
 
-    /// Order the records by a payload field.
-    pub order_by: Option<OrderByInterface>,
+    /// Order of the records.
+    pub scroll_order: ScrollOrder,
+}
+
+/// Order of the records in a scroll, used as a part of query request
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ScrollOrder {
+    /// Ascending order of the ids
+    #[default]
+    ById,
+    /// Order by a payload field
+    ByField(OrderBy),
+    /// Uniformly random order, with the sampling keys as scores
+    Random,
 }
 
 impl ScrollRequestInternal {
@@ -407,6 +429,17 @@ pub struct SearchRequest {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If true, describe how each segment of each shard replica has executed the search: the
+    /// chosen strategy, estimated and actual filter cardinality, and the time spent.
+    /// Not supported in batch requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explain: Option<bool>,
+    /// Continue the results right after the last point of the previous page, by passing the
+    /// `next_page_token` of its response. Pages never repeat points of the previous pages.
+    /// Changes made in between the requests only affect the points after the last one returned.
+    /// Must be used with the same search, and without offset. Ignored in batch requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -561,6 +594,16 @@ pub struct RecommendRequestInternal {
     /// How to use positive and negative examples to find the results
     pub strategy: Option<api::rest::RecommendStrategy>,
 
+    /// Weights of the positive examples, in the same order.
+    /// Only used by the `contrastive` strategy, 1.0 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positive_weights: Option<Vec<ScoreType>>,
+
+    /// Weights of the negative examples, in the same order.
+    /// Only used by the `contrastive` strategy, 1.0 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_weights: Option<Vec<ScoreType>>,
+
     /// Look only for points which satisfies this conditions
     #[validate]
     pub filter: Option<Filter>,
@@ -634,6 +677,16 @@ pub struct RecommendGroupsRequestInternal {
     #[serde(default)]
     pub strategy: Option<RecommendStrategy>,
 
+    /// Weights of the positive examples, in the same order.
+    /// Only used by the `contrastive` strategy, 1.0 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positive_weights: Option<Vec<ScoreType>>,
+
+    /// Weights of the negative examples, in the same order.
+    /// Only used by the `contrastive` strategy, 1.0 by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_weights: Option<Vec<ScoreType>>,
+
     /// Look only for points which satisfies this conditions
     #[validate]
     pub filter: Option<Filter>,
@@ -799,17 +852,513 @@ pub struct CountRequestInternal {
     /// Approximate count might be unreliable during the indexing process. Default: true
     #[serde(default = "default_exact_count")]
     pub exact: bool,
+    /// If set, also count points for each value of this payload key, most frequent values first.
+    /// Must have a keyword, integer or bool index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<JsonPath>,
+    /// Max number of `group_by` values to return. Default: 10.
+    /// If `exact` is false, counts are estimated the same way as in facet requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub group_by_limit: Option<usize>,
+    /// If set, also estimate the number of distinct values of this payload key among the counted points.
+    /// Small numbers of values are counted exactly, larger ones have an error of about 2%
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinct: Option<JsonPath>,
+    /// If true, describe how each segment of each shard replica has counted the points: the
+    /// chosen strategy, estimated and actual filter cardinality, and the time spent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explain: Option<bool>,
+}
+
+impl CountRequestInternal {
+    /// Params to count the points of each `group_by` value, if it is requested
+    pub fn group_by_params(&self) -> Option<FacetParams> {
+        Some(FacetParams {
+            key: self.group_by.clone()?,
+            limit: self.group_by_limit.unwrap_or(FacetParams::DEFAULT_LIMIT),
+            filter: self.filter.clone(),
+            exact: self.exact,
+        })
+    }
 }
 
 pub const fn default_exact_count() -> bool {
     true
 }
 
-#[derive(Debug, Serialize, JsonSchema)]
+#[derive(Debug, Default, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CountResult {
     /// Number of points which satisfy the conditions
     pub count: usize,
+    /// Number of points for each value of the `group_by` key, most frequent first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<FacetValueHit>>,
+    /// Estimated number of distinct values of the `distinct` key among the counted points
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_distinct_estimate"
+    )]
+    #[schemars(with = "Option<usize>")]
+    pub distinct: Option<CardinalitySketch>,
+    /// How the segments of a shard replica have counted the points, if the request is explained
+    #[serde(skip)]
+    pub segments: Option<Vec<SegmentExplanation>>,
+    /// How the segments of each shard replica have counted the points, if the request is explained
+    #[serde(skip)]
+    pub explanations: Vec<ShardExplanation>,
+}
+
+fn serialize_distinct_estimate<S: serde::Serializer>(
+    sketch: &Option<CardinalitySketch>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    sketch
+        .as_ref()
+        .map(CardinalitySketch::estimate)
+        .serialize(serializer)
+}
+
+impl CountResult {
+    /// Combine counts of disjoint sets of points, e.g. of different shards
+    pub fn merge(&mut self, other: Self) {
+        self.count += other.count;
+        if let Some(other_groups) = other.groups {
+            let counts = self
+                .groups
+                .take()
+                .into_iter()
+                .flatten()
+                .chain(other_groups)
+                .map(|hit| (hit.value, hit.count));
+            self.groups = Some(merge_facet_counts(counts, None));
+        }
+        if let Some(other_distinct) = other.distinct {
+            self.distinct
+                .get_or_insert_with(CardinalitySketch::default)
+                .merge(other_distinct);
+        }
+        self.explanations.extend(other.explanations);
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub facet_request: FacetRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Facet Request
+/// Counts the number of points which satisfy the given filter, per each value of the given payload key.
+/// Returns the most frequent values first.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetRequestInternal {
+    /// Payload key to count values of. Must have a keyword, integer or bool index
+    pub key: JsonPath,
+    /// Max number of values to return. Default: 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// If true, count exact number of points for each value.
+    /// If false, counts are estimated, which is faster: with a broad filter the counts of the index
+    /// are scaled by the share of matching points, and only the most frequent values of each segment
+    /// and shard are merged. Default: false
+    #[serde(default)]
+    pub exact: bool,
+}
+
+impl FacetRequestInternal {
+    pub fn facet_params(&self) -> FacetParams {
+        FacetParams {
+            key: self.key.clone(),
+            limit: self.limit.unwrap_or(FacetParams::DEFAULT_LIMIT),
+            filter: self.filter.clone(),
+            exact: self.exact,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FacetResult {
+    /// Values of the payload key with the number of points having them, most frequent first
+    pub hits: Vec<FacetValueHit>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct AggregateRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub aggregate_request: AggregateRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Aggregate Request
+/// Computes statistics of the values of an integer, float or datetime payload key
+/// over the points which satisfy the given filter.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct AggregateRequestInternal {
+    /// Payload key to aggregate values of.
+    /// Uses the integer, float or datetime index of the key if there is one, otherwise reads values from payload
+    pub key: JsonPath,
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// If set, also count values per histogram bucket
+    #[validate]
+    pub histogram: Option<HistogramRequest>,
+    /// If set, also count points per calendar bucket. Requires a datetime index on the key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub date_histogram: Option<DateHistogramRequest>,
+}
+
+/// Width of histogram buckets
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case", untagged)]
+pub enum HistogramRequest {
+    /// Buckets of a fixed width, starting from zero.
+    /// For datetime keys the interval is in seconds
+    Interval { interval: f64 },
+    /// Given number of buckets of equal width, which span from the min to the max value
+    Buckets { buckets: usize },
+}
+
+impl Validate for HistogramRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let (field, valid) = match self {
+            HistogramRequest::Interval { interval } => ("interval", *interval > 0.0),
+            HistogramRequest::Buckets { buckets } => ("buckets", *buckets > 0),
+        };
+        if valid {
+            return Ok(());
+        }
+
+        let mut errors = ValidationErrors::new();
+        errors.add(field, {
+            let mut error = ValidationError::new("range");
+            error.add_param(Cow::from("min"), &0);
+            error.message = Some(Cow::from("must be greater than 0"));
+            error
+        });
+        Err(errors)
+    }
+}
+
+/// Buckets of calendar units, such as days or months, in a time zone
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct DateHistogramRequest {
+    pub interval: CalendarInterval,
+    /// IANA name of the time zone of bucket edges, for example `Europe/Berlin`. Default: UTC
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_time_zone")]
+    pub time_zone: Option<String>,
+    /// If set, also count the values of a keyword, integer or bool key within each bucket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub facet: Option<DateHistogramFacet>,
+}
+
+impl DateHistogramRequest {
+    pub fn bucketing(&self) -> CollectionResult<DateHistogramBucketing> {
+        let time_zone = match &self.time_zone {
+            Some(time_zone) => time_zone
+                .parse()
+                .map_err(|err| CollectionError::bad_input(format!("Invalid time zone: {err}")))?,
+            None => chrono_tz::UTC,
+        };
+        Ok(DateHistogramBucketing {
+            interval: self.interval,
+            time_zone,
+            facet: self.facet.as_ref().map(|facet| facet.key.clone()),
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct DateHistogramFacet {
+    /// Payload key of the facet. Requires a keyword, integer or bool index
+    pub key: JsonPath,
+    /// Max number of values to return per bucket. Default is 10
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+}
+
+fn validate_time_zone(time_zone: &str) -> Result<(), ValidationError> {
+    if time_zone.parse::<chrono_tz::Tz>().is_ok() {
+        return Ok(());
+    }
+    let mut error = ValidationError::new("time_zone");
+    error.message = Some(Cow::from("must be an IANA time zone name"));
+    Err(error)
+}
+
+/// Value of an aggregation. Values of datetime keys are aggregated as datetimes
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum AggregationValue {
+    Number(f64),
+    Datetime(DateTimePayloadType),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HistogramBucket {
+    /// Lower bound of the bucket, inclusive
+    pub from: AggregationValue,
+    /// Upper bound of the bucket, exclusive
+    pub to: AggregationValue,
+    /// Number of values in the bucket
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DateHistogramBucket {
+    /// Start of the bucket, inclusive
+    pub from: DateTimePayloadType,
+    /// End of the bucket, exclusive
+    pub to: DateTimePayloadType,
+    /// Number of points with a value in the bucket
+    pub count: usize,
+    /// Most frequent values of the facet key among the points of the bucket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet: Option<Vec<FacetValueHit>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AggregateResult {
+    /// Number of aggregated values. Each value of a multi-value key is counted separately
+    pub count: usize,
+    pub min: Option<AggregationValue>,
+    pub max: Option<AggregationValue>,
+    /// Sum of the values. Not available for datetime keys
+    pub sum: Option<f64>,
+    pub avg: Option<AggregationValue>,
+    /// Histogram buckets, in ascending order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Vec<HistogramBucket>>,
+    /// Date histogram buckets in ascending order, only non-empty buckets are returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_histogram: Option<Vec<DateHistogramBucket>>,
+}
+
+impl AggregateResult {
+    pub fn from_aggregation(
+        aggregation: NumericAggregation,
+        bucketing: Option<&HistogramBucketing>,
+        date_histogram: Option<&DateHistogramBucketing>,
+        facet_limit: Option<usize>,
+        is_datetime: bool,
+    ) -> Self {
+        let value = |value: f64| {
+            if is_datetime {
+                chrono::DateTime::from_timestamp_micros(value.round() as i64)
+                    .map_or(AggregationValue::Number(value), |datetime| {
+                        AggregationValue::Datetime(datetime.into())
+                    })
+            } else {
+                AggregationValue::Number(value)
+            }
+        };
+
+        let histogram = bucketing.map(|bucketing| {
+            let bucket = |bucket: i64, count: usize| HistogramBucket {
+                from: value(bucketing.bucket_start(bucket)),
+                to: value(bucketing.bucket_start(bucket + 1)),
+                count,
+            };
+            match bucketing.buckets {
+                // Fixed number of buckets, include the empty ones
+                Some(buckets) if aggregation.count > 0 => (0..buckets as i64)
+                    .map(|i| bucket(i, aggregation.histogram.get(&i).copied().unwrap_or(0)))
+                    .collect(),
+                _ => aggregation
+                    .histogram
+                    .iter()
+                    .map(|(&i, &count)| bucket(i, count))
+                    .collect(),
+            }
+        });
+
+        let datetime = |timestamp: i64| -> DateTimePayloadType {
+            chrono::DateTime::from_timestamp_micros(timestamp)
+                .unwrap_or_default()
+                .into()
+        };
+
+        Self {
+            count: aggregation.count,
+            min: aggregation.min.map(value),
+            max: aggregation.max.map(value),
+            sum: (!is_datetime && aggregation.count > 0).then_some(aggregation.sum),
+            avg: aggregation.avg().map(value),
+            histogram,
+            date_histogram: date_histogram.map(|bucketing| {
+                aggregation
+                    .date_histogram
+                    .into_iter()
+                    .map(|(bucket, counts)| DateHistogramBucket {
+                        from: datetime(bucket),
+                        to: datetime(bucketing.next_bucket(bucket)),
+                        count: counts.count,
+                        facet: bucketing.facet.as_ref().map(|_| {
+                            merge_facet_counts(
+                                counts.facet,
+                                Some(facet_limit.unwrap_or(FacetParams::DEFAULT_LIMIT)),
+                            )
+                        }),
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequest {
+    #[serde(flatten)]
+    #[validate]
+    pub search_matrix_request: SearchMatrixRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Search Matrix Request
+/// Samples random points which satisfy the given filter, and finds the nearest neighbours of each
+/// of them among the sampled points.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixRequestInternal {
+    /// Look only for points which satisfies this conditions
+    #[validate]
+    pub filter: Option<Filter>,
+    /// How many points to sample, at most 1000. Default: 10
+    #[validate(range(min = 2, max = 1000))]
+    pub sample: Option<usize>,
+    /// How many nearest neighbours to find for each sampled point, at most 1000.
+    /// If not set, all of the other sampled points are returned, which is the full distance matrix.
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: Option<usize>,
+    /// Name of the vector to use. Default: the default vector
+    pub using: Option<String>,
+}
+
+impl SearchMatrixRequestInternal {
+    pub const DEFAULT_SAMPLE: usize = 10;
+}
+
+/// Sampled points with their nearest neighbours among the sampled points
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SearchMatrixResult {
+    /// Ids of the sampled points
+    pub sample_ids: Vec<PointIdType>,
+    /// Nearest neighbours of each sampled point, in the order of `sample_ids`
+    pub nearests: Vec<Vec<ScoredPoint>>,
+}
+
+/// Nearest neighbours of the sampled points, as a sparse matrix in coordinate format.
+/// Row and column offsets refer to the positions in `ids`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixOffsetsResponse {
+    /// Row offsets of the scores, each refers to a sampled point
+    pub offsets_row: Vec<u64>,
+    /// Column offsets of the scores, each refers to a neighbour of the sampled point
+    pub offsets_col: Vec<u64>,
+    /// Scores between the points of the rows and the columns
+    pub scores: Vec<ScoreType>,
+    /// Ids of the sampled points
+    pub ids: Vec<PointIdType>,
+}
+
+/// Pair of a sampled point and one of its nearest neighbours
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPair {
+    pub a: PointIdType,
+    pub b: PointIdType,
+    pub score: ScoreType,
+}
+
+/// Nearest neighbours of the sampled points, as a list of pairs
+#[derive(Debug, Clone, PartialEq, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchMatrixPairsResponse {
+    /// Pairs of the sampled points with their nearest neighbours
+    pub pairs: Vec<SearchMatrixPair>,
+}
+
+impl From<SearchMatrixResult> for SearchMatrixOffsetsResponse {
+    fn from(result: SearchMatrixResult) -> Self {
+        let SearchMatrixResult {
+            sample_ids,
+            nearests,
+        } = result;
+
+        let offsets: HashMap<_, _> = sample_ids
+            .iter()
+            .enumerate()
+            .map(|(offset, id)| (*id, offset as u64))
+            .collect();
+
+        let mut response = SearchMatrixOffsetsResponse {
+            ids: sample_ids,
+            ..Default::default()
+        };
+        for (row, nearest) in nearests.into_iter().enumerate() {
+            for point in nearest {
+                let Some(col) = offsets.get(&point.id) else {
+                    continue;
+                };
+                response.offsets_row.push(row as u64);
+                response.offsets_col.push(*col);
+                response.scores.push(point.score);
+            }
+        }
+        response
+    }
+}
+
+impl From<SearchMatrixResult> for SearchMatrixPairsResponse {
+    fn from(result: SearchMatrixResult) -> Self {
+        let SearchMatrixResult {
+            sample_ids,
+            nearests,
+        } = result;
+
+        let pairs = sample_ids
+            .into_iter()
+            .zip(nearests)
+            .flat_map(|(a, nearest)| {
+                nearest.into_iter().map(move |point| SearchMatrixPair {
+                    a,
+                    b: point.id,
+                    score: point.score,
+                })
+            })
+            .collect();
+
+        SearchMatrixPairsResponse { pairs }
+    }
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -1012,6 +1561,14 @@ impl From<OperationError> for CollectionError {
             },
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
             OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingGeoIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingOrderingIndexForOrderBy { .. } => {
+                Self::bad_input(format!("{err}"))
+            }
+            OperationError::MissingMapIndexForFacet { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingDatetimeIndexForHistogram { .. } => {
+                Self::bad_input(format!("{err}"))
+            }
         }
     }
 }
@@ -1265,6 +1822,11 @@ pub struct VectorParams {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivector_config: Option<MultiVectorConfig>,
+
+    /// Name of the embedding provider, which embeds raw documents and images of this vector.
+    /// The provider must be configured on every node of the cluster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<String>,
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1311,6 +1873,13 @@ pub struct SparseVectorParams {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// Allows to insert and query this vector with raw text documents, which are encoded
+    /// into term frequencies on the server. Combine with `idf` modifier for BM25-like ranking.
+    /// Default: documents are not accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub document: Option<SparseDocumentParams>,
 }
 
 impl Anonymize for SparseVectorParams {
@@ -1318,6 +1887,45 @@ impl Anonymize for SparseVectorParams {
         Self {
             index: self.index.anonymize(),
             modifier: self.modifier.clone(),
+            document: self.document.clone(),
+        }
+    }
+}
+
+/// Params of the server-side encoding of raw text documents into a sparse vector.
+/// Dimension of a token is its hash, so all peers encode the same token the same way.
+#[derive(
+    Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Default,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseDocumentParams {
+    /// Tokenizer to split the documents with. Default: word
+    #[serde(default)]
+    pub tokenizer: TokenizerType,
+
+    /// Skip tokens shorter than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub min_token_len: Option<usize>,
+
+    /// Skip tokens longer than this
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_token_len: Option<usize>,
+
+    /// If true, lowercase all tokens. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowercase: Option<bool>,
+}
+
+impl SparseDocumentParams {
+    pub fn text_index_params(&self) -> TextIndexParams {
+        TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: self.tokenizer,
+            min_token_len: self.min_token_len,
+            max_token_len: self.max_token_len,
+            lowercase: self.lowercase,
         }
     }
 }
@@ -1809,6 +2417,10 @@ impl From<QueryEnum> for QueryVector {
         match query {
             QueryEnum::Nearest(named) => QueryVector::Nearest(named.into()),
             QueryEnum::RecommendBestScore(named) => QueryVector::Recommend(named.query),
+            QueryEnum::RecommendSumScores(named) => QueryVector::RecommendSumScores(named.query),
+            QueryEnum::RecommendContrastive(named) => {
+                QueryVector::RecommendContrastive(named.query)
+            }
             QueryEnum::Discover(named) => QueryVector::Discovery(named.query),
             QueryEnum::Context(named) => QueryVector::Context(named.query),
         }
@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
-use api::rest::{LookupLocation, RecommendStrategy};
+use api::rest::{Document, Image, LookupLocation, RecommendStrategy, Sample};
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::data_types::formula::FormulaQuery;
 use segment::data_types::order_by::OrderBy;
 use segment::data_types::vectors::{
     MultiDenseVectorInternal, NamedQuery, NamedVectorStruct, Vector, VectorRef, DEFAULT_VECTOR_NAME,
@@ -11,9 +12,11 @@ use segment::types::{
     Condition, ExtendedPointId, Filter, HasIdCondition, PointIdType, SearchParams,
     WithPayloadInterface, WithVector,
 };
-use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::vector_storage::query::{
+    ContextPair, ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery,
+};
 
-use super::shard_query::{Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest};
+use super::shard_query::{Fusion, MmrQuery, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::common::fetch_vectors::ReferencedVectors;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -66,9 +69,43 @@ pub enum Query {
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Score the results of the prefetches with a formula
+    Formula(FormulaQuery),
+
+    /// Diversify the results of the prefetches with maximal marginal relevance
+    Mmr(MmrInput),
+
+    /// Sample the points, which satisfy the filter
+    Sample(Sample),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MmrInput {
+    /// Query, to which the relevance of the candidates is measured, always a nearest query
+    pub nearest: VectorQuery<VectorInput>,
+    /// Weight of the relevance, from 0.0 for only diversity to 1.0 for only relevance
+    pub lambda: f32,
 }
 
 impl Query {
+    /// Vector query, whose vector references need to be resolved
+    pub fn vector_query(&self) -> Option<&VectorQuery<VectorInput>> {
+        match self {
+            Query::Vector(vector_query) => Some(vector_query),
+            Query::Mmr(mmr) => Some(&mmr.nearest),
+            Query::Fusion(_) | Query::OrderBy(_) | Query::Formula(_) | Query::Sample(_) => None,
+        }
+    }
+
+    pub fn vector_query_mut(&mut self) -> Option<&mut VectorQuery<VectorInput>> {
+        match self {
+            Query::Vector(vector_query) => Some(vector_query),
+            Query::Mmr(mmr) => Some(&mut mmr.nearest),
+            Query::Fusion(_) | Query::OrderBy(_) | Query::Formula(_) | Query::Sample(_) => None,
+        }
+    }
+
     pub fn try_into_scoring_query(
         self,
         ids_to_vectors: &ReferencedVectors,
@@ -87,6 +124,19 @@ impl Query {
             }
             Query::Fusion(fusion) => ScoringQuery::Fusion(fusion),
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
+            Query::Formula(formula) => ScoringQuery::Formula(formula),
+            Query::Sample(sample) => ScoringQuery::Sample(sample),
+            Query::Mmr(MmrInput { nearest, lambda }) => {
+                let query_enum = nearest
+                    .ids_into_vectors(ids_to_vectors, lookup_vector_name, lookup_collection)
+                    .into_query_enum(using)?;
+                let QueryEnum::Nearest(vector) = query_enum else {
+                    return Err(CollectionError::bad_request(
+                        "MMR query must be based on a nearest query",
+                    ));
+                };
+                ScoringQuery::Mmr(MmrQuery { vector, lambda })
+            }
         };
 
         Ok(scoring_query)
@@ -96,13 +146,17 @@ impl Query {
 pub enum VectorInput {
     Id(PointIdType),
     Vector(Vector),
+    /// Raw text, to be encoded into a sparse vector by the collection before the query is resolved
+    Document(Document),
+    /// Image, to be embedded into a dense vector by the collection before the query is resolved
+    Image(Image),
 }
 
 impl VectorInput {
     pub fn as_id(&self) -> Option<&PointIdType> {
         match self {
             VectorInput::Id(id) => Some(id),
-            VectorInput::Vector(_) => None,
+            VectorInput::Vector(_) | VectorInput::Document(_) | VectorInput::Image(_) => None,
         }
     }
 }
@@ -112,6 +166,8 @@ pub enum VectorQuery<T> {
     Nearest(T),
     RecommendAverageVector(RecoQuery<T>),
     RecommendBestScore(RecoQuery<T>),
+    RecommendSumScores(RecoSumQuery<T>),
+    RecommendContrastive(ContrastiveQuery<T>),
     Discover(DiscoveryQuery<T>),
     Context(ContextQuery<T>),
 }
@@ -123,13 +179,78 @@ impl<T> VectorQuery<T> {
             VectorQuery::Nearest(input) => Box::new(std::iter::once(input)),
             VectorQuery::RecommendAverageVector(query) => Box::new(query.flat_iter()),
             VectorQuery::RecommendBestScore(query) => Box::new(query.flat_iter()),
+            VectorQuery::RecommendSumScores(query) => Box::new(query.flat_iter()),
+            VectorQuery::RecommendContrastive(query) => Box::new(query.flat_iter()),
             VectorQuery::Discover(query) => Box::new(query.flat_iter()),
             VectorQuery::Context(query) => Box::new(query.flat_iter()),
         }
     }
+
+    /// Same as [`VectorQuery::flat_iter`], but allows to modify the items in place
+    pub fn flat_iter_mut(&mut self) -> Box<dyn Iterator<Item = &mut T> + '_> {
+        match self {
+            VectorQuery::Nearest(input) => Box::new(std::iter::once(input)),
+            VectorQuery::RecommendAverageVector(query) | VectorQuery::RecommendBestScore(query) => {
+                Box::new(query.positives.iter_mut().chain(query.negatives.iter_mut()))
+            }
+            VectorQuery::RecommendSumScores(query) => {
+                Box::new(query.positives.iter_mut().chain(query.negatives.iter_mut()))
+            }
+            VectorQuery::RecommendContrastive(query) => Box::new(
+                query
+                    .positives
+                    .iter_mut()
+                    .chain(query.negatives.iter_mut())
+                    .map(|(example, _weight)| example),
+            ),
+            VectorQuery::Discover(query) => Box::new(
+                std::iter::once(&mut query.target).chain(
+                    query
+                        .pairs
+                        .iter_mut()
+                        .flat_map(|pair| [&mut pair.positive, &mut pair.negative]),
+                ),
+            ),
+            VectorQuery::Context(query) => Box::new(
+                query
+                    .pairs
+                    .iter_mut()
+                    .flat_map(|pair| [&mut pair.positive, &mut pair.negative]),
+            ),
+        }
+    }
 }
 
 impl VectorQuery<VectorInput> {
+    /// Recommendation query of the given strategy, weights are only used by the contrastive one
+    fn recommend(
+        strategy: RecommendStrategy,
+        positives: Vec<VectorInput>,
+        negatives: Vec<VectorInput>,
+        positive_weights: Option<Vec<ScoreType>>,
+        negative_weights: Option<Vec<ScoreType>>,
+    ) -> Self {
+        match strategy {
+            RecommendStrategy::AverageVector => {
+                VectorQuery::RecommendAverageVector(RecoQuery::new(positives, negatives))
+            }
+            RecommendStrategy::BestScore => {
+                VectorQuery::RecommendBestScore(RecoQuery::new(positives, negatives))
+            }
+            RecommendStrategy::SumScores => {
+                VectorQuery::RecommendSumScores(RecoSumQuery::new(positives, negatives))
+            }
+            RecommendStrategy::Contrastive => {
+                VectorQuery::RecommendContrastive(ContrastiveQuery::with_weights(
+                    positives,
+                    negatives,
+                    positive_weights,
+                    negative_weights,
+                ))
+            }
+        }
+    }
+
     /// Turns all [VectorInput]s into [Vector]s, using the provided [ReferencedVectors] to look up the vectors.
     ///
     /// Will panic if the ids are not found in the [ReferencedVectors].
@@ -149,7 +270,8 @@ impl VectorQuery<VectorInput> {
             }
             VectorQuery::RecommendAverageVector(reco) => {
                 let (positives, negatives) = Self::resolve_reco_reference(
-                    reco,
+                    reco.positives,
+                    reco.negatives,
                     ids_to_vectors,
                     lookup_vector_name,
                     lookup_collection,
@@ -158,13 +280,44 @@ impl VectorQuery<VectorInput> {
             }
             VectorQuery::RecommendBestScore(reco) => {
                 let (positives, negatives) = Self::resolve_reco_reference(
-                    reco,
+                    reco.positives,
+                    reco.negatives,
                     ids_to_vectors,
                     lookup_vector_name,
                     lookup_collection,
                 );
                 VectorQuery::RecommendBestScore(RecoQuery::new(positives, negatives))
             }
+            VectorQuery::RecommendSumScores(reco) => {
+                let (positives, negatives) = Self::resolve_reco_reference(
+                    reco.positives,
+                    reco.negatives,
+                    ids_to_vectors,
+                    lookup_vector_name,
+                    lookup_collection,
+                );
+                VectorQuery::RecommendSumScores(RecoSumQuery::new(positives, negatives))
+            }
+            VectorQuery::RecommendContrastive(contrastive) => {
+                let resolve_weighted = |examples: Vec<(VectorInput, ScoreType)>| {
+                    examples
+                        .into_iter()
+                        .filter_map(|(vector_input, weight)| {
+                            ids_to_vectors
+                                .resolve_reference(
+                                    lookup_collection,
+                                    lookup_vector_name,
+                                    vector_input,
+                                )
+                                .map(|vector| (vector, weight))
+                        })
+                        .collect()
+                };
+                VectorQuery::RecommendContrastive(ContrastiveQuery::new(
+                    resolve_weighted(contrastive.positives),
+                    resolve_weighted(contrastive.negatives),
+                ))
+            }
             VectorQuery::Discover(discover) => {
                 let target = ids_to_vectors
                     .resolve_reference(lookup_collection, lookup_vector_name, discover.target)
@@ -203,15 +356,15 @@ impl VectorQuery<VectorInput> {
         }
     }
 
-    /// Resolves the references in the RecoQuery into actual vectors.
+    /// Resolves the references in the positive and negative examples into actual vectors.
     fn resolve_reco_reference(
-        reco_query: RecoQuery<VectorInput>,
+        positives: Vec<VectorInput>,
+        negatives: Vec<VectorInput>,
         ids_to_vectors: &ReferencedVectors,
         lookup_vector_name: &str,
         lookup_collection: Option<&String>,
     ) -> (Vec<Vector>, Vec<Vector>) {
-        let positives = reco_query
-            .positives
+        let positives = positives
             .into_iter()
             .filter_map(|vector_input| {
                 ids_to_vectors.resolve_reference(
@@ -221,8 +374,7 @@ impl VectorQuery<VectorInput> {
                 )
             })
             .collect();
-        let negatives = reco_query
-            .negatives
+        let negatives = negatives
             .into_iter()
             .filter_map(|vector_input| {
                 ids_to_vectors.resolve_reference(
@@ -254,6 +406,16 @@ impl VectorQuery<Vector> {
                 query: reco,
                 using: Some(using),
             }),
+            VectorQuery::RecommendSumScores(reco) => QueryEnum::RecommendSumScores(NamedQuery {
+                query: reco,
+                using: Some(using),
+            }),
+            VectorQuery::RecommendContrastive(contrastive) => {
+                QueryEnum::RecommendContrastive(NamedQuery {
+                    query: contrastive,
+                    using: Some(using),
+                })
+            }
             VectorQuery::Discover(discover) => QueryEnum::Discover(NamedQuery {
                 query: discover,
                 using: Some(using),
@@ -315,7 +477,7 @@ impl CollectionPrefetch {
         };
 
         if !lookup_other_collection {
-            if let Some(Query::Vector(vector_query)) = &self.query {
+            if let Some(vector_query) = self.query.as_ref().and_then(Query::vector_query) {
                 if let VectorQuery::Nearest(VectorInput::Id(id)) = vector_query {
                     refs.push(*id);
                 }
@@ -376,7 +538,7 @@ impl CollectionPrefetch {
     pub fn flatten_resolver_requests(&self) -> Vec<CollectionQueryResolveRequest> {
         let mut inner_queries = vec![];
         // resolve query for root query
-        if let Some(Query::Vector(vector_query)) = &self.query {
+        if let Some(vector_query) = self.query.as_ref().and_then(Query::vector_query) {
             let resolve_root = CollectionQueryResolveRequest {
                 vector_query,
                 lookup_from: self.lookup_from.clone(),
@@ -417,7 +579,7 @@ impl CollectionQueryRequest {
         };
 
         if !lookup_other_collection {
-            if let Some(Query::Vector(vector_query)) = &self.query {
+            if let Some(vector_query) = self.query.as_ref().and_then(Query::vector_query) {
                 if let VectorQuery::Nearest(VectorInput::Id(id)) = vector_query {
                     refs.push(*id);
                 }
@@ -507,6 +669,16 @@ impl CollectionQueryRequest {
                         "Can't use score_threshold with an order_by query.",
                     ));
                 }
+                Some(Query::Mmr(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "Can't use score_threshold with an MMR query.",
+                    ));
+                }
+                Some(Query::Sample(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "Can't use score_threshold with a sample query.",
+                    ));
+                }
                 None => {
                     return Err(CollectionError::bad_request(
                         "A query is needed to use the score_threshold. Can't have score_threshold without defining a query.",
@@ -525,17 +697,30 @@ impl CollectionQueryRequest {
             }
         }
 
+        // Check that formula queries are not combined with a using vector name
+        if let Some(Query::Formula(_)) = query {
+            if using != DEFAULT_VECTOR_NAME {
+                return Err(CollectionError::bad_request(
+                    "Formula queries cannot be combined with the 'using' field.",
+                ));
+            }
+        }
+
         Ok(())
     }
 }
 
 mod from_rest {
     use api::rest::schema as rest;
+    use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
+    use segment::common::score_fusion::Normalization;
 
     use super::*;
 
-    impl From<rest::QueryRequestInternal> for CollectionQueryRequest {
-        fn from(value: rest::QueryRequestInternal) -> Self {
+    impl TryFrom<rest::QueryRequestInternal> for CollectionQueryRequest {
+        type Error = CollectionError;
+
+        fn try_from(value: rest::QueryRequestInternal) -> CollectionResult<Self> {
             let rest::QueryRequestInternal {
                 prefetch,
                 query,
@@ -550,9 +735,13 @@ mod from_rest {
                 lookup_from,
             } = value;
 
-            Self {
-                prefetch: prefetch.into_iter().flatten().map(From::from).collect(),
-                query: query.map(From::from),
+            Ok(Self {
+                prefetch: prefetch
+                    .into_iter()
+                    .flatten()
+                    .map(TryFrom::try_from)
+                    .collect::<CollectionResult<_>>()?,
+                query: query.map(TryFrom::try_from).transpose()?,
                 using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
                 filter,
                 score_threshold,
@@ -562,12 +751,14 @@ mod from_rest {
                 with_vector: with_vector.unwrap_or(Self::DEFAULT_WITH_VECTOR),
                 with_payload: with_payload.unwrap_or(Self::DEFAULT_WITH_PAYLOAD),
                 lookup_from: lookup_from.map(LookupLocation::from),
-            }
+            })
         }
     }
 
-    impl From<rest::Prefetch> for CollectionPrefetch {
-        fn from(value: rest::Prefetch) -> Self {
+    impl TryFrom<rest::Prefetch> for CollectionPrefetch {
+        type Error = CollectionError;
+
+        fn try_from(value: rest::Prefetch) -> CollectionResult<Self> {
             let rest::Prefetch {
                 prefetch,
                 query,
@@ -579,28 +770,36 @@ mod from_rest {
                 lookup_from,
             } = value;
 
-            Self {
-                prefetch: prefetch.into_iter().flatten().map(From::from).collect(),
-                query: query.map(From::from),
+            Ok(Self {
+                prefetch: prefetch
+                    .into_iter()
+                    .flatten()
+                    .map(TryFrom::try_from)
+                    .collect::<CollectionResult<_>>()?,
+                query: query.map(TryFrom::try_from).transpose()?,
                 using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
                 filter,
                 score_threshold,
                 limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params,
                 lookup_from,
-            }
+            })
         }
     }
 
-    impl From<rest::QueryInterface> for Query {
-        fn from(value: rest::QueryInterface) -> Self {
-            Query::from(rest::Query::from(value))
+    impl TryFrom<rest::QueryInterface> for Query {
+        type Error = CollectionError;
+
+        fn try_from(value: rest::QueryInterface) -> CollectionResult<Self> {
+            Query::try_from(rest::Query::from(value))
         }
     }
 
-    impl From<rest::Query> for Query {
-        fn from(value: rest::Query) -> Self {
-            match value {
+    impl TryFrom<rest::Query> for Query {
+        type Error = CollectionError;
+
+        fn try_from(value: rest::Query) -> CollectionResult<Self> {
+            let query = match value {
                 rest::Query::Nearest(nearest) => {
                     Query::Vector(VectorQuery::Nearest(From::from(nearest.nearest)))
                 }
@@ -608,8 +807,20 @@ mod from_rest {
                 rest::Query::Discover(discover) => Query::Vector(From::from(discover.discover)),
                 rest::Query::Context(context) => Query::Vector(From::from(context.context)),
                 rest::Query::OrderBy(order_by) => Query::OrderBy(OrderBy::from(order_by.order_by)),
-                rest::Query::Fusion(fusion) => Query::Fusion(Fusion::from(fusion.fusion)),
-            }
+                rest::Query::Fusion(fusion) => Query::Fusion(Fusion::from(fusion)),
+                rest::Query::Formula(formula) => Query::Formula(
+                    FormulaQuery::try_from(formula).map_err(CollectionError::bad_input)?,
+                ),
+                rest::Query::Mmr(mmr) => {
+                    let rest::MmrInput { vector, lambda } = mmr.mmr;
+                    Query::Mmr(MmrInput {
+                        nearest: VectorQuery::Nearest(VectorInput::from(vector)),
+                        lambda,
+                    })
+                }
+                rest::Query::Sample(sample) => Query::Sample(sample.sample),
+            };
+            Ok(query)
         }
     }
 
@@ -619,16 +830,20 @@ mod from_rest {
                 positive,
                 negative,
                 strategy,
+                positive_weights,
+                negative_weights,
             } = value;
 
             let positives = positive.into_iter().flatten().map(From::from).collect();
             let negatives = negative.into_iter().flatten().map(From::from).collect();
-            let reco_query = RecoQuery::new(positives, negatives);
 
-            match strategy.unwrap_or_default() {
-                RecommendStrategy::AverageVector => VectorQuery::RecommendAverageVector(reco_query),
-                RecommendStrategy::BestScore => VectorQuery::RecommendBestScore(reco_query),
-            }
+            VectorQuery::recommend(
+                strategy.unwrap_or_default(),
+                positives,
+                negatives,
+                positive_weights,
+                negative_weights,
+            )
         }
     }
 
@@ -673,6 +888,8 @@ mod from_rest {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(MultiDenseVectorInternal::new_unchecked(multi_dense)),
                 ),
+                rest::VectorInput::Document(document) => VectorInput::Document(document),
+                rest::VectorInput::Image(image) => VectorInput::Image(image),
             }
         }
     }
@@ -687,20 +904,39 @@ mod from_rest {
         }
     }
 
-    impl From<rest::Fusion> for Fusion {
-        fn from(value: rest::Fusion) -> Self {
-            match value {
-                rest::Fusion::Rrf => Fusion::Rrf,
+    impl From<rest::FusionQuery> for Fusion {
+        fn from(value: rest::FusionQuery) -> Self {
+            let rest::FusionQuery {
+                fusion,
+                weights,
+                normalization,
+                k,
+            } = value;
+
+            match fusion {
+                rest::Fusion::Rrf => Fusion::Rrf {
+                    k: k.map_or(DEFAULT_RRF_K, |k| k as usize),
+                    weights: weights.unwrap_or_default(),
+                },
+                rest::Fusion::Dbsf => Fusion::Dbsf {
+                    weights: weights.unwrap_or_default(),
+                    normalization: normalization
+                        .map(Normalization::from)
+                        .unwrap_or(Normalization::Distr),
+                },
             }
         }
     }
 }
 
 pub mod from_grpc {
+    use api::grpc::conversions::json_path_from_proto;
     use api::grpc::qdrant::{self as grpc};
+    use segment::data_types::groups::{GroupOrderBy, GroupScoring};
     use tonic::Status;
 
     use super::*;
+    use crate::grouping::group_by::{GroupRequest, SourceRequest};
 
     impl TryFrom<api::grpc::qdrant::QueryPoints> for CollectionQueryRequest {
         type Error = Status;
@@ -722,6 +958,9 @@ pub mod from_grpc {
                 shard_key_selector: _,
                 lookup_from,
                 timeout: _,
+                explain: _,
+                profile: _,
+                page_token: _,
             } = value;
 
             let request = CollectionQueryRequest {
@@ -753,6 +992,89 @@ pub mod from_grpc {
         }
     }
 
+    impl TryFrom<grpc::QueryPointGroups> for GroupRequest {
+        type Error = Status;
+
+        fn try_from(value: grpc::QueryPointGroups) -> Result<Self, Self::Error> {
+            let grpc::QueryPointGroups {
+                collection_name: _,
+                prefetch,
+                query,
+                using,
+                filter,
+                params,
+                score_threshold,
+                with_payload,
+                with_vectors,
+                lookup_from,
+                limit,
+                group_size,
+                group_by,
+                read_consistency: _,
+                with_lookup,
+                timeout: _,
+                shard_key_selector: _,
+                then_group_by,
+                group_scoring,
+                group_order_by,
+            } = value;
+
+            let limit = limit
+                .map(|l| l as usize)
+                .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT);
+
+            let collection_query_request = CollectionQueryRequest {
+                prefetch: prefetch
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?,
+                query: query.map(TryFrom::try_from).transpose()?,
+                using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
+                filter: filter.map(TryFrom::try_from).transpose()?,
+                score_threshold,
+                limit,
+                offset: 0,
+                params: params.map(From::from),
+                with_vector: with_vectors
+                    .map(From::from)
+                    .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_VECTOR),
+                with_payload: with_payload
+                    .map(TryFrom::try_from)
+                    .transpose()?
+                    .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
+                lookup_from: lookup_from.map(From::from),
+            };
+
+            let group_scoring = group_scoring
+                .map(|scoring| {
+                    grpc::GroupScoring::from_i32(scoring)
+                        .map(GroupScoring::from)
+                        .ok_or_else(|| Status::invalid_argument("Unknown group scoring"))
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            Ok(GroupRequest {
+                source: SourceRequest::Query(collection_query_request),
+                group_by: json_path_from_proto(&group_by)?,
+                then_group_by: then_group_by
+                    .iter()
+                    .map(|key| json_path_from_proto(key))
+                    .collect::<Result<_, _>>()?,
+                group_scoring,
+                group_order_by: group_order_by.map(GroupOrderBy::try_from).transpose()?,
+                group_size: group_size
+                    .map(|s| s as usize)
+                    .unwrap_or(CollectionQueryRequest::DEFAULT_GROUP_SIZE),
+                limit,
+                with_lookup: with_lookup
+                    .map(api::rest::WithLookupInterface::try_from)
+                    .transpose()?
+                    .map(Into::into),
+            })
+        }
+    }
+
     impl TryFrom<grpc::PrefetchQuery> for CollectionPrefetch {
         type Error = Status;
 
@@ -807,6 +1129,23 @@ pub mod from_grpc {
                 Variant::Context(context) => Query::Vector(TryFrom::try_from(context)?),
                 Variant::OrderBy(order_by) => Query::OrderBy(OrderBy::try_from(order_by)?),
                 Variant::Fusion(fusion) => Query::Fusion(Fusion::try_from(fusion)?),
+                Variant::FusionParams(params) => Query::Fusion(Fusion::try_from(params)?),
+                Variant::Formula(formula) => Query::Formula(FormulaQuery::try_from(formula)?),
+                Variant::Mmr(mmr) => {
+                    let grpc::Mmr { vector, lambda } = mmr;
+                    let vector =
+                        vector.ok_or_else(|| Status::invalid_argument("MMR vector is missing"))?;
+                    if !(0.0..=1.0).contains(&lambda) {
+                        return Err(Status::invalid_argument(format!(
+                            "MMR lambda must be between 0.0 and 1.0, got {lambda}"
+                        )));
+                    }
+                    Query::Mmr(MmrInput {
+                        nearest: VectorQuery::Nearest(VectorInput::try_from(vector)?),
+                        lambda,
+                    })
+                }
+                Variant::Sample(sample) => Query::Sample(Sample::try_from(sample)?),
             };
 
             Ok(query)
@@ -821,6 +1160,8 @@ pub mod from_grpc {
                 positive,
                 negative,
                 strategy,
+                positive_weights,
+                negative_weights,
             } = value;
 
             let positives = positive
@@ -832,17 +1173,30 @@ pub mod from_grpc {
                 .map(TryFrom::try_from)
                 .collect::<Result<Vec<_>, _>>()?;
 
-            let reco_query = RecoQuery::new(positives, negatives);
-
             let strategy = strategy
                 .and_then(grpc::RecommendStrategy::from_i32)
                 .map(RecommendStrategy::from)
                 .unwrap_or_default();
 
-            let query = match strategy {
-                RecommendStrategy::AverageVector => VectorQuery::RecommendAverageVector(reco_query),
-                RecommendStrategy::BestScore => VectorQuery::RecommendBestScore(reco_query),
-            };
+            // Repeated fields can't be unset, no weights are the same as default weights
+            let positive_weights = Some(positive_weights).filter(|weights| !weights.is_empty());
+            let negative_weights = Some(negative_weights).filter(|weights| !weights.is_empty());
+            strategy
+                .validate_weights(
+                    positives.len(),
+                    negatives.len(),
+                    positive_weights.as_deref(),
+                    negative_weights.as_deref(),
+                )
+                .map_err(Status::invalid_argument)?;
+
+            let query = VectorQuery::recommend(
+                strategy,
+                positives,
+                negatives,
+                positive_weights,
+                negative_weights,
+            );
 
             Ok(query)
         }
@@ -899,6 +1253,10 @@ pub mod from_grpc {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(From::from(multi_dense)),
                 ),
+                Variant::Document(grpc::Document { text }) => {
+                    VectorInput::Document(Document { text })
+                }
+                Variant::Image(grpc::Image { image }) => VectorInput::Image(Image { image }),
             };
 
             Ok(vector_input)
@@ -0,0 +1,424 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+use api::grpc::qdrant as grpc;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use common::types::ScoreType;
+use segment::data_types::order_by::{OrderBy, OrderValue, StartFrom};
+use segment::types::{
+    Condition, Filter, FloatPayloadType, IntPayloadType, Order, PointIdType, ScoredPoint,
+};
+use segment::utils::scored_point_ties::ScoredPointTies;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::shard_query::{ScoringQuery, ShardQueryRequest};
+use crate::config::CollectionParams;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::shard::ShardId;
+
+/// Deepest position of a shard, which a token keeps.
+///
+/// Only matters for the requests, which shards can't skip the previous pages of by a score or
+/// order value. Deeper pages of those ask the shards for more points in rounds.
+pub const MAX_SHARD_POSITION: usize = 100_000;
+
+/// Position of a paginated query, right after the last point of a page.
+///
+/// Clients get it as an opaque token, and pass it back to continue with the next page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// Score of the last point of the page
+    score: ScoreType,
+    /// Order value of the last point of the page, if ordered by a payload field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    order_value: Option<CursorOrderValue>,
+    /// Id of the last point of the page, breaks the ties of the scores
+    id: PointIdType,
+    /// Hash of the collection and the request, which the token continues
+    request_hash: u64,
+    /// Time of the first page, relative datetime ranges of all pages are resolved against it
+    now: DateTime<Utc>,
+    /// How many points of each shard are ranked up to the last point of the page.
+    ///
+    /// Only limits how many points to request from the shards, so stale positions don't affect
+    /// the results, just the amount of work. Empty if the request is bounded by the cursor.
+    #[serde(default)]
+    shard_positions: BTreeMap<ShardId, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CursorOrderValue {
+    Int(IntPayloadType),
+    Float(FloatPayloadType),
+    Keyword(String),
+    Compound(Vec<CursorOrderValue>),
+}
+
+impl From<OrderValue> for CursorOrderValue {
+    fn from(value: OrderValue) -> Self {
+        match value {
+            OrderValue::Int(value) => CursorOrderValue::Int(value),
+            OrderValue::Float(value) => CursorOrderValue::Float(value),
+            OrderValue::Keyword(value) => CursorOrderValue::Keyword(value),
+            OrderValue::Compound(values) => {
+                CursorOrderValue::Compound(values.into_iter().map(Self::from).collect())
+            }
+        }
+    }
+}
+
+impl From<CursorOrderValue> for OrderValue {
+    fn from(value: CursorOrderValue) -> Self {
+        match value {
+            CursorOrderValue::Int(value) => OrderValue::Int(value),
+            CursorOrderValue::Float(value) => OrderValue::Float(value),
+            CursorOrderValue::Keyword(value) => OrderValue::Keyword(value),
+            CursorOrderValue::Compound(values) => {
+                OrderValue::Compound(values.into_iter().map(Self::from).collect())
+            }
+        }
+    }
+}
+
+impl PageCursor {
+    pub fn new(
+        last_point: &ScoredPoint,
+        request_hash: u64,
+        now: DateTime<Utc>,
+        shard_positions: BTreeMap<ShardId, usize>,
+    ) -> Self {
+        Self {
+            score: last_point.score,
+            order_value: last_point.order_value.clone().map(CursorOrderValue::from),
+            id: last_point.id,
+            request_hash,
+            now,
+            // Positions only save the rounds of requests to the shards, so the deep ones can be cut
+            shard_positions: shard_positions
+                .into_iter()
+                .map(|(shard_id, position)| (shard_id, position.min(MAX_SHARD_POSITION)))
+                .collect(),
+        }
+    }
+
+    pub fn from_token(token: &str) -> CollectionResult<Self> {
+        let invalid_token = || CollectionError::bad_input("Invalid page token");
+        let bytes = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid_token())?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| invalid_token())?;
+
+        // Tokens come from the clients, only accept the values which could have been issued
+        let is_valid = !cursor.score.is_nan()
+            && cursor
+                .shard_positions
+                .values()
+                .all(|&position| position <= MAX_SHARD_POSITION);
+        if !is_valid {
+            return Err(invalid_token());
+        }
+
+        Ok(cursor)
+    }
+
+    pub fn to_token(&self) -> String {
+        // Serializing a plain struct into a buffer can't fail
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Check that the token was issued for the same collection and request
+    pub fn check_request_hash(&self, request_hash: u64) -> CollectionResult<()> {
+        if self.request_hash != request_hash {
+            return Err(CollectionError::bad_input(
+                "Page token was issued for a different request or collection",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    pub fn shard_positions(&self) -> &BTreeMap<ShardId, usize> {
+        &self.shard_positions
+    }
+
+    /// Largest number of points, which one shard has ranked up to the cursor
+    pub fn max_shard_position(&self) -> usize {
+        self.shard_positions.values().copied().max().unwrap_or(0)
+    }
+
+    /// Whether the point goes after the cursor in the given ranking
+    pub fn precedes(&self, point: &ScoredPoint, ranking: Ranking) -> bool {
+        let last_point = ScoredPoint {
+            id: self.id,
+            version: 0,
+            score: self.score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: self.order_value.clone().map(OrderValue::from),
+        };
+        rank_cmp(&last_point, point, ranking) == Ordering::Less
+    }
+
+    /// Bound the request to the points from the cursor on, so that shards skip the previous pages.
+    ///
+    /// Shards still return the points with the same score or order value as the cursor, except
+    /// for the cursor point itself, the rest of the ties is dropped by [`PageCursor::precedes`].
+    /// Returns `false` if shards can't apply the bound for the ranking of the request.
+    pub fn bound_request(
+        &self,
+        request: &mut ShardQueryRequest,
+        order: Order,
+        collection_params: &CollectionParams,
+    ) -> CollectionResult<bool> {
+        let is_bounded = match &mut request.query {
+            Some(ScoringQuery::Vector(query)) => {
+                // Shards compare scores to the threshold in the order of the distance, which is
+                // not the order of the ranking for some of the custom scores
+                let distance = collection_params.get_distance(query.get_vector_name())?;
+                distance.distance_order() == order
+                    && bound_score(&mut request.score_threshold, self.score, order)
+            }
+            Some(ScoringQuery::Formula(_)) => {
+                bound_score(&mut request.score_threshold, self.score, order)
+            }
+            Some(ScoringQuery::OrderBy(order_by)) => self
+                .order_value
+                .clone()
+                .map(OrderValue::from)
+                .map_or(false, |value| bound_order_by(order_by, &value)),
+            Some(ScoringQuery::Fusion(_) | ScoringQuery::Mmr(_) | ScoringQuery::Sample(_))
+            | None => false,
+        };
+
+        if is_bounded {
+            let cursor_point = Condition::HasId(HashSet::from([self.id]).into());
+            request.filter = Filter::merge_opts(
+                request.filter.take(),
+                Some(Filter::new_must_not(cursor_point)),
+            );
+        }
+
+        Ok(is_bounded)
+    }
+}
+
+/// Hash of the collection and the request without its limit and offset, so that a token only
+/// continues the request it was issued for.
+///
+/// Relative datetime ranges of the request must be resolved first.
+pub fn request_hash(collection_id: &str, request: &ShardQueryRequest) -> CollectionResult<u64> {
+    let request = grpc::QueryShardPoints::try_from(ShardQueryRequest {
+        limit: 0,
+        offset: 0,
+        ..request.clone()
+    })?;
+
+    // Maps of a JSON value are sorted by keys, so the same request always has the same hash
+    let request = serde_json::to_value(&request).map_err(|err| {
+        CollectionError::service_error(format!("Failed to hash the page request: {err}"))
+    })?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(collection_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(request.to_string().as_bytes());
+    let digest = hasher.finalize();
+
+    let mut hash = [0; 8];
+    hash.copy_from_slice(&digest[..8]);
+    Ok(u64::from_le_bytes(hash))
+}
+
+/// Set the score threshold to the score of the cursor, keeping the threshold of the request.
+///
+/// Thresholds of the shards are exclusive, so the threshold is one step worse than the score to
+/// keep the ties of the cursor. Returns `false` if there is no such score.
+fn bound_score(threshold: &mut Option<ScoreType>, score: ScoreType, order: Order) -> bool {
+    if !score.is_finite() {
+        return false;
+    }
+
+    let towards_larger = order == Order::SmallBetter;
+    let bound = if score == 0.0 {
+        let smallest = ScoreType::from_bits(1);
+        if towards_larger {
+            smallest
+        } else {
+            -smallest
+        }
+    } else {
+        // Larger bits are further away from zero
+        let away_from_zero = (score > 0.0) == towards_larger;
+        let bits = score.to_bits();
+        ScoreType::from_bits(if away_from_zero { bits + 1 } else { bits - 1 })
+    };
+
+    *threshold = Some(match (*threshold, order) {
+        (Some(threshold), Order::LargeBetter) => bound.max(threshold),
+        (Some(threshold), Order::SmallBetter) => bound.min(threshold),
+        (None, _) => bound,
+    });
+    true
+}
+
+/// Start the ordered read of each key from the order value of the cursor.
+///
+/// Returns `false` if some of the values can't be a start, so the read is left as is.
+fn bound_order_by(order_by: &mut OrderBy, value: &OrderValue) -> bool {
+    let starts: Option<Vec<_>> = value.components().iter().map(start_from_value).collect();
+    let Some(starts) = starts.filter(|starts| starts.len() == order_by.keys().count()) else {
+        return false;
+    };
+
+    let mut starts = starts.into_iter();
+    order_by.start_from = starts.next();
+    for (key, start_from) in order_by.then_by_keys_mut().zip(starts) {
+        key.start_from = Some(start_from);
+    }
+    true
+}
+
+fn start_from_value(value: &OrderValue) -> Option<StartFrom> {
+    match value {
+        // Integer starts are compared as floats, only the exact ones can bound the read
+        OrderValue::Int(value)
+            if value.unsigned_abs() <= 1 << FloatPayloadType::MANTISSA_DIGITS =>
+        {
+            Some(StartFrom::Integer(*value))
+        }
+        OrderValue::Float(value) if !value.is_nan() => Some(StartFrom::Float(*value)),
+        OrderValue::Keyword(value) => Some(StartFrom::Keyword(value.clone())),
+        OrderValue::Int(_) | OrderValue::Float(_) | OrderValue::Compound(_) => None,
+    }
+}
+
+/// How the points are ranked in the results
+#[derive(Debug, Clone, Copy)]
+pub enum Ranking<'a> {
+    /// By score, or by order value if there is one
+    Order(Order),
+    /// By the order values, with the directions of each of the keys
+    OrderBy(&'a OrderBy),
+}
+
+/// Compares the points by their rank in the results: `Less` means `a` goes before `b`.
+///
+/// Ties of the scores are broken by ids, the same way as when merging the results of the shards.
+pub fn rank_cmp(a: &ScoredPoint, b: &ScoredPoint, ranking: Ranking) -> Ordering {
+    match ranking {
+        Ranking::Order(Order::LargeBetter) => ScoredPointTies(b).cmp(&ScoredPointTies(a)),
+        Ranking::Order(Order::SmallBetter) => ScoredPointTies(a).cmp(&ScoredPointTies(b)),
+        Ranking::OrderBy(order_by) => match (&a.order_value, &b.order_value) {
+            (Some(value_a), Some(value_b)) => order_by.cmp_points((value_a, a.id), (value_b, b.id)),
+            _ => rank_cmp(a, b, Ranking::Order(Order::from(order_by.direction()))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::Distance;
+
+    use super::*;
+
+    fn point(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    fn cursor(last_point: &ScoredPoint, shard_positions: BTreeMap<ShardId, usize>) -> PageCursor {
+        PageCursor::new(last_point, 17, Utc::now(), shard_positions)
+    }
+
+    #[test]
+    fn test_page_token_roundtrip() {
+        let cursor = cursor(&point(42, 0.5), BTreeMap::from([(0, 3), (2, 7)]));
+
+        let decoded = PageCursor::from_token(&cursor.to_token()).unwrap();
+        assert_eq!(decoded, cursor);
+        assert_eq!(decoded.max_shard_position(), 7);
+        assert!(decoded.check_request_hash(17).is_ok());
+        assert!(decoded.check_request_hash(18).is_err());
+
+        assert!(PageCursor::from_token("not a token").is_err());
+
+        let deep = cursor(
+            &point(42, 0.5),
+            BTreeMap::from([(0, MAX_SHARD_POSITION + 1)]),
+        );
+        assert_eq!(deep.max_shard_position(), MAX_SHARD_POSITION);
+
+        let mut too_deep = deep;
+        too_deep.shard_positions.insert(1, MAX_SHARD_POSITION + 1);
+        assert!(PageCursor::from_token(&too_deep.to_token()).is_err());
+    }
+
+    #[test]
+    fn test_bound_score_keeps_ties() {
+        for (score, order) in [
+            (0.5, Order::LargeBetter),
+            (-0.5, Order::LargeBetter),
+            (0.0, Order::LargeBetter),
+            (0.5, Order::SmallBetter),
+            (-0.5, Order::SmallBetter),
+            (0.0, Order::SmallBetter),
+        ] {
+            let mut threshold = None;
+            assert!(bound_score(&mut threshold, score, order));
+            let threshold = threshold.unwrap();
+            assert_ne!(threshold, score);
+            let distance = match order {
+                Order::LargeBetter => Distance::Dot,
+                Order::SmallBetter => Distance::Euclid,
+            };
+            assert!(distance.check_threshold(score, threshold));
+        }
+
+        // Threshold of the request is kept
+        let mut threshold = Some(0.7);
+        assert!(bound_score(&mut threshold, 0.5, Order::SmallBetter));
+        assert!(threshold.unwrap() < 0.7);
+        let mut threshold = Some(0.7);
+        assert!(bound_score(&mut threshold, 0.9, Order::LargeBetter));
+        assert!(threshold.unwrap() < 0.9 && threshold.unwrap() > 0.7);
+        let mut threshold = Some(0.7);
+        assert!(bound_score(&mut threshold, 0.7, Order::LargeBetter));
+        assert_eq!(threshold, Some(0.7));
+
+        assert!(!bound_score(
+            &mut None,
+            ScoreType::INFINITY,
+            Order::LargeBetter
+        ));
+    }
+
+    #[test]
+    fn test_page_cursor_precedes() {
+        let cursor = cursor(&point(5, 0.5), BTreeMap::new());
+
+        assert!(cursor.precedes(&point(1, 0.4), Ranking::Order(Order::LargeBetter)));
+        assert!(!cursor.precedes(&point(1, 0.6), Ranking::Order(Order::LargeBetter)));
+        assert!(!cursor.precedes(&point(5, 0.5), Ranking::Order(Order::LargeBetter)));
+
+        assert!(cursor.precedes(&point(1, 0.6), Ranking::Order(Order::SmallBetter)));
+        assert!(!cursor.precedes(&point(1, 0.4), Ranking::Order(Order::SmallBetter)));
+
+        // ties go by id, the same way as the shard results are merged
+        assert!(cursor.precedes(&point(4, 0.5), Ranking::Order(Order::LargeBetter)));
+        assert!(cursor.precedes(&point(6, 0.5), Ranking::Order(Order::SmallBetter)));
+    }
+}
@@ -1,12 +1,12 @@
 //! Types used within `LocalShard` to represent a planned `ShardQueryRequest`
 
-use api::rest::OrderByInterface;
+use api::rest::Sample;
 use common::types::ScoreType;
 use segment::types::{Filter, WithPayloadInterface, WithVector};
 
 use super::shard_query::{ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequest, QueryScrollRequestInternal,
+    CollectionError, CollectionResult, CoreSearchRequest, QueryScrollRequestInternal, ScrollOrder,
 };
 
 const MAX_PREFETCH_DEPTH: usize = 64;
@@ -105,7 +105,17 @@ impl PlannedQuery {
                 CollectionError::bad_request("cannot have prefetches without a query".to_string())
             })?;
 
+            rescore.check_prefetches_count(prefetches.len())?;
+
             if rescore.needs_intermediate_results() {
+                // MMR at collection level needs the vectors of the candidates
+                let with_vector = match &rescore {
+                    ScoringQuery::Mmr(mmr) => {
+                        with_vector_including(with_vector, mmr.get_vector_name())
+                    }
+                    _ => with_vector,
+                };
+
                 // pass `with_vector` and `with_payload` down one level, as the sources will be sent as intermediate results to the collection
                 let sources = recurse_prefetches(
                     &mut self.searches,
@@ -168,10 +178,35 @@ impl PlannedQuery {
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
+                Some(ScoringQuery::Mmr(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply MMR without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
-                        order_by: Some(OrderByInterface::Struct(order_by)),
+                        scroll_order: ScrollOrder::ByField(order_by),
+                        limit,
+                        filter,
+                        with_vector,
+                        with_payload,
+                    };
+
+                    let idx = self.scrolls.len();
+                    self.scrolls.push(scroll);
+
+                    vec![Source::ScrollsIdx(idx)]
+                }
+                Some(ScoringQuery::Sample(Sample::Random)) => {
+                    // Everything should come from 1 random scroll
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::Random,
                         limit,
                         filter,
                         with_vector,
@@ -186,7 +221,7 @@ impl PlannedQuery {
                 None => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
-                        order_by: None,
+                        scroll_order: ScrollOrder::ById,
                         limit,
                         filter,
                         with_vector,
@@ -246,6 +281,10 @@ fn recurse_prefetches(
         let filter = Filter::merge_opts(propagate_filter.clone(), filter);
 
         let source = if !prefetches.is_empty() {
+            if let Some(query) = &query {
+                query.check_prefetches_count(prefetches.len())?;
+            }
+
             // This has nested prefetches. Recurse into them
             let inner_sources = recurse_prefetches(
                 core_searches,
@@ -297,9 +336,33 @@ fn recurse_prefetches(
                         "cannot apply Fusion without prefetches".to_string(),
                     ))
                 }
+                Some(ScoringQuery::Formula(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply Formula without prefetches".to_string(),
+                    ))
+                }
+                Some(ScoringQuery::Mmr(_)) => {
+                    return Err(CollectionError::bad_request(
+                        "cannot apply MMR without prefetches".to_string(),
+                    ))
+                }
                 Some(ScoringQuery::OrderBy(order_by)) => {
                     let scroll = QueryScrollRequestInternal {
-                        order_by: Some(OrderByInterface::Struct(order_by)),
+                        scroll_order: ScrollOrder::ByField(order_by),
+                        filter,
+                        with_vector: with_vector.clone(),
+                        with_payload: with_payload.clone(),
+                        limit,
+                    };
+
+                    let idx = scrolls.len();
+                    scrolls.push(scroll);
+
+                    Source::ScrollsIdx(idx)
+                }
+                Some(ScoringQuery::Sample(Sample::Random)) => {
+                    let scroll = QueryScrollRequestInternal {
+                        scroll_order: ScrollOrder::Random,
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
@@ -313,7 +376,7 @@ fn recurse_prefetches(
                 }
                 None => {
                     let scroll = QueryScrollRequestInternal {
-                        order_by: None,
+                        scroll_order: ScrollOrder::ById,
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
@@ -345,11 +408,26 @@ impl TryFrom<Vec<ShardQueryRequest>> for PlannedQuery {
     }
 }
 
+/// Extend `with_vector` so that the given vector is fetched too
+fn with_vector_including(with_vector: WithVector, vector_name: &str) -> WithVector {
+    match with_vector {
+        WithVector::Bool(true) => WithVector::Bool(true),
+        WithVector::Bool(false) => WithVector::Selector(vec![vector_name.to_string()]),
+        WithVector::Selector(mut names) => {
+            if !names.iter().any(|name| name == vector_name) {
+                names.push(vector_name.to_string());
+            }
+            WithVector::Selector(names)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use std::collections::HashSet;
 
+    use segment::common::score_fusion::Normalization;
     use segment::data_types::vectors::{MultiDenseVectorInternal, NamedVectorStruct, Vector};
     use segment::json_path::JsonPath;
     use segment::types::{
@@ -565,7 +643,7 @@ mod tests {
                     score_threshold: None,
                 },
             ],
-            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            query: Some(ScoringQuery::Fusion(Fusion::rrf())),
             filter: Some(filter_outer.clone()),
             score_threshold: None,
             limit: 50,
@@ -622,7 +700,7 @@ mod tests {
     fn test_try_from_rrf_without_source() {
         let request = ShardQueryRequest {
             prefetches: vec![],
-            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            query: Some(ScoringQuery::Fusion(Fusion::rrf())),
             filter: Some(Filter::default()),
             score_threshold: None,
             limit: 50,
@@ -665,7 +743,7 @@ mod tests {
                 filter: dummy_filter.clone(),
                 score_threshold: Some(0.1),
             }],
-            query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+            query: Some(ScoringQuery::Fusion(Fusion::rrf())),
             filter: Some(Filter::default()),
             score_threshold: Some(0.666),
             limit: 50,
@@ -874,7 +952,7 @@ mod tests {
                 prefetches: vec![
                     ShardPrefetch {
                         prefetches: vec![dummy_core_prefetch(30), dummy_core_prefetch(40)],
-                        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+                        query: Some(ScoringQuery::Fusion(Fusion::rrf())),
                         filter: None,
                         params: None,
                         score_threshold: None,
@@ -882,7 +960,7 @@ mod tests {
                     },
                     dummy_scroll_prefetch(50),
                 ],
-                query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+                query: Some(ScoringQuery::Fusion(Fusion::rrf())),
                 filter: None,
                 score_threshold: None,
                 limit: 10,
@@ -914,7 +992,7 @@ mod tests {
                         Source::Prefetch(MergePlan {
                             sources: vec![Source::SearchesIdx(1), Source::SearchesIdx(2),],
                             rescore_params: Some(RescoreParams {
-                                rescore: ScoringQuery::Fusion(Fusion::Rrf),
+                                rescore: ScoringQuery::Fusion(Fusion::rrf()),
                                 limit: 10,
                                 score_threshold: None,
                                 with_vector: WithVector::Bool(true),
@@ -941,4 +1019,60 @@ mod tests {
         assert_eq!(planned_query.scrolls[0].limit, 20);
         assert_eq!(planned_query.scrolls[1].limit, 50);
     }
+
+    #[test]
+    fn test_try_from_dbsf_weights() {
+        let dbsf = |weights: Vec<f32>| {
+            ScoringQuery::Fusion(Fusion::Dbsf {
+                weights,
+                normalization: Normalization::MinMax,
+            })
+        };
+        let request = |query: ScoringQuery| ShardQueryRequest {
+            prefetches: vec![
+                ShardPrefetch {
+                    prefetches: vec![dummy_core_prefetch(30), dummy_core_prefetch(40)],
+                    query: Some(query.clone()),
+                    filter: None,
+                    params: None,
+                    score_threshold: None,
+                    limit: 10,
+                },
+                dummy_scroll_prefetch(50),
+            ],
+            query: Some(query),
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_payload: WithPayloadInterface::Bool(false),
+            with_vector: WithVector::Bool(false),
+        };
+
+        let planned_query = PlannedQuery::try_from(vec![request(dbsf(vec![0.5, 2.0]))]).unwrap();
+        assert_eq!(
+            planned_query.root_plans,
+            vec![MergePlan {
+                sources: vec![
+                    Source::Prefetch(MergePlan {
+                        sources: vec![Source::SearchesIdx(0), Source::SearchesIdx(1)],
+                        rescore_params: Some(RescoreParams {
+                            rescore: dbsf(vec![0.5, 2.0]),
+                            limit: 10,
+                            score_threshold: None,
+                            with_vector: WithVector::Bool(false),
+                            with_payload: WithPayloadInterface::Bool(false),
+                        }),
+                    }),
+                    Source::ScrollsIdx(0),
+                ],
+                rescore_params: None,
+            }]
+        );
+
+        // more weights than prefetches
+        let planned_query = PlannedQuery::try_from(vec![request(dbsf(vec![1.0, 1.0, 1.0]))]);
+        assert!(planned_query.is_err());
+    }
 }
@@ -1,15 +1,30 @@
+use std::collections::HashSet;
+
 use api::grpc::qdrant as grpc;
+use api::rest::Sample;
+use chrono::{DateTime, Utc};
 use common::types::ScoreType;
 use itertools::Itertools;
+use segment::common::operation_error::OperationResult;
+use segment::common::reciprocal_rank_fusion::{rrf_scoring, DEFAULT_RRF_K};
+use segment::common::score_fusion::{score_fusion, Aggregation, Normalization, ScoreFusion};
+use segment::data_types::formula::FormulaQuery;
 use segment::data_types::order_by::OrderBy;
-use segment::data_types::vectors::{NamedQuery, NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector};
-use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::data_types::vectors::{
+    Named, NamedQuery, NamedVectorStruct, Vector, VectorRef, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{
+    Distance, Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector,
+};
+use segment::vector_storage::query::{
+    ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery,
+};
+use segment::vector_storage::{preprocess_vector, score_vectors};
 use tonic::Status;
 
 use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::CollectionResult;
+use crate::operations::types::{CollectionError, CollectionResult};
 
 /// Internal response type for a universal query request.
 ///
@@ -45,7 +60,183 @@ impl ShardQueryRequest {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Fusion {
-    Rrf,
+    /// Reciprocal rank fusion
+    Rrf {
+        /// Constant, added to the rank of each point
+        k: usize,
+        /// Multipliers of the rank scores of each prefetch, 1.0 for the missing ones
+        weights: Vec<f32>,
+    },
+    /// Distribution-based score fusion, sums up normalized scores of the prefetches
+    Dbsf {
+        /// Multipliers of the scores of each prefetch, 1.0 for the missing ones
+        weights: Vec<f32>,
+        normalization: Normalization,
+    },
+}
+
+impl Fusion {
+    /// Rank fusion with default parameters
+    pub fn rrf() -> Self {
+        Fusion::Rrf {
+            k: DEFAULT_RRF_K,
+            weights: Vec::new(),
+        }
+    }
+
+    /// Score fusion with default parameters
+    pub fn dbsf() -> Self {
+        Fusion::Dbsf {
+            weights: Vec::new(),
+            normalization: Normalization::Distr,
+        }
+    }
+
+    /// Check that the fusion can be applied to this many prefetches
+    pub fn check_prefetches_count(&self, prefetches_count: usize) -> CollectionResult<()> {
+        let weights = match self {
+            Fusion::Rrf { weights, .. } | Fusion::Dbsf { weights, .. } => weights,
+        };
+        if weights.len() > prefetches_count {
+            return Err(CollectionError::bad_request(format!(
+                "fusion has {} weights, but there are only {} prefetches",
+                weights.len(),
+                prefetches_count,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Combine the results of the prefetches into a single list, from the best to the worst point.
+    ///
+    /// `sources_order` is the order of the scores of each prefetch, as defined by its query.
+    pub fn fuse(
+        &self,
+        sources: Vec<Vec<ScoredPoint>>,
+        sources_order: &[Order],
+    ) -> Vec<ScoredPoint> {
+        debug_assert_eq!(sources.len(), sources_order.len());
+        match self {
+            Fusion::Rrf { k, weights } => rrf_scoring(sources, *k, weights),
+            Fusion::Dbsf {
+                weights,
+                normalization,
+            } => score_fusion(
+                sources.into_iter().zip(sources_order.iter().copied()),
+                ScoreFusion {
+                    method: Aggregation::Sum,
+                    norm: *normalization,
+                    weights: weights.clone(),
+                    order: Order::LargeBetter,
+                },
+            ),
+        }
+    }
+}
+
+/// Maximal marginal relevance: re-selects the candidates, balancing their similarity to the query
+/// against their similarity to the already selected candidates
+#[derive(Debug, Clone, PartialEq)]
+pub struct MmrQuery {
+    /// Vector, to which the relevance of the candidates is measured
+    pub vector: NamedVectorStruct,
+    /// Weight of the relevance, from 0.0 for only diversity to 1.0 for only relevance
+    pub lambda: f32,
+}
+
+impl MmrQuery {
+    pub fn get_vector_name(&self) -> &str {
+        self.vector.get_name()
+    }
+
+    /// Select up to `limit` candidates, in the order of selection.
+    ///
+    /// Candidates must contain the vector of the query. The score of a selected point is its
+    /// marginal relevance at the moment of selection.
+    pub fn select(
+        &self,
+        candidates: Vec<ScoredPoint>,
+        distance: Distance,
+        limit: usize,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let vector_name = self.get_vector_name();
+        let query = preprocess_vector(distance, self.vector.clone().to_vector());
+
+        let mut seen = HashSet::new();
+        let candidates = candidates
+            .into_iter()
+            .filter(|point| seen.insert(point.id))
+            .collect_vec();
+
+        let vectors: Vec<VectorRef> = candidates
+            .iter()
+            .map(|point| {
+                point
+                    .vector
+                    .as_ref()
+                    .and_then(|vector| vector.get(vector_name))
+                    .ok_or_else(|| {
+                        CollectionError::service_error(format!(
+                            "MMR candidate {} has no vector {vector_name}",
+                            point.id,
+                        ))
+                    })
+            })
+            .try_collect()?;
+
+        let relevance: Vec<ScoreType> = vectors
+            .iter()
+            .map(|vector| score_vectors(distance, VectorRef::from(&query), *vector))
+            .try_collect()?;
+
+        // Max similarity of each candidate to the selected ones, none are selected yet
+        let mut max_similarity = vec![None; candidates.len()];
+        let mut remaining = (0..candidates.len()).collect_vec();
+        let mut selected = Vec::with_capacity(limit.min(candidates.len()));
+
+        while selected.len() < limit && !remaining.is_empty() {
+            let marginal_relevance = |idx: usize| {
+                let redundancy = max_similarity[idx].unwrap_or(0.0);
+                self.lambda * relevance[idx] - (1.0 - self.lambda) * redundancy
+            };
+
+            let (position, score) = remaining
+                .iter()
+                .enumerate()
+                .map(|(position, &idx)| (position, marginal_relevance(idx)))
+                .max_by(|(a_position, a_score), (b_position, b_score)| {
+                    a_score.total_cmp(b_score).then_with(|| {
+                        // Ties are broken by the smallest id
+                        let a_id = candidates[remaining[*a_position]].id;
+                        let b_id = candidates[remaining[*b_position]].id;
+                        b_id.cmp(&a_id)
+                    })
+                })
+                .expect("remaining candidates are not empty");
+
+            let best = remaining.swap_remove(position);
+            for &idx in &remaining {
+                let similarity = score_vectors(distance, vectors[best], vectors[idx])?;
+                max_similarity[idx] = Some(
+                    max_similarity[idx].map_or(similarity, |max: ScoreType| max.max(similarity)),
+                );
+            }
+            selected.push((best, score));
+        }
+
+        let mut candidates = candidates.into_iter().map(Some).collect_vec();
+        let selected = selected
+            .into_iter()
+            .filter_map(|(idx, score)| {
+                candidates[idx].take().map(|mut point| {
+                    point.score = score;
+                    point
+                })
+            })
+            .collect();
+
+        Ok(selected)
+    }
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -54,20 +245,54 @@ pub enum ScoringQuery {
     /// Score points against some vector(s)
     Vector(QueryEnum),
 
-    /// Reciprocal rank fusion
+    /// Fuse the results of the prefetches
     Fusion(Fusion),
 
     /// Order by a payload field
     OrderBy(OrderBy),
+
+    /// Score the results of the prefetches with a formula
+    Formula(FormulaQuery),
+
+    /// Diversify the results of the prefetches with maximal marginal relevance
+    Mmr(MmrQuery),
+
+    /// Sample points, scored by their sampling keys
+    Sample(Sample),
 }
 
 impl ScoringQuery {
     pub fn needs_intermediate_results(&self) -> bool {
         match self {
             ScoringQuery::Fusion(fusion) => match fusion {
-                Fusion::Rrf => true,
+                Fusion::Rrf { .. } | Fusion::Dbsf { .. } => true,
+            },
+            // Candidates of all shards are needed to select diverse results
+            ScoringQuery::Mmr(_) => true,
+            // Sampling keys of the shards are comparable, so shard results are merged as usual
+            ScoringQuery::Vector(_)
+            | ScoringQuery::OrderBy(_)
+            | ScoringQuery::Formula(_)
+            | ScoringQuery::Sample(_) => false,
+        }
+    }
+
+    /// Check that the query can be applied to the results of this many prefetches
+    pub fn check_prefetches_count(&self, prefetches_count: usize) -> CollectionResult<()> {
+        match self {
+            ScoringQuery::Fusion(fusion) => fusion.check_prefetches_count(prefetches_count),
+            ScoringQuery::Formula(formula) => match formula.formula.max_score_index() {
+                Some(index) if index >= prefetches_count => {
+                    Err(CollectionError::bad_request(format!(
+                        "formula uses the score of prefetch {index}, but there are only {prefetches_count} prefetches",
+                    )))
+                }
+                _ => Ok(()),
             },
-            ScoringQuery::Vector(_) | ScoringQuery::OrderBy(_) => false,
+            ScoringQuery::Vector(_)
+            | ScoringQuery::OrderBy(_)
+            | ScoringQuery::Mmr(_)
+            | ScoringQuery::Sample(_) => Ok(()),
         }
     }
 
@@ -75,6 +300,7 @@ impl ScoringQuery {
     pub fn get_vector_name(&self) -> Option<&str> {
         match self {
             ScoringQuery::Vector(query) => Some(query.get_vector_name()),
+            ScoringQuery::Mmr(mmr) => Some(mmr.get_vector_name()),
             _ => None,
         }
     }
@@ -87,18 +313,15 @@ impl ScoringQuery {
         let order = match opt_self {
             Some(scoring_query) => match scoring_query {
                 ScoringQuery::Vector(query_enum) => {
-                    if query_enum.is_distance_scored() {
-                        collection_params
-                            .get_distance(query_enum.get_vector_name())?
-                            .distance_order()
-                    } else {
-                        Order::LargeBetter
-                    }
+                    Self::vector_order(query_enum, collection_params)?
                 }
                 ScoringQuery::Fusion(fusion) => match fusion {
-                    Fusion::Rrf => Order::LargeBetter,
+                    Fusion::Rrf { .. } | Fusion::Dbsf { .. } => Order::LargeBetter,
                 },
                 ScoringQuery::OrderBy(order_by) => Order::from(order_by.direction()),
+                ScoringQuery::Formula(_) | ScoringQuery::Mmr(_) | ScoringQuery::Sample(_) => {
+                    Order::LargeBetter
+                }
             },
             None => {
                 // Order by ID
@@ -107,6 +330,21 @@ impl ScoringQuery {
         };
         Ok(order)
     }
+
+    /// Order of the scores of a vector query, as returned by the search
+    pub fn vector_order(
+        query_enum: &QueryEnum,
+        collection_params: &CollectionParams,
+    ) -> CollectionResult<Order> {
+        let order = if query_enum.is_distance_scored() {
+            collection_params
+                .get_distance(query_enum.get_vector_name())?
+                .distance_order()
+        } else {
+            Order::LargeBetter
+        };
+        Ok(order)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -140,9 +378,28 @@ impl ShardQueryRequest {
 
         filters
     }
+
+    /// Resolve relative datetime ranges in all filters of the request, including prefetches
+    pub fn resolve_relative_datetime(&mut self, now: DateTime<Utc>) -> OperationResult<()> {
+        if let Some(filter) = &mut self.filter {
+            filter.resolve_relative_datetime(now)?;
+        }
+        self.prefetches
+            .iter_mut()
+            .try_for_each(|prefetch| prefetch.resolve_relative_datetime(now))
+    }
 }
 
 impl ShardPrefetch {
+    fn resolve_relative_datetime(&mut self, now: DateTime<Utc>) -> OperationResult<()> {
+        if let Some(filter) = &mut self.filter {
+            filter.resolve_relative_datetime(now)?;
+        }
+        self.prefetches
+            .iter_mut()
+            .try_for_each(|prefetch| prefetch.resolve_relative_datetime(now))
+    }
+
     fn filter_refs(&self) -> Vec<Option<&Filter>> {
         let mut filters = vec![];
 
@@ -231,6 +488,21 @@ impl TryFrom<grpc::query_shard_points::Prefetch> for ShardPrefetch {
     }
 }
 
+fn named_vector_from_grpc_raw(
+    raw_vector: grpc::RawVector,
+    using: Option<String>,
+) -> Result<NamedVectorStruct, Status> {
+    let vector = Vector::try_from(raw_vector)?;
+    let name = match (using, &vector) {
+        (None, Vector::Sparse(_)) => {
+            return Err(Status::invalid_argument("Sparse vector must have a name"))
+        }
+        (Some(name), Vector::MultiDense(_) | Vector::Sparse(_) | Vector::Dense(_)) => name,
+        (None, Vector::MultiDense(_) | Vector::Dense(_)) => DEFAULT_VECTOR_NAME.to_string(),
+    };
+    Ok(NamedVectorStruct::new_from_vector(vector, name))
+}
+
 impl QueryEnum {
     fn try_from_grpc_raw_query(
         raw_query: grpc::RawQuery,
@@ -244,24 +516,17 @@ impl QueryEnum {
 
         let query_enum = match variant {
             Variant::Nearest(nearest) => {
-                let vector = Vector::try_from(nearest)?;
-                let name = match (using, &vector) {
-                    (None, Vector::Sparse(_)) => {
-                        return Err(Status::invalid_argument("Sparse vector must have a name"))
-                    }
-                    (Some(name), Vector::MultiDense(_) | Vector::Sparse(_) | Vector::Dense(_)) => {
-                        name
-                    }
-                    (None, Vector::MultiDense(_) | Vector::Dense(_)) => {
-                        DEFAULT_VECTOR_NAME.to_string()
-                    }
-                };
-                let named_vector = NamedVectorStruct::new_from_vector(vector, name);
-                QueryEnum::Nearest(named_vector)
+                QueryEnum::Nearest(named_vector_from_grpc_raw(nearest, using)?)
             }
             Variant::RecommendBestScore(recommend) => QueryEnum::RecommendBestScore(
                 NamedQuery::new(RecoQuery::try_from(recommend)?, using),
             ),
+            Variant::RecommendSumScores(recommend) => QueryEnum::RecommendSumScores(
+                NamedQuery::new(RecoSumQuery::try_from(recommend)?, using),
+            ),
+            Variant::RecommendContrastive(recommend) => QueryEnum::RecommendContrastive(
+                NamedQuery::new(ContrastiveQuery::try_from(recommend)?, using),
+            ),
             Variant::Discover(discovery) => QueryEnum::Discover(NamedQuery {
                 query: DiscoveryQuery::try_from(discovery)?,
                 using,
@@ -291,15 +556,92 @@ impl TryFrom<i32> for Fusion {
 impl From<api::grpc::qdrant::Fusion> for Fusion {
     fn from(fusion: api::grpc::qdrant::Fusion) -> Self {
         match fusion {
-            api::grpc::qdrant::Fusion::Rrf => Fusion::Rrf,
+            api::grpc::qdrant::Fusion::Rrf => Fusion::rrf(),
+            api::grpc::qdrant::Fusion::Dbsf => Fusion::dbsf(),
         }
     }
 }
 
-impl From<Fusion> for api::grpc::qdrant::Fusion {
+impl TryFrom<grpc::FusionParams> for Fusion {
+    type Error = Status;
+
+    fn try_from(params: grpc::FusionParams) -> Result<Self, Self::Error> {
+        let grpc::FusionParams {
+            fusion,
+            weights,
+            normalization,
+            k,
+        } = params;
+
+        let normalization = normalization
+            .map(|normalization| {
+                grpc::ScoreNormalization::from_i32(normalization)
+                    .map(Normalization::from)
+                    .ok_or_else(|| {
+                        Status::invalid_argument(format!(
+                            "invalid score normalization value {normalization}",
+                        ))
+                    })
+            })
+            .transpose()?;
+
+        if weights
+            .iter()
+            .any(|weight| !weight.is_finite() || *weight < 0.0)
+        {
+            return Err(Status::invalid_argument(
+                "weights must be finite non-negative numbers",
+            ));
+        }
+
+        match Fusion::try_from(fusion)? {
+            Fusion::Rrf { .. } => {
+                if normalization.is_some() {
+                    return Err(Status::invalid_argument(
+                        "normalization is only supported by DBSF fusion",
+                    ));
+                }
+                if k == Some(0) {
+                    return Err(Status::invalid_argument("k must be at least 1"));
+                }
+                Ok(Fusion::Rrf {
+                    k: k.map_or(DEFAULT_RRF_K, |k| k as usize),
+                    weights,
+                })
+            }
+            Fusion::Dbsf { .. } => {
+                if k.is_some() {
+                    return Err(Status::invalid_argument(
+                        "k is only supported by RRF fusion",
+                    ));
+                }
+                Ok(Fusion::Dbsf {
+                    weights,
+                    normalization: normalization.unwrap_or(Normalization::Distr),
+                })
+            }
+        }
+    }
+}
+
+impl From<Fusion> for grpc::FusionParams {
     fn from(fusion: Fusion) -> Self {
         match fusion {
-            Fusion::Rrf => api::grpc::qdrant::Fusion::Rrf,
+            Fusion::Rrf { k, weights } => Self {
+                fusion: grpc::Fusion::Rrf as i32,
+                weights,
+                normalization: None,
+                k: Some(k as u32),
+            },
+            Fusion::Dbsf {
+                weights,
+                normalization,
+            } => Self {
+                fusion: grpc::Fusion::Dbsf as i32,
+                weights,
+                normalization: Some(grpc::ScoreNormalization::from(normalization) as i32),
+                k: None,
+            },
         }
     }
 }
@@ -322,6 +664,24 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::OrderBy(order_by) => {
                 ScoringQuery::OrderBy(OrderBy::try_from(order_by)?)
             }
+            grpc::query_shard_points::query::Score::FusionParams(params) => {
+                ScoringQuery::Fusion(Fusion::try_from(params)?)
+            }
+            grpc::query_shard_points::query::Score::Formula(formula) => {
+                ScoringQuery::Formula(FormulaQuery::try_from(formula)?)
+            }
+            grpc::query_shard_points::query::Score::Mmr(mmr) => {
+                let grpc::query_shard_points::Mmr { vector, lambda } = mmr;
+                let vector =
+                    vector.ok_or_else(|| Status::invalid_argument("missing field: mmr.vector"))?;
+                ScoringQuery::Mmr(MmrQuery {
+                    vector: named_vector_from_grpc_raw(vector, using)?,
+                    lambda,
+                })
+            }
+            grpc::query_shard_points::query::Score::Sample(sample) => {
+                ScoringQuery::Sample(Sample::try_from(sample)?)
+            }
         };
 
         Ok(scoring_query)
@@ -337,6 +697,12 @@ impl From<QueryEnum> for grpc::RawQuery {
             QueryEnum::RecommendBestScore(named) => {
                 Variant::RecommendBestScore(grpc::raw_query::Recommend::from(named.query))
             }
+            QueryEnum::RecommendSumScores(named) => {
+                Variant::RecommendSumScores(grpc::raw_query::Recommend::from(named.query))
+            }
+            QueryEnum::RecommendContrastive(named) => {
+                Variant::RecommendContrastive(grpc::raw_query::WeightedRecommend::from(named.query))
+            }
             QueryEnum::Discover(named) => {
                 Variant::Discover(grpc::raw_query::Discovery::from(named.query))
             }
@@ -359,18 +725,36 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::Vector(query) => Self {
                 score: Some(Score::Vector(grpc::RawQuery::from(query))),
             },
+            // Default fusion is sent as is, so older peers can still understand it
+            ScoringQuery::Fusion(fusion) if fusion == Fusion::rrf() => Self {
+                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::Rrf as i32)),
+            },
             ScoringQuery::Fusion(fusion) => Self {
-                score: Some(Score::Fusion(api::grpc::qdrant::Fusion::from(fusion) as i32)),
+                score: Some(Score::FusionParams(grpc::FusionParams::from(fusion))),
             },
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Formula(formula) => Self {
+                score: Some(Score::Formula(grpc::Formula::from(formula))),
+            },
+            ScoringQuery::Mmr(MmrQuery { vector, lambda }) => Self {
+                score: Some(Score::Mmr(grpc::query_shard_points::Mmr {
+                    vector: Some(grpc::RawVector::from(vector.to_vector())),
+                    lambda,
+                })),
+            },
+            ScoringQuery::Sample(sample) => Self {
+                score: Some(Score::Sample(grpc::Sample::from(sample) as i32)),
+            },
         }
     }
 }
 
-impl From<ShardPrefetch> for grpc::query_shard_points::Prefetch {
-    fn from(value: ShardPrefetch) -> Self {
+impl TryFrom<ShardPrefetch> for grpc::query_shard_points::Prefetch {
+    type Error = Status;
+
+    fn try_from(value: ShardPrefetch) -> Result<Self, Self::Error> {
         let ShardPrefetch {
             prefetches,
             query,
@@ -379,22 +763,27 @@ impl From<ShardPrefetch> for grpc::query_shard_points::Prefetch {
             filter,
             score_threshold,
         } = value;
-        Self {
-            prefetch: prefetches.into_iter().map(Self::from).collect(),
+        Ok(Self {
+            prefetch: prefetches
+                .into_iter()
+                .map(Self::try_from)
+                .collect::<Result<_, _>>()?,
             using: query
                 .as_ref()
                 .and_then(|query| query.get_vector_name().map(ToOwned::to_owned)),
             query: query.map(From::from),
-            filter: filter.map(grpc::Filter::from),
+            filter: filter.map(grpc::Filter::try_from).transpose()?,
             params: params.map(grpc::SearchParams::from),
             score_threshold,
             limit: limit as u64,
-        }
+        })
     }
 }
 
-impl From<ShardQueryRequest> for grpc::QueryShardPoints {
-    fn from(value: ShardQueryRequest) -> Self {
+impl TryFrom<ShardQueryRequest> for grpc::QueryShardPoints {
+    type Error = Status;
+
+    fn try_from(value: ShardQueryRequest) -> Result<Self, Self::Error> {
         let ShardQueryRequest {
             prefetches,
             query,
@@ -407,22 +796,22 @@ impl From<ShardQueryRequest> for grpc::QueryShardPoints {
             with_payload,
         } = value;
 
-        Self {
+        Ok(Self {
             prefetch: prefetches
                 .into_iter()
-                .map(grpc::query_shard_points::Prefetch::from)
-                .collect(),
+                .map(grpc::query_shard_points::Prefetch::try_from)
+                .collect::<Result<_, _>>()?,
             using: query
                 .as_ref()
                 .and_then(|query| query.get_vector_name().map(ToOwned::to_owned)),
             query: query.map(From::from),
-            filter: filter.map(grpc::Filter::from),
+            filter: filter.map(grpc::Filter::try_from).transpose()?,
             params: params.map(grpc::SearchParams::from),
             score_threshold,
             limit: limit as u64,
             offset: offset as u64,
             with_payload: Some(grpc::WithPayloadSelector::from(with_payload)),
             with_vectors: Some(grpc::WithVectorsSelector::from(with_vector)),
-        }
+        })
     }
 }
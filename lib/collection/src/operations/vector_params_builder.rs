@@ -20,6 +20,7 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivector_config: None,
+                embedding: None,
             },
         }
     }
@@ -49,6 +50,11 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_embedding(mut self, provider_name: impl Into<String>) -> Self {
+        self.vector_params.embedding = Some(provider_name.into());
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
@@ -3,6 +3,7 @@ use std::iter::Peekable;
 use std::time::Duration;
 
 use api::rest::RecommendStrategy;
+use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::vectors::{
     DenseVector, NamedQuery, NamedVectorStruct, TypedMultiDenseVector, Vector, VectorElementType,
@@ -11,7 +12,7 @@ use segment::data_types::vectors::{
 use segment::types::{
     Condition, ExtendedPointId, Filter, HasIdCondition, PointIdType, ScoredPoint,
 };
-use segment::vector_storage::query::RecoQuery;
+use segment::vector_storage::query::{ContrastiveQuery, RecoQuery, RecoSumQuery};
 use sparse::common::sparse_vector::SparseVector;
 use tokio::sync::RwLockReadGuard;
 
@@ -26,7 +27,7 @@ use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
-    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch, RecommendExample,
     RecommendRequestInternal, UsingVector,
 };
 
@@ -204,7 +205,9 @@ pub fn recommend_into_core_search(
             reference_vectors_ids_to_exclude,
             all_vectors_records_map,
         ),
-        RecommendStrategy::BestScore => Ok(recommend_by_best_score(
+        RecommendStrategy::BestScore
+        | RecommendStrategy::SumScores
+        | RecommendStrategy::Contrastive => Ok(recommend_by_custom_score(
             request,
             reference_vectors_ids_to_exclude,
             all_vectors_records_map,
@@ -255,7 +258,9 @@ where
                     });
                 }
             }
-            RecommendStrategy::BestScore => {
+            RecommendStrategy::BestScore
+            | RecommendStrategy::SumScores
+            | RecommendStrategy::Contrastive => {
                 if request.positive.is_empty() && request.negative.is_empty() {
                     return Err(CollectionError::BadRequest {
                         description: "At least one positive or negative vector ID required with this strategy"
@@ -264,6 +269,18 @@ where
                 }
             }
         }
+        request
+            .strategy
+            .unwrap_or_default()
+            .validate_weights(
+                request.positive.len(),
+                request.negative.len(),
+                request.positive_weights.as_deref(),
+                request.negative_weights.as_deref(),
+            )
+            .map_err(|description| CollectionError::BadRequest {
+                description: description.to_owned(),
+            })?;
         Ok(())
     })?;
 
@@ -384,7 +401,8 @@ fn recommend_by_avg_vector(
     })
 }
 
-fn recommend_by_best_score(
+/// Search with a custom scoring query, for all the strategies besides `average_vector`
+fn recommend_by_custom_score(
     request: RecommendRequestInternal,
     reference_vectors_ids_to_exclude: Vec<PointIdType>,
     all_vectors_records_map: &ReferencedVectors,
@@ -394,7 +412,9 @@ fn recommend_by_best_score(
     let RecommendRequestInternal {
         positive,
         negative,
-        strategy: _,
+        strategy,
+        positive_weights,
+        negative_weights,
         filter,
         params,
         limit,
@@ -407,28 +427,59 @@ fn recommend_by_best_score(
     } = request;
 
     let lookup_collection_name = lookup_from.as_ref().map(|x| &x.collection);
-
-    let positive = convert_to_vectors_owned(
-        positive,
-        all_vectors_records_map,
-        &lookup_vector_name,
-        lookup_collection_name,
-    );
-
-    let negative = convert_to_vectors_owned(
-        negative,
-        all_vectors_records_map,
-        &lookup_vector_name,
-        lookup_collection_name,
-    );
-
-    let query = QueryEnum::RecommendBestScore(NamedQuery {
-        query: RecoQuery::new(positive, negative),
-        using: using.map(|x| match x {
-            UsingVector::Name(name) => name,
-        }),
+    let using = using.map(|x| match x {
+        UsingVector::Name(name) => name,
     });
 
+    let query = match strategy.unwrap_or_default() {
+        RecommendStrategy::Contrastive => {
+            // Weights are matched to the examples before the missing vectors are dropped
+            let positive = convert_to_weighted_vectors(
+                &positive,
+                positive_weights,
+                all_vectors_records_map,
+                &lookup_vector_name,
+                lookup_collection_name,
+            );
+            let negative = convert_to_weighted_vectors(
+                &negative,
+                negative_weights,
+                all_vectors_records_map,
+                &lookup_vector_name,
+                lookup_collection_name,
+            );
+            QueryEnum::RecommendContrastive(NamedQuery {
+                query: ContrastiveQuery::new(positive, negative),
+                using,
+            })
+        }
+        strategy => {
+            let positive = convert_to_vectors_owned(
+                positive,
+                all_vectors_records_map,
+                &lookup_vector_name,
+                lookup_collection_name,
+            );
+            let negative = convert_to_vectors_owned(
+                negative,
+                all_vectors_records_map,
+                &lookup_vector_name,
+                lookup_collection_name,
+            );
+            if strategy == RecommendStrategy::SumScores {
+                QueryEnum::RecommendSumScores(NamedQuery {
+                    query: RecoSumQuery::new(positive, negative),
+                    using,
+                })
+            } else {
+                QueryEnum::RecommendBestScore(NamedQuery {
+                    query: RecoQuery::new(positive, negative),
+                    using,
+                })
+            }
+        }
+    };
+
     CoreSearchRequest {
         query,
         filter: Some(Filter {
@@ -448,6 +499,32 @@ fn recommend_by_best_score(
     }
 }
 
+/// Vectors of the examples along with their weights, 1.0 by default.
+/// Examples without the looked up vector are skipped.
+fn convert_to_weighted_vectors(
+    examples: &[RecommendExample],
+    weights: Option<Vec<ScoreType>>,
+    all_vectors_records_map: &ReferencedVectors,
+    vector_name: &str,
+    collection_name: Option<&String>,
+) -> Vec<(Vector, ScoreType)> {
+    let weights = weights.into_iter().flatten().chain(std::iter::repeat(1.0));
+    examples
+        .iter()
+        .zip(weights)
+        .filter_map(|(example, weight)| {
+            convert_to_vectors(
+                std::iter::once(example),
+                all_vectors_records_map,
+                vector_name,
+                collection_name,
+            )
+            .next()
+            .map(|vector| (vector.to_owned(), weight))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use segment::data_types::vectors::{Vector, VectorRef};
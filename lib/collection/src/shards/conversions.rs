@@ -109,20 +109,20 @@ pub fn internal_delete_points_by_filter(
     filter: Filter,
     wait: bool,
     ordering: Option<WriteOrdering>,
-) -> DeletePointsInternal {
-    DeletePointsInternal {
+) -> CollectionResult<DeletePointsInternal> {
+    Ok(DeletePointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         delete_points: Some(DeletePoints {
             collection_name,
             wait: Some(wait),
             points: Some(PointsSelector {
-                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.try_into()?)),
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
         }),
-    }
+    })
 }
 
 pub fn internal_update_vectors(
@@ -190,15 +190,15 @@ pub fn internal_delete_vectors_by_filter(
     vector_names: Vec<String>,
     wait: bool,
     ordering: Option<WriteOrdering>,
-) -> DeleteVectorsInternal {
-    DeleteVectorsInternal {
+) -> CollectionResult<DeleteVectorsInternal> {
+    Ok(DeleteVectorsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         delete_vectors: Some(DeletePointVectors {
             collection_name,
             wait: Some(wait),
             points_selector: Some(PointsSelector {
-                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.try_into()?)),
             }),
             vectors: Some(VectorsSelector {
                 names: vector_names,
@@ -206,7 +206,7 @@ pub fn internal_delete_vectors_by_filter(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
         }),
-    }
+    })
 }
 
 pub fn internal_set_payload(
@@ -216,7 +216,7 @@ pub fn internal_set_payload(
     set_payload: SetPayloadOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
-) -> SetPayloadPointsInternal {
+) -> CollectionResult<SetPayloadPointsInternal> {
     let points_selector = if let Some(points) = set_payload.points {
         Some(PointsSelector {
             points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
@@ -224,12 +224,17 @@ pub fn internal_set_payload(
             })),
         })
     } else {
-        set_payload.filter.map(|filter| PointsSelector {
-            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
-        })
+        set_payload
+            .filter
+            .map(|filter| -> Result<_, Status> {
+                Ok(PointsSelector {
+                    points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.try_into()?)),
+                })
+            })
+            .transpose()?
     };
 
-    SetPayloadPointsInternal {
+    Ok(SetPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         set_payload_points: Some(SetPayloadPoints {
@@ -241,7 +246,7 @@ pub fn internal_set_payload(
             shard_key_selector: None,
             key: set_payload.key.map(|key| key.to_string()),
         }),
-    }
+    })
 }
 
 pub fn internal_delete_payload(
@@ -251,7 +256,7 @@ pub fn internal_delete_payload(
     delete_payload: DeletePayloadOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
-) -> DeletePayloadPointsInternal {
+) -> CollectionResult<DeletePayloadPointsInternal> {
     let points_selector = if let Some(points) = delete_payload.points {
         Some(PointsSelector {
             points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
@@ -259,12 +264,17 @@ pub fn internal_delete_payload(
             })),
         })
     } else {
-        delete_payload.filter.map(|filter| PointsSelector {
-            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
-        })
+        delete_payload
+            .filter
+            .map(|filter| -> Result<_, Status> {
+                Ok(PointsSelector {
+                    points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.try_into()?)),
+                })
+            })
+            .transpose()?
     };
 
-    DeletePayloadPointsInternal {
+    Ok(DeletePayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         delete_payload_points: Some(DeletePayloadPoints {
@@ -279,7 +289,7 @@ pub fn internal_delete_payload(
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
         }),
-    }
+    })
 }
 
 pub fn internal_clear_payload(
@@ -314,20 +324,20 @@ pub fn internal_clear_payload_by_filter(
     filter: Filter,
     wait: bool,
     ordering: Option<WriteOrdering>,
-) -> ClearPayloadPointsInternal {
-    ClearPayloadPointsInternal {
+) -> CollectionResult<ClearPayloadPointsInternal> {
+    Ok(ClearPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         clear_payload_points: Some(ClearPayloadPoints {
             collection_name,
             wait: Some(wait),
             points: Some(PointsSelector {
-                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+                points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.try_into()?)),
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
         }),
-    }
+    })
 }
 
 pub fn internal_create_index(
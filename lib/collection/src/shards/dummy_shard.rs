@@ -3,7 +3,9 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -11,7 +13,8 @@ use tokio::runtime::Handle;
 
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResult, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
@@ -93,6 +96,14 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn facet(&self, _: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        self.dummy()
+    }
+
+    async fn aggregate(&self, _: Arc<AggregationParams>) -> CollectionResult<NumericAggregation> {
+        self.dummy()
+    }
+
     async fn retrieve(
         &self,
         _: Arc<PointRequestInternal>,
@@ -110,4 +121,14 @@ impl ShardOperation for DummyShard {
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         self.dummy()
     }
+
+    async fn query_batch_profiled(
+        &self,
+        _requests: Arc<Vec<ShardQueryRequest>>,
+        _search_runtime_handle: &Handle,
+        _timeout: Option<Duration>,
+        _explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        self.dummy()
+    }
 }
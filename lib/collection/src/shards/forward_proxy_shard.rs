@@ -4,7 +4,9 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -20,7 +22,8 @@ use crate::operations::point_ops::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResult, PointRequestInternal,
+    Record, UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
@@ -355,6 +358,19 @@ impl ShardOperation for ForwardProxyShard {
         local_shard.count(request).await
     }
 
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.aggregate(request).await
+    }
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -378,4 +394,17 @@ impl ShardOperation for ForwardProxyShard {
             .query_batch(requests, search_runtime_handle, timeout)
             .await
     }
+
+    async fn query_batch_profiled(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .query_batch_profiled(requests, search_runtime_handle, timeout, explain)
+            .await
+    }
 }
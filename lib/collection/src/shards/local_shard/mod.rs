@@ -21,6 +21,7 @@ use common::types::TelemetryDetail;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::data_types::explain::{segment_name, SearchStrategy, SearchTrace, SegmentExplanation};
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
@@ -869,17 +870,16 @@ impl LocalShard {
         if some_segment.is_none() {
             return Ok(CardinalityEstimation::exact(0));
         }
-        let cardinality = segments
-            .iter()
-            .map(|(_id, segment)| segment.get().read().estimate_point_count(filter))
-            .fold(CardinalityEstimation::exact(0), |acc, x| {
-                CardinalityEstimation {
-                    primary_clauses: vec![],
-                    min: acc.min + x.min,
-                    exp: acc.exp + x.exp,
-                    max: acc.max + x.max,
-                }
-            });
+        let mut cardinality = CardinalityEstimation::exact(0);
+        for (_id, segment) in segments.iter() {
+            let segment_cardinality = segment.get().read().estimate_point_count(filter)?;
+            cardinality = CardinalityEstimation {
+                primary_clauses: vec![],
+                min: cardinality.min + segment_cardinality.min,
+                exp: cardinality.exp + segment_cardinality.exp,
+                max: cardinality.max + segment_cardinality.max,
+            };
+        }
         Ok(cardinality)
     }
 
@@ -888,13 +888,64 @@ impl LocalShard {
         filter: Option<&'a Filter>,
     ) -> CollectionResult<BTreeSet<PointIdType>> {
         let segments = self.segments().read();
-        let all_points: BTreeSet<_> = segments
-            .non_appendable_then_appendable_segments()
-            .flat_map(|segment| segment.get().read().read_filtered(None, None, filter))
-            .collect();
+        let mut all_points = BTreeSet::new();
+        for segment in segments.non_appendable_then_appendable_segments() {
+            all_points.extend(segment.get().read().read_filtered(None, None, filter)?);
+        }
         Ok(all_points)
     }
 
+    /// Same as [`LocalShard::read_filtered`] for `exact` counts, or
+    /// [`LocalShard::estimate_cardinality`] otherwise, but also returns how each segment has
+    /// counted the points
+    pub fn explain_count(
+        &self,
+        filter: Option<&Filter>,
+        exact: bool,
+    ) -> CollectionResult<(usize, Vec<SegmentExplanation>)> {
+        let segments = self.segments().read();
+        let mut all_points = BTreeSet::new();
+        let mut estimated_count = 0;
+        let mut explanations = Vec::new();
+
+        for segment in segments.non_appendable_then_appendable_segments() {
+            let segment = segment.get();
+            let segment = segment.read();
+            let timer = Instant::now();
+            let mut trace = SearchTrace::default();
+
+            let cardinality = segment.estimate_point_count(filter)?;
+            if filter.is_some() {
+                trace.record_estimation(&cardinality, timer.elapsed());
+            }
+            trace.strategy = Some(SearchStrategy::filtered_scan(&cardinality));
+
+            if exact {
+                let filtering_timer = Instant::now();
+                let points = segment.read_filtered(None, None, filter)?;
+                trace.record_filtering(points.len(), filtering_timer.elapsed());
+                all_points.extend(points);
+            } else {
+                estimated_count += cardinality.exp;
+            }
+
+            explanations.push(trace.into_explanation(
+                segment_name(&segment.data_path()),
+                segment.segment_type(),
+                segment.available_point_count(),
+                None,
+                timer.elapsed(),
+            ));
+        }
+
+        let count = if exact {
+            all_points.len()
+        } else {
+            estimated_count
+        };
+        Ok((count, explanations))
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         let segments_read_guard = self.segments.read();
         let segments: Vec<_> = segments_read_guard
@@ -1,25 +1,33 @@
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use api::rest::OrderByInterface;
-use futures::future::BoxFuture;
+use api::rest::Sample;
+use common::types::ScoreType;
+use futures::future::{try_join_all, BoxFuture};
 use futures::FutureExt;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
-use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
+use itertools::Itertools;
+use segment::data_types::formula::FormulaQuery;
+use segment::data_types::profile::{ProfileStage, QueryProfile};
+use segment::types::{
+    Filter, HasIdCondition, Order, PointIdType, ScoredPoint, WithPayloadInterface, WithVector,
+};
 use tokio::runtime::Handle;
 
 use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::config::CollectionParams;
 use crate::operations::types::{
     CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    QueryScrollRequestInternal,
+    QueryScrollRequestInternal, ScrollOrder,
 };
 use crate::operations::universal_query::planned_query::{
     MergePlan, PlannedQuery, RescoreParams, Source,
 };
-use crate::operations::universal_query::shard_query::{Fusion, ScoringQuery, ShardQueryResponse};
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryResponse};
 
 pub enum FetchedSource {
     Search(usize),
@@ -29,49 +37,110 @@ pub enum FetchedSource {
 struct PrefetchResults {
     search_results: Vec<Vec<ScoredPoint>>,
     scroll_results: Vec<Vec<ScoredPoint>>,
+    /// Order of the scores of each search
+    search_orders: Vec<Order>,
+    /// Order of the scores of each scroll
+    scroll_orders: Vec<Order>,
 }
 
 impl PrefetchResults {
-    fn new(search_results: Vec<Vec<ScoredPoint>>, scroll_results: Vec<Vec<ScoredPoint>>) -> Self {
+    fn new(
+        search_results: Vec<Vec<ScoredPoint>>,
+        scroll_results: Vec<Vec<ScoredPoint>>,
+        search_orders: Vec<Order>,
+        scroll_orders: Vec<Order>,
+    ) -> Self {
         Self {
             search_results,
             scroll_results,
+            search_orders,
+            scroll_orders,
         }
     }
 
-    fn get(&self, element: FetchedSource) -> CollectionResult<Cow<'_, Vec<ScoredPoint>>> {
+    fn get(&self, element: FetchedSource) -> CollectionResult<(Cow<'_, Vec<ScoredPoint>>, Order)> {
         match element {
-            FetchedSource::Search(idx) => self.search_results.get(idx).map(Cow::Borrowed),
-            FetchedSource::Scroll(idx) => self.scroll_results.get(idx).map(Cow::Borrowed),
+            FetchedSource::Search(idx) => self
+                .search_results
+                .get(idx)
+                .zip(self.search_orders.get(idx)),
+            FetchedSource::Scroll(idx) => self
+                .scroll_results
+                .get(idx)
+                .zip(self.scroll_orders.get(idx)),
         }
+        .map(|(points, order)| (Cow::Borrowed(points), *order))
         .ok_or_else(|| CollectionError::service_error("Expected a prefetched source to exist"))
     }
 }
 
 impl LocalShard {
+    /// Execute the planned query, measuring the time of each stage.
+    ///
+    /// If `explain` is set, the scoring, filtering and rescoring stages also hold how each segment
+    /// has executed them.
     pub async fn do_planned_query(
         &self,
         request: PlannedQuery,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<ShardQueryResponse>> {
-        let start_time = std::time::Instant::now();
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let start_time = Instant::now();
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
 
-        let searches_f = self.do_search(
+        let has_searches = !request.searches.is_empty();
+        let has_scrolls = !request.scrolls.is_empty();
+
+        let collection_params = self.collection_config.read().await.params.clone();
+        let search_orders = request
+            .searches
+            .iter()
+            .map(|search| ScoringQuery::vector_order(&search.query, &collection_params))
+            .collect::<CollectionResult<_>>()?;
+        let scroll_orders = request
+            .scrolls
+            .iter()
+            .map(|scroll| scroll_order(&scroll.scroll_order))
+            .collect();
+
+        let searches_f = timed(self.do_search_explained(
             Arc::new(CoreSearchRequestBatch {
                 searches: request.searches,
             }),
             search_runtime_handle,
             Some(timeout),
-        );
+            explain,
+        ));
 
-        let scrolls_f =
-            self.query_scroll_batch(Arc::new(request.scrolls), search_runtime_handle, timeout);
+        let scrolls_f = timed(self.query_scroll_batch(
+            Arc::new(request.scrolls),
+            search_runtime_handle,
+            timeout,
+            explain,
+        ));
 
         // execute both searches and scrolls concurrently
-        let (search_results, scroll_results) = tokio::try_join!(searches_f, scrolls_f)?;
-        let prefetch_holder = PrefetchResults::new(search_results, scroll_results);
+        let (
+            ((search_results, search_segments), search_time),
+            ((scroll_results, scroll_segments), scroll_time),
+        ) = tokio::try_join!(searches_f, scrolls_f)?;
+        let prefetch_holder =
+            PrefetchResults::new(search_results, scroll_results, search_orders, scroll_orders);
+
+        let mut profiles = Vec::new();
+        if has_searches {
+            profiles.push(
+                QueryProfile::new(ProfileStage::Scoring, search_time)
+                    .with_segments(search_segments),
+            );
+        }
+        if has_scrolls {
+            profiles.push(
+                QueryProfile::new(ProfileStage::Filtering, scroll_time)
+                    .with_segments(scroll_segments),
+            );
+        }
 
         // decrease timeout by the time spent so far
         let timeout = timeout.saturating_sub(start_time.elapsed());
@@ -80,15 +149,25 @@ impl LocalShard {
             self.recurse_prefetch(
                 merge_plan,
                 &prefetch_holder,
+                &collection_params,
                 search_runtime_handle,
                 timeout,
                 0,
+                explain,
             )
         });
 
-        let batched_scored_points = futures::future::try_join_all(merge_futures).await?;
+        let (batched_scored_points, plan_profiles): (Vec<_>, Vec<_>) =
+            futures::future::try_join_all(merge_futures)
+                .await?
+                .into_iter()
+                .unzip();
+        profiles.extend(plan_profiles.into_iter().flatten());
+
+        let profile =
+            QueryProfile::new(ProfileStage::Local, start_time.elapsed()).with_children(profiles);
 
-        Ok(batched_scored_points)
+        Ok((batched_scored_points, profile))
     }
 
     /// Fetches the payload and/or vector if required. This will filter out points if they are deleted between search and retrieve.
@@ -136,61 +215,88 @@ impl LocalShard {
         &'shard self,
         merge_plan: MergePlan,
         prefetch_holder: &'query PrefetchResults,
+        collection_params: &'query CollectionParams,
         search_runtime_handle: &'shard Handle,
         timeout: Duration,
         depth: usize,
-    ) -> BoxFuture<'query, CollectionResult<Vec<Vec<ScoredPoint>>>>
+        explain: bool,
+    ) -> BoxFuture<'query, CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<QueryProfile>)>>
     where
         'shard: 'query,
     {
         async move {
             let max_len = merge_plan.sources.len();
             let mut cow_sources = Vec::with_capacity(max_len);
+            let mut sources_order = Vec::with_capacity(max_len);
+            let mut profiles = Vec::new();
 
             // We need to preserve the order of the sources for some fusion strategies
             for source in merge_plan.sources.into_iter() {
                 match source {
                     Source::SearchesIdx(idx) => {
-                        cow_sources.push(prefetch_holder.get(FetchedSource::Search(idx))?)
+                        let (points, order) = prefetch_holder.get(FetchedSource::Search(idx))?;
+                        cow_sources.push(points);
+                        sources_order.push(order);
                     }
                     Source::ScrollsIdx(idx) => {
-                        cow_sources.push(prefetch_holder.get(FetchedSource::Scroll(idx))?)
+                        let (points, order) = prefetch_holder.get(FetchedSource::Scroll(idx))?;
+                        cow_sources.push(points);
+                        sources_order.push(order);
                     }
                     Source::Prefetch(prefetch) => {
-                        let merged = self
+                        let prefetch_timer = Instant::now();
+                        let order = ScoringQuery::order(
+                            prefetch
+                                .rescore_params
+                                .as_ref()
+                                .map(|rescore_params| &rescore_params.rescore),
+                            collection_params,
+                        )?;
+                        let (merged, prefetch_profiles) = self
                             .recurse_prefetch(
                                 prefetch,
                                 prefetch_holder,
+                                collection_params,
                                 search_runtime_handle,
                                 timeout,
                                 depth + 1,
+                                explain,
                             )
-                            .await?
-                            .into_iter()
-                            .map(Cow::Owned);
-                        cow_sources.extend(merged);
+                            .await?;
+                        sources_order.extend(merged.iter().map(|_| order));
+                        cow_sources.extend(merged.into_iter().map(Cow::Owned));
+                        profiles.push(
+                            QueryProfile::new(ProfileStage::Prefetch, prefetch_timer.elapsed())
+                                .with_children(prefetch_profiles),
+                        );
                     }
                 }
             }
 
             // Rescore or return plain sources
             if let Some(rescore_params) = merge_plan.rescore_params {
-                let rescored = self
+                let (rescored, rescore_profile) = self
                     .rescore(
                         cow_sources.into_iter(),
+                        &sources_order,
                         rescore_params,
                         search_runtime_handle,
                         timeout,
+                        explain,
                     )
                     .await?;
+                profiles.push(rescore_profile);
 
-                Ok(vec![rescored])
+                Ok((vec![rescored], profiles))
             } else {
                 // The sources here are passed to the next layer without any extra processing.
                 // It is either a query without prefetches, or a fusion request and the intermediate results are passed to the next layer.
                 debug_assert_eq!(depth, 0);
                 // TODO(universal-query): maybe there's a way to pass ownership of the prefetch_holder to avoid cloning with Cow::into_owned here
-                Ok(cow_sources.into_iter().map(Cow::into_owned).collect())
+                Ok((
+                    cow_sources.into_iter().map(Cow::into_owned).collect(),
+                    profiles,
+                ))
             }
         }
         .boxed()
@@ -200,10 +306,13 @@ impl LocalShard {
     async fn rescore<'a>(
         &self,
         sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
+        sources_order: &[Order],
         rescore_params: RescoreParams,
         search_runtime_handle: &Handle,
         timeout: Duration,
-    ) -> CollectionResult<Vec<ScoredPoint>> {
+        explain: bool,
+    ) -> CollectionResult<(Vec<ScoredPoint>, QueryProfile)> {
+        let rescore_timer = Instant::now();
         let RescoreParams {
             rescore,
             score_threshold,
@@ -213,26 +322,32 @@ impl LocalShard {
         } = rescore_params;
 
         match rescore {
-            ScoringQuery::Fusion(Fusion::Rrf) => {
+            ScoringQuery::Fusion(fusion) => {
                 let sources: Vec<_> = sources.map(Cow::into_owned).collect();
 
-                let top_rrf = rrf_scoring(sources);
+                let fused = fusion.fuse(sources, sources_order);
 
-                let top_rrf: Vec<_> = if let Some(score_threshold) = score_threshold {
-                    top_rrf
+                let top_fused: Vec<_> = if let Some(score_threshold) = score_threshold {
+                    fused
                         .into_iter()
                         .take_while(|point| point.score >= score_threshold)
                         .take(limit)
                         .collect()
                 } else {
-                    top_rrf.into_iter().take(limit).collect()
+                    fused.into_iter().take(limit).collect()
                 };
 
-                let filled_top_rrf = self
-                    .fill_with_payload_or_vectors(top_rrf, with_payload, with_vector)
+                let fetching_timer = Instant::now();
+                let filled_top_fused = self
+                    .fill_with_payload_or_vectors(top_fused, with_payload, with_vector)
                     .await?;
+                let fetching_profile =
+                    QueryProfile::new(ProfileStage::Fetching, fetching_timer.elapsed());
+
+                let profile = QueryProfile::new(ProfileStage::Fusion, rescore_timer.elapsed())
+                    .with_children(vec![fetching_profile]);
 
-                Ok(filled_top_rrf)
+                Ok((filled_top_fused, profile))
             }
             ScoringQuery::OrderBy(order_by) => {
                 // create single scroll request for rescoring query
@@ -245,21 +360,59 @@ impl LocalShard {
                     filter: Some(filter),
                     with_payload,
                     with_vector,
-                    order_by: Some(OrderByInterface::Struct(order_by)),
+                    scroll_order: ScrollOrder::ByField(order_by),
                 };
 
-                self.query_scroll_batch(
-                    Arc::new(vec![scroll_request]),
-                    search_runtime_handle,
-                    timeout,
-                )
-                .await?
-                .pop()
-                .ok_or_else(|| {
+                let (mut rescored, segments) = self
+                    .query_scroll_batch(
+                        Arc::new(vec![scroll_request]),
+                        search_runtime_handle,
+                        timeout,
+                        explain,
+                    )
+                    .await?;
+                let rescored = rescored.pop().ok_or_else(|| {
                     CollectionError::service_error(
                         "Rescoring with order-by query didn't return expected batch of results",
                     )
-                })
+                })?;
+
+                let profile = QueryProfile::new(ProfileStage::Rescoring, rescore_timer.elapsed())
+                    .with_segments(segments);
+
+                Ok((rescored, profile))
+            }
+            ScoringQuery::Sample(Sample::Random) => {
+                // create single random scroll request over the points of the sources
+                let filter = filter_with_sources_ids(sources);
+
+                // Note: score_threshold is not used in this case, as scores are sampling keys
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload,
+                    with_vector,
+                    scroll_order: ScrollOrder::Random,
+                };
+
+                let (mut sampled, segments) = self
+                    .query_scroll_batch(
+                        Arc::new(vec![scroll_request]),
+                        search_runtime_handle,
+                        timeout,
+                        explain,
+                    )
+                    .await?;
+                let sampled = sampled.pop().ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with sample query didn't return expected batch of results",
+                    )
+                })?;
+
+                let profile = QueryProfile::new(ProfileStage::Rescoring, rescore_timer.elapsed())
+                    .with_segments(segments);
+
+                Ok((sampled, profile))
             }
             ScoringQuery::Vector(query_enum) => {
                 // create single search request for rescoring query
@@ -280,22 +433,154 @@ impl LocalShard {
                     searches: vec![search_request],
                 };
 
-                self.do_search(
-                    Arc::new(rescoring_core_search_request),
-                    search_runtime_handle,
-                    Some(timeout),
-                )
-                .await?
+                let (mut rescored, segments) = self
+                    .do_search_explained(
+                        Arc::new(rescoring_core_search_request),
+                        search_runtime_handle,
+                        Some(timeout),
+                        explain,
+                    )
+                    .await?;
                 // One search request is sent. We expect only one result
-                .pop()
-                .ok_or_else(|| {
+                let rescored = rescored.pop().ok_or_else(|| {
                     CollectionError::service_error(
                         "Rescoring with vector(s) query didn't return expected batch of results",
                     )
-                })
+                })?;
+
+                let profile = QueryProfile::new(ProfileStage::Rescoring, rescore_timer.elapsed())
+                    .with_segments(segments);
+
+                Ok((rescored, profile))
+            }
+            ScoringQuery::Formula(formula) => {
+                let rescored = self
+                    .rescore_with_formula(sources, formula, search_runtime_handle)
+                    .await?;
+
+                let top_rescored: Vec<_> = if let Some(score_threshold) = score_threshold {
+                    rescored
+                        .into_iter()
+                        .take_while(|point| point.score >= score_threshold)
+                        .take(limit)
+                        .collect()
+                } else {
+                    rescored.into_iter().take(limit).collect()
+                };
+
+                let fetching_timer = Instant::now();
+                let filled_top_rescored = self
+                    .fill_with_payload_or_vectors(top_rescored, with_payload, with_vector)
+                    .await?;
+                let fetching_profile =
+                    QueryProfile::new(ProfileStage::Fetching, fetching_timer.elapsed());
+
+                let profile = QueryProfile::new(ProfileStage::Rescoring, rescore_timer.elapsed())
+                    .with_children(vec![fetching_profile]);
+
+                Ok((filled_top_rescored, profile))
+            }
+            ScoringQuery::Mmr(mmr) => {
+                let candidates: Vec<_> = sources.flat_map(Cow::into_owned).collect();
+
+                let vector_name = mmr.get_vector_name().to_string();
+                let distance = self
+                    .collection_config
+                    .read()
+                    .await
+                    .params
+                    .get_distance(&vector_name)?;
+
+                // Candidates need the vector of the query to compute their similarities
+                let candidates = self
+                    .fill_with_payload_or_vectors(
+                        candidates,
+                        WithPayloadInterface::Bool(false),
+                        WithVector::Selector(vec![vector_name]),
+                    )
+                    .await?;
+
+                let mut selected = mmr.select(candidates, distance, limit)?;
+                selected.iter_mut().for_each(|point| point.vector = None);
+
+                let fetching_timer = Instant::now();
+                let filled_selected = self
+                    .fill_with_payload_or_vectors(selected, with_payload, with_vector)
+                    .await?;
+                let fetching_profile =
+                    QueryProfile::new(ProfileStage::Fetching, fetching_timer.elapsed());
+
+                let profile = QueryProfile::new(ProfileStage::Rescoring, rescore_timer.elapsed())
+                    .with_children(vec![fetching_profile]);
+
+                Ok((filled_selected, profile))
             }
         }
     }
+
+    /// Score the points of the prefetches with a formula, from the best to the worst point
+    async fn rescore_with_formula<'a>(
+        &self,
+        sources: impl Iterator<Item = Cow<'a, Vec<ScoredPoint>>>,
+        formula: FormulaQuery,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let prefetches_scores: Vec<HashMap<PointIdType, ScoreType>> = sources
+            .map(|source| source.iter().map(|point| (point.id, point.score)).collect())
+            .collect();
+
+        let formula = Arc::new(formula);
+        let prefetches_scores = Arc::new(prefetches_scores);
+
+        let (non_appendable, appendable) = self.segments().read().split_segments();
+
+        let rescore_segment = |segment: LockedSegment| {
+            let formula = formula.clone();
+            let prefetches_scores = prefetches_scores.clone();
+
+            search_runtime_handle.spawn_blocking(move || {
+                segment
+                    .get()
+                    .read()
+                    .rescore_with_formula(&formula, &prefetches_scores)
+            })
+        };
+
+        let non_appendable = try_join_all(non_appendable.into_iter().map(rescore_segment)).await?;
+        let appendable = try_join_all(appendable.into_iter().map(rescore_segment)).await?;
+
+        let all_rescored = non_appendable
+            .into_iter()
+            .chain(appendable)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A point may be present in several segments during optimization, keep the latest version
+        let mut latest: HashMap<PointIdType, ScoredPoint> = HashMap::new();
+        for point in all_rescored.into_iter().flatten() {
+            match latest.get(&point.id) {
+                Some(existing) if existing.version >= point.version => {}
+                _ => {
+                    latest.insert(point.id, point);
+                }
+            }
+        }
+
+        let rescored = latest
+            .into_values()
+            .sorted_unstable_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.id.cmp(&b.id)))
+            .collect();
+
+        Ok(rescored)
+    }
+}
+
+/// Await the future, measuring the time it took
+async fn timed<T>(
+    future: impl Future<Output = CollectionResult<T>>,
+) -> CollectionResult<(T, Duration)> {
+    let timer = Instant::now();
+    let result = future.await?;
+    Ok((result, timer.elapsed()))
 }
 
 /// Extracts point ids from sources, and creates a filter to only include those ids.
@@ -313,3 +598,12 @@ fn filter_with_sources_ids<'a>(sources: impl Iterator<Item = Cow<'a, Vec<ScoredP
         point_ids,
     )))
 }
+
+/// Order of the scores of a scroll, same as of the equivalent scoring query
+fn scroll_order(scroll_order: &ScrollOrder) -> Order {
+    match scroll_order {
+        ScrollOrder::ById => Order::SmallBetter,
+        ScrollOrder::ByField(order_by) => Order::from(order_by.direction()),
+        ScrollOrder::Random => Order::LargeBetter,
+    }
+}
@@ -1,9 +1,13 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use common::types::ScoreType;
 use futures::future::try_join_all;
 use itertools::Itertools as _;
-use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
+use segment::common::operation_error::OperationResult;
+use segment::data_types::explain::{segment_name, SearchStrategy, SearchTrace, SegmentExplanation};
+use segment::data_types::order_by::{OrderBy, OrderValue};
+use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -13,32 +17,40 @@ use super::LocalShard;
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
-    CollectionError, CollectionResult, QueryScrollRequestInternal, Record,
+    CollectionError, CollectionResult, QueryScrollRequestInternal, Record, ScrollOrder,
 };
 
 impl LocalShard {
     /// Basic parallel batching, it is conveniently used for the universal query API.
+    ///
+    /// If `explain` is set, also returns how each segment has executed the scrolls.
     pub(super) async fn query_scroll_batch(
         &self,
         batch: Arc<Vec<QueryScrollRequestInternal>>,
         search_runtime_handle: &Handle,
         timeout: Duration,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        explain: bool,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentExplanation>)> {
         let scrolls = batch
             .iter()
-            .map(|request| self.query_scroll(request, search_runtime_handle));
+            .map(|request| self.query_scroll(request, search_runtime_handle, explain));
 
         // execute all the scrolls concurrently
         let all_scroll_results = try_join_all(scrolls);
-        tokio::time::timeout(timeout, all_scroll_results)
-            .await
-            .map_err(|_| {
-                log::debug!(
-                    "Query scroll timeout reached: {} seconds",
-                    timeout.as_secs()
-                );
-                CollectionError::timeout(timeout.as_secs() as usize, "Query scroll")
-            })?
+        let (results, explanations): (Vec<_>, Vec<_>) =
+            tokio::time::timeout(timeout, all_scroll_results)
+                .await
+                .map_err(|_| {
+                    log::debug!(
+                        "Query scroll timeout reached: {} seconds",
+                        timeout.as_secs()
+                    );
+                    CollectionError::timeout(timeout.as_secs() as usize, "Query scroll")
+                })??
+                .into_iter()
+                .unzip();
+
+        Ok((results, explanations.into_iter().flatten().collect()))
     }
 
     /// Scroll a single page, to be used for the universal query API only.
@@ -46,12 +58,13 @@ impl LocalShard {
         &self,
         request: &QueryScrollRequestInternal,
         search_runtime_handle: &Handle,
-    ) -> CollectionResult<Vec<ScoredPoint>> {
+        explain: bool,
+    ) -> CollectionResult<(Vec<ScoredPoint>, Vec<SegmentExplanation>)> {
         let QueryScrollRequestInternal {
             limit,
             with_vector,
             filter,
-            order_by,
+            scroll_order,
             with_payload,
         } = request;
 
@@ -59,43 +72,48 @@ impl LocalShard {
 
         let offset_id = None;
 
-        let order_by = order_by.clone().map(OrderBy::from);
-
-        let point_results = match order_by {
-            None => self
-                .scroll_by_id(
-                    offset_id,
-                    limit,
-                    with_payload,
-                    with_vector,
-                    filter.as_ref(),
-                    search_runtime_handle,
-                )
-                .await?
-                .into_iter()
-                .map(|record| ScoredPoint {
-                    id: record.id,
-                    version: 0,
-                    score: 0.0,
-                    payload: record.payload,
-                    vector: record.vector,
-                    shard_key: record.shard_key,
-                    order_value: None,
-                })
-                .collect(),
-            Some(order_by) => {
-                let (records, values) = self
+        let point_results = match scroll_order {
+            ScrollOrder::ById => {
+                let (records, explanations) = self
+                    .scroll_by_id(
+                        offset_id,
+                        limit,
+                        with_payload,
+                        with_vector,
+                        filter.as_ref(),
+                        search_runtime_handle,
+                        explain,
+                    )
+                    .await?;
+
+                let points = records
+                    .into_iter()
+                    .map(|record| ScoredPoint {
+                        id: record.id,
+                        version: 0,
+                        score: 0.0,
+                        payload: record.payload,
+                        vector: record.vector,
+                        shard_key: record.shard_key,
+                        order_value: None,
+                    })
+                    .collect();
+                (points, explanations)
+            }
+            ScrollOrder::ByField(order_by) => {
+                let (records, values, explanations) = self
                     .scroll_by_field(
                         limit,
                         with_payload,
                         with_vector,
                         filter.as_ref(),
                         search_runtime_handle,
-                        &order_by,
+                        order_by,
+                        explain,
                     )
                     .await?;
 
-                records
+                let points = records
                     .into_iter()
                     .zip(values)
                     .map(|(record, value)| ScoredPoint {
@@ -107,13 +125,42 @@ impl LocalShard {
                         shard_key: record.shard_key,
                         order_value: Some(value),
                     })
-                    .collect()
+                    .collect();
+                (points, explanations)
+            }
+            ScrollOrder::Random => {
+                let (records, keys, explanations) = self
+                    .scroll_randomly(
+                        limit,
+                        with_payload,
+                        with_vector,
+                        filter.as_ref(),
+                        search_runtime_handle,
+                        explain,
+                    )
+                    .await?;
+
+                let points = records
+                    .into_iter()
+                    .zip(keys)
+                    .map(|(record, key)| ScoredPoint {
+                        id: record.id,
+                        version: 0,
+                        score: key,
+                        payload: record.payload,
+                        vector: record.vector,
+                        shard_key: record.shard_key,
+                        order_value: None,
+                    })
+                    .collect();
+                (points, explanations)
             }
         };
 
         Ok(point_results)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn scroll_by_id(
         &self,
         offset: Option<ExtendedPointId>,
@@ -122,7 +169,8 @@ impl LocalShard {
         with_vector: &WithVector,
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
-    ) -> CollectionResult<Vec<Record>> {
+        explain: bool,
+    ) -> CollectionResult<(Vec<Record>, Vec<SegmentExplanation>)> {
         let segments = self.segments();
 
         let (non_appendable, appendable) = segments.read().split_segments();
@@ -131,19 +179,26 @@ impl LocalShard {
             let filter = filter.cloned();
 
             search_runtime_handle.spawn_blocking(move || {
-                segment
-                    .get()
-                    .read()
-                    .read_filtered(offset, Some(limit), filter.as_ref())
+                let segment = segment.get();
+                let segment = segment.read();
+                read_explained(&*segment, filter.as_ref(), explain, || {
+                    segment.read_filtered(offset, Some(limit), filter.as_ref())
+                })
             })
         };
 
         let non_appendable = try_join_all(non_appendable.into_iter().map(read_filtered)).await?;
         let appendable = try_join_all(appendable.into_iter().map(read_filtered)).await?;
 
-        let point_ids = non_appendable
+        let (all_reads, explanations): (Vec<_>, Vec<_>) = non_appendable
             .into_iter()
             .chain(appendable)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        let point_ids = all_reads
+            .into_iter()
             .flatten()
             .sorted()
             .dedup()
@@ -159,9 +214,13 @@ impl LocalShard {
             .filter_map(|point| records_map.get(point).cloned())
             .collect();
 
-        Ok(ordered_records)
+        Ok((
+            ordered_records,
+            explanations.into_iter().flatten().collect(),
+        ))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn scroll_by_field(
         &self,
         limit: usize,
@@ -170,7 +229,8 @@ impl LocalShard {
         filter: Option<&Filter>,
         search_runtime_handle: &Handle,
         order_by: &OrderBy,
-    ) -> CollectionResult<(Vec<Record>, Vec<OrderValue>)> {
+        explain: bool,
+    ) -> CollectionResult<(Vec<Record>, Vec<OrderValue>, Vec<SegmentExplanation>)> {
         let segments = self.segments();
 
         let (non_appendable, appendable) = segments.read().split_segments();
@@ -180,10 +240,11 @@ impl LocalShard {
             let order_by = order_by.clone();
 
             search_runtime_handle.spawn_blocking(move || {
-                segment
-                    .get()
-                    .read()
-                    .read_ordered_filtered(Some(limit), filter.as_ref(), &order_by)
+                let segment = segment.get();
+                let segment = segment.read();
+                read_explained(&*segment, filter.as_ref(), explain, || {
+                    segment.read_ordered_filtered(Some(limit), filter.as_ref(), &order_by)
+                })
             })
         };
 
@@ -191,16 +252,19 @@ impl LocalShard {
             try_join_all(non_appendable.into_iter().map(read_ordered_filtered)).await?;
         let appendable = try_join_all(appendable.into_iter().map(read_ordered_filtered)).await?;
 
-        let all_reads = non_appendable
+        let (all_reads, explanations): (Vec<_>, Vec<_>) = non_appendable
             .into_iter()
             .chain(appendable)
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
 
         let (values, point_ids): (Vec<_>, Vec<_>) = all_reads
             .into_iter()
-            .kmerge_by(|a, b| match order_by.direction() {
-                Direction::Asc => a <= b,
-                Direction::Desc => a >= b,
+            .kmerge_by(|(value_a, id_a), (value_b, id_b)| {
+                order_by
+                    .cmp_points((value_a, *id_a), (value_b, *id_b))
+                    .is_le()
             })
             .dedup()
             .take(limit)
@@ -217,6 +281,108 @@ impl LocalShard {
             .filter_map(|point| records_map.get(point).cloned())
             .collect();
 
-        Ok((ordered_records, values))
+        Ok((
+            ordered_records,
+            values,
+            explanations.into_iter().flatten().collect(),
+        ))
+    }
+
+    /// Uniformly random sample of the points, along with their sampling keys in descending order
+    pub async fn scroll_randomly(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        explain: bool,
+    ) -> CollectionResult<(Vec<Record>, Vec<ScoreType>, Vec<SegmentExplanation>)> {
+        let segments = self.segments();
+
+        let (non_appendable, appendable) = segments.read().split_segments();
+
+        let read_random_filtered = |segment: LockedSegment| {
+            let filter = filter.cloned();
+
+            search_runtime_handle.spawn_blocking(move || {
+                let segment = segment.get();
+                let segment = segment.read();
+                read_explained(&*segment, filter.as_ref(), explain, || {
+                    segment.read_random_filtered(limit, filter.as_ref())
+                })
+            })
+        };
+
+        let non_appendable =
+            try_join_all(non_appendable.into_iter().map(read_random_filtered)).await?;
+        let appendable = try_join_all(appendable.into_iter().map(read_random_filtered)).await?;
+
+        let (all_reads, explanations): (Vec<_>, Vec<_>) = non_appendable
+            .into_iter()
+            .chain(appendable)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        // Samples of the segments are merged by the largest keys
+        let (keys, point_ids): (Vec<_>, Vec<_>) = all_reads
+            .into_iter()
+            .kmerge_by(|a, b| a.0 >= b.0)
+            .unique_by(|(_, point_id)| *point_id)
+            .take(limit)
+            .unzip();
+
+        let with_payload = WithPayload::from(with_payload_interface);
+
+        // Fetch with the requested vector and payload
+        let records_map =
+            SegmentsSearcher::retrieve(segments, &point_ids, &with_payload, with_vector)?;
+
+        let (ordered_records, keys) = point_ids
+            .iter()
+            .zip(keys)
+            .filter_map(|(point, key)| records_map.get(point).map(|record| (record.clone(), key)))
+            .unzip();
+
+        Ok((
+            ordered_records,
+            keys,
+            explanations.into_iter().flatten().collect(),
+        ))
+    }
+}
+
+/// Read the points of the segment, and also explain the read, if `explain` is set.
+///
+/// The strategy is derived from the estimated cardinality of the filter, same as for `count`.
+fn read_explained<T>(
+    segment: &dyn SegmentEntry,
+    filter: Option<&Filter>,
+    explain: bool,
+    read: impl FnOnce() -> OperationResult<T>,
+) -> OperationResult<(T, Option<SegmentExplanation>)> {
+    if !explain {
+        return Ok((read()?, None));
     }
+
+    let timer = Instant::now();
+    let mut trace = SearchTrace::default();
+
+    let cardinality = segment.estimate_point_count(filter)?;
+    if filter.is_some() {
+        trace.record_estimation(&cardinality, timer.elapsed());
+    }
+    trace.strategy = Some(SearchStrategy::filtered_scan(&cardinality));
+
+    let result = read()?;
+
+    let explanation = trace.into_explanation(
+        segment_name(&segment.data_path()),
+        segment.segment_type(),
+        segment.available_point_count(),
+        None,
+        timer.elapsed(),
+    );
+    Ok((result, Some(explanation)))
 }
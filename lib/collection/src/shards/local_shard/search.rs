@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use segment::data_types::explain::SegmentExplanation;
 use segment::types::ScoredPoint;
 use tokio::runtime::Handle;
 
@@ -17,6 +18,21 @@ impl LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let (results, _explanations) = self
+            .do_search_explained(core_request, search_runtime_handle, timeout, false)
+            .await?;
+        Ok(results)
+    }
+
+    /// Same as [`LocalShard::do_search`], but if `explain` is set, also returns how each segment
+    /// has executed the searches
+    pub async fn do_search_explained(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<SegmentExplanation>)> {
         let is_stopped_guard = StoppingGuard::new();
 
         let (query_context, collection_params) = {
@@ -32,10 +48,13 @@ impl LocalShard {
 
             let Some(query_context) = query_context_opt else {
                 // No segments to search
-                return Ok(vec![]);
+                return Ok((vec![], vec![]));
             };
 
-            (query_context, collection_config.params.clone())
+            (
+                Arc::new(query_context.with_explain(explain)),
+                collection_config.params.clone(),
+            )
         };
 
         let search_request = SegmentsSearcher::search(
@@ -43,7 +62,7 @@ impl LocalShard {
             Arc::clone(&core_request),
             search_runtime_handle,
             true,
-            query_context,
+            Arc::clone(&query_context),
         );
 
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
@@ -69,6 +88,8 @@ impl LocalShard {
                         }
                         // Don't post-process if we are dealing with custom scoring
                         QueryEnum::RecommendBestScore(_)
+                        | QueryEnum::RecommendSumScores(_)
+                        | QueryEnum::RecommendContrastive(_)
                         | QueryEnum::Discover(_)
                         | QueryEnum::Context(_) => {}
                     };
@@ -86,6 +107,6 @@ impl LocalShard {
                 }
             })
             .collect();
-        Ok(top_results)
+        Ok((top_results, query_context.take_explanations()))
     }
 }
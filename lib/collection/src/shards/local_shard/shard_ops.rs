@@ -2,7 +2,11 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
+use segment::data_types::distinct::{CardinalitySketch, DistinctParams};
+use segment::data_types::facets::merge_facet_counts;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -12,7 +16,8 @@ use tokio::sync::oneshot;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResult, PointRequestInternal,
+    Record, UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::planned_query::PlannedQuery;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
@@ -116,18 +121,21 @@ impl ShardOperation for LocalShard {
     ) -> CollectionResult<Vec<Record>> {
         match order_by {
             None => {
-                self.scroll_by_id(
-                    offset,
-                    limit,
-                    with_payload_interface,
-                    with_vector,
-                    filter,
-                    search_runtime_handle,
-                )
-                .await
+                let (records, _) = self
+                    .scroll_by_id(
+                        offset,
+                        limit,
+                        with_payload_interface,
+                        with_vector,
+                        filter,
+                        search_runtime_handle,
+                        false,
+                    )
+                    .await?;
+                Ok(records)
             }
             Some(order_by) => {
-                let (mut records, values) = self
+                let (mut records, values, _) = self
                     .scroll_by_field(
                         limit,
                         with_payload_interface,
@@ -135,14 +143,17 @@ impl ShardOperation for LocalShard {
                         filter,
                         search_runtime_handle,
                         order_by,
+                        false,
                     )
                     .await?;
 
                 records.iter_mut().zip(values).for_each(|(record, value)| {
                     // TODO(1.11): stop inserting the value in the payload, only use the order_value
                     // Add order_by value to the payload. It will be removed in the next step, after crossing the shard boundary.
-                    let new_payload =
-                        OrderBy::insert_order_value_in_payload(record.payload.take(), value);
+                    let new_payload = OrderBy::insert_order_value_in_payload(
+                        record.payload.take(),
+                        value.clone(),
+                    );
 
                     record.payload = Some(new_payload);
                     record.order_value = Some(value);
@@ -169,13 +180,92 @@ impl ShardOperation for LocalShard {
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
-        let total_count = if request.exact {
+        let mut segment_explanations = None;
+        let total_count = if request.explain.unwrap_or_default() {
+            let (count, explanations) =
+                self.explain_count(request.filter.as_ref(), request.exact)?;
+            segment_explanations = Some(explanations);
+            count
+        } else if request.exact {
             let all_points = self.read_filtered(request.filter.as_ref())?;
             all_points.len()
         } else {
             self.estimate_cardinality(request.filter.as_ref())?.exp
         };
-        Ok(CountResult { count: total_count })
+
+        let mut result = CountResult {
+            count: total_count,
+            segments: segment_explanations,
+            ..Default::default()
+        };
+
+        let segments = self.segments().read();
+
+        if let Some(params) = request.group_by_params() {
+            // In approximate mode only the most frequent values of each segment are merged
+            let segment_limit = (!params.exact).then_some(params.limit);
+
+            let mut counts = Vec::new();
+            for segment in segments.non_appendable_then_appendable_segments() {
+                let segment_counts = segment.get().read().facet(&params)?;
+                counts.extend(
+                    merge_facet_counts(segment_counts, segment_limit)
+                        .into_iter()
+                        .map(|hit| (hit.value, hit.count)),
+                );
+            }
+            result.groups = Some(merge_facet_counts(counts, segment_limit));
+        }
+
+        if let Some(distinct) = &request.distinct {
+            let params = DistinctParams {
+                key: distinct.clone(),
+                filter: request.filter.clone(),
+            };
+            let mut sketch = CardinalitySketch::default();
+            for segment in segments.non_appendable_then_appendable_segments() {
+                sketch.merge(segment.get().read().distinct(&params)?);
+            }
+            result.distinct = Some(sketch);
+        }
+
+        Ok(result)
+    }
+
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        let params = request.facet_params();
+
+        // In approximate mode only the most frequent values of each segment are merged
+        let segment_limit = (!params.exact).then_some(params.limit);
+
+        let mut counts = Vec::new();
+        {
+            let segments = self.segments().read();
+            for segment in segments.non_appendable_then_appendable_segments() {
+                let segment_counts = segment.get().read().facet(&params)?;
+                counts.extend(
+                    merge_facet_counts(segment_counts, segment_limit)
+                        .into_iter()
+                        .map(|hit| (hit.value, hit.count)),
+                );
+            }
+        }
+
+        Ok(FacetResult {
+            hits: merge_facet_counts(counts, segment_limit),
+        })
+    }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation> {
+        let mut aggregation = NumericAggregation::default();
+        let segments = self.segments().read();
+        for segment in segments.non_appendable_then_appendable_segments() {
+            aggregation.merge(segment.get().read().aggregate(&request)?);
+        }
+        Ok(aggregation)
     }
 
     async fn retrieve(
@@ -204,7 +294,23 @@ impl ShardOperation for LocalShard {
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let planned_query = PlannedQuery::try_from(requests.as_ref().to_owned())?;
 
-        self.do_planned_query(planned_query, search_runtime_handle, timeout)
+        let (responses, _profile) = self
+            .do_planned_query(planned_query, search_runtime_handle, timeout, false)
+            .await?;
+
+        Ok(responses)
+    }
+
+    async fn query_batch_profiled(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let planned_query = PlannedQuery::try_from(requests.as_ref().to_owned())?;
+
+        self.do_planned_query(planned_query, search_runtime_handle, timeout, explain)
             .await
     }
 }
@@ -6,7 +6,9 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
@@ -21,7 +23,8 @@ use crate::operations::operation_effect::{
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResult, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
@@ -234,6 +237,21 @@ impl ShardOperation for ProxyShard {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    /// Forward read-only `aggregate` to `wrapped_shard`
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.aggregate(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -259,4 +277,18 @@ impl ShardOperation for ProxyShard {
             .query_batch(request, search_runtime_handle, timeout)
             .await
     }
+
+    /// Forward read-only `query_batch_profiled` to `wrapped_shard`
+    async fn query_batch_profiled(
+        &self,
+        request: Arc<Vec<ShardQueryRequest>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .query_batch_profiled(request, search_runtime_handle, timeout, explain)
+            .await
+    }
 }
@@ -6,7 +6,9 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -20,7 +22,8 @@ use super::update_tracker::UpdateTracker;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResult, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
@@ -278,6 +281,27 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .facet(request)
+            .await
+    }
+
+    /// Forward read-only `aggregate` to `wrapped_shard`
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .aggregate(request)
+            .await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -306,6 +330,22 @@ impl ShardOperation for QueueProxyShard {
             .query_batch(requests, search_runtime_handle, timeout)
             .await
     }
+
+    /// Forward read-only `query_batch_profiled` to `wrapped_shard`
+    async fn query_batch_profiled(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .query_batch_profiled(requests, search_runtime_handle, timeout, explain)
+            .await
+    }
 }
 
 // Safe guard in debug mode to ensure that `finalize()` is called before dropping
@@ -566,6 +606,21 @@ impl ShardOperation for Inner {
         local_shard.count(request).await
     }
 
+    /// Forward read-only `facet` to `wrapped_shard`
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.facet(request).await
+    }
+
+    /// Forward read-only `aggregate` to `wrapped_shard`
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.aggregate(request).await
+    }
+
     /// Forward read-only `retrieve` to `wrapped_shard`
     async fn retrieve(
         &self,
@@ -591,6 +646,20 @@ impl ShardOperation for Inner {
             .query_batch(request, search_runtime_handle, timeout)
             .await
     }
+
+    /// Forward read-only `query_batch_profiled` to `wrapped_shard`
+    async fn query_batch_profiled(
+        &self,
+        request: Arc<Vec<ShardQueryRequest>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .query_batch_profiled(request, search_runtime_handle, timeout, explain)
+            .await
+    }
 }
 
 /// Transfer batch of operations without retries
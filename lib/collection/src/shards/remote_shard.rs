@@ -1,7 +1,7 @@
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::collections_internal_client::CollectionsInternalClient;
 use api::grpc::qdrant::points_internal_client::PointsInternalClient;
@@ -9,12 +9,12 @@ use api::grpc::qdrant::qdrant_client::QdrantClient;
 use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
-    CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
-    QueryBatchPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest,
-    RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation,
-    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    AggregatePointsInternal, CollectionOperationResponse, CoreSearchBatchPointsInternal,
+    CountPoints, CountPointsInternal, FacetCounts, FacetCountsInternal, GetCollectionInfoRequest,
+    GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal, GetShardRecoveryPointRequest,
+    HealthCheckRequest, InitiateShardTransferRequest, QueryBatchPointsInternal, QueryShardPoints,
+    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
+    ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
@@ -24,7 +24,10 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
+use segment::data_types::facets::FacetValueHit;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::{ProfileStage, QueryProfile};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -45,7 +48,8 @@ use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, FacetRequestInternal, FacetResult, PointRequestInternal,
+    Record, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::vector_ops::VectorOperations;
@@ -290,7 +294,7 @@ impl RemoteShard {
                         filter,
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
                     })
@@ -358,7 +362,7 @@ impl RemoteShard {
                         vector_names.clone(),
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client
                             .delete_vectors(tonic::Request::new(request.clone()))
@@ -377,7 +381,7 @@ impl RemoteShard {
                         set_payload,
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client
                             .set_payload(tonic::Request::new(request.clone()))
@@ -394,7 +398,7 @@ impl RemoteShard {
                         delete_payload,
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client
                             .delete_payload(tonic::Request::new(request.clone()))
@@ -428,7 +432,7 @@ impl RemoteShard {
                         filter,
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client
                             .clear_payload(tonic::Request::new(request.clone()))
@@ -445,7 +449,7 @@ impl RemoteShard {
                         set_payload,
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client
                             .overwrite_payload(tonic::Request::new(request.clone()))
@@ -628,6 +632,75 @@ impl RemoteShard {
 
         Ok(())
     }
+
+    /// Send a batch of universal queries to the remote shard, optionally profiling its execution
+    async fn query_batch_remote(
+        &self,
+        requests: &[ShardQueryRequest],
+        timeout: Option<Duration>,
+        profile: bool,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, Option<QueryProfile>)> {
+        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
+        timer.set_success(false);
+
+        let batch_response = self
+            .with_points_client(|mut client| async move {
+                let query_points = requests
+                    .iter()
+                    .map(|request| QueryShardPoints::try_from(request.clone()))
+                    .collect::<Result<_, Status>>()?;
+
+                let request = &QueryBatchPointsInternal {
+                    collection_name: self.collection_id.clone(),
+                    query_points,
+                    shard_id: Some(self.id),
+                    timeout: timeout.map(|t| t.as_secs()),
+                    profile: profile.then_some(true),
+                    explain: explain.then_some(true),
+                };
+
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.query_batch(request).await
+            })
+            .await?
+            .into_inner();
+
+        let result = batch_response
+            .results
+            .into_iter()
+            .zip(requests.iter())
+            .map(|(query_result, request)| {
+                let is_payload_required = request.with_payload.is_required();
+
+                query_result
+                    .intermediate_results
+                    .into_iter()
+                    .map(|intermediate| {
+                        intermediate
+                            .result
+                            .into_iter()
+                            .map(|point| try_scored_point_from_grpc(point, is_payload_required))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .try_collect()?;
+
+        let profile = batch_response
+            .profile
+            .map(QueryProfile::try_from)
+            .transpose()?;
+
+        timer.set_success(true);
+
+        Ok((result, profile))
+    }
 }
 
 // New-type to own the type in the crate for conversions via From
@@ -663,7 +736,7 @@ impl ShardOperation for RemoteShard {
     ) -> CollectionResult<Vec<Record>> {
         let scroll_points = ScrollPoints {
             collection_name: self.collection_id.clone(),
-            filter: filter.map(|f| f.clone().into()),
+            filter: filter.map(|f| f.clone().try_into()).transpose()?,
             offset: offset.map(|o| o.into()),
             limit: Some(limit as u32),
             with_payload: Some(with_payload_interface.clone().into()),
@@ -726,8 +799,8 @@ impl ShardOperation for RemoteShard {
         let search_points = batch_request
             .searches
             .iter()
-            .map(|s| CollectionCoreSearchRequest((self.collection_id.clone(), s)).into())
-            .collect();
+            .map(|s| CollectionCoreSearchRequest((self.collection_id.clone(), s)).try_into())
+            .collect::<Result<_, Status>>()?;
 
         let request = &CoreSearchBatchPointsInternal {
             collection_name: self.collection_id.clone(),
@@ -775,11 +848,16 @@ impl ShardOperation for RemoteShard {
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
         let count_points = CountPoints {
             collection_name: self.collection_id.clone(),
-            filter: request.filter.clone().map(|f| f.into()),
+            filter: request.filter.clone().map(|f| f.try_into()).transpose()?,
             exact: Some(request.exact),
             read_consistency: None,
             shard_key_selector: None,
+            explain: request.explain,
+            group_by: request.group_by.as_ref().map(ToString::to_string),
+            distinct: request.distinct.as_ref().map(ToString::to_string),
+            group_by_limit: request.group_by_limit.map(|limit| limit as u64),
         };
+        let explain = request.explain.unwrap_or_default();
 
         let request = &CountPointsInternal {
             count_points: Some(count_points),
@@ -791,13 +869,85 @@ impl ShardOperation for RemoteShard {
             })
             .await?
             .into_inner();
-        count_response.result.map_or_else(
+        let mut count_result = count_response.result.map_or_else(
             || {
                 Err(CollectionError::service_error(
                     "Unexpected empty CountResult".to_string(),
                 ))
             },
-            |count_result| Ok(count_result.into()),
+            |count_result| Ok(CountResult::try_from(count_result)?),
+        )?;
+
+        // The remote peer explains only the requested replica of this shard
+        if explain {
+            let segments = count_response
+                .explain
+                .into_iter()
+                .flat_map(|shard| shard.segments)
+                .map(TryFrom::try_from)
+                .collect::<Result<_, Status>>()?;
+            count_result.segments = Some(segments);
+        }
+
+        Ok(count_result)
+    }
+
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult> {
+        let facet_counts = FacetCounts {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            filter: request.filter.clone().map(|f| f.try_into()).transpose()?,
+            limit: request.limit.map(|limit| limit as u64),
+            exact: Some(request.exact),
+            read_consistency: None,
+            shard_key_selector: None,
+        };
+
+        let request = &FacetCountsInternal {
+            facet_counts: Some(facet_counts),
+            shard_id: Some(self.id),
+        };
+        let facet_response = self
+            .with_points_client(|mut client| async move {
+                client.facet(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+
+        let hits = facet_response
+            .hits
+            .into_iter()
+            .map(FacetValueHit::try_from)
+            .collect::<Result<_, Status>>()?;
+
+        Ok(FacetResult { hits })
+    }
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation> {
+        let request = &AggregatePointsInternal {
+            collection_name: self.collection_id.clone(),
+            key: request.key.to_string(),
+            filter: request.filter.clone().map(|f| f.try_into()).transpose()?,
+            histogram: request.histogram.map(From::from),
+            shard_id: Some(self.id),
+            date_histogram: request.date_histogram.clone().map(From::from),
+        };
+        let aggregate_response = self
+            .with_points_client(|mut client| async move {
+                client.aggregate(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+        aggregate_response.result.map_or_else(
+            || {
+                Err(CollectionError::service_error(
+                    "Unexpected empty NumericAggregation".to_string(),
+                ))
+            },
+            |aggregation| Ok(aggregation.try_into()?),
         )
     }
 
@@ -842,59 +992,30 @@ impl ShardOperation for RemoteShard {
         _search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
-        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
-        timer.set_success(false);
-
-        let requests = requests.as_ref();
-
-        let batch_response = self
-            .with_points_client(|mut client| async move {
-                let query_points = requests
-                    .iter()
-                    .map(|request| QueryShardPoints::from(request.clone()))
-                    .collect();
-
-                let request = &QueryBatchPointsInternal {
-                    collection_name: self.collection_id.clone(),
-                    query_points,
-                    shard_id: Some(self.id),
-                    timeout: timeout.map(|t| t.as_secs()),
-                };
-
-                let mut request = tonic::Request::new(request.clone());
-
-                if let Some(timeout) = timeout {
-                    request.set_timeout(timeout);
-                }
+        let (result, _profile) = self
+            .query_batch_remote(requests.as_ref(), timeout, false, false)
+            .await?;
 
-                client.query_batch(request).await
-            })
-            .await?
-            .into_inner();
+        Ok(result)
+    }
 
-        let result = batch_response
-            .results
-            .into_iter()
-            .zip(requests.iter())
-            .map(|(query_result, request)| {
-                let is_payload_required = request.with_payload.is_required();
+    async fn query_batch_profiled(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let start_time = Instant::now();
 
-                query_result
-                    .intermediate_results
-                    .into_iter()
-                    .map(|intermediate| {
-                        intermediate
-                            .result
-                            .into_iter()
-                            .map(|point| try_scored_point_from_grpc(point, is_payload_required))
-                            .collect()
-                    })
-                    .collect()
-            })
-            .try_collect()?;
+        let (result, remote_profile) = self
+            .query_batch_remote(requests.as_ref(), timeout, true, explain)
+            .await?;
 
-        timer.set_success(true);
+        // Round trip time, with the breakdown of the time spent on the remote peer
+        let profile = QueryProfile::new(ProfileStage::Remote, start_time.elapsed())
+            .with_children(remote_profile.into_iter().collect());
 
-        Ok(result)
+        Ok((result, profile))
     }
 }
@@ -1,8 +1,11 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use futures::FutureExt as _;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
+use segment::data_types::explain::ShardExplanation;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::{ProfileStage, QueryProfile};
 use segment::types::*;
 
 use super::ShardReplicaSet;
@@ -83,10 +86,55 @@ impl ShardReplicaSet {
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
     ) -> CollectionResult<CountResult> {
+        let shard_id = self.shard_id;
         self.execute_and_resolve_read_operation(
             |shard| {
                 let request = request.clone();
-                async move { shard.count(request).await }.boxed()
+                async move {
+                    shard.count(request).await.map(|mut result| {
+                        if let Some(segments) = result.segments.take() {
+                            result
+                                .explanations
+                                .push(ShardExplanation { shard_id, segments });
+                        }
+                        result
+                    })
+                }
+                .boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn facet(
+        &self,
+        request: Arc<FacetRequestInternal>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+    ) -> CollectionResult<FacetResult> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.facet(request).await }.boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
+
+    pub async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+    ) -> CollectionResult<NumericAggregation> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let request = request.clone();
+                async move { shard.aggregate(request).await }.boxed()
             },
             read_consistency,
             local_only,
@@ -157,4 +205,40 @@ impl ShardReplicaSet {
         )
         .await
     }
+
+    pub async fn query_batch_profiled(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)> {
+        let start_time = Instant::now();
+
+        let (responses, replica_profiles) = self
+            .execute_and_resolve_read_operation(
+                |shard| {
+                    let requests = Arc::clone(&requests);
+                    let search_runtime = self.search_runtime.clone();
+
+                    async move {
+                        shard
+                            .query_batch_profiled(requests, &search_runtime, timeout, explain)
+                            .await
+                            .map(|(responses, profile)| (responses, vec![profile]))
+                    }
+                    .boxed()
+                },
+                read_consistency,
+                local_only,
+            )
+            .await?;
+
+        let profile = QueryProfile::new(ProfileStage::Shard, start_time.elapsed())
+            .with_shard_id(self.shard_id)
+            .with_children(replica_profiles);
+
+        Ok((responses, profile))
+    }
 }
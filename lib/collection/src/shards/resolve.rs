@@ -1,11 +1,15 @@
 use std::collections::{HashMap, HashSet};
 use std::hash;
 
+use segment::data_types::aggregations::NumericAggregation;
+use segment::data_types::distinct::CardinalitySketch;
+use segment::data_types::facets::{merge_facet_counts, FacetValue};
+use segment::data_types::profile::QueryProfile;
 use segment::types::{Payload, ScoredPoint};
 use tinyvec::TinyVec;
 
 use crate::common::transpose_iterator::transposed_iter;
-use crate::operations::types::{CountResult, Record};
+use crate::operations::types::{CountResult, FacetResult, Record};
 use crate::operations::universal_query::shard_query::ShardQueryResponse;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -20,26 +24,79 @@ pub trait Resolve: Sized {
 
 impl Resolve for CountResult {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
-        match condition {
-            ResolveCondition::All => Self {
-                count: records
-                    .iter()
-                    .map(|result| result.count)
-                    .min()
-                    .unwrap_or_default(),
-            },
-            ResolveCondition::Majority => {
-                let mut counts = records
-                    .iter()
-                    .map(|result| result.count)
-                    .collect::<Vec<_>>();
-                counts.sort_unstable();
-                let middle = counts.len() / 2;
-                Self {
-                    count: counts.get(middle).copied().unwrap_or_default(),
-                }
+        let counts = records.iter().map(|result| result.count).collect();
+
+        let mut groups = Vec::new();
+        let mut distinct = None;
+        let mut explanations = Vec::new();
+        for record in records {
+            groups.extend(record.groups.map(|hits| FacetResult { hits }));
+            // Replicas may have different segments, each of them is explained
+            explanations.extend(record.explanations);
+            if let Some(sketch) = record.distinct {
+                // Replicas have the same values, merging sketches doesn't count them twice
+                distinct
+                    .get_or_insert_with(CardinalitySketch::default)
+                    .merge(sketch);
             }
         }
+
+        Self {
+            count: resolve_count(counts, condition),
+            groups: (!groups.is_empty()).then(|| FacetResult::resolve(groups, condition).hits),
+            distinct,
+            segments: None,
+            explanations,
+        }
+    }
+}
+
+impl Resolve for FacetResult {
+    fn resolve(results: Vec<Self>, condition: ResolveCondition) -> Self {
+        let replicas = results.len();
+
+        let mut counts_per_value: HashMap<FacetValue, Vec<usize>> = HashMap::new();
+        for result in results {
+            for hit in result.hits {
+                counts_per_value
+                    .entry(hit.value)
+                    .or_default()
+                    .push(hit.count);
+            }
+        }
+
+        // Value missing in the response of a replica is counted as zero
+        let resolved_counts = counts_per_value.into_iter().map(|(value, mut counts)| {
+            counts.resize(replicas, 0);
+            (value, resolve_count(counts, condition))
+        });
+
+        Self {
+            hits: merge_facet_counts(resolved_counts, None),
+        }
+    }
+}
+
+impl Resolve for NumericAggregation {
+    fn resolve(mut aggregations: Vec<Self>, condition: ResolveCondition) -> Self {
+        // Pick the aggregation of the replica whose value count would be chosen by `resolve_count`
+        aggregations.sort_by_key(|aggregation| aggregation.count);
+        let index = match condition {
+            ResolveCondition::All => 0,
+            ResolveCondition::Majority => aggregations.len() / 2,
+        };
+        aggregations.into_iter().nth(index).unwrap_or_default()
+    }
+}
+
+fn resolve_count(mut counts: Vec<usize>, condition: ResolveCondition) -> usize {
+    match condition {
+        ResolveCondition::All => counts.iter().copied().min().unwrap_or_default(),
+        ResolveCondition::Majority => {
+            counts.sort_unstable();
+            let middle = counts.len() / 2;
+            counts.get(middle).copied().unwrap_or_default()
+        }
     }
 }
 
@@ -84,6 +141,15 @@ impl Resolve for Vec<ShardQueryResponse> {
     }
 }
 
+impl Resolve for (Vec<ShardQueryResponse>, Vec<QueryProfile>) {
+    fn resolve(results: Vec<Self>, condition: ResolveCondition) -> Self {
+        let (batches, profiles): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+        // Replicas are queried concurrently, keep the profile of each replica which has answered
+        let profiles = profiles.into_iter().flatten().collect();
+        (Resolve::resolve(batches, condition), profiles)
+    }
+}
+
 fn record_eq(this: &Record, other: &Record) -> bool {
     this.id == other.id && this.vector == other.vector && payload_eq(&this.payload, &other.payload)
 }
@@ -248,6 +314,7 @@ mod test {
     use std::fmt;
 
     use common::types::ScoreType;
+    use segment::data_types::facets::FacetValueHit;
 
     use super::*;
 
@@ -525,6 +592,66 @@ mod test {
         test_resolve_simple(input_4(), expected_4_majority(), ResolveCondition::Majority);
     }
 
+    fn facet_result(hits: &[(&str, usize)]) -> FacetResult {
+        FacetResult {
+            hits: hits
+                .iter()
+                .map(|(value, count)| FacetValueHit {
+                    value: FacetValue::Keyword(value.to_string()),
+                    count: *count,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn resolve_facet_result() {
+        let input = vec![
+            facet_result(&[("a", 10), ("b", 5), ("c", 1)]),
+            facet_result(&[("a", 10), ("b", 4)]),
+            facet_result(&[("a", 9), ("b", 5), ("c", 1)]),
+        ];
+
+        test_resolve(
+            input.clone(),
+            facet_result(&[("a", 9), ("b", 4)]),
+            ResolveCondition::All,
+        );
+        test_resolve(
+            input,
+            facet_result(&[("a", 10), ("b", 5), ("c", 1)]),
+            ResolveCondition::Majority,
+        );
+    }
+
+    #[test]
+    fn resolve_numeric_aggregation() {
+        let aggregation = |values: &[f64]| {
+            let mut aggregation = NumericAggregation::default();
+            for value in values {
+                aggregation.add(*value, None);
+            }
+            aggregation
+        };
+
+        let input = vec![
+            aggregation(&[1.0, 2.0, 3.0]),
+            aggregation(&[1.0, 2.0]),
+            aggregation(&[1.0, 2.0, 3.0]),
+        ];
+
+        test_resolve(
+            input.clone(),
+            aggregation(&[1.0, 2.0]),
+            ResolveCondition::All,
+        );
+        test_resolve(
+            input,
+            aggregation(&[1.0, 2.0, 3.0]),
+            ResolveCondition::Majority,
+        );
+    }
+
     fn test_resolve<T, E>(input: Vec<T>, expected: E, condition: ResolveCondition)
     where
         T: Resolve + Clone + PartialEq<E> + fmt::Debug,
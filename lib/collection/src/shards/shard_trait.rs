@@ -2,7 +2,9 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::profile::QueryProfile;
 use segment::types::*;
 use tokio::runtime::Handle;
 
@@ -41,6 +43,13 @@ pub trait ShardOperation {
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult>;
 
+    async fn facet(&self, request: Arc<FacetRequestInternal>) -> CollectionResult<FacetResult>;
+
+    async fn aggregate(
+        &self,
+        request: Arc<AggregationParams>,
+    ) -> CollectionResult<NumericAggregation>;
+
     async fn retrieve(
         &self,
         request: Arc<PointRequestInternal>,
@@ -54,6 +63,17 @@ pub trait ShardOperation {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ShardQueryResponse>>;
+
+    /// Same as `query_batch`, but also returns the time spent on each stage of the query.
+    ///
+    /// If `explain` is set, the stages also hold how each segment has executed them.
+    async fn query_batch_profiled(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<(Vec<ShardQueryResponse>, QueryProfile)>;
 }
 
 pub type ShardOperationSS = dyn ShardOperation + Send + Sync;
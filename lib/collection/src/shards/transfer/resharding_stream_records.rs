@@ -66,6 +66,10 @@ pub(crate) async fn transfer_resharding_stream_records(
             .count_local(Arc::new(CountRequestInternal {
                 filter: None,
                 exact: true,
+                group_by: None,
+                group_by_limit: None,
+                distinct: None,
+                explain: None,
             }))
             .await?
         else {
@@ -51,6 +51,10 @@ pub(super) async fn transfer_stream_records(
             .count_local(Arc::new(CountRequestInternal {
                 filter: None,
                 exact: true,
+                group_by: None,
+                group_by_limit: None,
+                distinct: None,
+                explain: None,
             }))
             .await?
         else {
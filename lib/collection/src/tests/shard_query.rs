@@ -1,8 +1,12 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use api::rest::Sample;
 use common::cpu::CpuBudget;
+use segment::data_types::formula::{Expression, FormulaQuery, Variable};
+use segment::data_types::profile::{ProfileStage, QueryProfile};
 use segment::data_types::vectors::{NamedVectorStruct, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{PointIdType, WithPayloadInterface, WithVector};
+use segment::types::{Distance, PointIdType, WithPayloadInterface, WithVector};
 use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
@@ -10,7 +14,7 @@ use tokio::sync::RwLock;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::CollectionError;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest,
+    Fusion, MmrQuery, ScoringQuery, ShardPrefetch, ShardQueryRequest,
 };
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::local_shard::LocalShard;
@@ -53,7 +57,7 @@ async fn test_shard_query_rrf_rescoring() {
     // RRF query without prefetches
     let query = ShardQueryRequest {
         prefetches: vec![],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::rrf())),
         filter: None,
         score_threshold: None,
         limit: 0,
@@ -87,7 +91,7 @@ async fn test_shard_query_rrf_rescoring() {
     let outer_limit = 2;
     let query = ShardQueryRequest {
         prefetches: vec![nearest_query_prefetch.clone()],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::rrf())),
         filter: None,
         score_threshold: None,
         limit: outer_limit,
@@ -133,7 +137,7 @@ async fn test_shard_query_rrf_rescoring() {
             nearest_query_prefetch.clone(),
             nearest_query_prefetch.clone(),
         ],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::rrf())),
         filter: None,
         score_threshold: None,
         limit: outer_limit,
@@ -176,7 +180,7 @@ async fn test_shard_query_rrf_rescoring() {
                 ..nearest_query_prefetch.clone()
             },
         ],
-        query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
+        query: Some(ScoringQuery::Fusion(Fusion::rrf())),
         filter: None,
         score_threshold: None,
         limit: outer_limit,
@@ -401,3 +405,477 @@ async fn test_shard_query_payload_vector() {
         assert!(scored_point.payload.is_some());
     });
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_profile() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let nearest_query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    ));
+    let nearest_query_prefetch = ShardPrefetch {
+        prefetches: vec![], // no recursion here
+        query: Some(ScoringQuery::Vector(nearest_query.clone())),
+        limit: 3,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+
+    // rescoring the fusion of two prefetches against a vector
+    let outer_limit = 2;
+    let query = ShardQueryRequest {
+        prefetches: vec![ShardPrefetch {
+            prefetches: vec![
+                nearest_query_prefetch.clone(),
+                nearest_query_prefetch.clone(),
+            ],
+            query: Some(ScoringQuery::Fusion(Fusion::rrf())),
+            limit: 3,
+            params: None,
+            filter: None,
+            score_threshold: None,
+        }],
+        query: Some(ScoringQuery::Vector(nearest_query)),
+        filter: None,
+        score_threshold: None,
+        limit: outer_limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let (mut sources_scores, profile) = shard
+        .query_batch_profiled(Arc::new(vec![query]), &current_runtime, None, false)
+        .await
+        .unwrap();
+    let sources_scores = sources_scores.pop().unwrap();
+
+    assert_eq!(sources_scores.len(), 1);
+    assert_eq!(sources_scores[0].len(), outer_limit);
+
+    let stages = |profile: &QueryProfile| {
+        profile
+            .children
+            .iter()
+            .map(|child| child.stage)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(profile.stage, ProfileStage::Local);
+    assert_eq!(
+        stages(&profile),
+        vec![
+            ProfileStage::Scoring,
+            ProfileStage::Prefetch,
+            ProfileStage::Rescoring,
+        ],
+    );
+
+    let prefetch = &profile.children[1];
+    assert_eq!(stages(prefetch), vec![ProfileStage::Fusion]);
+    assert_eq!(stages(&prefetch.children[0]), vec![ProfileStage::Fetching]);
+
+    // nested stages can't take longer than the enclosing one
+    assert!(profile
+        .children
+        .iter()
+        .all(|child| child.time <= profile.time));
+
+    // profiling alone doesn't explain the segments
+    assert!(profile.children[0].segments.is_empty());
+
+    // filtering without a vector query is explained per segment as well
+    let scroll_query = ShardQueryRequest {
+        prefetches: vec![],
+        query: None,
+        filter: None,
+        score_threshold: None,
+        limit: outer_limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let (_, profile) = shard
+        .query_batch_profiled(Arc::new(vec![scroll_query]), &current_runtime, None, true)
+        .await
+        .unwrap();
+
+    assert_eq!(stages(&profile), vec![ProfileStage::Filtering]);
+    assert!(!profile.children[0].segments.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_formula_rescoring() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let nearest_query = QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    ));
+    let nearest_query_prefetch = ShardPrefetch {
+        prefetches: vec![], // no recursion here
+        query: Some(ScoringQuery::Vector(nearest_query)),
+        limit: 3,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+
+    // 1 - $score inverts the order of the prefetch
+    let formula = FormulaQuery {
+        formula: Expression::Sum(vec![
+            Expression::Constant(1.0),
+            Expression::Neg(Box::new(Expression::Variable(Variable::Score(0)))),
+        ]),
+        defaults: Default::default(),
+    };
+
+    let query = |formula: FormulaQuery| ShardQueryRequest {
+        prefetches: vec![nearest_query_prefetch.clone()],
+        query: Some(ScoringQuery::Formula(formula)),
+        filter: None,
+        score_threshold: None,
+        limit: 2,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(true),
+    };
+
+    let prefetched = shard
+        .query_batch(
+            Arc::new(vec![ShardQueryRequest {
+                prefetches: vec![],
+                query: nearest_query_prefetch.query.clone(),
+                filter: None,
+                score_threshold: None,
+                limit: 3,
+                offset: 0,
+                params: None,
+                with_vector: WithVector::Bool(false),
+                with_payload: WithPayloadInterface::Bool(false),
+            }]),
+            &current_runtime,
+            None,
+        )
+        .await
+        .unwrap()
+        .pop()
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    let rescored = shard
+        .query_batch(Arc::new(vec![query(formula)]), &current_runtime, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    assert_eq!(rescored.len(), 2);
+    assert_eq!(rescored[0].id, prefetched[2].id);
+    assert_eq!(rescored[1].id, prefetched[1].id);
+    assert!((rescored[0].score - (1.0 - prefetched[2].score)).abs() < 1e-6);
+    assert!(rescored.iter().all(|point| point.payload.is_some()));
+
+    // the formula can't use the score of a missing prefetch
+    let formula = FormulaQuery {
+        formula: Expression::Variable(Variable::Score(1)),
+        defaults: Default::default(),
+    };
+    let result = shard
+        .query_batch(Arc::new(vec![query(formula)]), &current_runtime, None)
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadRequest { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_mmr() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let query_vector = NamedVectorStruct::new_from_vector(
+        Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        DEFAULT_VECTOR_NAME,
+    );
+
+    let nearest_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            query_vector.clone(),
+        ))),
+        limit: 5,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+
+    // MMR in a prefetch is selected on the shard, only relevance counts with lambda 1.0
+    let relevance_mmr = MmrQuery {
+        vector: query_vector.clone(),
+        lambda: 1.0,
+    };
+    let query = ShardQueryRequest {
+        prefetches: vec![ShardPrefetch {
+            prefetches: vec![nearest_prefetch.clone()],
+            query: Some(ScoringQuery::Mmr(relevance_mmr)),
+            limit: 2,
+            params: None,
+            filter: None,
+            score_threshold: None,
+        }],
+        query: Some(ScoringQuery::Fusion(Fusion::rrf())),
+        filter: None,
+        score_threshold: None,
+        limit: 2,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let sources = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    assert_eq!(sources.len(), 1);
+    let selected = &sources[0];
+    assert_eq!(selected.len(), 2);
+    assert_eq!(selected[0].id, PointIdType::NumId(5));
+    assert_eq!(selected[1].id, PointIdType::NumId(1));
+    assert!((selected[0].score - 34.0).abs() < 1e-6);
+    assert!(selected.iter().all(|point| point.vector.is_none()));
+
+    // MMR at the root propagates the candidates with their vectors to the collection
+    let mmr = MmrQuery {
+        vector: query_vector,
+        lambda: 0.5,
+    };
+    let query = ShardQueryRequest {
+        prefetches: vec![nearest_prefetch],
+        query: Some(ScoringQuery::Mmr(mmr.clone())),
+        filter: None,
+        score_threshold: None,
+        limit: 2,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let mut sources = shard
+        .query_batch(Arc::new(vec![query]), &current_runtime, None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+
+    assert_eq!(sources.len(), 1);
+    let candidates = sources.pop().unwrap();
+    assert_eq!(candidates.len(), 5);
+    assert!(candidates.iter().all(|point| point.vector.is_some()));
+
+    let selected = mmr.select(candidates, Distance::Dot, 2).unwrap();
+    assert_eq!(selected.len(), 2);
+    assert_eq!(selected[0].id, PointIdType::NumId(5));
+    assert!((selected[0].score - 17.0).abs() < 1e-6);
+    assert_ne!(selected[0].id, selected[1].id);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_query_sample() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let upsert_ops = upsert_operation();
+
+    shard.update(upsert_ops.into(), true).await.unwrap();
+
+    let query = |prefetches, limit| ShardQueryRequest {
+        prefetches,
+        query: Some(ScoringQuery::Sample(Sample::Random)),
+        filter: None,
+        score_threshold: None,
+        limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(true),
+    };
+
+    let sample = |request| {
+        let shard = &shard;
+        let current_runtime = &current_runtime;
+        async move {
+            shard
+                .query_batch(Arc::new(vec![request]), current_runtime, None)
+                .await
+                .unwrap()
+                .pop()
+                .unwrap()
+                .pop()
+                .unwrap()
+        }
+    };
+
+    // distinct random points, in descending order of the sampling keys
+    let sampled = sample(query(vec![], 3)).await;
+    assert_eq!(sampled.len(), 3);
+    assert_eq!(
+        sampled
+            .iter()
+            .map(|point| point.id)
+            .collect::<HashSet<_>>()
+            .len(),
+        3
+    );
+    assert!(sampled
+        .windows(2)
+        .all(|pair| pair[0].score >= pair[1].score));
+    assert!(sampled.iter().all(|point| point.payload.is_some()));
+
+    // the sample can't be larger than the shard
+    let sampled = sample(query(vec![], 10)).await;
+    assert_eq!(sampled.len(), 5);
+
+    // sampling of the prefetched points
+    let nearest_prefetch = ShardPrefetch {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            NamedVectorStruct::new_from_vector(
+                Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+                DEFAULT_VECTOR_NAME,
+            ),
+        ))),
+        limit: 2,
+        params: None,
+        filter: None,
+        score_threshold: None,
+    };
+    let sampled = sample(query(vec![nearest_prefetch], 10)).await;
+    let sampled_ids: HashSet<_> = sampled.iter().map(|point| point.id).collect();
+    assert_eq!(
+        sampled_ids,
+        HashSet::from([PointIdType::NumId(5), PointIdType::NumId(1)])
+    );
+}
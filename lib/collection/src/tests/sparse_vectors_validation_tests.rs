@@ -118,6 +118,8 @@ fn validate_error_sparse_vector_recommend_request_internal() {
         positive: vec![wrong_recommend_example()],
         negative: vec![wrong_recommend_example()],
         strategy: None,
+        positive_weights: None,
+        negative_weights: None,
         filter: None,
         params: None,
         limit: 5,
@@ -13,7 +13,7 @@ use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::{Direction, OrderBy, StartFrom, ThenBy};
 use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructInternal};
 use segment::types::{
     Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
@@ -175,6 +175,10 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
             ),
         ))),
         exact: true,
+        group_by: None,
+        group_by_limit: None,
+        distinct: None,
+        explain: None,
     };
 
     let count_res = collection
@@ -548,6 +552,8 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo_origin: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -578,6 +584,8 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo_origin: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -617,6 +625,8 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo_origin: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -655,6 +665,8 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo_origin: None,
+                        then_by: vec![],
                     })),
                 },
                 None,
@@ -715,6 +727,118 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .all(|&x| x == 2));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_key_ordered_scroll() {
+    test_multi_key_ordered_scroll_with_shards(1).await;
+    test_multi_key_ordered_scroll_with_shards(N_SHARDS).await;
+}
+
+async fn test_multi_key_ordered_scroll_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const CATEGORY_KEY: &str = "category";
+    const PRICE_KEY: &str = "price";
+
+    let items = [
+        ("games", 30.0),
+        ("books", 10.0),
+        ("music", 5.0),
+        ("books", 20.0),
+        ("games", 10.0),
+        ("books", 15.0),
+        ("music", 25.0),
+        ("games", 20.0),
+    ];
+
+    let payloads = items
+        .iter()
+        .map(|(category, price)| {
+            let mut payload_map = Map::new();
+            payload_map.insert(CATEGORY_KEY.to_string(), (*category).into());
+            payload_map.insert(PRICE_KEY.to_string(), (*price).into());
+            Some(Payload(payload_map))
+        })
+        .collect_vec();
+
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: (0..items.len() as u64).map(|x| x.into()).collect_vec(),
+            vectors: BatchVectorStructInternal::from(vec![vec![1.0, 0.0, 0.0, 0.0]; items.len()])
+                .into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    ));
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            CATEGORY_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            PRICE_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Float),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let all_shards = ShardSelectorInternal::All;
+    let scroll = |category_from: Option<&str>, price_from: Option<f64>| {
+        let order_by = OrderBy {
+            key: CATEGORY_KEY.parse().unwrap(),
+            direction: Some(Direction::Asc),
+            start_from: category_from.map(|category| StartFrom::Keyword(category.to_string())),
+            geo_origin: None,
+            then_by: vec![ThenBy::Key(OrderBy {
+                key: PRICE_KEY.parse().unwrap(),
+                direction: Some(Direction::Desc),
+                start_from: price_from.map(StartFrom::Float),
+                geo_origin: None,
+                then_by: vec![],
+            })],
+        };
+        collection.scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(4),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: false.into(),
+                order_by: Some(OrderByInterface::Struct(order_by)),
+            },
+            None,
+            &all_shards,
+        )
+    };
+
+    let ids = |points: &[api::rest::Record]| points.iter().map(|point| point.id).collect_vec();
+    let expected = |ids: &[u64]| ids.iter().map(|&id| id.into()).collect_vec();
+
+    // books by price desc, then games by price desc
+    let first_page = scroll(None, None).await.unwrap();
+    assert_eq!(ids(&first_page.points), expected(&[3, 5, 1, 0]));
+
+    // `start_from` is inclusive, so the last point of the previous page comes again
+    let second_page = scroll(Some("games"), Some(30.0)).await.unwrap();
+    assert_eq!(ids(&second_page.points), expected(&[0, 7, 4, 6]));
+
+    // start from the middle of a category
+    let partial_page = scroll(Some("books"), Some(15.0)).await.unwrap();
+    assert_eq!(ids(&partial_page.points), expected(&[5, 1, 0, 7]));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
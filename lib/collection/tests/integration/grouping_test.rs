@@ -131,6 +131,8 @@ mod group_by {
         let request = GroupRequest::with_limit_from_request(
             SourceRequest::Recommend(RecommendRequestInternal {
                 strategy: Default::default(),
+                positive_weights: None,
+                negative_weights: None,
                 filter: None,
                 params: None,
                 limit: 4,
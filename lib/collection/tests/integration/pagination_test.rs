@@ -5,7 +5,7 @@ use collection::operations::point_ops::{
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::CollectionUpdateOperations;
 use segment::data_types::vectors::VectorStructInternal;
-use segment::types::WithPayloadInterface;
+use segment::types::{ExtendedPointId, WithPayloadInterface};
 use tempfile::Builder;
 
 use crate::common::{simple_collection_fixture, N_SHARDS};
@@ -123,3 +123,125 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         assert_eq!(page_9_result[i], reference_result[page_size * 9 + i]);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_page_tokens() {
+    test_collection_search_page_tokens_with_shards(1).await;
+    test_collection_search_page_tokens_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_page_tokens_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new()
+        .prefix("test_collection_search_page_tokens")
+        .tempdir()
+        .unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let upsert = |ids: std::ops::Range<u64>| {
+        let points = ids
+            .map(|i| PointStruct {
+                id: i.into(),
+                vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+                payload: None,
+            })
+            .collect();
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        ))
+    };
+
+    collection
+        .update_from_client_simple(upsert(0..200), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let search_request = |limit| SearchRequestInternal {
+        vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+        filter: None,
+        limit,
+        offset: None,
+        with_payload: None,
+        with_vector: None,
+        params: None,
+        score_threshold: None,
+    };
+
+    let mut page_ids = Vec::new();
+    let mut page_token = None;
+    loop {
+        let (page, next_page_token, explanations) = collection
+            .search_page(
+                search_request(15).into(),
+                page_token.as_deref(),
+                None,
+                ShardSelectorInternal::All,
+                None,
+                true,
+            )
+            .await
+            .unwrap();
+
+        page_ids.extend(page.iter().map(|point| point.id));
+
+        // Each page is explained, including the ones continued by the token
+        assert!(!explanations.is_empty());
+
+        // Points with better scores than the ones already returned don't affect the next pages
+        if page_token.is_none() {
+            collection
+                .update_from_client_simple(upsert(1000..1010), true, WriteOrdering::default())
+                .await
+                .unwrap();
+        }
+
+        match next_page_token {
+            Some(token) => page_token = Some(token),
+            None => break,
+        }
+    }
+
+    let expected_ids: Vec<_> = (0..200).rev().map(ExtendedPointId::from).collect();
+    assert_eq!(page_ids, expected_ids);
+
+    // Offset can't be mixed with the token
+    let mut offset_request = search_request(15);
+    offset_request.offset = Some(10);
+    let (_, token, _) = collection
+        .search_page(
+            search_request(15).into(),
+            None,
+            None,
+            ShardSelectorInternal::All,
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+    let result = collection
+        .search_page(
+            offset_request.into(),
+            token.as_deref(),
+            None,
+            ShardSelectorInternal::All,
+            None,
+            false,
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Token only continues the request it was issued for
+    let mut other_request = search_request(15);
+    other_request.vector = vec![0.0, 1.0, 0.0, 0.0].into();
+    let result = collection
+        .search_page(
+            other_request.into(),
+            token.as_deref(),
+            None,
+            ShardSelectorInternal::All,
+            None,
+            false,
+        )
+        .await;
+    assert!(result.is_err());
+}
@@ -18,7 +18,7 @@ use std::sync::atomic::AtomicBool;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::{QueryVector, VectorRef};
-use crate::types::{SegmentConfig, SparseVectorDataConfig, VectorDataConfig};
+use crate::types::{Filter, SegmentConfig, SparseVectorDataConfig, VectorDataConfig};
 
 pub type Flusher = Box<dyn FnOnce() -> OperationResult<()> + Send>;
 /// Check that the given vector name is part of the segment config.
@@ -61,6 +61,16 @@ fn check_query_vector(
         QueryVector::Recommend(reco_query) => reco_query.flat_iter().try_for_each(|vector| {
             check_vector_against_config(VectorRef::from(vector), vector_config)
         })?,
+        QueryVector::RecommendSumScores(reco_query) => {
+            reco_query.flat_iter().try_for_each(|vector| {
+                check_vector_against_config(VectorRef::from(vector), vector_config)
+            })?
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            contrastive_query.flat_iter().try_for_each(|vector| {
+                check_vector_against_config(VectorRef::from(vector), vector_config)
+            })?
+        }
         QueryVector::Discovery(discovery_query) => {
             discovery_query.flat_iter().try_for_each(|vector| {
                 check_vector_against_config(VectorRef::from(vector), vector_config)
@@ -87,6 +97,16 @@ fn check_query_sparse_vector(
         QueryVector::Recommend(reco_query) => reco_query.flat_iter().try_for_each(|vector| {
             check_sparse_vector_against_config(VectorRef::from(vector), vector_config)
         })?,
+        QueryVector::RecommendSumScores(reco_query) => {
+            reco_query.flat_iter().try_for_each(|vector| {
+                check_sparse_vector_against_config(VectorRef::from(vector), vector_config)
+            })?
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            contrastive_query.flat_iter().try_for_each(|vector| {
+                check_sparse_vector_against_config(VectorRef::from(vector), vector_config)
+            })?
+        }
         QueryVector::Discovery(discovery_query) => {
             discovery_query.flat_iter().try_for_each(|vector| {
                 check_sparse_vector_against_config(VectorRef::from(vector), vector_config)
@@ -214,6 +234,19 @@ fn check_sparse_vector_against_config(
     }
 }
 
+/// Check that relative datetime ranges of the filter are resolved into absolute ones.
+///
+/// Relative ranges are resolved once on the node which received the request, so that all shards
+/// compare against the same instant. A segment never resolves them against its own clock.
+pub fn check_filter_resolved(filter: Option<&Filter>) -> OperationResult<()> {
+    if filter.is_some_and(Filter::has_relative_datetime) {
+        return Err(OperationError::service_error(
+            "Relative datetime range must be resolved before the filter reaches the segment",
+        ));
+    }
+    Ok(())
+}
+
 pub fn check_stopped(is_stopped: &AtomicBool) -> OperationResult<()> {
     if is_stopped.load(std::sync::atomic::Ordering::Relaxed) {
         return Err(OperationError::Cancelled {
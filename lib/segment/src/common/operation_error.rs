@@ -58,6 +58,16 @@ pub enum OperationError {
     WrongPayloadKey { description: String },
     #[error("No range index for `order_by` key: `{key}`. Please create one to use `order_by`. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Range conditions")]
     MissingRangeIndexForOrderBy { key: String },
+    #[error(
+        "No geo index for `order_by` key: `{key}`. Please create one to order by geo distance"
+    )]
+    MissingGeoIndexForOrderBy { key: String },
+    #[error("No range or keyword index for `order_by` key: `{key}`. Please create one to order by multiple keys or by a keyword")]
+    MissingOrderingIndexForOrderBy { key: String },
+    #[error("No keyword, integer or bool index for facet key: `{key}`. Please create one to facet on this field")]
+    MissingMapIndexForFacet { key: String },
+    #[error("No datetime index for date histogram key: `{key}`. Please create one to build a date histogram")]
+    MissingDatetimeIndexForHistogram { key: String },
 }
 
 impl OperationError {
@@ -24,6 +24,7 @@ pub enum Aggregation {
     Sum,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Normalization {
     /// Uses the minimum and maximum scores as extremes
     MinMax,
@@ -31,8 +32,12 @@ pub enum Normalization {
     Distr,
 }
 
+/// Fuses lists of points by their normalized scores.
+///
+/// Each list comes with the order of its scores. Normalized scores of lists, where smaller is
+/// better, like distances, are flipped, so that the best points get higher scores.
 pub fn score_fusion(
-    all_results: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    all_results: impl IntoIterator<Item = (Vec<ScoredPoint>, Order)>,
     params: ScoreFusion,
 ) -> Vec<ScoredPoint> {
     let ScoreFusion {
@@ -47,9 +52,15 @@ pub fn score_fusion(
     all_results
         .into_iter()
         // normalize
-        .map(|points| match norm {
-            Normalization::MinMax => min_max_norm(points),
-            Normalization::Distr => distr_norm(points),
+        .map(|(points, points_order)| {
+            let points = match norm {
+                Normalization::MinMax => min_max_norm(points),
+                Normalization::Distr => distr_norm(points),
+            };
+            match points_order {
+                Order::LargeBetter => points,
+                Order::SmallBetter => invert_norm(points),
+            }
         })
         // weight each list of points
         .zip(weights)
@@ -81,6 +92,12 @@ pub fn score_fusion(
         .collect()
 }
 
+/// Flips normalized scores, so that the best point of the list gets the highest score.
+fn invert_norm(mut points: Vec<ScoredPoint>) -> Vec<ScoredPoint> {
+    points.iter_mut().for_each(|p| p.score = 1.0 - p.score);
+    points
+}
+
 /// Normalizes the scores of the given points between 0.0 and 1.0, using the given minimum and maximum scores as extremes.
 fn norm(mut points: Vec<ScoredPoint>, min: ScoreType, max: ScoreType) -> Vec<ScoredPoint> {
     // Protect against division by zero
@@ -173,6 +190,40 @@ mod tests {
         assert!((a - b).abs() < 1e-2, "{} is not close to {}", a, b);
     }
 
+    #[test]
+    fn test_score_fusion_distance_lists() {
+        let similarities = vec![point(1, 0.9), point(2, 0.5), point(3, 0.1)];
+        // Euclidean-like distances, the closest point comes first
+        let distances = vec![point(3, 1.0), point(2, 5.0), point(4, 9.0)];
+
+        let fused = score_fusion(
+            [
+                (similarities, Order::LargeBetter),
+                (distances, Order::SmallBetter),
+            ],
+            ScoreFusion {
+                method: Aggregation::Sum,
+                norm: Normalization::MinMax,
+                weights: vec![1.0, 2.0],
+                order: Order::LargeBetter,
+            },
+        );
+
+        let ids = fused.iter().map(|p| p.id).collect_vec();
+        let scores = fused.iter().map(|p| p.score).collect_vec();
+        assert_eq!(
+            ids,
+            vec![3, 2, 1, 4]
+                .into_iter()
+                .map(PointIdType::NumId)
+                .collect_vec()
+        );
+        assert_close(scores[0], 2.0);
+        assert_close(scores[1], 1.5);
+        assert_close(scores[2], 1.0);
+        assert_close(scores[3], 0.0);
+    }
+
     proptest! {
         #[test]
         fn welford_calc_vs_naive(scores in prop::collection::vec(-100.0..100.0f32, 2..1000)) {
@@ -0,0 +1,374 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{
+    DateTime, Datelike, Days, LocalResult, Months, NaiveDate, NaiveDateTime, Offset, TimeDelta,
+    TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::data_types::facets::FacetValue;
+use crate::json_path::JsonPath;
+use crate::types::{DateTimePayloadType, Filter};
+
+/// Parameters of a numeric aggregation, as seen by a single segment
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregationParams {
+    pub key: JsonPath,
+    pub filter: Option<Filter>,
+    pub histogram: Option<HistogramBucketing>,
+    pub date_histogram: Option<DateHistogramBucketing>,
+}
+
+/// Fixed-width histogram buckets.
+///
+/// Value `v` falls into the bucket number `floor((v - offset) / interval)`.
+/// If the number of buckets is limited, values outside of them are put into the first or the last bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBucketing {
+    pub interval: f64,
+    pub offset: f64,
+    pub buckets: Option<usize>,
+}
+
+impl HistogramBucketing {
+    pub fn bucket_of(&self, value: f64) -> i64 {
+        let bucket = ((value - self.offset) / self.interval).floor() as i64;
+        match self.buckets {
+            Some(buckets) => bucket.clamp(0, buckets as i64 - 1),
+            None => bucket,
+        }
+    }
+
+    /// Lower bound of the bucket
+    pub fn bucket_start(&self, bucket: i64) -> f64 {
+        self.offset + bucket as f64 * self.interval
+    }
+}
+
+/// Calendar unit of date histogram buckets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarInterval {
+    Minute,
+    Hour,
+    Day,
+    /// Weeks start on Monday
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+/// Calendar-aware buckets of datetime values.
+///
+/// Bucket edges are local times of the time zone, so a day bucket spans 23 or 25 hours
+/// when daylight saving time starts or ends. Buckets are identified by their start timestamp in microseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateHistogramBucketing {
+    pub interval: CalendarInterval,
+    pub time_zone: Tz,
+    /// Keyword, integer or bool key to count the values of within each bucket
+    pub facet: Option<JsonPath>,
+}
+
+impl DateHistogramBucketing {
+    /// Local times skipped by a DST transition are searched with this step
+    const GAP_STEP_MINUTES: i64 = 15;
+    /// Time zones never skip more than a day
+    const MAX_GAP_STEPS: i64 = 2 * 24 * 60 / Self::GAP_STEP_MINUTES;
+
+    /// Start of the bucket containing the timestamp
+    pub fn bucket_of(&self, timestamp: i64) -> i64 {
+        let Some(datetime) = DateTime::<Utc>::from_timestamp_micros(timestamp) else {
+            return timestamp;
+        };
+        let local = datetime.with_timezone(&self.time_zone);
+        let start = self.truncate(local.naive_local());
+        match self.interval {
+            // Align sub-day buckets using the offset of the value itself,
+            // so that an hour repeated at the end of DST gets a bucket of its own
+            CalendarInterval::Minute | CalendarInterval::Hour => {
+                (start - local.offset().fix()).and_utc().timestamp_micros()
+            }
+            CalendarInterval::Day
+            | CalendarInterval::Week
+            | CalendarInterval::Month
+            | CalendarInterval::Quarter
+            | CalendarInterval::Year => self.resolve_local(start),
+        }
+    }
+
+    /// Start of the bucket which follows the given one
+    pub fn next_bucket(&self, bucket: i64) -> i64 {
+        let Some(datetime) = DateTime::<Utc>::from_timestamp_micros(bucket) else {
+            return bucket;
+        };
+        let date = self
+            .truncate(datetime.with_timezone(&self.time_zone).naive_local())
+            .date();
+        let next = match self.interval {
+            CalendarInterval::Minute => return self.bucket_of(bucket + 60 * 1_000_000),
+            CalendarInterval::Hour => return self.bucket_of(bucket + 60 * 60 * 1_000_000),
+            CalendarInterval::Day => date.checked_add_days(Days::new(1)),
+            CalendarInterval::Week => date.checked_add_days(Days::new(7)),
+            CalendarInterval::Month => date.checked_add_months(Months::new(1)),
+            CalendarInterval::Quarter => date.checked_add_months(Months::new(3)),
+            CalendarInterval::Year => date.checked_add_months(Months::new(12)),
+        };
+        match next {
+            Some(next) => self.resolve_local(NaiveDateTime::from(next)),
+            None => i64::MAX,
+        }
+    }
+
+    /// Start of the calendar unit containing the local time
+    fn truncate(&self, local: NaiveDateTime) -> NaiveDateTime {
+        let date = local.date();
+        let (date, hour, minute) = match self.interval {
+            CalendarInterval::Minute => (date, local.hour(), local.minute()),
+            CalendarInterval::Hour => (date, local.hour(), 0),
+            CalendarInterval::Day => (date, 0, 0),
+            CalendarInterval::Week => {
+                let weekday = date.weekday().num_days_from_monday();
+                (date - TimeDelta::days(i64::from(weekday)), 0, 0)
+            }
+            CalendarInterval::Month => (date.with_day(1).unwrap_or(date), 0, 0),
+            CalendarInterval::Quarter => {
+                let month = date.month0() / 3 * 3 + 1;
+                let start = NaiveDate::from_ymd_opt(date.year(), month, 1);
+                (start.unwrap_or(date), 0, 0)
+            }
+            CalendarInterval::Year => {
+                let start = NaiveDate::from_ymd_opt(date.year(), 1, 1);
+                (start.unwrap_or(date), 0, 0)
+            }
+        };
+        date.and_hms_opt(hour, minute, 0).unwrap_or(local)
+    }
+
+    /// Earliest timestamp at which the clock of the time zone shows the local time, or a later one
+    /// if the local time is skipped by a DST transition
+    fn resolve_local(&self, local: NaiveDateTime) -> i64 {
+        let mut candidate = local;
+        for _ in 0..Self::MAX_GAP_STEPS {
+            match self.time_zone.from_local_datetime(&candidate) {
+                LocalResult::Single(datetime) | LocalResult::Ambiguous(datetime, _) => {
+                    return datetime.timestamp_micros();
+                }
+                LocalResult::None => candidate += TimeDelta::minutes(Self::GAP_STEP_MINUTES),
+            }
+        }
+        local.and_utc().timestamp_micros()
+    }
+}
+
+/// Points of a single date histogram bucket
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DateHistogramCounts {
+    /// Number of points with a value in the bucket. A point is counted once per bucket
+    pub count: usize,
+    /// Number of points of the bucket per value of the facet key
+    pub facet: HashMap<FacetValue, usize>,
+}
+
+impl DateHistogramCounts {
+    pub fn merge(&mut self, other: DateHistogramCounts) {
+        self.count += other.count;
+        for (value, count) in other.facet {
+            *self.facet.entry(value).or_default() += count;
+        }
+    }
+}
+
+/// Partial aggregation of numeric values.
+///
+/// Aggregations of different segments and shards are combined with [`NumericAggregation::merge`].
+/// Every value of a multi-value field is counted separately.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NumericAggregation {
+    pub count: usize,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub sum: f64,
+    /// Number of values per bucket, only non-empty buckets are stored
+    pub histogram: BTreeMap<i64, usize>,
+    /// Points per date histogram bucket, only non-empty buckets are stored
+    pub date_histogram: BTreeMap<i64, DateHistogramCounts>,
+}
+
+impl NumericAggregation {
+    pub fn add(&mut self, value: f64, bucketing: Option<&HistogramBucketing>) {
+        self.count += 1;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+        self.sum += value;
+        if let Some(bucketing) = bucketing {
+            *self
+                .histogram
+                .entry(bucketing.bucket_of(value))
+                .or_default() += 1;
+        }
+    }
+
+    pub fn merge(&mut self, other: NumericAggregation) {
+        self.count += other.count;
+        self.min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        self.sum += other.sum;
+        for (bucket, count) in other.histogram {
+            *self.histogram.entry(bucket).or_default() += count;
+        }
+        for (bucket, counts) in other.date_histogram {
+            self.date_histogram.entry(bucket).or_default().merge(counts);
+        }
+    }
+
+    pub fn avg(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.sum / self.count as f64)
+    }
+}
+
+/// Convert a payload value into a number to aggregate.
+///
+/// Datetime strings are converted into timestamps, the same way the datetime index stores them.
+pub fn aggregation_value_from_json(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(string) => string
+            .parse::<DateTimePayloadType>()
+            .ok()
+            .map(|datetime| datetime.timestamp() as f64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_numeric_aggregations() {
+        let bucketing = HistogramBucketing {
+            interval: 10.0,
+            offset: 0.0,
+            buckets: None,
+        };
+
+        let mut left = NumericAggregation::default();
+        for value in [1.0, 12.0, 15.0] {
+            left.add(value, Some(&bucketing));
+        }
+
+        let mut right = NumericAggregation::default();
+        for value in [-3.0, 19.0] {
+            right.add(value, Some(&bucketing));
+        }
+
+        left.merge(right);
+        left.merge(NumericAggregation::default());
+
+        assert_eq!(left.count, 5);
+        assert_eq!(left.min, Some(-3.0));
+        assert_eq!(left.max, Some(19.0));
+        assert_eq!(left.sum, 44.0);
+        assert_eq!(left.avg(), Some(8.8));
+        assert_eq!(left.histogram, BTreeMap::from([(-1, 1), (0, 1), (1, 3)]));
+    }
+
+    #[test]
+    fn test_limited_buckets() {
+        let bucketing = HistogramBucketing {
+            interval: 2.5,
+            offset: 5.0,
+            buckets: Some(4),
+        };
+
+        assert_eq!(bucketing.bucket_of(5.0), 0);
+        assert_eq!(bucketing.bucket_of(7.4), 0);
+        assert_eq!(bucketing.bucket_of(7.5), 1);
+        // Max value of the range is in the last bucket
+        assert_eq!(bucketing.bucket_of(15.0), 3);
+        assert_eq!(bucketing.bucket_start(3), 12.5);
+    }
+
+    fn timestamp(datetime: &str) -> i64 {
+        datetime.parse::<DateTimePayloadType>().unwrap().timestamp()
+    }
+
+    #[test]
+    fn test_date_histogram_buckets_across_dst() {
+        let bucketing = DateHistogramBucketing {
+            interval: CalendarInterval::Day,
+            time_zone: Tz::Europe__Berlin,
+            facet: None,
+        };
+
+        // DST starts on 2024-03-31 at 02:00, so the day lasts 23 hours
+        let bucket = bucketing.bucket_of(timestamp("2024-03-31T12:00:00+02:00"));
+        assert_eq!(bucket, timestamp("2024-03-31T00:00:00+01:00"));
+        assert_eq!(
+            bucketing.next_bucket(bucket),
+            timestamp("2024-04-01T00:00:00+02:00"),
+        );
+        assert_eq!(
+            bucketing.bucket_of(timestamp("2024-03-31T23:59:59+02:00")),
+            bucket,
+        );
+
+        // DST ends on 2024-10-27 at 03:00, and the hour from 02:00 to 03:00 is repeated
+        let bucketing = DateHistogramBucketing {
+            interval: CalendarInterval::Hour,
+            ..bucketing
+        };
+        let first = bucketing.bucket_of(timestamp("2024-10-27T02:30:00+02:00"));
+        let second = bucketing.bucket_of(timestamp("2024-10-27T02:30:00+01:00"));
+        assert_eq!(first, timestamp("2024-10-27T02:00:00+02:00"));
+        assert_eq!(second, timestamp("2024-10-27T02:00:00+01:00"));
+        assert_eq!(bucketing.next_bucket(first), second);
+    }
+
+    #[test]
+    fn test_date_histogram_calendar_units() {
+        let bucketing = |interval| DateHistogramBucketing {
+            interval,
+            time_zone: Tz::America__New_York,
+            facet: None,
+        };
+        let value = timestamp("2024-08-15T01:30:00Z");
+
+        // Local time of the value is 2024-08-14T21:30:00-04:00, a Wednesday
+        let week = bucketing(CalendarInterval::Week);
+        assert_eq!(
+            week.bucket_of(value),
+            timestamp("2024-08-12T00:00:00-04:00")
+        );
+        let month = bucketing(CalendarInterval::Month);
+        assert_eq!(
+            month.bucket_of(value),
+            timestamp("2024-08-01T00:00:00-04:00")
+        );
+        // November starts before DST ends
+        assert_eq!(
+            month.next_bucket(timestamp("2024-10-01T00:00:00-04:00")),
+            timestamp("2024-11-01T00:00:00-04:00"),
+        );
+        let quarter = bucketing(CalendarInterval::Quarter);
+        assert_eq!(
+            quarter.next_bucket(quarter.bucket_of(value)),
+            timestamp("2024-10-01T00:00:00-04:00"),
+        );
+        let year = bucketing(CalendarInterval::Year);
+        assert_eq!(
+            year.next_bucket(year.bucket_of(value)),
+            timestamp("2025-01-01T00:00:00-05:00"),
+        );
+    }
+}
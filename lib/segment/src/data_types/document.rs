@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashMap};
+
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+use crate::data_types::index::TextIndexParams;
+use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
+
+/// Number of occurrences of each token of a document, in the order of their first occurrence
+pub fn document_term_frequencies(text: &str, params: &TextIndexParams) -> Vec<(String, usize)> {
+    let mut tokens = Vec::new();
+    Tokenizer::tokenize_doc(text, params, |token| tokens.push(token.to_string()));
+    count_tokens(tokens)
+}
+
+/// Same as [`document_term_frequencies`], but tokenizes the text as a query.
+///
+/// The difference matters for the prefix tokenizer, which only expands documents into prefixes.
+pub fn query_term_frequencies(text: &str, params: &TextIndexParams) -> Vec<(String, usize)> {
+    let mut tokens = Vec::new();
+    Tokenizer::tokenize_query(text, params, |token| tokens.push(token.to_string()));
+    count_tokens(tokens)
+}
+
+fn count_tokens(tokens: Vec<String>) -> Vec<(String, usize)> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut frequencies: Vec<(String, usize)> = Vec::new();
+    for token in tokens {
+        match positions.get(&token) {
+            Some(&position) => frequencies[position].1 += 1,
+            None => {
+                positions.insert(token.clone(), frequencies.len());
+                frequencies.push((token, 1));
+            }
+        }
+    }
+    frequencies
+}
+
+/// Dimension of a token, which does not need any vocabulary: the same token always gets the same id.
+///
+/// Different tokens may collide into the same dimension, which is rare enough for the ranking.
+pub fn hashed_dim_id(token: &str) -> DimId {
+    seahash::hash(token.as_bytes()) as DimId
+}
+
+/// Builds a sparse vector of term frequencies.
+///
+/// Frequencies of the tokens, which share the same dimension, are summed up.
+pub fn term_frequency_vector(
+    frequencies: impl IntoIterator<Item = (DimId, usize)>,
+) -> SparseVector {
+    let mut dimensions: BTreeMap<DimId, DimWeight> = BTreeMap::new();
+    for (dim_id, count) in frequencies {
+        *dimensions.entry(dim_id).or_default() += count as DimWeight;
+    }
+
+    let (indices, values) = dimensions.into_iter().unzip();
+    SparseVector { indices, values }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::index::TokenizerType;
+
+    #[test]
+    fn test_term_frequency_vector() {
+        let params = TextIndexParams {
+            tokenizer: TokenizerType::Word,
+            ..Default::default()
+        };
+
+        let frequencies = document_term_frequencies("The cat and the hat", &params);
+        assert_eq!(
+            frequencies,
+            vec![
+                ("the".to_string(), 2),
+                ("cat".to_string(), 1),
+                ("and".to_string(), 1),
+                ("hat".to_string(), 1),
+            ]
+        );
+
+        let vector = term_frequency_vector(
+            frequencies
+                .iter()
+                .map(|(token, count)| (hashed_dim_id(token), *count)),
+        );
+        assert_eq!(vector.indices.len(), 4);
+        assert!(vector.indices.windows(2).all(|pair| pair[0] < pair[1]));
+        let the_position = vector
+            .indices
+            .iter()
+            .position(|&dim_id| dim_id == hashed_dim_id("the"))
+            .unwrap();
+        assert_eq!(vector.values[the_position], 2.0);
+
+        // Colliding dimensions are summed up
+        let vector = term_frequency_vector([(7, 1), (3, 2), (7, 2)]);
+        assert_eq!(vector.indices, vec![3, 7]);
+        assert_eq!(vector.values, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_prefix_query_is_not_expanded() {
+        let params = TextIndexParams {
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(2),
+            max_token_len: Some(3),
+            ..Default::default()
+        };
+
+        let document = document_term_frequencies("cats", &params);
+        assert_eq!(
+            document,
+            vec![("ca".to_string(), 1), ("cat".to_string(), 1)]
+        );
+
+        let query = query_term_frequencies("cat", &params);
+        assert_eq!(query, vec![("cat".to_string(), 1)]);
+    }
+}
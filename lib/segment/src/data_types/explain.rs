@@ -0,0 +1,205 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use common::types::PointOffsetType;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::data_types::query_context::VectorQueryContext;
+use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
+use crate::index::PayloadIndex;
+use crate::types::{Filter, SegmentType};
+
+/// The way points are found in a segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchStrategy {
+    /// Check every point of the segment
+    FullScan,
+    /// Select points with the payload index, then check them
+    PayloadIndex,
+    /// Traverse the HNSW graph, checking the filter on visited points
+    Graph,
+    /// Traverse the inverted index of sparse vectors, checking the filter on visited points
+    InvertedIndex,
+}
+
+impl SearchStrategy {
+    /// Strategy of iterating over points, which match the filter
+    pub fn filtered_scan(cardinality: &CardinalityEstimation) -> Self {
+        if cardinality.primary_clauses.is_empty() {
+            SearchStrategy::FullScan
+        } else {
+            SearchStrategy::PayloadIndex
+        }
+    }
+}
+
+/// Estimated number of points, matching the filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct CardinalityInfo {
+    pub min: usize,
+    pub exp: usize,
+    pub max: usize,
+}
+
+impl From<&CardinalityEstimation> for CardinalityInfo {
+    fn from(estimation: &CardinalityEstimation) -> Self {
+        Self {
+            min: estimation.min,
+            exp: estimation.exp,
+            max: estimation.max,
+        }
+    }
+}
+
+/// What a segment has done to find the points of a request, recorded while the request runs
+#[derive(Debug, Clone, Default)]
+pub struct SearchTrace {
+    pub strategy: Option<SearchStrategy>,
+    pub estimated_cardinality: Option<CardinalityInfo>,
+    pub indexes_used: Vec<String>,
+    pub estimation_time: Duration,
+    pub actual_cardinality: Option<usize>,
+    pub filtering_time: Duration,
+}
+
+impl SearchTrace {
+    pub fn record_estimation(&mut self, cardinality: &CardinalityEstimation, time: Duration) {
+        self.estimated_cardinality = Some(CardinalityInfo::from(cardinality));
+        self.indexes_used = indexes_used(cardinality);
+        self.estimation_time += time;
+    }
+
+    pub fn record_filtering(&mut self, matched_points: usize, time: Duration) {
+        self.actual_cardinality = Some(matched_points);
+        self.filtering_time += time;
+    }
+
+    pub fn into_explanation(
+        self,
+        segment: String,
+        segment_type: SegmentType,
+        points_count: usize,
+        vector: Option<String>,
+        time: Duration,
+    ) -> SegmentExplanation {
+        let SearchTrace {
+            strategy,
+            estimated_cardinality,
+            indexes_used,
+            estimation_time,
+            actual_cardinality,
+            filtering_time,
+        } = self;
+
+        SegmentExplanation {
+            segment,
+            segment_type,
+            points_count,
+            vector,
+            strategy: strategy.unwrap_or(SearchStrategy::FullScan),
+            estimated_cardinality,
+            actual_cardinality,
+            indexes_used,
+            estimation_time: estimation_time.as_secs_f64(),
+            filtering_time: filtering_time.as_secs_f64(),
+            time: time.as_secs_f64(),
+        }
+    }
+}
+
+/// Select all points matching the filter with the payload index.
+///
+/// If the search is explained, also records the filtering. The cardinality is estimated for the
+/// explanation, unless the vector index has already done it to choose the strategy.
+pub fn query_points_traced<P: PayloadIndex + ?Sized>(
+    payload_index: &P,
+    filter: &Filter,
+    query_context: &VectorQueryContext,
+) -> Vec<PointOffsetType> {
+    if !query_context.is_traced() {
+        return payload_index.query_points(filter);
+    }
+
+    query_context.trace(|trace| {
+        if trace.estimated_cardinality.is_none() {
+            let timer = Instant::now();
+            let cardinality = payload_index.estimate_cardinality(filter);
+            trace.record_estimation(&cardinality, timer.elapsed());
+            trace.strategy = Some(SearchStrategy::filtered_scan(&cardinality));
+        } else if trace.indexes_used.is_empty() {
+            trace.strategy = Some(SearchStrategy::FullScan);
+        } else {
+            trace.strategy = Some(SearchStrategy::PayloadIndex);
+        }
+    });
+
+    let timer = Instant::now();
+    let points = payload_index.query_points(filter);
+    let time = timer.elapsed();
+    query_context.trace(|trace| trace.record_filtering(points.len(), time));
+    points
+}
+
+/// Name of the segment, as shown in the explanations
+pub fn segment_name(segment_path: &Path) -> String {
+    segment_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Keys of payload indexes, used by the primary clauses of the estimation
+fn indexes_used(cardinality: &CardinalityEstimation) -> Vec<String> {
+    let mut indexes: Vec<_> = cardinality
+        .primary_clauses
+        .iter()
+        .map(|clause| match clause {
+            PrimaryCondition::Condition(condition) => condition.key.to_string(),
+            PrimaryCondition::IsEmpty(condition) => condition.is_empty.key.to_string(),
+            PrimaryCondition::IsNull(condition) => condition.is_null.key.to_string(),
+            PrimaryCondition::Ids(_) => "has_id".to_string(),
+        })
+        .collect();
+    indexes.sort_unstable();
+    indexes.dedup();
+    indexes
+}
+
+/// How a segment has executed a request
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SegmentExplanation {
+    /// Name of the segment directory
+    pub segment: String,
+    pub segment_type: SegmentType,
+    /// Number of available points in the segment
+    pub points_count: usize,
+    /// Searched vector. Absent if the request doesn't search vectors, like `count`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector: Option<String>,
+    pub strategy: SearchStrategy,
+    /// Cardinality of the filter, as estimated by the payload index.
+    /// Absent if there is no filter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cardinality: Option<CardinalityInfo>,
+    /// Number of points, which actually match the filter.
+    /// Absent if the strategy hasn't selected all of the matching points, like a graph search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_cardinality: Option<usize>,
+    /// Payload indexes, selected by the estimator to find matching points
+    pub indexes_used: Vec<String>,
+    /// Time spent on cardinality estimation, in seconds
+    pub estimation_time: f64,
+    /// Time spent on selecting all points matching the filter, in seconds
+    pub filtering_time: f64,
+    /// Time spent on the request in the segment, in seconds
+    pub time: f64,
+}
+
+/// How a request was executed by the segments of a shard replica
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ShardExplanation {
+    pub shard_id: u32,
+    pub segments: Vec<SegmentExplanation>,
+}
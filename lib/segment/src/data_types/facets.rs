@@ -0,0 +1,124 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use itertools::Itertools;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::json_path::JsonPath;
+use crate::types::{Filter, IntPayloadType};
+
+/// Parameters of a facet request, as seen by a single segment
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetParams {
+    pub key: JsonPath,
+    pub limit: usize,
+    pub filter: Option<Filter>,
+    pub exact: bool,
+}
+
+impl FacetParams {
+    pub const DEFAULT_LIMIT: usize = 10;
+}
+
+/// Value of a facet, borrowed from the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FacetValueRef<'a> {
+    Keyword(&'a str),
+    Int(&'a IntPayloadType),
+    Bool(bool),
+}
+
+impl<'a> FacetValueRef<'a> {
+    pub fn to_owned(&self) -> FacetValue {
+        match self {
+            FacetValueRef::Keyword(keyword) => FacetValue::Keyword((*keyword).to_string()),
+            FacetValueRef::Int(int) => FacetValue::Int(**int),
+            FacetValueRef::Bool(bool) => FacetValue::Bool(*bool),
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(untagged)]
+pub enum FacetValue {
+    Keyword(String),
+    Int(IntPayloadType),
+    Bool(bool),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FacetValueHit {
+    pub value: FacetValue,
+    pub count: usize,
+}
+
+impl FacetValueHit {
+    /// Order of hits in a response: the most frequent values first, ties are broken by the value
+    fn sort_key(&self) -> (Reverse<usize>, &FacetValue) {
+        (Reverse(self.count), &self.value)
+    }
+}
+
+/// Sum up counts of the same values, sort the hits and keep `limit` most frequent ones
+pub fn merge_facet_counts<I>(counts: I, limit: Option<usize>) -> Vec<FacetValueHit>
+where
+    I: IntoIterator<Item = (FacetValue, usize)>,
+{
+    let mut merged: HashMap<FacetValue, usize> = HashMap::new();
+    for (value, count) in counts {
+        *merged.entry(value).or_default() += count;
+    }
+
+    let hits = merged
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(value, count)| FacetValueHit { value, count });
+
+    match limit {
+        Some(limit) => hits
+            .k_smallest_by(limit, |a, b| a.sort_key().cmp(&b.sort_key()))
+            .collect(),
+        None => hits
+            .sorted_unstable_by(|a, b| a.sort_key().cmp(&b.sort_key()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_facet_counts() {
+        let counts = vec![
+            (FacetValue::Keyword("red".to_string()), 3),
+            (FacetValue::Keyword("blue".to_string()), 2),
+            (FacetValue::Keyword("green".to_string()), 4),
+            (FacetValue::Keyword("blue".to_string()), 3),
+            (FacetValue::Keyword("black".to_string()), 0),
+        ];
+
+        let hits = merge_facet_counts(counts.clone(), Some(2));
+        assert_eq!(
+            hits,
+            vec![
+                FacetValueHit {
+                    value: FacetValue::Keyword("blue".to_string()),
+                    count: 5,
+                },
+                FacetValueHit {
+                    value: FacetValue::Keyword("green".to_string()),
+                    count: 4,
+                },
+            ]
+        );
+
+        let hits = merge_facet_counts(counts, None);
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[2].value, FacetValue::Keyword("red".to_string()));
+    }
+}
@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use common::types::ScoreType;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::json_path::JsonPath;
+use crate::types::{DateTimePayloadType, GeoPoint};
+
+/// Variable of a formula: the score of a prefetch, or a payload value of the scored point.
+///
+/// Written as `$score` for the first prefetch, `$score[<index>]` for any prefetch,
+/// or as a payload key otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Variable {
+    /// Score of the point in the prefetch with this index
+    Score(usize),
+    /// Numeric or datetime payload value, datetimes are timestamps in microseconds
+    Payload(JsonPath),
+}
+
+impl Variable {
+    const SCORE: &'static str = "$score";
+}
+
+impl FromStr for Variable {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some(rest) = value.strip_prefix(Self::SCORE) else {
+            return JsonPath::from_str(value)
+                .map(Variable::Payload)
+                .map_err(|_| format!("invalid formula variable: {value}"));
+        };
+        if rest.is_empty() {
+            return Ok(Variable::Score(0));
+        }
+        rest.strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .and_then(|index| index.parse().ok())
+            .map(Variable::Score)
+            .ok_or_else(|| format!("invalid score variable: {value}, expected $score[<index>]"))
+    }
+}
+
+impl TryFrom<String> for Variable {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Variable::Score(index) => write!(f, "{}[{index}]", Self::SCORE),
+            Variable::Payload(key) => write!(f, "{key}"),
+        }
+    }
+}
+
+impl From<Variable> for String {
+    fn from(value: Variable) -> Self {
+        value.to_string()
+    }
+}
+
+impl JsonSchema for Variable {
+    fn schema_name() -> String {
+        "FormulaVariable".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.metadata().description = Some(
+            "Score of a prefetch, `$score` or `$score[<index>]`, \
+            or a payload key of a numeric or datetime value"
+                .to_string(),
+        );
+        schema.into()
+    }
+}
+
+/// Expression, which computes the score of a point
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Constant(f64),
+    Variable(Variable),
+    Sum(Vec<Expression>),
+    Mult(Vec<Expression>),
+    Neg(Box<Expression>),
+    Div {
+        left: Box<Expression>,
+        right: Box<Expression>,
+        /// Result of the division by zero, the formula fails without it
+        by_zero_default: Option<f64>,
+    },
+    Abs(Box<Expression>),
+    Sqrt(Box<Expression>),
+    Pow {
+        base: Box<Expression>,
+        exponent: Box<Expression>,
+    },
+    Exp(Box<Expression>),
+    Log10(Box<Expression>),
+    Ln(Box<Expression>),
+    /// Decay of the distance between a payload value and the origin, from 1.0 down to 0.0
+    Decay {
+        kind: DecayKind,
+        params: DecayParams,
+    },
+    /// Distance in meters from the origin to the geo value of the payload field
+    GeoDistance {
+        key: JsonPath,
+        origin: GeoPoint,
+    },
+}
+
+/// Shape of a decay function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayKind {
+    /// Linear decay, reaches 0.0 at some distance
+    Lin,
+    /// Exponential decay
+    Exp,
+    /// Gaussian decay
+    Gauss,
+}
+
+/// Origin of a decay, its type defines how the payload value is interpreted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum DecayOrigin {
+    /// Numeric value, the distance is the absolute difference
+    Number(f64),
+    /// Geo point, the distance is in meters
+    Geo(GeoPoint),
+    /// Datetime, the distance is in seconds
+    DateTime(DateTimePayloadType),
+}
+
+/// Parameters of a decay function
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecayParams {
+    /// Payload key of the value, whose distance from the origin is decayed
+    pub key: JsonPath,
+    pub origin: DecayOrigin,
+    /// Distance from the origin plus `offset`, at which the decay reaches `decay`
+    pub scale: f64,
+    /// Distance from the origin, within which there is no decay
+    pub offset: f64,
+    /// Value of the decay at the distance `scale` plus `offset`
+    pub decay: f64,
+}
+
+impl DecayParams {
+    pub const DEFAULT_OFFSET: f64 = 0.0;
+
+    pub const DEFAULT_DECAY: f64 = 0.5;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            return Err(format!("decay scale must be positive, got {}", self.scale));
+        }
+        if !(self.offset.is_finite() && self.offset >= 0.0) {
+            return Err(format!(
+                "decay offset must not be negative, got {}",
+                self.offset,
+            ));
+        }
+        if !(self.decay > 0.0 && self.decay < 1.0) {
+            return Err(format!(
+                "decay must be between 0.0 and 1.0 exclusively, got {}",
+                self.decay,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Distance from the origin to the value of the point, `None` if the point has no such value
+    fn distance(&self, input: &impl FormulaInput) -> OperationResult<Option<f64>> {
+        let distance = match &self.origin {
+            DecayOrigin::Number(origin) => input
+                .payload_value(&self.key)?
+                .map(|value| (value - origin).abs()),
+            DecayOrigin::DateTime(origin) => input
+                .payload_value(&self.key)?
+                .map(|micros| (micros - origin.timestamp() as f64).abs() / 1_000_000.0),
+            DecayOrigin::Geo(origin) => input.geo_distance(&self.key, origin)?,
+        };
+        Ok(distance)
+    }
+
+    /// Parameters are expected to be validated when the formula is built from a request
+    fn evaluate(&self, kind: DecayKind, input: &impl FormulaInput) -> OperationResult<f64> {
+        // Points without a value are considered infinitely far away
+        let Some(distance) = self.distance(input)? else {
+            return Ok(0.0);
+        };
+        let distance = (distance - self.offset).max(0.0) / self.scale;
+
+        let value = match kind {
+            DecayKind::Lin => (1.0 - (1.0 - self.decay) * distance).max(0.0),
+            DecayKind::Exp => self.decay.powf(distance),
+            DecayKind::Gauss => self.decay.powf(distance * distance),
+        };
+        Ok(value)
+    }
+}
+
+/// Values of the variables of a single point
+pub trait FormulaInput {
+    /// Score of the point in the prefetch, 0.0 if the prefetch didn't return the point
+    fn score(&self, prefetch: usize) -> ScoreType;
+
+    /// First numeric value of the payload field, `None` if there is no such value.
+    /// Datetimes are timestamps in microseconds.
+    fn payload_value(&self, key: &JsonPath) -> OperationResult<Option<f64>>;
+
+    /// Distance in meters from `origin` to the closest geo value of the payload field,
+    /// `None` if there is no such value
+    fn geo_distance(&self, key: &JsonPath, origin: &GeoPoint) -> OperationResult<Option<f64>>;
+}
+
+impl Expression {
+    /// Largest index of the prefetches, whose scores are used by the expression
+    pub fn max_score_index(&self) -> Option<usize> {
+        match self {
+            Expression::Constant(_) | Expression::Decay { .. } | Expression::GeoDistance { .. } => {
+                None
+            }
+            Expression::Variable(Variable::Score(index)) => Some(*index),
+            Expression::Variable(Variable::Payload(_)) => None,
+            Expression::Sum(expressions) | Expression::Mult(expressions) => expressions
+                .iter()
+                .filter_map(Expression::max_score_index)
+                .max(),
+            Expression::Div { left, right, .. } => {
+                left.max_score_index().max(right.max_score_index())
+            }
+            Expression::Pow { base, exponent } => {
+                base.max_score_index().max(exponent.max_score_index())
+            }
+            Expression::Neg(expression)
+            | Expression::Abs(expression)
+            | Expression::Sqrt(expression)
+            | Expression::Exp(expression)
+            | Expression::Log10(expression)
+            | Expression::Ln(expression) => expression.max_score_index(),
+        }
+    }
+
+    fn evaluate(
+        &self,
+        input: &impl FormulaInput,
+        defaults: &HashMap<JsonPath, f64>,
+    ) -> OperationResult<f64> {
+        let default = |key: &JsonPath| {
+            defaults
+                .get(key)
+                .copied()
+                .ok_or_else(|| OperationError::ValidationError {
+                    description: format!(
+                        "no value of {key} in the payload, and no default value for it",
+                    ),
+                })
+        };
+
+        let value = match self {
+            Expression::Constant(value) => *value,
+            Expression::Variable(Variable::Score(index)) => f64::from(input.score(*index)),
+            Expression::Variable(Variable::Payload(key)) => match input.payload_value(key)? {
+                Some(value) => value,
+                None => default(key)?,
+            },
+            Expression::Sum(expressions) => {
+                let mut sum = 0.0;
+                for expression in expressions {
+                    sum += expression.evaluate(input, defaults)?;
+                }
+                sum
+            }
+            Expression::Mult(expressions) => {
+                let mut product = 1.0;
+                for expression in expressions {
+                    product *= expression.evaluate(input, defaults)?;
+                }
+                product
+            }
+            Expression::Neg(expression) => -expression.evaluate(input, defaults)?,
+            Expression::Div {
+                left,
+                right,
+                by_zero_default,
+            } => {
+                let left = left.evaluate(input, defaults)?;
+                let right = right.evaluate(input, defaults)?;
+                match (right == 0.0, by_zero_default) {
+                    (true, Some(default)) => *default,
+                    _ => left / right,
+                }
+            }
+            Expression::Abs(expression) => expression.evaluate(input, defaults)?.abs(),
+            Expression::Sqrt(expression) => expression.evaluate(input, defaults)?.sqrt(),
+            Expression::Pow { base, exponent } => base
+                .evaluate(input, defaults)?
+                .powf(exponent.evaluate(input, defaults)?),
+            Expression::Exp(expression) => expression.evaluate(input, defaults)?.exp(),
+            Expression::Log10(expression) => expression.evaluate(input, defaults)?.log10(),
+            Expression::Ln(expression) => expression.evaluate(input, defaults)?.ln(),
+            Expression::Decay { kind, params } => params.evaluate(*kind, input)?,
+            Expression::GeoDistance { key, origin } => match input.geo_distance(key, origin)? {
+                Some(distance) => distance,
+                None => default(key)?,
+            },
+        };
+        Ok(value)
+    }
+}
+
+/// Score points with an arbitrary expression over the scores of the prefetches and payload values
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaQuery {
+    pub formula: Expression,
+    /// Values of the payload variables, which points don't have
+    pub defaults: HashMap<JsonPath, f64>,
+}
+
+impl FormulaQuery {
+    /// Score of a single point, fails if it is not a finite number
+    pub fn evaluate(&self, input: &impl FormulaInput) -> OperationResult<ScoreType> {
+        let score = self.formula.evaluate(input, &self.defaults)? as ScoreType;
+        if !score.is_finite() {
+            return Err(OperationError::ValidationError {
+                description: format!("formula evaluated to a non-finite score: {score}"),
+            });
+        }
+        Ok(score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::field_index::geo_index::geo_distance;
+
+    struct TestInput {
+        scores: Vec<ScoreType>,
+        payload: HashMap<JsonPath, f64>,
+    }
+
+    impl FormulaInput for TestInput {
+        fn score(&self, prefetch: usize) -> ScoreType {
+            self.scores.get(prefetch).copied().unwrap_or(0.0)
+        }
+
+        fn payload_value(&self, key: &JsonPath) -> OperationResult<Option<f64>> {
+            Ok(self.payload.get(key).copied())
+        }
+
+        fn geo_distance(&self, key: &JsonPath, origin: &GeoPoint) -> OperationResult<Option<f64>> {
+            // Payload values are longitudes on the equator in the tests
+            Ok(self.payload.get(key).map(|lon| {
+                geo_distance(
+                    origin,
+                    &GeoPoint {
+                        lon: *lon,
+                        lat: 0.0,
+                    },
+                )
+            }))
+        }
+    }
+
+    fn var(variable: &str) -> Box<Expression> {
+        Box::new(Expression::Variable(variable.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!("$score".parse::<Variable>(), Ok(Variable::Score(0)));
+        assert_eq!("$score[2]".parse::<Variable>(), Ok(Variable::Score(2)));
+        assert_eq!(
+            "a.b".parse::<Variable>(),
+            Ok(Variable::Payload(JsonPath::new("a.b")))
+        );
+        assert!("$score[x]".parse::<Variable>().is_err());
+        assert!("$score2".parse::<Variable>().is_err());
+        assert_eq!(Variable::Score(1).to_string(), "$score[1]");
+    }
+
+    #[test]
+    fn test_evaluate_formula() {
+        // 0.7 * $score + 0.2 * ln(popularity) + 0.1 * $score[1]
+        let formula = FormulaQuery {
+            formula: Expression::Sum(vec![
+                Expression::Mult(vec![Expression::Constant(0.7), *var("$score")]),
+                Expression::Mult(vec![
+                    Expression::Constant(0.2),
+                    Expression::Ln(var("popularity")),
+                ]),
+                Expression::Mult(vec![Expression::Constant(0.1), *var("$score[1]")]),
+            ]),
+            defaults: HashMap::from([(JsonPath::new("popularity"), 1.0)]),
+        };
+        assert_eq!(formula.formula.max_score_index(), Some(1));
+
+        let input = TestInput {
+            scores: vec![0.5, 2.0],
+            payload: HashMap::from([(JsonPath::new("popularity"), std::f64::consts::E)]),
+        };
+        let score = formula.evaluate(&input).unwrap();
+        assert!((score - (0.35 + 0.2 + 0.2)).abs() < 1e-6);
+
+        // missing prefetch score and payload value
+        let input = TestInput {
+            scores: vec![1.0],
+            payload: HashMap::new(),
+        };
+        let score = formula.evaluate(&input).unwrap();
+        assert!((score - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_evaluate_formula_errors() {
+        let input = TestInput {
+            scores: vec![],
+            payload: HashMap::new(),
+        };
+
+        let missing = FormulaQuery {
+            formula: *var("price"),
+            defaults: HashMap::new(),
+        };
+        assert!(missing.evaluate(&input).is_err());
+
+        let division = |by_zero_default| FormulaQuery {
+            formula: Expression::Div {
+                left: Box::new(Expression::Constant(1.0)),
+                right: var("$score"),
+                by_zero_default,
+            },
+            defaults: HashMap::new(),
+        };
+        assert!(division(None).evaluate(&input).is_err());
+        assert_eq!(division(Some(3.0)).evaluate(&input).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_evaluate_decay() {
+        let decay = |kind, origin, offset| FormulaQuery {
+            formula: Expression::Decay {
+                kind,
+                params: DecayParams {
+                    key: JsonPath::new("x"),
+                    origin,
+                    scale: 10.0,
+                    offset,
+                    decay: 0.5,
+                },
+            },
+            defaults: HashMap::new(),
+        };
+        let input = |x: f64| TestInput {
+            scores: vec![],
+            payload: HashMap::from([(JsonPath::new("x"), x)]),
+        };
+        let number = DecayOrigin::Number(5.0);
+
+        // at the origin and within the offset there is no decay
+        for kind in [DecayKind::Lin, DecayKind::Exp, DecayKind::Gauss] {
+            assert_eq!(
+                decay(kind, number.clone(), 0.0)
+                    .evaluate(&input(5.0))
+                    .unwrap(),
+                1.0
+            );
+            assert_eq!(
+                decay(kind, number.clone(), 3.0)
+                    .evaluate(&input(2.0))
+                    .unwrap(),
+                1.0
+            );
+            // at the scale, the value is the decay
+            let at_scale = decay(kind, number.clone(), 0.0)
+                .evaluate(&input(-5.0))
+                .unwrap();
+            assert!((at_scale - 0.5).abs() < 1e-6);
+        }
+
+        // linear decay reaches zero, the others approach it
+        assert_eq!(
+            decay(DecayKind::Lin, number.clone(), 0.0)
+                .evaluate(&input(30.0))
+                .unwrap(),
+            0.0
+        );
+        let exp = decay(DecayKind::Exp, number.clone(), 0.0)
+            .evaluate(&input(25.0))
+            .unwrap();
+        let gauss = decay(DecayKind::Gauss, number.clone(), 0.0)
+            .evaluate(&input(25.0))
+            .unwrap();
+        assert!((exp - 0.25).abs() < 1e-6);
+        assert!((gauss - 0.0625).abs() < 1e-6);
+
+        // datetimes are compared in seconds
+        let origin: DateTimePayloadType = "2024-01-01T00:00:00Z".parse().unwrap();
+        let micros = origin.timestamp() as f64 + 10_000_000.0;
+        let datetime = decay(DecayKind::Exp, DecayOrigin::DateTime(origin), 0.0);
+        assert!((datetime.evaluate(&input(micros)).unwrap() - 0.5).abs() < 1e-6);
+
+        // geo points are compared in meters
+        let geo = FormulaQuery {
+            formula: Expression::Decay {
+                kind: DecayKind::Gauss,
+                params: DecayParams {
+                    key: JsonPath::new("x"),
+                    origin: DecayOrigin::Geo(GeoPoint { lon: 0.0, lat: 0.0 }),
+                    scale: 111_195.0,
+                    offset: 0.0,
+                    decay: 0.5,
+                },
+            },
+            defaults: HashMap::new(),
+        };
+        assert!((geo.evaluate(&input(1.0)).unwrap() - 0.5).abs() < 1e-3);
+
+        // points without the value are infinitely far away
+        let empty = TestInput {
+            scores: vec![],
+            payload: HashMap::new(),
+        };
+        assert_eq!(
+            decay(DecayKind::Lin, number.clone(), 0.0)
+                .evaluate(&empty)
+                .unwrap(),
+            0.0
+        );
+
+        // invalid parameters fail the query
+        let invalid = decay(DecayKind::Exp, number, -1.0);
+        assert!(invalid.evaluate(&input(0.0)).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_geo_distance() {
+        let distance = |defaults| FormulaQuery {
+            formula: Expression::GeoDistance {
+                key: JsonPath::new("x"),
+                origin: GeoPoint { lon: 0.0, lat: 0.0 },
+            },
+            defaults,
+        };
+        let input = TestInput {
+            scores: vec![],
+            payload: HashMap::from([(JsonPath::new("x"), 1.0)]),
+        };
+        let meters = distance(HashMap::new()).evaluate(&input).unwrap();
+        assert!((meters - 111_195.0).abs() < 1.0);
+
+        // points without the value use the default, or fail without it
+        let empty = TestInput {
+            scores: vec![],
+            payload: HashMap::new(),
+        };
+        assert!(distance(HashMap::new()).evaluate(&empty).is_err());
+        let with_default = distance(HashMap::from([(JsonPath::new("x"), 1e7)]));
+        assert_eq!(with_default.evaluate(&empty).unwrap(), 1e7);
+    }
+}
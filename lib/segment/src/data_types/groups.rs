@@ -2,6 +2,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use crate::data_types::order_by::Direction;
+use crate::json_path::JsonPath;
+
 /// Value of the group_by key, shared across all the hits in the group
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Clone, Hash)]
 #[serde(untagged)]
@@ -9,6 +12,8 @@ pub enum GroupId {
     String(String),
     NumberU64(u64),
     NumberI64(i64),
+    /// Values of each field, when grouping by several fields at once
+    Compound(Vec<GroupId>),
 }
 
 impl From<u64> for GroupId {
@@ -41,6 +46,9 @@ impl From<GroupId> for serde_json::Value {
             GroupId::String(s) => serde_json::Value::String(s),
             GroupId::NumberU64(n) => json!(n),
             GroupId::NumberI64(n) => json!(n),
+            GroupId::Compound(ids) => {
+                serde_json::Value::Array(ids.into_iter().map(serde_json::Value::from).collect())
+            }
         }
     }
 }
@@ -71,7 +79,49 @@ impl GroupId {
         match self {
             GroupId::NumberI64(id) => u64::try_from(*id).ok(),
             GroupId::NumberU64(id) => Some(*id),
-            GroupId::String(_) => None,
+            GroupId::String(_) | GroupId::Compound(_) => None,
         }
     }
+
+    /// Values of the group key, one per grouped field
+    pub fn components(&self) -> &[GroupId] {
+        match self {
+            GroupId::Compound(ids) => ids,
+            id => std::slice::from_ref(id),
+        }
+    }
+}
+
+/// How the score of a group is aggregated from the scores of its hits
+///
+/// Only the hits collected for the returned groups take part, at most `group_size` per group,
+/// so `Sum`, `Avg` and `Count` are approximate: a group may have more matching points than were
+/// collected, and groups which were not filled in time are not ranked at all.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupScoring {
+    /// Score of the best hit in the group
+    #[default]
+    Best,
+    /// Sum of the scores of the collected hits in the group
+    Sum,
+    /// Average score of the collected hits in the group
+    Avg,
+    /// Number of collected hits in the group, more hits rank higher
+    Count,
+}
+
+/// Payload field to rank groups by, before their score
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct GroupOrderBy {
+    /// Payload key to order groups by. The value is taken from the best hit of each group
+    pub key: JsonPath,
+    /// Direction of ordering: `asc` or `desc`. Default is ascending.
+    pub direction: Option<Direction>,
+}
+
+impl GroupOrderBy {
+    pub fn direction(&self) -> Direction {
+        self.direction.unwrap_or_default()
+    }
 }
@@ -16,6 +16,11 @@ pub struct KeywordIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: KeywordIndexType,
 
+    /// If true - used for tenant optimization: points of the same tenant are stored together
+    /// within segments. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_tenant: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[cfg(any())]
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -43,6 +48,11 @@ pub struct IntegerIndexParams {
     /// If true - support ranges filters.
     pub range: bool,
 
+    /// If true - use this index to store points in segments in the order of its values.
+    /// Only one numeric or datetime index should be principal. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_principal: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg(any())]
@@ -55,6 +65,7 @@ impl Default for IntegerIndexParams {
             r#type: Default::default(),
             lookup: true,
             range: true,
+            is_principal: None,
         }
     }
 }
@@ -74,6 +85,11 @@ pub struct FloatIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: FloatIndexType,
 
+    /// If true - use this index to store points in segments in the order of its values.
+    /// Only one numeric or datetime index should be principal. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_principal: Option<bool>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg(any())]
@@ -176,6 +192,32 @@ pub struct DatetimeIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
     pub r#type: DatetimeIndexType,
 
+    /// If true - use this index to store points in segments in the order of its values.
+    /// Only one numeric or datetime index should be principal. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_principal: Option<bool>,
+
+    /// If true, store the index on disk. Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg(any())]
+    pub on_disk: Option<bool>,
+}
+
+// Geo shape
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoShapeIndexType {
+    #[default]
+    GeoShape,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoShapeIndexParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: GeoShapeIndexType,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg(any())]
@@ -1,8 +1,16 @@
+pub mod aggregations;
+pub mod distinct;
+pub mod document;
+pub mod explain;
+pub mod facets;
+pub mod formula;
 pub mod groups;
 pub mod index;
 pub mod named_vectors;
 pub mod order_by;
 pub mod primitive;
+pub mod principal_range;
+pub mod profile;
 pub mod query_context;
 pub mod tiny_map;
 pub mod vectors;
@@ -1,14 +1,17 @@
+use std::cmp::Ordering;
+use std::iter;
 use std::str::FromStr;
 
 use num_cmp::NumCmp;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, Payload, Range, RangeInterface,
+    DateTimePayloadType, FloatPayloadType, GeoPoint, IntPayloadType, Order, Payload, PointIdType,
+    Range, RangeInterface,
 };
 
 const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
@@ -57,6 +60,8 @@ pub enum StartFrom {
     Float(FloatPayloadType),
 
     Datetime(DateTimePayloadType),
+
+    Keyword(String),
 }
 
 fn order_by_example() -> OrderBy {
@@ -64,6 +69,8 @@ fn order_by_example() -> OrderBy {
         key: JsonPath::from_str("timestamp").unwrap(),
         direction: Some(Direction::Desc),
         start_from: Some(StartFrom::Integer(123)),
+        geo_origin: None,
+        then_by: vec![],
     }
 }
 
@@ -79,6 +86,62 @@ pub struct OrderBy {
 
     /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
     pub start_from: Option<StartFrom>,
+
+    /// Order by distance (in meters) from this location to the geo values of `key`, instead of the values themselves.
+    /// Requires a geo index on `key`. In this case `start_from` is a distance in meters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_origin: Option<GeoPoint>,
+
+    /// Keys to order by, when the values of the previous keys are equal. For example `category`,
+    /// then `price` and then `{"id": "asc"}`. Their `start_from` values continue the `start_from`
+    /// of the first key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[validate(custom = "validate_then_by")]
+    pub then_by: Vec<ThenBy>,
+}
+
+/// Key to order by, when the values of the previous keys are equal
+#[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ThenBy {
+    /// Order by point id
+    Id(IdOrder),
+    /// Order by payload key
+    Key(OrderBy),
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Copy, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IdOrder {
+    /// Direction of ordering by point id
+    pub id: Direction,
+}
+
+fn validate_then_by(then_by: &[ThenBy]) -> Result<(), ValidationError> {
+    let (ids, keys): (Vec<_>, Vec<_>) =
+        then_by.iter().partition(|key| matches!(key, ThenBy::Id(_)));
+    if !ids.is_empty() && (ids.len() > 1 || !matches!(then_by.last(), Some(ThenBy::Id(_)))) {
+        return Err(ValidationError::new(
+            "point id can only be the last then_by key",
+        ));
+    }
+    let keys = keys.into_iter().filter_map(|key| match key {
+        ThenBy::Key(key) => Some(key),
+        ThenBy::Id(_) => None,
+    });
+    for key in keys {
+        if key.is_compound() {
+            return Err(ValidationError::new(
+                "then_by keys can't have their own then_by keys",
+            ));
+        }
+        if key.geo_origin.is_some() {
+            return Err(ValidationError::new(
+                "then_by keys can't be ordered by geo distance",
+            ));
+        }
+    }
+    Ok(())
 }
 
 impl OrderBy {
@@ -96,6 +159,8 @@ impl OrderBy {
                 StartFrom::Datetime(dt) => {
                     RangeInterface::DateTime(self.direction().as_range_from(*dt))
                 }
+                // Keywords are not in range of any number
+                StartFrom::Keyword(_) => RangeInterface::Float(Range::default()),
             })
             .unwrap_or_else(|| RangeInterface::Float(Range::default()))
     }
@@ -107,17 +172,96 @@ impl OrderBy {
     pub fn start_from(&self) -> OrderValue {
         self.start_from
             .as_ref()
-            .map(|start_from| match start_from {
-                StartFrom::Integer(i) => OrderValue::Int(*i),
-                StartFrom::Float(f) => OrderValue::Float(*f),
-                StartFrom::Datetime(dt) => OrderValue::Int(dt.timestamp()),
-            })
+            .map(OrderValue::from)
             .unwrap_or_else(|| match self.direction() {
                 Direction::Asc => OrderValue::MIN,
                 Direction::Desc => OrderValue::MAX,
             })
     }
 
+    /// Whether there are more keys to order by, after this one
+    pub fn is_compound(&self) -> bool {
+        !self.then_by.is_empty()
+    }
+
+    /// All the payload keys to order by, starting with this one
+    pub fn keys(&self) -> impl Iterator<Item = &OrderBy> + '_ {
+        iter::once(self).chain(self.then_by_keys())
+    }
+
+    /// Payload keys to order by after this one, without the point id
+    pub fn then_by_keys(&self) -> impl Iterator<Item = &OrderBy> + '_ {
+        self.then_by.iter().filter_map(|key| match key {
+            ThenBy::Key(key) => Some(key),
+            ThenBy::Id(_) => None,
+        })
+    }
+
+    pub fn then_by_keys_mut(&mut self) -> impl Iterator<Item = &mut OrderBy> + '_ {
+        self.then_by.iter_mut().filter_map(|key| match key {
+            ThenBy::Key(key) => Some(key),
+            ThenBy::Id(_) => None,
+        })
+    }
+
+    /// Direction of ordering by point id, when the values of all the keys are equal.
+    /// Default is the direction of the first key.
+    pub fn id_direction(&self) -> Direction {
+        self.then_by
+            .iter()
+            .find_map(|key| match key {
+                ThenBy::Id(IdOrder { id }) => Some(*id),
+                ThenBy::Key(_) => None,
+            })
+            .unwrap_or_else(|| self.direction())
+    }
+
+    /// Starting value of the compound key, covering the keys up to the last one with `start_from`
+    pub fn compound_start_from(&self) -> Option<OrderValue> {
+        let values = self
+            .keys()
+            .map(|key| key.start_from.as_ref().map(OrderValue::from))
+            .collect::<Vec<_>>();
+        let len = values.iter().rposition(Option::is_some)? + 1;
+        let values = values
+            .into_iter()
+            .zip(self.keys())
+            .take(len)
+            .map(|(value, key)| value.unwrap_or_else(|| key.start_from()))
+            .collect();
+        Some(OrderValue::Compound(values))
+    }
+
+    /// Compares the order values by the directions of the keys: `Less` means that `a` goes first.
+    ///
+    /// Compound values are compared up to the length of the shortest one.
+    pub fn cmp_values(&self, a: &OrderValue, b: &OrderValue) -> Ordering {
+        a.components()
+            .iter()
+            .zip(b.components())
+            .zip(self.keys())
+            .map(|((a, b), key)| match key.direction() {
+                Direction::Asc => a.cmp(b),
+                Direction::Desc => b.cmp(a),
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// Compares the points by their order values, ties are broken by the ids in the direction of
+    /// the id key, or of the first key if there is none: `Less` means that `a` goes first.
+    pub fn cmp_points(
+        &self,
+        (a_value, a_id): (&OrderValue, PointIdType),
+        (b_value, b_id): (&OrderValue, PointIdType),
+    ) -> Ordering {
+        self.cmp_values(a_value, b_value)
+            .then_with(|| match self.id_direction() {
+                Direction::Asc => a_id.cmp(&b_id),
+                Direction::Desc => b_id.cmp(&a_id),
+            })
+    }
+
     pub fn insert_order_value_in_payload(
         payload: Option<Payload>,
         value: impl Into<serde_json::Value>,
@@ -129,13 +273,18 @@ impl OrderBy {
         new_payload
     }
 
+    /// Order value of the points without a value, so that they go last
+    pub fn missing_value(&self) -> OrderValue {
+        match self.direction() {
+            Direction::Asc => OrderValue::MAX,
+            Direction::Desc => OrderValue::MIN,
+        }
+    }
+
     fn json_value_to_ordering_value(&self, value: Option<serde_json::Value>) -> OrderValue {
         value
             .and_then(|v| OrderValue::try_from(v).ok())
-            .unwrap_or_else(|| match self.direction() {
-                Direction::Asc => OrderValue::MAX,
-                Direction::Desc => OrderValue::MIN,
-            })
+            .unwrap_or_else(|| self.missing_value())
     }
 
     pub fn get_order_value_from_payload(&self, payload: Option<&Payload>) -> OrderValue {
@@ -151,11 +300,14 @@ impl OrderBy {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum OrderValue {
     Int(IntPayloadType),
     Float(FloatPayloadType),
+    Keyword(String),
+    /// Values of each key of a compound order
+    Compound(Vec<OrderValue>),
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -164,12 +316,42 @@ impl std::hash::Hash for OrderValue {
         match self {
             OrderValue::Int(i) => i.hash(state),
             OrderValue::Float(f) => f.to_bits().hash(state),
+            OrderValue::Keyword(keyword) => keyword.hash(state),
+            OrderValue::Compound(values) => values.hash(state),
         }
     }
 }
 impl OrderValue {
     const MAX: Self = Self::Float(f64::NAN);
     const MIN: Self = Self::Float(f64::MIN);
+
+    pub fn as_f64(&self) -> f64 {
+        match self {
+            OrderValue::Int(value) => *value as f64,
+            OrderValue::Float(value) => *value,
+            OrderValue::Keyword(_) => f64::NAN,
+            OrderValue::Compound(values) => values.first().map_or(f64::NAN, OrderValue::as_f64),
+        }
+    }
+
+    /// Values of each key, a single one if the value is not compound
+    pub fn components(&self) -> &[OrderValue] {
+        match self {
+            OrderValue::Compound(values) => values,
+            value => std::slice::from_ref(value),
+        }
+    }
+}
+
+impl From<&StartFrom> for OrderValue {
+    fn from(start_from: &StartFrom) -> Self {
+        match start_from {
+            StartFrom::Integer(i) => OrderValue::Int(*i),
+            StartFrom::Float(f) => OrderValue::Float(*f),
+            StartFrom::Datetime(dt) => OrderValue::Int(dt.timestamp()),
+            StartFrom::Keyword(keyword) => OrderValue::Keyword(keyword.clone()),
+        }
+    }
 }
 
 impl From<OrderValue> for serde_json::Value {
@@ -179,6 +361,10 @@ impl From<OrderValue> for serde_json::Value {
                 .map(serde_json::Value::Number)
                 .unwrap_or(serde_json::Value::Null),
             OrderValue::Int(value) => serde_json::Value::Number(serde_json::Number::from(value)),
+            OrderValue::Keyword(value) => serde_json::Value::String(value),
+            OrderValue::Compound(values) => {
+                serde_json::Value::Array(values.into_iter().map(serde_json::Value::from).collect())
+            }
         }
     }
 }
@@ -187,11 +373,19 @@ impl TryFrom<serde_json::Value> for OrderValue {
     type Error = ();
 
     fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
-        value
-            .as_i64()
-            .map(Self::from)
-            .or_else(|| value.as_f64().map(Self::from))
-            .ok_or(())
+        match value {
+            serde_json::Value::String(keyword) => Ok(Self::Keyword(keyword)),
+            serde_json::Value::Array(values) => values
+                .into_iter()
+                .map(Self::try_from)
+                .collect::<Result<_, _>>()
+                .map(Self::Compound),
+            value => value
+                .as_i64()
+                .map(Self::from)
+                .or_else(|| value.as_f64().map(Self::from))
+                .ok_or(()),
+        }
     }
 }
 
@@ -211,34 +405,39 @@ impl Eq for OrderValue {}
 
 impl PartialEq for OrderValue {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (OrderValue::Float(a), OrderValue::Float(b)) => OrderedFloat(*a) == OrderedFloat(*b),
-            (OrderValue::Int(a), OrderValue::Int(b)) => a == b,
-            (OrderValue::Float(a), OrderValue::Int(b)) => a.num_eq(*b),
-            (OrderValue::Int(a), OrderValue::Float(b)) => a.num_eq(*b),
-        }
+        self.cmp(other).is_eq()
     }
 }
 
 impl PartialOrd for OrderValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
 impl Ord for OrderValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    /// Numbers go before keywords, except for the NaN, which is greater than any other value.
+    /// Compound values are compared key by key, in ascending order.
+    fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
+            (OrderValue::Compound(_), _) | (_, OrderValue::Compound(_)) => {
+                self.components().cmp(other.components())
+            }
+            (OrderValue::Keyword(a), OrderValue::Keyword(b)) => a.cmp(b),
+            (OrderValue::Keyword(_), OrderValue::Float(b)) if b.is_nan() => Ordering::Less,
+            (OrderValue::Keyword(_), _) => Ordering::Greater,
+            (OrderValue::Float(a), OrderValue::Keyword(_)) if a.is_nan() => Ordering::Greater,
+            (_, OrderValue::Keyword(_)) => Ordering::Less,
             (OrderValue::Float(a), OrderValue::Float(b)) => OrderedFloat(*a).cmp(&OrderedFloat(*b)),
             (OrderValue::Int(a), OrderValue::Int(b)) => a.cmp(b),
             (OrderValue::Float(a), OrderValue::Int(b)) => {
                 // num_cmp() might return None only if the float value is NaN. We follow the
                 // OrderedFloat logic here: the NaN is always greater than any other value.
-                a.num_cmp(*b).unwrap_or(std::cmp::Ordering::Greater)
+                a.num_cmp(*b).unwrap_or(Ordering::Greater)
             }
             (OrderValue::Int(a), OrderValue::Float(b)) => {
                 // Ditto, but the NaN is on the right side of the comparison.
-                a.num_cmp(*b).unwrap_or(std::cmp::Ordering::Less)
+                a.num_cmp(*b).unwrap_or(Ordering::Less)
             }
         }
     }
@@ -248,7 +447,7 @@ impl Ord for OrderValue {
 mod tests {
     use proptest::proptest;
 
-    use crate::data_types::order_by::OrderValue;
+    use super::*;
 
     proptest! {
 
@@ -266,4 +465,74 @@ mod tests {
             assert!(OrderValue::MAX.cmp(&OrderValue::from(f64::NAN)).is_ge());
         }
     }
+
+    #[test]
+    fn test_compound_ordering() {
+        let key = |key: &str, direction| OrderBy {
+            key: key.parse().unwrap(),
+            direction: Some(direction),
+            start_from: None,
+            geo_origin: None,
+            then_by: vec![],
+        };
+        let order_by = OrderBy {
+            then_by: vec![ThenBy::Key(key("price", Direction::Desc))],
+            ..key("category", Direction::Asc)
+        };
+        let value = |category: &str, price: f64| {
+            OrderValue::Compound(vec![
+                OrderValue::Keyword(category.to_string()),
+                OrderValue::Float(price),
+            ])
+        };
+
+        let cmp = |a, b| order_by.cmp_values(&a, &b);
+        assert!(cmp(value("books", 10.0), value("games", 20.0)).is_lt());
+        assert!(cmp(value("books", 20.0), value("books", 10.0)).is_lt());
+        assert!(cmp(value("books", 10.0), value("books", 10.0)).is_eq());
+
+        // missing values go last in both directions
+        assert!(cmp(
+            value("books", 10.0),
+            OrderValue::Compound(vec![OrderValue::MAX, OrderValue::Float(30.0)])
+        )
+        .is_lt());
+        assert!(cmp(value("books", 10.0), value("books", f64::MIN)).is_lt());
+
+        // partial start covers only the first key
+        let start = OrderValue::Compound(vec![OrderValue::Keyword("books".to_string())]);
+        assert!(cmp(value("books", 0.0), start).is_eq());
+
+        // ties are broken by ids, in the direction of the first key
+        let a = value("books", 10.0);
+        assert!(order_by
+            .cmp_points((&a, PointIdType::NumId(1)), (&a, PointIdType::NumId(2)))
+            .is_lt());
+
+        // or in the direction of the explicit id key
+        let mut order_by = order_by;
+        order_by.then_by.push(ThenBy::Id(IdOrder {
+            id: Direction::Desc,
+        }));
+        assert!(order_by.validate().is_ok());
+        assert!(order_by
+            .cmp_points((&a, PointIdType::NumId(1)), (&a, PointIdType::NumId(2)))
+            .is_gt());
+
+        // the id key goes last
+        order_by
+            .then_by
+            .push(ThenBy::Key(key("price", Direction::Asc)));
+        assert!(order_by.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_then_by() {
+        let order_by: OrderBy = serde_json::from_str(
+            r#"{"key": "category", "then_by": [{"key": "price", "direction": "desc"}, {"id": "asc"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(order_by.then_by_keys().count(), 1);
+        assert_eq!(order_by.id_direction(), Direction::Asc);
+    }
 }
@@ -0,0 +1,140 @@
+use crate::data_types::order_by::OrderValue;
+use crate::types::{Condition, Filter, PayloadKeyType, Range, RangeInterface};
+
+/// Minimal and maximal values of the principal field among points of a segment.
+///
+/// Points of optimized segments are ordered by the principal field, so ranges of segments
+/// rarely overlap, and a segment can be skipped if its range can't match the filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrincipalRange {
+    pub key: PayloadKeyType,
+    pub min: OrderValue,
+    pub max: OrderValue,
+}
+
+impl PrincipalRange {
+    /// Range covering both ranges, if they are of the same field
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        (self.key == other.key).then(|| Self {
+            key: self.key.clone(),
+            min: self.min.clone().min(other.min.clone()),
+            max: self.max.clone().max(other.max.clone()),
+        })
+    }
+
+    /// Check if points of the range might match the filter.
+    ///
+    /// Only range conditions on the principal field in `must`, `should` and `min_should`
+    /// clauses are considered. So `true` doesn't mean there is a match, but `false` means
+    /// there is none. `must_not` can't be checked, as points without the principal field
+    /// are not in the range, but still match it.
+    pub fn may_match(&self, filter: &Filter) -> bool {
+        let must = filter.must.as_ref().map_or(true, |must| {
+            must.iter()
+                .all(|condition| self.may_match_condition(condition))
+        });
+        let should = filter.should.as_ref().map_or(true, |should| {
+            should.is_empty()
+                || should
+                    .iter()
+                    .any(|condition| self.may_match_condition(condition))
+        });
+        let min_should = filter.min_should.as_ref().map_or(true, |min_should| {
+            min_should
+                .conditions
+                .iter()
+                .filter(|condition| self.may_match_condition(condition))
+                .take(min_should.min_count)
+                .count()
+                == min_should.min_count
+        });
+        must && should && min_should
+    }
+
+    fn may_match_condition(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Field(field) if field.key == self.key => field
+                .range
+                .as_ref()
+                .map_or(true, |range| self.overlaps(range)),
+            Condition::Filter(filter) => self.may_match(filter),
+            _ => true,
+        }
+    }
+
+    fn overlaps(&self, range: &RangeInterface) -> bool {
+        // Datetime values are indexed as microseconds since epoch
+        let range: Range<f64> = match range {
+            RangeInterface::Float(range) => range.clone(),
+            RangeInterface::DateTime(range) => range.map(|dt| dt.timestamp() as f64),
+            // Unresolved relative ranges are rejected by the segment, nothing to prune by
+            RangeInterface::RelativeDateTime(_) => return true,
+        };
+
+        let (min, max) = (self.min.as_f64(), self.max.as_f64());
+
+        range.gt.map_or(true, |gt| max > gt)
+            && range.gte.map_or(true, |gte| max >= gte)
+            && range.lt.map_or(true, |lt| min < lt)
+            && range.lte.map_or(true, |lte| min <= lte)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json_path::JsonPath;
+    use crate::types::FieldCondition;
+
+    fn range_filter(key: &str, gte: Option<f64>, lt: Option<f64>) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_range(
+            JsonPath::new(key),
+            Range {
+                lt,
+                gt: None,
+                gte,
+                lte: None,
+            },
+        )))
+    }
+
+    #[test]
+    fn test_principal_range_may_match() {
+        let range = PrincipalRange {
+            key: JsonPath::new("created_at"),
+            min: OrderValue::Int(10),
+            max: OrderValue::Int(20),
+        };
+
+        assert!(range.may_match(&range_filter("created_at", Some(15.0), None)));
+        assert!(range.may_match(&range_filter("created_at", Some(20.0), Some(30.0))));
+        assert!(range.may_match(&range_filter("created_at", None, Some(10.5))));
+        assert!(!range.may_match(&range_filter("created_at", Some(20.5), None)));
+        assert!(!range.may_match(&range_filter("created_at", None, Some(10.0))));
+
+        // Conditions on other fields are not considered
+        assert!(range.may_match(&range_filter("other", Some(100.0), None)));
+
+        // At least one of `should` conditions must be in range
+        let out_of_range = Condition::Filter(range_filter("created_at", Some(100.0), None));
+        let in_range = Condition::Filter(range_filter("created_at", Some(15.0), None));
+        assert!(!range.may_match(&Filter::new_should(out_of_range.clone())));
+        let should = Filter {
+            should: Some(vec![out_of_range.clone(), in_range]),
+            ..Default::default()
+        };
+        assert!(range.may_match(&should));
+
+        // Points without the principal field match `must_not`
+        assert!(range.may_match(&Filter::new_must_not(out_of_range)));
+
+        let other = PrincipalRange {
+            key: JsonPath::new("created_at"),
+            min: OrderValue::Int(30),
+            max: OrderValue::Int(40),
+        };
+        let union = range.union(&other).unwrap();
+        assert_eq!(union.min, OrderValue::Int(10));
+        assert_eq!(union.max, OrderValue::Int(40));
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::data_types::explain::{SegmentExplanation, ShardExplanation};
+
+/// Stage of the query execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileStage {
+    /// Whole query, as executed by the collection
+    Query,
+    /// Fetching vectors of points, referenced by id in the query
+    Lookup,
+    /// Query on a single shard, including all of its replicas
+    Shard,
+    /// Query on the replica of a shard, located on this peer
+    Local,
+    /// Round trip to the replica of a shard, located on another peer
+    Remote,
+    /// Nested prefetch, including its own prefetches
+    Prefetch,
+    /// Scoring points against vectors
+    Scoring,
+    /// Selecting points by filter and payload order, without vector scoring
+    Filtering,
+    /// Scoring results of prefetches with the query
+    Rescoring,
+    /// Fusion of results of prefetches
+    Fusion,
+    /// Fetching payload and vectors of the selected points
+    Fetching,
+    /// Merging results of shards
+    Merging,
+}
+
+/// Time spent on a stage of the query, with the breakdown into nested stages
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct QueryProfile {
+    pub stage: ProfileStage,
+    /// Shard, the stage was executed on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_id: Option<u32>,
+    /// Time spent on the stage, including nested stages, in seconds
+    pub time: f64,
+    /// Nested stages. Stages of the same level may run concurrently, so their time may overlap.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<QueryProfile>,
+    /// How the segments have executed the stage, if the query is explained.
+    /// Reported per shard replica with [`QueryProfile::take_explanations`], not as a part of the profile.
+    #[serde(skip)]
+    pub segments: Vec<SegmentExplanation>,
+}
+
+impl QueryProfile {
+    pub fn new(stage: ProfileStage, time: Duration) -> Self {
+        Self {
+            stage,
+            shard_id: None,
+            time: time.as_secs_f64(),
+            children: Vec::new(),
+            segments: Vec::new(),
+        }
+    }
+
+    pub fn with_shard_id(mut self, shard_id: u32) -> Self {
+        self.shard_id = Some(shard_id);
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<QueryProfile>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn with_segments(mut self, segments: Vec<SegmentExplanation>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Move the explanations of segments out of the profile, one per replica of each shard.
+    ///
+    /// Profile of a remote replica contains the shard stage of its own peer, its segments are
+    /// reported as a part of the remote replica.
+    pub fn take_explanations(&mut self) -> Vec<ShardExplanation> {
+        let mut explanations = Vec::new();
+        self.take_shard_explanations(&mut explanations);
+        explanations.sort_by_key(|explanation| explanation.shard_id);
+        explanations
+    }
+
+    fn take_shard_explanations(&mut self, explanations: &mut Vec<ShardExplanation>) {
+        match (self.stage, self.shard_id) {
+            (ProfileStage::Shard, Some(shard_id)) => {
+                explanations.extend(self.children.iter_mut().map(|replica| ShardExplanation {
+                    shard_id,
+                    segments: replica.take_segments(),
+                }));
+            }
+            _ => {
+                for child in &mut self.children {
+                    child.take_shard_explanations(explanations);
+                }
+            }
+        }
+    }
+
+    fn take_segments(&mut self) -> Vec<SegmentExplanation> {
+        let mut segments = std::mem::take(&mut self.segments);
+        for child in &mut self.children {
+            segments.extend(child.take_segments());
+        }
+        segments
+    }
+}
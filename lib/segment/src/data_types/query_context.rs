@@ -4,8 +4,10 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitSlice;
+use parking_lot::Mutex;
 use sparse::common::types::{DimId, DimWeight};
 
+use crate::data_types::explain::{SearchTrace, SegmentExplanation};
 use crate::data_types::tiny_map;
 
 #[derive(Debug)]
@@ -26,6 +28,10 @@ pub struct QueryContext {
     /// Required for processing sparse vector search with `idf-dot` similarity.
     #[allow(dead_code)]
     idf: tiny_map::TinyMap<String, HashMap<DimId, usize>>,
+
+    /// How each segment has executed the searches.
+    /// Only collected if the request is explained.
+    explanations: Option<Mutex<Vec<SegmentExplanation>>>,
 }
 
 impl QueryContext {
@@ -35,6 +41,7 @@ impl QueryContext {
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
             idf: tiny_map::TinyMap::new(),
+            explanations: None,
         }
     }
 
@@ -47,6 +54,19 @@ impl QueryContext {
         self
     }
 
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explanations = explain.then(Mutex::default);
+        self
+    }
+
+    /// Explanations of the segment searches, collected so far
+    pub fn take_explanations(&self) -> Vec<SegmentExplanation> {
+        self.explanations
+            .as_ref()
+            .map(|explanations| std::mem::take(&mut *explanations.lock()))
+            .unwrap_or_default()
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -109,6 +129,7 @@ impl<'a> SegmentQueryContext<'a> {
                 is_stopped: Some(&query_context.is_stopped),
                 idf: query_context.idf.get(vector_name),
                 deleted_points: self.deleted_points,
+                trace: None,
             }
         } else {
             VectorQueryContext {
@@ -122,6 +143,20 @@ impl<'a> SegmentQueryContext<'a> {
         self.deleted_points = Some(deleted_points);
         self
     }
+
+    pub fn is_explained(&self) -> bool {
+        self.query_context
+            .map_or(false, |query_context| query_context.explanations.is_some())
+    }
+
+    pub fn add_explanation(&self, explanation: SegmentExplanation) {
+        if let Some(explanations) = self
+            .query_context
+            .and_then(|query_context| query_context.explanations.as_ref())
+        {
+            explanations.lock().push(explanation);
+        }
+    }
 }
 
 /// Query context related to a specific vector
@@ -139,6 +174,9 @@ pub struct VectorQueryContext<'a> {
     idf: Option<&'a HashMap<DimId, usize>>,
 
     deleted_points: Option<&'a BitSlice>,
+
+    /// Record of the search, if it is explained
+    trace: Option<&'a Mutex<SearchTrace>>,
 }
 
 pub enum SimpleCow<'a, T> {
@@ -157,7 +195,23 @@ impl<'a, T> Deref for SimpleCow<'a, T> {
     }
 }
 
-impl VectorQueryContext<'_> {
+impl<'a> VectorQueryContext<'a> {
+    pub fn with_trace(mut self, trace: &'a Mutex<SearchTrace>) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+
+    pub fn is_traced(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Record what the search has done, if it is explained
+    pub fn trace(&self, f: impl FnOnce(&mut SearchTrace)) {
+        if let Some(trace) = self.trace {
+            f(&mut trace.lock());
+        }
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -211,6 +265,7 @@ impl Default for VectorQueryContext<'_> {
             is_stopped: None,
             idf: None,
             deleted_points: None,
+            trace: None,
         }
     }
 }
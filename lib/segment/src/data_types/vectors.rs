@@ -12,7 +12,9 @@ use super::named_vectors::NamedVectors;
 use super::primitive::PrimitiveVectorElement;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::transpose_map_into_named_vector;
-use crate::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
+use crate::vector_storage::query::{
+    ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery, TransformInto,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Vector {
@@ -660,6 +662,8 @@ impl NamedQuery<RecoQuery<Vector>> {
 pub enum QueryVector {
     Nearest(Vector),
     Recommend(RecoQuery<Vector>),
+    RecommendSumScores(RecoSumQuery<Vector>),
+    RecommendContrastive(ContrastiveQuery<Vector>),
     Discovery(DiscoveryQuery<Vector>),
     Context(ContextQuery<Vector>),
 }
@@ -672,6 +676,12 @@ impl TransformInto<QueryVector, Vector, Vector> for QueryVector {
         match self {
             QueryVector::Nearest(v) => f(v).map(QueryVector::Nearest),
             QueryVector::Recommend(v) => Ok(QueryVector::Recommend(v.transform(&mut f)?)),
+            QueryVector::RecommendSumScores(v) => {
+                Ok(QueryVector::RecommendSumScores(v.transform(&mut f)?))
+            }
+            QueryVector::RecommendContrastive(v) => {
+                Ok(QueryVector::RecommendContrastive(v.transform(&mut f)?))
+            }
             QueryVector::Discovery(v) => Ok(QueryVector::Discovery(v.transform(&mut f)?)),
             QueryVector::Context(v) => Ok(QueryVector::Context(v.transform(&mut f)?)),
         }
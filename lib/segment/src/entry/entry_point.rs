@@ -1,9 +1,13 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use common::types::TelemetryDetail;
+use common::types::{ScoreType, TelemetryDetail};
 
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
+use crate::data_types::aggregations::{AggregationParams, NumericAggregation};
+use crate::data_types::distinct::{CardinalitySketch, DistinctParams};
+use crate::data_types::facets::{FacetParams, FacetValue};
+use crate::data_types::formula::FormulaQuery;
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
@@ -113,7 +117,18 @@ pub trait SegmentEntry {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
-    ) -> Vec<PointIdType>;
+    ) -> OperationResult<Vec<PointIdType>>;
+
+    /// Uniformly random sample of up to `limit` points which satisfy the filtering condition,
+    /// each with its sampling key, in descending order of the keys.
+    ///
+    /// Keys are distributed as the largest random keys of all points which satisfy the condition,
+    /// so merging samples by the largest keys gives a uniform sample of all of them.
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> OperationResult<Vec<(ScoreType, PointIdType)>>;
 
     /// Return points which satisfies filtering condition ordered by the `order_by.key` field,
     /// starting with `order_by.start_from` value including.
@@ -126,6 +141,30 @@ pub trait SegmentEntry {
         order_by: &'a OrderBy,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
 
+    /// Count points which satisfy the filtering condition, per each value of the `request.key` field.
+    ///
+    /// Will fail if there is no keyword, integer or bool index for the key.
+    fn facet(&self, request: &FacetParams) -> OperationResult<HashMap<FacetValue, usize>>;
+
+    /// Aggregate numeric values of the `request.key` field over points which satisfy the filtering condition.
+    ///
+    /// Uses the numeric index of the field if there is one, otherwise reads values from payload.
+    fn aggregate(&self, request: &AggregationParams) -> OperationResult<NumericAggregation>;
+
+    /// Sketch distinct values of the `request.key` field among points which satisfy the filtering condition.
+    ///
+    /// Uses the keyword, integer or bool index of the field if there is one, otherwise reads values from payload.
+    fn distinct(&self, request: &DistinctParams) -> OperationResult<CardinalitySketch>;
+
+    /// Score the points of the prefetches with the formula.
+    ///
+    /// Only points stored in this segment are returned, in no particular order.
+    fn rescore_with_formula(
+        &self,
+        formula: &FormulaQuery,
+        prefetches_scores: &[HashMap<PointIdType, ScoreType>],
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
     /// Read points in [from; to) range
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType>;
 
@@ -133,7 +172,10 @@ pub trait SegmentEntry {
     fn has_point(&self, point_id: PointIdType) -> bool;
 
     /// Estimate available point count in this segment for given filter.
-    fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation;
+    fn estimate_point_count<'a>(
+        &'a self,
+        filter: Option<&'a Filter>,
+    ) -> OperationResult<CardinalityEstimation>;
 
     fn vector_names(&self) -> HashSet<String>;
 
@@ -213,6 +213,26 @@ impl BinaryIndex {
     pub fn values_has_false(&self, point_id: PointOffsetType) -> bool {
         self.memory.get(point_id).has_false()
     }
+
+    /// Number of points which have a true value
+    pub fn trues_count(&self) -> usize {
+        self.memory.trues_count()
+    }
+
+    /// Number of points which have a false value
+    pub fn falses_count(&self) -> usize {
+        self.memory.falses_count()
+    }
+
+    /// Iterate over points which have a true value
+    pub fn iter_has_true(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.memory.iter_has_true()
+    }
+
+    /// Iterate over points which have a false value
+    pub fn iter_has_false(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.memory.iter_has_false()
+    }
 }
 
 impl PayloadFieldIndex for BinaryIndex {
@@ -0,0 +1,116 @@
+use common::types::PointOffsetType;
+use smol_str::SmolStr;
+
+use super::binary_index::BinaryIndex;
+use super::map_index::MapIndex;
+use crate::data_types::facets::FacetValueRef;
+use crate::types::IntPayloadType;
+
+/// Field index which can be used to count points per value
+pub enum FacetIndex<'a> {
+    Keyword(&'a MapIndex<SmolStr>),
+    Int(&'a MapIndex<IntPayloadType>),
+    Bool(&'a BinaryIndex),
+}
+
+type PointsIterator<'a> = Box<dyn Iterator<Item = PointOffsetType> + 'a>;
+
+impl<'a> FacetIndex<'a> {
+    /// Values of the point
+    pub fn get_values(
+        &self,
+        point_id: PointOffsetType,
+    ) -> Box<dyn Iterator<Item = FacetValueRef<'a>> + 'a> {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|keyword| FacetValueRef::Keyword(keyword.as_str())),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .get_values(point_id)
+                    .into_iter()
+                    .flatten()
+                    .map(FacetValueRef::Int),
+            ),
+            FacetIndex::Bool(index) => {
+                let has_true = index.values_has_true(point_id).then_some(true);
+                let has_false = index.values_has_false(point_id).then_some(false);
+                Box::new(
+                    has_true
+                        .into_iter()
+                        .chain(has_false)
+                        .map(FacetValueRef::Bool),
+                )
+            }
+        }
+    }
+
+    /// Iterate over all values along with the points having them
+    pub fn iter_values_map(
+        &self,
+    ) -> Box<dyn Iterator<Item = (FacetValueRef<'a>, PointsIterator<'a>)> + 'a> {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .iter_values_map()
+                    .map(|(keyword, points)| (FacetValueRef::Keyword(keyword.as_str()), points)),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .iter_values_map()
+                    .map(|(int, points)| (FacetValueRef::Int(int), points)),
+            ),
+            FacetIndex::Bool(index) => Box::new(
+                [
+                    (
+                        FacetValueRef::Bool(true),
+                        Box::new(index.iter_has_true()) as PointsIterator<'a>,
+                    ),
+                    (
+                        FacetValueRef::Bool(false),
+                        Box::new(index.iter_has_false()) as PointsIterator<'a>,
+                    ),
+                ]
+                .into_iter(),
+            ),
+        }
+    }
+
+    /// Iterate over all values along with the number of points having them
+    pub fn iter_counts_per_value(
+        &self,
+    ) -> Box<dyn Iterator<Item = (FacetValueRef<'a>, usize)> + 'a> {
+        match *self {
+            FacetIndex::Keyword(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(keyword, count)| (FacetValueRef::Keyword(keyword.as_str()), count)),
+            ),
+            FacetIndex::Int(index) => Box::new(
+                index
+                    .iter_counts_per_value()
+                    .map(|(int, count)| (FacetValueRef::Int(int), count)),
+            ),
+            FacetIndex::Bool(index) => Box::new(
+                [
+                    (FacetValueRef::Bool(true), index.trues_count()),
+                    (FacetValueRef::Bool(false), index.falses_count()),
+                ]
+                .into_iter(),
+            ),
+        }
+    }
+
+    /// Total number of point-value pairs in the index
+    pub fn values_count(&self) -> usize {
+        match *self {
+            FacetIndex::Keyword(index) => index.get_values_count(),
+            FacetIndex::Int(index) => index.get_values_count(),
+            FacetIndex::Bool(index) => index.trues_count() + index.falses_count(),
+        }
+    }
+}
@@ -1,17 +1,21 @@
 use std::fmt::Formatter;
 
 use common::types::PointOffsetType;
+use itertools::Either;
 use serde_json::Value;
 use smol_str::SmolStr;
 
+use super::facet_index::FacetIndex;
 use super::map_index::MapIndex;
 use super::numeric_index::StreamRange;
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
-use crate::data_types::order_by::OrderValue;
+use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::index::field_index::binary_index::BinaryIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
+use crate::index::field_index::geo_index::geo_shape_index::GeoShapeIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::telemetry::PayloadIndexTelemetry;
@@ -103,8 +107,10 @@ pub enum FieldIndex {
     KeywordIndex(MapIndex<SmolStr>),
     FloatIndex(NumericIndex<FloatPayloadType>),
     GeoIndex(GeoMapIndex),
+    GeoShapeIndex(GeoShapeIndex),
     FullTextIndex(FullTextIndex),
     BinaryIndex(BinaryIndex),
+    NullIndex(NullIndex),
 }
 
 impl std::fmt::Debug for FieldIndex {
@@ -116,8 +122,10 @@ impl std::fmt::Debug for FieldIndex {
             FieldIndex::KeywordIndex(_index) => write!(f, "KeywordIndex"),
             FieldIndex::FloatIndex(_index) => write!(f, "FloatIndex"),
             FieldIndex::GeoIndex(_index) => write!(f, "GeoIndex"),
+            FieldIndex::GeoShapeIndex(_index) => write!(f, "GeoShapeIndex"),
             FieldIndex::BinaryIndex(_index) => write!(f, "BinaryIndex"),
             FieldIndex::FullTextIndex(_index) => write!(f, "FullTextIndex"),
+            FieldIndex::NullIndex(_index) => write!(f, "NullIndex"),
         }
     }
 }
@@ -142,7 +150,9 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(_) => None,
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
+            FieldIndex::GeoShapeIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
+            FieldIndex::NullIndex(_) => None,
             FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
                 Some(Match::Text(MatchText { text })) => {
                     let query = full_text_index.parse_query(text);
@@ -167,8 +177,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
             FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
+            FieldIndex::GeoShapeIndex(payload_field_index) => payload_field_index,
             FieldIndex::BinaryIndex(payload_field_index) => payload_field_index,
             FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
+            FieldIndex::NullIndex(payload_field_index) => payload_field_index,
         }
     }
 
@@ -180,8 +192,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FloatIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::GeoIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::GeoShapeIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::BinaryIndex(ref mut payload_field_index) => payload_field_index.load(),
             FieldIndex::FullTextIndex(ref mut payload_field_index) => payload_field_index.load(),
+            FieldIndex::NullIndex(ref mut payload_field_index) => payload_field_index.load(),
         }
     }
 
@@ -193,8 +207,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(index) => index.clear(),
             FieldIndex::FloatIndex(index) => index.clear(),
             FieldIndex::GeoIndex(index) => index.clear(),
+            FieldIndex::GeoShapeIndex(index) => index.clear(),
             FieldIndex::BinaryIndex(index) => index.clear(),
             FieldIndex::FullTextIndex(index) => index.clear(),
+            FieldIndex::NullIndex(index) => index.clear(),
         }
     }
 
@@ -206,8 +222,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(index) => index.recreate(),
             FieldIndex::FloatIndex(index) => index.recreate(),
             FieldIndex::GeoIndex(index) => index.recreate(),
+            FieldIndex::GeoShapeIndex(index) => index.recreate(),
             FieldIndex::BinaryIndex(index) => index.recreate(),
             FieldIndex::FullTextIndex(index) => index.recreate(),
+            FieldIndex::NullIndex(index) => index.recreate(),
         }
     }
 
@@ -263,12 +281,18 @@ impl FieldIndex {
             FieldIndex::GeoIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::GeoShapeIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
             FieldIndex::BinaryIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
             FieldIndex::FullTextIndex(ref mut payload_field_index) => {
                 payload_field_index.add_point(id, payload)
             }
+            FieldIndex::NullIndex(ref mut payload_field_index) => {
+                payload_field_index.add_point(id, payload)
+            }
         }
     }
 
@@ -280,8 +304,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(index) => index.remove_point(point_id),
             FieldIndex::FloatIndex(index) => index.remove_point(point_id),
             FieldIndex::GeoIndex(index) => index.remove_point(point_id),
+            FieldIndex::GeoShapeIndex(index) => index.remove_point(point_id),
             FieldIndex::BinaryIndex(index) => index.remove_point(point_id),
             FieldIndex::FullTextIndex(index) => index.remove_point(point_id),
+            FieldIndex::NullIndex(index) => index.remove_point(point_id),
         }
     }
 
@@ -293,8 +319,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(index) => index.get_telemetry_data(),
             FieldIndex::FloatIndex(index) => index.get_telemetry_data(),
             FieldIndex::GeoIndex(index) => index.get_telemetry_data(),
+            FieldIndex::GeoShapeIndex(index) => index.get_telemetry_data(),
             FieldIndex::BinaryIndex(index) => index.get_telemetry_data(),
             FieldIndex::FullTextIndex(index) => index.get_telemetry_data(),
+            FieldIndex::NullIndex(index) => index.get_telemetry_data(),
         }
     }
 
@@ -306,8 +334,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(index) => index.values_count(point_id),
             FieldIndex::FloatIndex(index) => index.values_count(point_id),
             FieldIndex::GeoIndex(index) => index.values_count(point_id),
+            FieldIndex::GeoShapeIndex(index) => index.values_count(point_id),
             FieldIndex::BinaryIndex(index) => index.values_count(point_id),
             FieldIndex::FullTextIndex(index) => index.values_count(point_id),
+            FieldIndex::NullIndex(index) => index.values_count(point_id),
         }
     }
 
@@ -319,8 +349,10 @@ impl FieldIndex {
             FieldIndex::KeywordIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FloatIndex(index) => index.values_is_empty(point_id),
             FieldIndex::GeoIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::GeoShapeIndex(index) => index.values_is_empty(point_id),
             FieldIndex::BinaryIndex(index) => index.values_is_empty(point_id),
             FieldIndex::FullTextIndex(index) => index.values_is_empty(point_id),
+            FieldIndex::NullIndex(index) => index.values_is_empty(point_id),
         }
     }
 
@@ -332,8 +364,61 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
             | FieldIndex::GeoIndex(_)
+            | FieldIndex::GeoShapeIndex(_)
             | FieldIndex::BinaryIndex(_)
-            | FieldIndex::FullTextIndex(_) => None,
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::NullIndex(_) => None,
+        }
+    }
+
+    /// Index, which can stream the points in order of the values: numeric or keyword one
+    pub fn as_ordering(&self) -> Option<OrderingFieldIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(OrderingFieldIndex::Keyword(index)),
+            index => index.as_numeric().map(OrderingFieldIndex::Numeric),
+        }
+    }
+
+    pub fn as_datetime(&self) -> Option<&NumericIndex<IntPayloadType>> {
+        match self {
+            FieldIndex::DatetimeIndex(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    pub fn as_geo(&self) -> Option<&GeoMapIndex> {
+        match self {
+            FieldIndex::GeoIndex(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    pub fn as_keyword(&self) -> Option<&MapIndex<SmolStr>> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    pub fn as_facet_index(&self) -> Option<FacetIndex> {
+        match self {
+            FieldIndex::KeywordIndex(index) => Some(FacetIndex::Keyword(index)),
+            FieldIndex::IntMapIndex(index) => Some(FacetIndex::Int(index)),
+            FieldIndex::BinaryIndex(index) => Some(FacetIndex::Bool(index)),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::GeoShapeIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::NullIndex(_) => None,
+        }
+    }
+
+    pub fn as_null_index(&self) -> Option<&NullIndex> {
+        match self {
+            FieldIndex::NullIndex(index) => Some(index),
+            _ => None,
         }
     }
 }
@@ -388,3 +473,72 @@ impl<'a> NumericFieldIndex<'a> {
         }
     }
 }
+
+pub enum OrderingFieldIndex<'a> {
+    Numeric(NumericFieldIndex<'a>),
+    Keyword(&'a MapIndex<SmolStr>),
+}
+
+impl<'a> OrderingFieldIndex<'a> {
+    /// Stream the points in the direction of `order_by`, starting from its `start_from` value.
+    ///
+    /// Points are repeated for as many values as they have.
+    pub fn stream(
+        &self,
+        order_by: &OrderBy,
+    ) -> Box<dyn Iterator<Item = (OrderValue, PointOffsetType)> + 'a> {
+        match self {
+            OrderingFieldIndex::Numeric(index) => {
+                let range_iter = index.stream_range(&order_by.as_range());
+                match order_by.direction() {
+                    Direction::Asc => Box::new(range_iter),
+                    Direction::Desc => Box::new(range_iter.rev()),
+                }
+            }
+            OrderingFieldIndex::Keyword(index) => {
+                let index: &'a MapIndex<SmolStr> = index;
+                let start_from = order_by.start_from();
+                let values_iter = index
+                    .iter_sorted_values_map()
+                    .map(|(value, points)| (OrderValue::Keyword(value.to_string()), points));
+                let directed_values_iter = match order_by.direction() {
+                    Direction::Asc => {
+                        Either::Left(values_iter.skip_while(move |(value, _)| value < &start_from))
+                    }
+                    Direction::Desc => Either::Right(
+                        values_iter
+                            .rev()
+                            .skip_while(move |(value, _)| value > &start_from),
+                    ),
+                };
+                Box::new(
+                    directed_values_iter.flat_map(|(value, points)| {
+                        points.map(move |point| (value.clone(), point))
+                    }),
+                )
+            }
+        }
+    }
+
+    /// The first value of the point in the given direction
+    pub fn get_ordering_value(
+        &self,
+        idx: PointOffsetType,
+        direction: Direction,
+    ) -> Option<OrderValue> {
+        let values = match self {
+            OrderingFieldIndex::Numeric(index) => Either::Left(index.get_ordering_values(idx)),
+            OrderingFieldIndex::Keyword(index) => Either::Right(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .map(|value| OrderValue::Keyword(value.to_string())),
+            ),
+        };
+        match direction {
+            Direction::Asc => values.min(),
+            Direction::Desc => values.max(),
+        }
+    }
+}
@@ -420,6 +420,93 @@ fn minimum_bounding_rectangle_for_boundary(boundary: &LineString) -> GeoBounding
     }
 }
 
+/// Neighbourhood of a location at some geo-hash precision
+#[derive(Debug)]
+pub struct GeoHashRing {
+    /// Geo-hash of the location together with all adjacent geo-hashes
+    pub hashes: Vec<GeoHash>,
+    /// Any point outside of `hashes` is at least this far from the location (in meters)
+    pub covered_radius: f64,
+}
+
+/// Sequence of expanding neighbourhoods around `origin`, from the most precise one
+/// up to the whole globe.
+///
+/// Used to stream points in order of distance: once all points within a ring are known,
+/// the ones closer than `covered_radius` can't be preceded by any point from the outer rings.
+pub fn geo_hash_rings(origin: &GeoPoint) -> OperationResult<Vec<GeoHashRing>> {
+    let center = encode_max_precision(origin.lon, origin.lat).map_err(|err| {
+        OperationError::service_error(format!("failed to encode geo-hash: {err}"))
+    })?;
+
+    let mut rings = Vec::with_capacity(GEOHASH_MAX_LENGTH + 1);
+    for precision in (1..=center.len()).rev() {
+        let cell: GeoHash = center[..precision].into();
+        let mut hashes = vec![cell.clone()];
+        for direction in [
+            Direction::N,
+            Direction::NE,
+            Direction::E,
+            Direction::SE,
+            Direction::S,
+            Direction::SW,
+            Direction::W,
+            Direction::NW,
+        ] {
+            let neighbor = sphere_neighbor(&cell, direction).map_err(|err| {
+                OperationError::service_error(format!("failed to find geo-hash neighbor: {err}"))
+            })?;
+            if !hashes.contains(&neighbor) {
+                hashes.push(neighbor);
+            }
+        }
+        let covered_radius = ring_covered_radius(origin, &cell)?;
+        rings.push(GeoHashRing {
+            hashes,
+            covered_radius,
+        });
+    }
+    // Last ring is the whole globe
+    rings.push(GeoHashRing {
+        hashes: vec![GeoHash::default()],
+        covered_radius: f64::INFINITY,
+    });
+    Ok(rings)
+}
+
+/// Lower bound of the distance from `origin` to any point outside of `cell` and its neighbors
+fn ring_covered_radius(origin: &GeoPoint, cell: &str) -> OperationResult<f64> {
+    let rect = decode_bbox(cell).map_err(|err| {
+        OperationError::service_error(format!("failed to decode geo-hash: {err}"))
+    })?;
+    let width = rect.max().x - rect.min().x;
+    let height = rect.max().y - rect.min().y;
+
+    let east_offset = rect.max().x + width - origin.lon;
+    let west_offset = origin.lon - (rect.min().x - width);
+    if east_offset.max(west_offset) >= 90.0 {
+        // Ring is too wide, the other side of the globe might be close through the poles
+        return Ok(0.0);
+    }
+
+    // Distance to the great circle of a meridian, which is `offset` degrees away
+    let cos_lat = origin.lat.to_radians().cos();
+    let meridian_distance =
+        |offset: f64| EARTH_RADIUS_METERS * (offset.to_radians().sin() * cos_lat).asin();
+
+    let mut radius = meridian_distance(east_offset).min(meridian_distance(west_offset));
+
+    let north = rect.max().y + height;
+    if north < LAT_RANGE.end {
+        radius = radius.min(EARTH_RADIUS_METERS * (north - origin.lat).to_radians());
+    }
+    let south = rect.min().y - height;
+    if south > LAT_RANGE.start {
+        radius = radius.min(EARTH_RADIUS_METERS * (origin.lat - south).to_radians());
+    }
+    Ok(radius)
+}
+
 #[cfg(test)]
 mod tests {
     use rand::rngs::StdRng;
@@ -1040,4 +1127,33 @@ mod tests {
         let hashes2 = circle_hashes(&circle2, GEOHASH_MAX_LENGTH);
         assert!(hashes2.is_err());
     }
+
+    #[test]
+    fn geo_hash_rings_cover_radius() {
+        let mut rnd = StdRng::seed_from_u64(42);
+
+        for origin in [BERLIN, NYC] {
+            let origin_point = Point::new(origin.lon, origin.lat);
+            let rings = geo_hash_rings(&origin).unwrap();
+
+            assert_eq!(rings.len(), GEOHASH_MAX_LENGTH + 1);
+            assert_eq!(rings.last().unwrap().covered_radius, f64::INFINITY);
+
+            for _ in 0..1000 {
+                let point = GeoPoint {
+                    lon: origin.lon + rnd.gen_range(-1.0..1.0),
+                    lat: origin.lat + rnd.gen_range(-1.0..1.0),
+                };
+                let hash = encode_max_precision(point.lon, point.lat).unwrap();
+                let distance = origin_point.haversine_distance(&Point::new(point.lon, point.lat));
+
+                for ring in &rings {
+                    let inside = ring.hashes.iter().any(|h| hash.starts_with(h.as_str()));
+                    if !inside {
+                        assert!(distance >= ring.covered_radius);
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,461 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use itertools::Itertools;
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use super::GEO_QUERY_MAX_REGION;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::Flusher;
+use crate::index::field_index::geo_hash::{
+    encode_max_precision, geo_hash_to_box, polygon_hashes, GeoHash,
+};
+use crate::index::field_index::{
+    CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
+};
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{
+    FieldCondition, GeoLineString, GeoPoint, GeoPolygon, GeoShape, GeoShapeCondition,
+    GeoShapeRelation, PayloadKeyType,
+};
+
+/// Index for arbitrary geo shapes (points and polygons).
+///
+/// Each shape is covered by a set of geo-hash regions. Regions of the query shape are used to
+/// select candidate points, which are then checked exactly against the stored shapes.
+pub struct GeoShapeIndex {
+    /// Original shapes of each point, used for exact relation checks
+    point_to_shapes: Vec<Vec<GeoShape>>,
+    /// Points, which have at least one shape covering the geo-hash region
+    hash_to_points: BTreeMap<GeoHash, HashSet<PointOffsetType>>,
+    points_count: usize,
+    points_values_count: usize,
+    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+}
+
+impl GeoShapeIndex {
+    pub fn new(db: Arc<RwLock<DB>>, field: &str) -> Self {
+        let store_cf_name = Self::storage_cf_name(field);
+        let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
+            db,
+            &store_cf_name,
+        ));
+        Self {
+            point_to_shapes: vec![],
+            hash_to_points: BTreeMap::new(),
+            points_count: 0,
+            points_values_count: 0,
+            db_wrapper,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{field}_geo_shape")
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        PayloadIndexTelemetry {
+            field_name: None,
+            points_count: self.points_count,
+            points_values_count: self.points_values_count,
+            histogram_bucket_size: None,
+        }
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        self.point_to_shapes
+            .get(point_id as usize)
+            .map(Vec::len)
+            .unwrap_or(0)
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        self.values_count(point_id) == 0
+    }
+
+    pub fn get_values(&self, point_id: PointOffsetType) -> Option<&[GeoShape]> {
+        self.point_to_shapes
+            .get(point_id as usize)
+            .map(Vec::as_slice)
+    }
+
+    /// Geo-hash regions, which together cover the shape
+    fn shape_hashes(shape: &GeoShape) -> OperationResult<Vec<GeoHash>> {
+        match shape {
+            GeoShape::Point(point) => Ok(vec![encode_max_precision(point.lon, point.lat)
+                .map_err(|e| {
+                    OperationError::service_error(format!("Malformed geo point: {e}"))
+                })?]),
+            GeoShape::Polygon(polygon) => polygon_hashes(polygon, GEO_QUERY_MAX_REGION),
+        }
+    }
+
+    /// Points with at least one stored region overlapping any of the `hashes` regions.
+    ///
+    /// Regions overlap if one of them is a prefix of the other.
+    fn overlapping_points(&self, hashes: &[GeoHash]) -> Vec<PointOffsetType> {
+        let mut candidates = HashSet::new();
+        for hash in hashes {
+            // Stored regions inside the query region
+            for (_, points) in self
+                .hash_to_points
+                .range(hash.clone()..)
+                .take_while(|(stored, _)| stored.starts_with(hash.as_str()))
+            {
+                candidates.extend(points.iter().copied());
+            }
+            // Stored regions enclosing the query region
+            for prefix_len in 0..hash.len() {
+                if let Some(points) = self.hash_to_points.get(&hash[..prefix_len]) {
+                    candidates.extend(points.iter().copied());
+                }
+            }
+        }
+        candidates.into_iter().sorted_unstable().collect()
+    }
+
+    fn matching_points(
+        &self,
+        condition: &GeoShapeCondition,
+    ) -> OperationResult<Vec<PointOffsetType>> {
+        let hashes = Self::shape_hashes(&condition.shape)?;
+        let checker = condition.checker();
+        let points = self
+            .overlapping_points(&hashes)
+            .into_iter()
+            .filter(|&point_id| {
+                self.point_to_shapes[point_id as usize]
+                    .iter()
+                    .any(|shape| checker.check_shape(shape))
+            })
+            .collect();
+        Ok(points)
+    }
+
+    fn add_shapes(&mut self, id: PointOffsetType, shapes: Vec<GeoShape>) -> OperationResult<()> {
+        for shape in &shapes {
+            for hash in Self::shape_hashes(shape)? {
+                self.hash_to_points.entry(hash).or_default().insert(id);
+            }
+        }
+
+        if self.point_to_shapes.len() <= id as usize {
+            self.point_to_shapes.resize_with(id as usize + 1, Vec::new);
+        }
+        self.points_count += 1;
+        self.points_values_count += shapes.len();
+        self.point_to_shapes[id as usize] = shapes;
+        Ok(())
+    }
+
+    /// Polygon of a geo-hash region, used to describe payload blocks
+    fn hash_polygon(hash: &GeoHash) -> GeoPolygon {
+        let bbox = geo_hash_to_box(hash);
+        let (top, left) = (bbox.top_left.lat, bbox.top_left.lon);
+        let (bottom, right) = (bbox.bottom_right.lat, bbox.bottom_right.lon);
+        let points = [
+            (left, top),
+            (right, top),
+            (right, bottom),
+            (left, bottom),
+            (left, top),
+        ]
+        .into_iter()
+        .map(|(lon, lat)| GeoPoint { lon, lat })
+        .collect();
+        GeoPolygon {
+            exterior: GeoLineString { points },
+            interiors: None,
+        }
+    }
+}
+
+impl PayloadFieldIndex for GeoShapeIndex {
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        let db_lock = self.db_wrapper.lock_db();
+        let pending_deletes = self.db_wrapper.pending_deletes();
+
+        let mut stored = vec![];
+        for (key, value) in db_lock.iter_pending_deletes(pending_deletes)? {
+            let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
+            let shapes: Vec<GeoShape> = serde_cbor::from_slice(&value)?;
+            stored.push((idx, shapes));
+        }
+        drop(db_lock);
+
+        for (idx, shapes) in stored {
+            self.add_shapes(idx, shapes)?;
+        }
+        Ok(true)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.db_wrapper.remove_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn filter<'a>(
+        &'a self,
+        condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        match &condition.geo_shape {
+            Some(geo_shape) => Ok(Box::new(self.matching_points(geo_shape)?.into_iter())),
+            None => Err(OperationError::service_error("failed to filter")),
+        }
+    }
+
+    fn estimate_cardinality(
+        &self,
+        condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        match &condition.geo_shape {
+            Some(geo_shape) => {
+                let hashes = Self::shape_hashes(&geo_shape.shape)?;
+                // Candidates share a covering region with the shape, so each of them may match
+                // regardless of the relation, and the exact check happens only on filtering
+                let candidates = self.overlapping_points(&hashes).len();
+                Ok(CardinalityEstimation {
+                    primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
+                    min: 0,
+                    exp: candidates,
+                    max: candidates,
+                })
+            }
+            None => Err(OperationError::service_error(
+                "failed to estimate cardinality",
+            )),
+        }
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(
+            self.hash_to_points
+                .iter()
+                .filter(move |(hash, points)| points.len() > threshold && !hash.is_empty())
+                .map(move |(hash, points)| PayloadBlockCondition {
+                    condition: FieldCondition::new_geo_shape(
+                        key.clone(),
+                        GeoShapeCondition {
+                            relation: GeoShapeRelation::Intersects,
+                            shape: GeoShape::Polygon(Self::hash_polygon(hash)),
+                        },
+                    ),
+                    cardinality: points.len(),
+                }),
+        )
+    }
+}
+
+impl ValueIndexer<GeoShape> for GeoShapeIndex {
+    fn add_many(&mut self, id: PointOffsetType, values: Vec<GeoShape>) -> OperationResult<()> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let encoded = serde_cbor::to_vec(&values).map_err(|e| {
+            OperationError::service_error(format!("Failed to serialize geo shapes: {e}"))
+        })?;
+        self.add_shapes(id, values)?;
+        self.db_wrapper.put(id.to_be_bytes(), encoded)?;
+        Ok(())
+    }
+
+    fn get_value(&self, value: &serde_json::Value) -> Option<GeoShape> {
+        GeoShape::from_value(value)
+    }
+
+    fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        let Some(shapes) = self.point_to_shapes.get_mut(id as usize) else {
+            return Ok(());
+        };
+        if shapes.is_empty() {
+            return Ok(());
+        }
+        let shapes = std::mem::take(shapes);
+
+        for shape in &shapes {
+            for hash in Self::shape_hashes(shape)? {
+                if let Some(points) = self.hash_to_points.get_mut(&hash) {
+                    points.remove(&id);
+                    if points.is_empty() {
+                        self.hash_to_points.remove(&hash);
+                    }
+                }
+            }
+        }
+
+        self.points_count -= 1;
+        self.points_values_count -= shapes.len();
+        self.db_wrapper.remove(id.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::{Builder, TempDir};
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::json_path::JsonPath;
+    use crate::types::test_utils::build_polygon;
+
+    const FIELD_NAME: &str = "area";
+
+    fn new_index() -> (TempDir, GeoShapeIndex) {
+        let tmp_dir = Builder::new().prefix("test_db").tempdir().unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let index = GeoShapeIndex::new(db, FIELD_NAME);
+        index.recreate().unwrap();
+        (tmp_dir, index)
+    }
+
+    fn square(lon: f64, lat: f64, size: f64) -> serde_json::Value {
+        json!({
+            "exterior": {
+                "points": [
+                    { "lon": lon, "lat": lat },
+                    { "lon": lon + size, "lat": lat },
+                    { "lon": lon + size, "lat": lat + size },
+                    { "lon": lon, "lat": lat + size },
+                    { "lon": lon, "lat": lat }
+                ]
+            }
+        })
+    }
+
+    fn condition(relation: GeoShapeRelation, shape: GeoShape) -> FieldCondition {
+        FieldCondition::new_geo_shape(
+            JsonPath::new(FIELD_NAME),
+            GeoShapeCondition { relation, shape },
+        )
+    }
+
+    fn fill(index: &mut GeoShapeIndex) {
+        // Berlin area
+        index.add_point(0, &[&square(13.0, 52.0, 1.0)]).unwrap();
+        // Point in Berlin
+        index
+            .add_point(1, &[&json!({ "lon": 13.4, "lat": 52.5 })])
+            .unwrap();
+        // Area around NYC and point in Berlin
+        index
+            .add_point(
+                2,
+                &[&json!([square(-74.5, 40.5, 1.0), { "lon": 13.1, "lat": 52.1 }])],
+            )
+            .unwrap();
+        // Not a shape
+        index.add_point(3, &[&json!("Berlin")]).unwrap();
+    }
+
+    fn check_filter(index: &GeoShapeIndex) {
+        let berlin_center = GeoShape::Point(GeoPoint {
+            lon: 13.4,
+            lat: 52.5,
+        });
+        let found = index
+            .filter(&condition(
+                GeoShapeRelation::Intersects,
+                berlin_center.clone(),
+            ))
+            .unwrap()
+            .collect_vec();
+        assert_eq!(found, vec![0, 1]);
+
+        let found = index
+            .filter(&condition(GeoShapeRelation::Contains, berlin_center))
+            .unwrap()
+            .collect_vec();
+        // Equal points contain each other
+        assert_eq!(found, vec![0, 1]);
+
+        let germany = GeoShape::Polygon(build_polygon(vec![
+            (6.0, 47.0),
+            (15.0, 47.0),
+            (15.0, 55.0),
+            (6.0, 55.0),
+            (6.0, 47.0),
+        ]));
+        let found = index
+            .filter(&condition(GeoShapeRelation::Within, germany.clone()))
+            .unwrap()
+            .collect_vec();
+        assert_eq!(found, vec![0, 1, 2]);
+
+        let estimation = index
+            .estimate_cardinality(&condition(GeoShapeRelation::Intersects, germany.clone()))
+            .unwrap();
+        assert!(estimation.max >= 3);
+
+        let estimation = index
+            .estimate_cardinality(&condition(GeoShapeRelation::Within, germany))
+            .unwrap();
+        assert_eq!(estimation.min, 0);
+        assert_eq!(estimation.exp, estimation.max);
+        assert!(estimation.max >= 3);
+    }
+
+    #[test]
+    fn test_filter_shapes() {
+        let (_tmp_dir, mut index) = new_index();
+        fill(&mut index);
+
+        assert_eq!(index.count_indexed_points(), 3);
+        assert_eq!(index.values_count(2), 2);
+        assert!(index.values_is_empty(3));
+        check_filter(&index);
+
+        index.remove_point(1).unwrap();
+        let found = index
+            .filter(&condition(
+                GeoShapeRelation::Intersects,
+                GeoShape::Point(GeoPoint {
+                    lon: 13.4,
+                    lat: 52.5,
+                }),
+            ))
+            .unwrap()
+            .collect_vec();
+        assert_eq!(found, vec![0]);
+        assert_eq!(index.count_indexed_points(), 2);
+    }
+
+    #[test]
+    fn test_load_from_disk() {
+        let (tmp_dir, mut index) = new_index();
+        fill(&mut index);
+        index.flusher()().unwrap();
+        drop(index);
+
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let mut index = GeoShapeIndex::new(db, FIELD_NAME);
+        assert!(index.load().unwrap());
+        assert_eq!(index.count_indexed_points(), 3);
+        check_filter(&index);
+    }
+}
@@ -1,9 +1,12 @@
-use std::cmp::{max, min};
+use std::cmp::{max, min, Reverse};
+use std::collections::{BinaryHeap, HashSet};
 use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use geo::{HaversineDistance, Point};
 use itertools::Itertools;
+use ordered_float::OrderedFloat;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
@@ -14,8 +17,8 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::Flusher;
 use crate::index::field_index::geo_hash::{
-    circle_hashes, common_hash_prefix, geo_hash_to_box, polygon_hashes, polygon_hashes_estimation,
-    rectangle_hashes, GeoHash,
+    circle_hashes, common_hash_prefix, geo_hash_rings, geo_hash_to_box, polygon_hashes,
+    polygon_hashes_estimation, rectangle_hashes, GeoHash,
 };
 use crate::index::field_index::stat_tools::estimate_multi_value_selection_cardinality;
 use crate::index::field_index::{
@@ -24,9 +27,15 @@ use crate::index::field_index::{
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{FieldCondition, GeoPoint, PayloadKeyType};
 
+pub mod geo_shape_index;
 pub mod immutable_geo_index;
 pub mod mutable_geo_index;
 
+/// Haversine distance in meters between two geo points
+pub fn geo_distance(origin: &GeoPoint, point: &GeoPoint) -> f64 {
+    Point::new(origin.lon, origin.lat).haversine_distance(&Point::new(point.lon, point.lat))
+}
+
 /// Max number of sub-regions computed for an input geo query
 // TODO discuss value, should it be dynamically computed?
 const GEO_QUERY_MAX_REGION: usize = 12;
@@ -234,6 +243,50 @@ impl GeoMapIndex {
         }
     }
 
+    /// Distance in meters from `origin` to the closest geo value of the point
+    pub fn min_distance(&self, idx: PointOffsetType, origin: &GeoPoint) -> Option<f64> {
+        self.get_values(idx)?
+            .iter()
+            .map(|value| geo_distance(origin, value))
+            .min_by(|a, b| a.total_cmp(b))
+    }
+
+    /// Iterate over indexed points in order of increasing distance (in meters) from `origin`.
+    ///
+    /// Points are collected from expanding geo-hash rings around the origin, so only the
+    /// neighbourhood required for the next result is read from the index.
+    pub fn stream_by_distance(
+        &self,
+        origin: GeoPoint,
+    ) -> OperationResult<impl Iterator<Item = (f64, PointOffsetType)> + '_> {
+        let mut rings = geo_hash_rings(&origin)?.into_iter();
+        let mut visited: HashSet<PointOffsetType> = HashSet::new();
+        let mut pending: BinaryHeap<Reverse<(OrderedFloat<f64>, PointOffsetType)>> =
+            BinaryHeap::new();
+        let mut covered_radius = f64::NEG_INFINITY;
+
+        Ok(std::iter::from_fn(move || loop {
+            if let Some(Reverse((distance, _))) = pending.peek() {
+                if distance.0 <= covered_radius {
+                    return pending
+                        .pop()
+                        .map(|Reverse((distance, idx))| (distance.0, idx));
+                }
+            }
+
+            let ring = rings.next()?;
+            for idx in self.get_iterator(ring.hashes) {
+                if !visited.insert(idx) {
+                    continue;
+                }
+                if let Some(distance) = self.min_distance(idx, &origin) {
+                    pending.push(Reverse((OrderedFloat(distance), idx)));
+                }
+            }
+            covered_radius = ring.covered_radius;
+        }))
+    }
+
     /// Get iterator over smallest geo-hash regions larger than `threshold` points
     fn get_large_hashes(
         &self,
@@ -776,6 +829,30 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn stream_by_distance(#[case] is_appendable: bool) {
+        let field_index = build_random_index(500, 3, is_appendable);
+
+        for origin in [BERLIN, NYC, TOKYO] {
+            let streamed = field_index
+                .stream_by_distance(origin.clone())
+                .unwrap()
+                .collect_vec();
+            assert_eq!(streamed.len(), 500);
+
+            let mut expected = (0..500)
+                .map(|idx| (field_index.min_distance(idx, &origin).unwrap(), idx))
+                .collect_vec();
+            expected.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let streamed_distances = streamed.iter().map(|(d, _)| *d).collect_vec();
+            let expected_distances = expected.iter().map(|(d, _)| *d).collect_vec();
+            assert_eq!(streamed_distances, expected_distances);
+        }
+    }
+
     #[rstest]
     #[case(true)]
     #[case(false)]
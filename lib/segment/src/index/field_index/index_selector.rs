@@ -5,14 +5,18 @@ use rocksdb::DB;
 
 use super::binary_index::BinaryIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
+use crate::index::field_index::geo_index::geo_shape_index::GeoShapeIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::MapIndex;
+use crate::index::field_index::null_index::NullIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::FieldIndex;
 use crate::json_path::JsonPath;
 use crate::types::{FloatPayloadType, IntPayloadType, PayloadFieldSchema, PayloadSchemaParams};
 
-/// Selects index types based on field type
+/// Selects index types based on field type.
+///
+/// Every field also gets a [`NullIndex`], which goes last, after the indexes of the field type.
 pub fn index_selector(
     field: &JsonPath,
     payload_schema: &PayloadFieldSchema,
@@ -22,7 +26,9 @@ pub fn index_selector(
     let field: String = field.to_string();
     let field = field.as_str();
 
-    match payload_schema.expand().as_ref() {
+    let null_index = FieldIndex::NullIndex(NullIndex::new(db.clone(), field));
+
+    let mut indexes = match payload_schema.expand().as_ref() {
         PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(MapIndex::new(
             db,
             field,
@@ -51,6 +57,9 @@ pub fn index_selector(
             field,
             is_appendable,
         ))],
+        PayloadSchemaParams::GeoShape(_) => {
+            vec![FieldIndex::GeoShapeIndex(GeoShapeIndex::new(db, field))]
+        }
         PayloadSchemaParams::Text(text_index_params) => vec![FieldIndex::FullTextIndex(
             FullTextIndex::new(db, text_index_params.clone(), field, is_appendable),
         )],
@@ -62,5 +71,7 @@ pub fn index_selector(
                 NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
             )]
         }
-    }
+    };
+    indexes.push(null_index);
+    indexes
 }
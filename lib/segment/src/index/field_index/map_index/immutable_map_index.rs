@@ -7,6 +7,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use common::types::PointOffsetType;
+use itertools::Itertools as _;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
@@ -17,9 +18,11 @@ use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDelet
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::index::field_index::immutable_point_to_values::ImmutablePointToValues;
 
-pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
+pub struct ImmutableMapIndex<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> {
     value_to_points: HashMap<N, Range<u32>>,
     value_to_points_container: Vec<PointOffsetType>,
+    /// Indexed values in ascending order
+    sorted_values: Vec<N>,
     point_to_values: ImmutablePointToValues<N>,
     /// Amount of point which have at least one indexed payload value
     indexed_points: usize,
@@ -27,7 +30,7 @@ pub struct ImmutableMapIndex<N: Hash + Eq + Clone + Display + FromStr + Default>
     db_wrapper: DatabaseColumnScheduledDeleteWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
+impl<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
@@ -37,6 +40,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         Self {
             value_to_points: Default::default(),
             value_to_points_container: Default::default(),
+            sorted_values: Default::default(),
             point_to_values: Default::default(),
             indexed_points: 0,
             values_count: 0,
@@ -175,12 +179,17 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
         self.values_count = values_count;
         self.value_to_points.clear();
         self.value_to_points_container.clear();
+        self.sorted_values.clear();
 
-        // flatten values-to-points map
-        for (value, points) in map {
+        // flatten values-to-points map, in order of the values
+        for (value, points) in map
+            .into_iter()
+            .sorted_unstable_by(|(a, _), (b, _)| a.cmp(b))
+        {
             let points = points.into_iter().collect::<Vec<_>>();
             let container_len = self.value_to_points_container.len() as u32;
             let range = container_len..container_len + points.len() as u32;
+            self.sorted_values.push(value.clone());
             self.value_to_points.insert(value, range.clone());
             self.value_to_points_container.extend(points);
         }
@@ -232,4 +241,9 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> ImmutableMapIndex<N> {
     pub fn get_values_iterator(&self) -> Box<dyn Iterator<Item = &N> + '_> {
         Box::new(self.value_to_points.keys())
     }
+
+    /// Iterate over the indexed values in ascending order
+    pub fn get_sorted_values_iterator(&self) -> impl DoubleEndedIterator<Item = &N> + '_ {
+        self.sorted_values.iter()
+    }
 }
@@ -30,12 +30,12 @@ use crate::types::{
 pub mod immutable_map_index;
 pub mod mutable_map_index;
 
-pub enum MapIndex<N: Hash + Eq + Clone + Display + FromStr + Default> {
+pub enum MapIndex<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> {
     Mutable(MutableMapIndex<N>),
     Immutable(ImmutableMapIndex<N>),
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
+impl<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> MapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str, is_appendable: bool) -> Self {
         if is_appendable {
             MapIndex::Mutable(MutableMapIndex::new(db, field_name))
@@ -72,7 +72,7 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
-    fn get_values_count(&self) -> usize {
+    pub fn get_values_count(&self) -> usize {
         match self {
             MapIndex::Mutable(index) => index.get_values_count(),
             MapIndex::Immutable(index) => index.get_values_count(),
@@ -117,6 +117,45 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MapIndex<N> {
         }
     }
 
+    /// Iterate over all indexed values along with the number of points having them
+    pub fn iter_counts_per_value(&self) -> impl Iterator<Item = (&N, usize)> + '_ {
+        self.get_values_iterator().map(|value| {
+            let count = self.get_points_with_value_count(value).unwrap_or(0);
+            (value, count)
+        })
+    }
+
+    /// Iterate over the indexed values in ascending order, along with the points having them.
+    ///
+    /// Immutable index keeps the values sorted, while mutable one sorts them on each call.
+    pub fn iter_sorted_values_map(
+        &self,
+    ) -> Box<
+        dyn DoubleEndedIterator<Item = (&N, Box<dyn Iterator<Item = PointOffsetType> + '_>)> + '_,
+    > {
+        match self {
+            MapIndex::Mutable(index) => Box::new(
+                index
+                    .get_values_iterator()
+                    .sorted_unstable()
+                    .map(|value| (value, index.get_iterator(value))),
+            ),
+            MapIndex::Immutable(index) => Box::new(
+                index
+                    .get_sorted_values_iterator()
+                    .map(|value| (value, index.get_iterator(value))),
+            ),
+        }
+    }
+
+    /// Iterate over all indexed values along with the points having them
+    pub fn iter_values_map(
+        &self,
+    ) -> impl Iterator<Item = (&N, Box<dyn Iterator<Item = PointOffsetType> + '_>)> + '_ {
+        self.get_values_iterator()
+            .map(|value| (value, self.get_iterator(value)))
+    }
+
     pub fn storage_cf_name(field: &str) -> String {
         format!("{field}_map")
     }
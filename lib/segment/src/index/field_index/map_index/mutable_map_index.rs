@@ -23,7 +23,7 @@ pub struct MutableMapIndex<N: Hash + Eq + Clone + Display + FromStr> {
     pub(super) db_wrapper: DatabaseColumnScheduledDeleteWrapper,
 }
 
-impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
+impl<N: Hash + Eq + Ord + Clone + Display + FromStr + Default> MutableMapIndex<N> {
     pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> Self {
         let store_cf_name = MapIndex::<N>::storage_cf_name(field_name);
         let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
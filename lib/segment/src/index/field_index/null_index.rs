@@ -0,0 +1,293 @@
+use std::sync::Arc;
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+use serde_json::Value;
+
+use super::{CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
+use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
+use crate::common::utils::{check_is_empty, check_is_null};
+use crate::common::Flusher;
+use crate::telemetry::PayloadIndexTelemetry;
+use crate::types::{FieldCondition, PayloadKeyType};
+
+/// Flags of a point, as stored in the database
+struct NullItem {
+    value: u8,
+}
+
+impl NullItem {
+    const HAS_VALUES: u8 = 0b0000_0001;
+    const IS_NULL: u8 = 0b0000_0010;
+
+    fn new(has_values: bool, is_null: bool) -> Self {
+        let mut value = 0;
+        if has_values {
+            value |= Self::HAS_VALUES;
+        }
+        if is_null {
+            value |= Self::IS_NULL;
+        }
+        Self { value }
+    }
+
+    fn has_values(&self) -> bool {
+        self.value & Self::HAS_VALUES != 0
+    }
+
+    fn is_null(&self) -> bool {
+        self.value & Self::IS_NULL != 0
+    }
+}
+
+/// Index of points which have a non-empty or a null value of the field.
+///
+/// Is built for every indexed field, regardless of its type, and answers `is_empty` and `is_null`
+/// conditions without reading payloads. Points which are not indexed have neither flag,
+/// so points without the field, or without any payload at all, are empty.
+pub struct NullIndex {
+    /// Points with at least one value, which is not null or an empty array
+    has_values: BitVec,
+    /// Points with at least one null value
+    is_null: BitVec,
+    has_values_count: usize,
+    is_null_count: usize,
+    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+}
+
+impl NullIndex {
+    pub fn new(db: Arc<RwLock<DB>>, field_name: &str) -> NullIndex {
+        let store_cf_name = Self::storage_cf_name(field_name);
+        let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
+            db,
+            &store_cf_name,
+        ));
+        Self {
+            has_values: BitVec::new(),
+            is_null: BitVec::new(),
+            has_values_count: 0,
+            is_null_count: 0,
+            db_wrapper,
+        }
+    }
+
+    fn storage_cf_name(field: &str) -> String {
+        format!("{}_null", field)
+    }
+
+    pub fn recreate(&self) -> OperationResult<()> {
+        self.db_wrapper.recreate_column_family()
+    }
+
+    pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
+        PayloadIndexTelemetry {
+            field_name: None,
+            points_count: self.has_values_count,
+            points_values_count: self.has_values_count,
+            histogram_bucket_size: None,
+        }
+    }
+
+    /// Check if the point has a value, which is not null or an empty array
+    pub fn has_values(&self, point_id: PointOffsetType) -> bool {
+        self.has_values
+            .get(point_id as usize)
+            .map_or(false, |bit| *bit)
+    }
+
+    /// Check if the point has a null value
+    pub fn is_null(&self, point_id: PointOffsetType) -> bool {
+        self.is_null
+            .get(point_id as usize)
+            .map_or(false, |bit| *bit)
+    }
+
+    /// Number of points with a value, which is not null or an empty array
+    pub fn has_values_count(&self) -> usize {
+        self.has_values_count
+    }
+
+    /// Number of points with a null value
+    pub fn is_null_count(&self) -> usize {
+        self.is_null_count
+    }
+
+    /// Iterate over points with a null value
+    pub fn iter_null(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.is_null.iter_ones().map(|id| id as PointOffsetType)
+    }
+
+    pub fn values_count(&self, point_id: PointOffsetType) -> usize {
+        usize::from(self.has_values(point_id))
+    }
+
+    pub fn values_is_empty(&self, point_id: PointOffsetType) -> bool {
+        !self.has_values(point_id)
+    }
+
+    fn set(&mut self, point_id: PointOffsetType, item: &NullItem) {
+        let idx = point_id as usize;
+        if idx >= self.has_values.len() {
+            self.has_values.resize(idx + 1, false);
+            self.is_null.resize(idx + 1, false);
+        }
+
+        match (
+            self.has_values.replace(idx, item.has_values()),
+            item.has_values(),
+        ) {
+            (false, true) => self.has_values_count += 1,
+            (true, false) => self.has_values_count -= 1,
+            _ => {}
+        }
+        match (self.is_null.replace(idx, item.is_null()), item.is_null()) {
+            (false, true) => self.is_null_count += 1,
+            (true, false) => self.is_null_count -= 1,
+            _ => {}
+        }
+    }
+
+    pub fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
+        let item = NullItem::new(
+            !check_is_empty(payload.iter().copied()),
+            check_is_null(payload.iter().copied()),
+        );
+        if item.value == 0 {
+            return self.remove_point(id);
+        }
+        self.set(id, &item);
+        self.db_wrapper.put(id.to_be_bytes(), [item.value])?;
+        Ok(())
+    }
+
+    pub fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        if (id as usize) < self.has_values.len() {
+            self.set(id, &NullItem::new(false, false));
+        }
+        self.db_wrapper.remove(id.to_be_bytes())?;
+        Ok(())
+    }
+}
+
+impl PayloadFieldIndex for NullIndex {
+    fn count_indexed_points(&self) -> usize {
+        self.has_values_count
+    }
+
+    fn load(&mut self) -> OperationResult<bool> {
+        if !self.db_wrapper.has_column_family()? {
+            return Ok(false);
+        }
+
+        let db_lock = self.db_wrapper.lock_db();
+        let pending_deletes = self.db_wrapper.pending_deletes();
+
+        let mut items = Vec::new();
+        for (key, value) in db_lock.iter_pending_deletes(pending_deletes)? {
+            let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
+            debug_assert_eq!(value.len(), 1);
+            items.push((idx, NullItem { value: value[0] }));
+        }
+        drop(db_lock);
+
+        for (idx, item) in items {
+            self.set(idx, &item);
+        }
+        Ok(true)
+    }
+
+    fn clear(self) -> OperationResult<()> {
+        self.db_wrapper.remove_column_family()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+
+    fn filter<'a>(
+        &'a self,
+        _condition: &'a FieldCondition,
+    ) -> OperationResult<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
+        Err(OperationError::service_error(
+            "null index does not support field conditions",
+        ))
+    }
+
+    fn estimate_cardinality(
+        &self,
+        _condition: &FieldCondition,
+    ) -> OperationResult<CardinalityEstimation> {
+        Err(OperationError::service_error(
+            "null index does not support field conditions",
+        ))
+    }
+
+    fn payload_blocks(
+        &self,
+        _threshold: usize,
+        _key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        Box::new(std::iter::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::{Builder, TempDir};
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+
+    const FIELD_NAME: &str = "field";
+
+    fn new_null_index() -> (TempDir, NullIndex) {
+        let tmp_dir = Builder::new().prefix("test_db").tempdir().unwrap();
+        let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
+        let index = NullIndex::new(db, FIELD_NAME);
+        index.recreate().unwrap();
+        (tmp_dir, index)
+    }
+
+    #[test]
+    fn test_null_index_flags() {
+        let (_tmp_dir, mut index) = new_null_index();
+
+        let payloads = [
+            vec![json!(1)],
+            vec![json!(null)],
+            vec![json!([])],
+            vec![json!([1, null])],
+            vec![json!(null), json!("a")],
+            vec![],
+        ];
+        for (id, values) in payloads.iter().enumerate() {
+            let values = values.iter().collect::<Vec<_>>();
+            index.add_point(id as PointOffsetType, &values).unwrap();
+        }
+
+        let has_values = (0..payloads.len() as PointOffsetType)
+            .filter(|&id| index.has_values(id))
+            .collect::<Vec<_>>();
+        assert_eq!(has_values, vec![0, 3, 4]);
+        assert_eq!(index.iter_null().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(index.has_values_count(), 3);
+        assert_eq!(index.is_null_count(), 2);
+
+        index.remove_point(4).unwrap();
+        index.add_point(0, &[&json!(null)]).unwrap();
+        assert_eq!(index.has_values_count(), 1);
+        assert_eq!(index.iter_null().collect::<Vec<_>>(), vec![0, 1]);
+
+        index.flusher()().unwrap();
+        let mut loaded = NullIndex::new(index.db_wrapper.get_database(), FIELD_NAME);
+        assert!(loaded.load().unwrap());
+        assert_eq!(loaded.has_values_count(), 1);
+        assert!(loaded.has_values(3));
+        assert_eq!(loaded.iter_null().collect::<Vec<_>>(), vec![0, 1]);
+    }
+}
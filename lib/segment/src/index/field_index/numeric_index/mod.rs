@@ -138,6 +138,20 @@ impl<T: Encodable + Numericable> Range<T> {
     }
 }
 
+/// Convert range condition into a range of index values.
+///
+/// Relative datetime bounds must be resolved before the request reaches the segment.
+/// Returns `None` if the range can't be represented, so nothing matches.
+fn range_of_values<T: Numericable>(range: &RangeInterface) -> Option<Range<T>> {
+    match range {
+        RangeInterface::Float(float_range) => Some(float_range.map(T::from_f64)),
+        RangeInterface::DateTime(datetime_range) => {
+            Some(datetime_range.map(|dt| T::from_i64(dt.timestamp())))
+        }
+        RangeInterface::RelativeDateTime(_) => None,
+    }
+}
+
 pub enum NumericIndex<T: Encodable + Numericable + Default> {
     Mutable(MutableNumericIndex<T>),
     Immutable(ImmutableNumericIndex<T>),
@@ -231,11 +245,8 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
             return CardinalityEstimation::exact(0);
         }
 
-        let range = match range {
-            RangeInterface::Float(float_range) => float_range.map(T::from_f64),
-            RangeInterface::DateTime(datetime_range) => {
-                datetime_range.map(|dt| T::from_i64(dt.timestamp()))
-            }
+        let Some(range) = range_of_values::<T>(range) else {
+            return CardinalityEstimation::exact(0);
         };
 
         let lbound = if let Some(lte) = range.lte {
@@ -339,13 +350,10 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
             .as_ref()
             .ok_or_else(|| OperationError::service_error("failed to get range condition"))?;
 
-        let (start_bound, end_bound) = match range_cond {
-            RangeInterface::Float(float_range) => float_range.map(T::from_f64),
-            RangeInterface::DateTime(datetime_range) => {
-                datetime_range.map(|dt| T::from_i64(dt.timestamp()))
-            }
-        }
-        .as_index_key_bounds();
+        let Some(range) = range_of_values::<T>(range_cond) else {
+            return Ok(Box::new(vec![].into_iter()));
+        };
+        let (start_bound, end_bound) = range.as_index_key_bounds();
 
         // map.range
         // Panics if range start > end. Panics if range start == end and both bounds are Excluded.
@@ -530,11 +538,8 @@ where
         &self,
         range: &RangeInterface,
     ) -> Box<dyn DoubleEndedIterator<Item = (T, PointOffsetType)> + '_> {
-        let range = match range {
-            RangeInterface::Float(float_range) => float_range.map(T::from_f64),
-            RangeInterface::DateTime(datetime_range) => {
-                datetime_range.map(|dt| T::from_i64(dt.timestamp()))
-            }
+        let Some(range) = range_of_values::<T>(range) else {
+            return Box::new(vec![].into_iter());
         };
         let (start_bound, end_bound) = range.as_index_key_bounds();
 
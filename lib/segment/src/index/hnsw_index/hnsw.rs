@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::thread;
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
@@ -24,6 +25,7 @@ use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use crate::common::BYTES_IN_KB;
+use crate::data_types::explain::{query_points_traced, SearchStrategy};
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
@@ -606,7 +608,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let payload_index = self.payload_index.borrow();
         // share filtered points for all query vectors
-        let filtered_points = payload_index.query_points(filter);
+        let filtered_points = query_points_traced(&*payload_index, filter, vector_query_context);
         vectors
             .iter()
             .map(|vector| {
@@ -752,6 +754,48 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         postprocess_result.truncate(top);
         Ok(postprocess_result)
     }
+
+    /// Decide if a filtered search should traverse the graph, or score matching points directly
+    ///
+    /// Depending on the amount of filtered-out points the optimal strategy could be
+    /// - to retrieve possible points and score them after
+    /// - to use HNSW index with filtering condition
+    fn is_graph_search_for_filter(
+        &self,
+        query_filter: &Filter,
+        query_context: &VectorQueryContext,
+    ) -> bool {
+        let payload_index = self.payload_index.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let available_vector_count = vector_storage.available_vector_count();
+        let estimation_timer = Instant::now();
+        let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+        let query_cardinality = adjust_to_available_vectors(
+            query_point_cardinality,
+            available_vector_count,
+            id_tracker.available_point_count(),
+        );
+        query_context
+            .trace(|trace| trace.record_estimation(&query_cardinality, estimation_timer.elapsed()));
+
+        if query_cardinality.max < self.config.full_scan_threshold {
+            return false;
+        }
+
+        if query_cardinality.min > self.config.full_scan_threshold {
+            return true;
+        }
+
+        // Fast cardinality estimation is not enough, do sample estimation of cardinality
+        let filter_context = payload_index.filter_context(query_filter);
+        sample_check_cardinality(
+            id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+            |idx| filter_context.check(idx),
+            self.config.full_scan_threshold,
+            available_vector_count, // Check cardinality among available vectors
+        )
+    }
 }
 
 impl HNSWIndex<GraphLinksMmap> {
@@ -784,6 +828,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
 
                 // Do plain or graph search
                 if plain_search {
+                    query_context.trace(|trace| trace.strategy = Some(SearchStrategy::FullScan));
                     let _timer = ScopeDurationMeasurer::new(if exact {
                         &self.searches_telemetry.exact_unfiltered
                     } else {
@@ -808,6 +853,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                         })
                         .collect()
                 } else {
+                    query_context.trace(|trace| trace.strategy = Some(SearchStrategy::Graph));
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
                     self.search_vectors_with_graph(vectors, None, top, params, query_context)
@@ -840,54 +886,9 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     );
                 }
 
-                let payload_index = self.payload_index.borrow();
-                let vector_storage = self.vector_storage.borrow();
-                let id_tracker = self.id_tracker.borrow();
-                let available_vector_count = vector_storage.available_vector_count();
-                let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
-                let query_cardinality = adjust_to_available_vectors(
-                    query_point_cardinality,
-                    available_vector_count,
-                    id_tracker.available_point_count(),
-                );
-
-                if query_cardinality.max < self.config.full_scan_threshold {
-                    // if cardinality is small - use plain index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
-                    return self.search_vectors_plain(
-                        vectors,
-                        query_filter,
-                        top,
-                        params,
-                        query_context,
-                    );
-                }
-
-                if query_cardinality.min > self.config.full_scan_threshold {
-                    // if cardinality is high enough - use HNSW index
-                    let _timer =
-                        ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
-                    return self.search_vectors_with_graph(
-                        vectors,
-                        filter,
-                        top,
-                        params,
-                        query_context,
-                    );
-                }
-
-                let filter_context = payload_index.filter_context(query_filter);
-
-                // Fast cardinality estimation is not enough, do sample estimation of cardinality
-                let id_tracker = self.id_tracker.borrow();
-                if sample_check_cardinality(
-                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
-                    |idx| filter_context.check(idx),
-                    self.config.full_scan_threshold,
-                    available_vector_count, // Check cardinality among available vectors
-                ) {
+                if self.is_graph_search_for_filter(query_filter, query_context) {
                     // if cardinality is high enough - use HNSW index
+                    query_context.trace(|trace| trace.strategy = Some(SearchStrategy::Graph));
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
                     self.search_vectors_with_graph(vectors, filter, top, params, query_context)
@@ -13,6 +13,7 @@ use crate::common::operation_time_statistics::{
     OperationDurationStatistics, OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use crate::common::{Flusher, BYTES_IN_KB};
+use crate::data_types::explain::{query_points_traced, SearchStrategy};
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
@@ -281,7 +282,7 @@ impl VectorIndex for PlainIndex {
                 let id_tracker = self.id_tracker.borrow();
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
-                let filtered_ids_vec = payload_index.query_points(filter);
+                let filtered_ids_vec = query_points_traced(&*payload_index, filter, query_context);
                 let deleted_points = query_context
                     .deleted_points()
                     .unwrap_or(id_tracker.deleted_point_bitslice());
@@ -301,6 +302,7 @@ impl VectorIndex for PlainIndex {
                     .collect()
             }
             None => {
+                query_context.trace(|trace| trace.strategy = Some(SearchStrategy::FullScan));
                 let _timer = ScopeDurationMeasurer::new(&self.unfiltered_searches_telemetry);
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
@@ -297,6 +297,7 @@ mod tests {
             geo_radius: None,
             values_count: None,
             geo_polygon: None,
+            geo_shape: None,
         })
     }
 
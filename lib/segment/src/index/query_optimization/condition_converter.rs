@@ -15,8 +15,8 @@ use crate::payload_storage::query_checker::{
 };
 use crate::types::{
     AnyVariants, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
-    GeoPolygon, GeoRadius, IntPayloadType, Match, MatchAny, MatchExcept, MatchText, MatchValue,
-    OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
+    GeoPolygon, GeoRadius, GeoShapeCondition, IntPayloadType, Match, MatchAny, MatchExcept,
+    MatchText, MatchValue, OwnedPayloadRef, PayloadContainer, Range, RangeInterface, ValueVariants,
 };
 
 pub fn condition_converter<'a>(
@@ -40,12 +40,19 @@ pub fn condition_converter<'a>(
                     })
                 })
             }),
-        // We can use index for `is_empty` condition effectively only when it is not empty.
+        // Null index knows exactly which points have values, so the payload is not needed.
+        // Otherwise, we can use index for `is_empty` condition effectively only when it is not empty.
         // If the index says it is "empty", we still need to check the payload.
         Condition::IsEmpty(is_empty) => {
-            let first_field_index = field_indexes
-                .get(&is_empty.is_empty.key)
-                .and_then(|indexes| indexes.first());
+            let indexes = field_indexes.get(&is_empty.is_empty.key);
+
+            if let Some(null_index) =
+                indexes.and_then(|indexes| indexes.iter().find_map(|index| index.as_null_index()))
+            {
+                return Box::new(move |point_id| !null_index.has_values(point_id));
+            }
+
+            let first_field_index = indexes.and_then(|indexes| indexes.first());
 
             let fallback = Box::new(move |point_id| {
                 payload_provider.with_payload(point_id, |payload| {
@@ -59,11 +66,20 @@ pub fn condition_converter<'a>(
             }
         }
 
-        Condition::IsNull(is_null) => Box::new(move |point_id| {
-            payload_provider.with_payload(point_id, |payload| {
-                check_is_null_condition(is_null, &payload)
-            })
-        }),
+        Condition::IsNull(is_null) => {
+            let null_index = field_indexes
+                .get(&is_null.is_null.key)
+                .and_then(|indexes| indexes.iter().find_map(|index| index.as_null_index()));
+
+            match null_index {
+                Some(null_index) => Box::new(move |point_id| null_index.is_null(point_id)),
+                None => Box::new(move |point_id| {
+                    payload_provider.with_payload(point_id, |payload| {
+                        check_is_null_condition(is_null, &payload)
+                    })
+                }),
+            }
+        }
         // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
         Condition::HasId(has_id) => {
             let segment_ids: HashSet<_> = has_id
@@ -163,6 +179,11 @@ pub fn field_condition_index<'a>(
             ..
         } => get_geo_polygon_checkers(index, geo_polygon.clone()),
 
+        FieldCondition {
+            geo_shape: Some(geo_shape),
+            ..
+        } => get_geo_shape_checkers(index, geo_shape),
+
         FieldCondition {
             key: _,
             r#match: None,
@@ -170,6 +191,7 @@ pub fn field_condition_index<'a>(
             geo_radius: None,
             geo_bounding_box: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: _, // No applicable index for values_count
         } => None,
     }
@@ -192,6 +214,25 @@ pub fn get_geo_polygon_checkers(
     }
 }
 
+pub fn get_geo_shape_checkers<'a>(
+    index: &'a FieldIndex,
+    geo_shape: &GeoShapeCondition,
+) -> Option<ConditionCheckerFn<'a>> {
+    match index {
+        FieldIndex::GeoShapeIndex(geo_shape_index) => {
+            let checker = geo_shape.checker();
+            Some(Box::new(move |point_id: PointOffsetType| {
+                geo_shape_index
+                    .get_values(point_id)
+                    .map_or(false, |values| {
+                        values.iter().any(|shape| checker.check_shape(shape))
+                    })
+            }))
+        }
+        _ => None,
+    }
+}
+
 pub fn get_geo_radius_checkers(
     index: &FieldIndex,
     geo_radius: GeoRadius,
@@ -229,6 +270,8 @@ pub fn get_range_checkers(index: &FieldIndex, range: RangeInterface) -> Option<C
     match range {
         RangeInterface::Float(range) => get_float_range_checkers(index, range),
         RangeInterface::DateTime(range) => get_datetime_range_checkers(index, range),
+        // Must be resolved before the request reaches the segment
+        RangeInterface::RelativeDateTime(_) => None,
     }
 }
 
@@ -362,6 +405,7 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
                     })
                 }))
             }
+            (_, FieldIndex::NullIndex(_)) => None,
             (_, index) => Some(Box::new(|point_id: PointOffsetType| {
                 // If there is any other value of any other index, then it's a match
                 index.values_count(point_id) > 0
@@ -4,6 +4,7 @@ use std::fs::{create_dir_all, remove_dir_all, rename};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
@@ -21,6 +22,7 @@ use super::indices_tracker::IndicesTracker;
 use super::sparse_index_config::SparseIndexType;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::operation_time_statistics::ScopeDurationMeasurer;
+use crate::data_types::explain::{query_points_traced, SearchStrategy};
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
@@ -301,14 +303,18 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 let mut filtered_points = match prefiltered_points {
                     Some(filtered_points) => filtered_points.iter().copied(),
                     None => {
-                        let filtered_points = payload_index.query_points(filter);
+                        let filtered_points =
+                            query_points_traced(&*payload_index, filter, vector_query_context);
                         *prefiltered_points = Some(filtered_points);
                         prefiltered_points.as_ref().unwrap().iter().copied()
                     }
                 };
                 Ok(raw_scorer.peek_top_iter(&mut filtered_points, top))
             }
-            None => Ok(raw_scorer.peek_top_all(top)),
+            None => {
+                vector_query_context.trace(|trace| trace.strategy = Some(SearchStrategy::FullScan));
+                Ok(raw_scorer.peek_top_all(top))
+            }
         }
     }
 
@@ -334,7 +340,8 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let ids = match prefiltered_points {
             Some(filtered_points) => filtered_points.iter(),
             None => {
-                let filtered_points = payload_index.query_points(filter);
+                let filtered_points =
+                    query_points_traced(&*payload_index, filter, vector_query_context);
                 *prefiltered_points = Some(filtered_points);
                 prefiltered_points.as_ref().unwrap().iter()
             }
@@ -374,6 +381,8 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             check_deleted_condition(idx, deleted_vectors, deleted_point_bitslice)
         };
 
+        vector_query_context.trace(|trace| trace.strategy = Some(SearchStrategy::InvertedIndex));
+
         let is_stopped = vector_query_context.is_stopped();
 
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
@@ -416,7 +425,11 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         match filter {
             Some(filter) => {
                 // if cardinality is small - use plain search
+                let estimation_timer = Instant::now();
                 let query_cardinality = self.get_query_cardinality(filter);
+                vector_query_context.trace(|trace| {
+                    trace.record_estimation(&query_cardinality, estimation_timer.elapsed())
+                });
                 let threshold = self
                     .config
                     .full_scan_threshold
@@ -464,7 +477,11 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 prefiltered_points,
                 vector_query_context,
             ),
-            QueryVector::Recommend(_) | QueryVector::Discovery(_) | QueryVector::Context(_) => {
+            QueryVector::Recommend(_)
+            | QueryVector::RecommendSumScores(_)
+            | QueryVector::RecommendContrastive(_)
+            | QueryVector::Discovery(_)
+            | QueryVector::Context(_) => {
                 let _timer = if filter.is_some() {
                     ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_plain)
                 } else {
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
+use itertools::Itertools;
 use log::debug;
 use parking_lot::RwLock;
 use rocksdb::DB;
@@ -15,10 +16,13 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::IndexesMap;
 use crate::common::Flusher;
+use crate::data_types::principal_range::PrincipalRange;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::index_selector::index_selector;
+use crate::index::field_index::null_index::NullIndex;
+use crate::index::field_index::numeric_index::StreamRange;
 use crate::index::field_index::{
-    CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
+    CardinalityEstimation, FieldIndex, NumericFieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
 use crate::index::payload_config::PayloadConfig;
 use crate::index::query_estimator::estimate_filter;
@@ -33,7 +37,7 @@ use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
     IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
-    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, Range, RangeInterface,
 };
 
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
@@ -207,6 +211,70 @@ impl StructPayloadIndex {
         self.id_tracker.borrow().available_point_count()
     }
 
+    /// Index of null and empty values of the field, if the field is indexed
+    fn null_index(&self, field: PayloadKeyTypeRef) -> Option<&NullIndex> {
+        self.field_indexes
+            .get(field)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_null_index()))
+    }
+
+    /// Principal field with its numeric index, if any field is indexed as principal
+    fn principal_index(&self) -> Option<(&PayloadKeyType, NumericFieldIndex<'_>)> {
+        let key = self
+            .config
+            .indexed_fields
+            .iter()
+            .filter(|(_, payload_schema)| payload_schema.is_principal())
+            .map(|(key, _)| key)
+            .min()?;
+
+        let numeric_index = self
+            .field_indexes
+            .get(key)?
+            .iter()
+            .find_map(|index| index.as_numeric())?;
+
+        Some((key, numeric_index))
+    }
+
+    /// Range of values of the principal field, if any field is indexed as principal
+    pub fn principal_range(&self) -> Option<PrincipalRange> {
+        let (key, numeric_index) = self.principal_index()?;
+
+        let full_range = RangeInterface::Float(Range {
+            lt: None,
+            gt: None,
+            gte: None,
+            lte: None,
+        });
+        let mut values = numeric_index.stream_range(&full_range);
+        let (min, _) = values.next()?;
+        let max = values
+            .next_back()
+            .map_or_else(|| min.clone(), |(max, _)| max);
+
+        Some(PrincipalRange {
+            key: key.clone(),
+            min,
+            max,
+        })
+    }
+
+    /// Range of values of the principal field of a single point
+    pub fn point_principal_range(&self, point_id: PointOffsetType) -> Option<PrincipalRange> {
+        let (key, numeric_index) = self.principal_index()?;
+        let (min, max) = numeric_index
+            .get_ordering_values(point_id)
+            .minmax()
+            .into_option()?;
+
+        Some(PrincipalRange {
+            key: key.clone(),
+            min,
+            max,
+        })
+    }
+
     fn struct_filtered_context<'a>(&'a self, filter: &'a Filter) -> StructFilterContext<'a> {
         let estimator = |condition: &Condition| self.condition_cardinality(condition, None);
         let id_tracker = self.id_tracker.borrow();
@@ -237,24 +305,34 @@ impl StructPayloadIndex {
                 let available_points = self.available_point_count();
                 let full_path = JsonPath::extend_or_new(nested_path, &field.key);
 
+                // Within a nested object, emptiness is checked per object rather than per point,
+                // so the null index can't tell the number of matching points.
+                if nested_path.is_none() {
+                    if let Some(null_index) = self.null_index(&full_path) {
+                        let empty_points =
+                            available_points.saturating_sub(null_index.has_values_count());
+                        return CardinalityEstimation::exact(empty_points).with_primary_clause(
+                            PrimaryCondition::IsEmpty(IsEmptyCondition {
+                                is_empty: PayloadField { key: full_path },
+                            }),
+                        );
+                    }
+                }
+
                 let mut indexed_points = 0;
                 if let Some(field_indexes) = self.field_indexes.get(&full_path) {
                     for index in field_indexes {
                         indexed_points = indexed_points.max(index.count_indexed_points())
                     }
                     CardinalityEstimation {
-                        primary_clauses: vec![PrimaryCondition::IsEmpty(IsEmptyCondition {
-                            is_empty: PayloadField { key: full_path },
-                        })],
+                        primary_clauses: vec![],
                         min: 0, // It is possible, that some non-empty payloads are not indexed
                         exp: available_points.saturating_sub(indexed_points), // Expect field type consistency
                         max: available_points.saturating_sub(indexed_points),
                     }
                 } else {
                     CardinalityEstimation {
-                        primary_clauses: vec![PrimaryCondition::IsEmpty(IsEmptyCondition {
-                            is_empty: PayloadField { key: full_path },
-                        })],
+                        primary_clauses: vec![],
                         min: 0,
                         exp: available_points / 2,
                         max: available_points,
@@ -265,24 +343,29 @@ impl StructPayloadIndex {
                 let available_points = self.available_point_count();
                 let full_path = JsonPath::extend_or_new(nested_path, &field.key);
 
+                if nested_path.is_none() {
+                    if let Some(null_index) = self.null_index(&full_path) {
+                        return CardinalityEstimation::exact(null_index.is_null_count())
+                            .with_primary_clause(PrimaryCondition::IsNull(IsNullCondition {
+                                is_null: PayloadField { key: full_path },
+                            }));
+                    }
+                }
+
                 let mut indexed_points = 0;
                 if let Some(field_indexes) = self.field_indexes.get(&full_path) {
                     for index in field_indexes {
                         indexed_points = indexed_points.max(index.count_indexed_points())
                     }
                     CardinalityEstimation {
-                        primary_clauses: vec![PrimaryCondition::IsNull(IsNullCondition {
-                            is_null: PayloadField { key: full_path },
-                        })],
+                        primary_clauses: vec![],
                         min: 0,
                         exp: available_points.saturating_sub(indexed_points),
                         max: available_points.saturating_sub(indexed_points),
                     }
                 } else {
                     CardinalityEstimation {
-                        primary_clauses: vec![PrimaryCondition::IsNull(IsNullCondition {
-                            is_null: PayloadField { key: full_path },
-                        })],
+                        primary_clauses: vec![],
                         min: 0,
                         exp: available_points / 2,
                         max: available_points,
@@ -321,6 +404,7 @@ impl StructPayloadIndex {
             .flat_map(|(name, field)| -> Vec<PayloadIndexTelemetry> {
                 field
                     .iter()
+                    .filter(|field| field.as_null_index().is_none())
                     .map(|field| field.get_telemetry_data().set_name(name.to_string()))
                     .collect()
             })
@@ -431,8 +515,22 @@ impl PayloadIndex for StructPayloadIndex {
                             )
                         }
                         PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
-                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
-                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
+                        PrimaryCondition::IsEmpty(IsEmptyCondition { is_empty: field }) => {
+                            match self.null_index(&field.key) {
+                                None => points_iterator_ref.iter_ids(),
+                                Some(null_index) => Box::new(
+                                    points_iterator_ref
+                                        .iter_ids()
+                                        .filter(|&id| !null_index.has_values(id)),
+                                ),
+                            }
+                        }
+                        PrimaryCondition::IsNull(IsNullCondition { is_null: field }) => {
+                            match self.null_index(&field.key) {
+                                None => points_iterator_ref.iter_ids(),
+                                Some(null_index) => Box::new(null_index.iter_null()),
+                            }
+                        }
                     }
                 })
                 .filter(|&id| !visited_list.check_and_update_visited(id))
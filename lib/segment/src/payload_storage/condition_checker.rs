@@ -6,8 +6,8 @@ use serde_json::Value;
 
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
-    RangeInterface, ValueVariants, ValuesCount,
+    GeoPolygon, GeoRadius, GeoShape, GeoShapeCondition, Match, MatchAny, MatchExcept, MatchText,
+    MatchValue, Range, RangeInterface, ValueVariants, ValuesCount,
 };
 
 /// Threshold representing the point to which iterating through an IndexSet is more efficient than using hashing.
@@ -40,6 +40,7 @@ impl ValueChecker for FieldCondition {
             geo_radius,
             geo_bounding_box,
             geo_polygon,
+            geo_shape,
             values_count,
             key: _,
         } = self;
@@ -52,6 +53,8 @@ impl ValueChecker for FieldCondition {
                 .is_some_and(|range_interface| match range_interface {
                     RangeInterface::Float(condition) => condition.check_match(payload),
                     RangeInterface::DateTime(condition) => condition.check_match(payload),
+                    // Must be resolved before the request reaches the segment
+                    RangeInterface::RelativeDateTime(_) => false,
                 })
             || geo_radius
                 .as_ref()
@@ -62,6 +65,9 @@ impl ValueChecker for FieldCondition {
             || geo_polygon
                 .as_ref()
                 .is_some_and(|condition| condition.check_match(payload))
+            || geo_shape
+                .as_ref()
+                .is_some_and(|condition| condition.check_match(payload))
             || values_count
                 .as_ref()
                 .is_some_and(|condition| condition.check_match(payload))
@@ -212,6 +218,12 @@ impl ValueChecker for GeoPolygon {
     }
 }
 
+impl ValueChecker for GeoShapeCondition {
+    fn check_match(&self, payload: &Value) -> bool {
+        GeoShape::from_value(payload).is_some_and(|shape| self.checker().check_shape(&shape))
+    }
+}
+
 impl ValueChecker for ValuesCount {
     fn check_match(&self, payload: &Value) -> bool {
         self.check_count(payload)
@@ -227,7 +239,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::types::GeoPoint;
+    use crate::types::{GeoPoint, GeoShapeRelation};
 
     #[test]
     fn test_geo_matching() {
@@ -261,6 +273,53 @@ mod tests {
         assert!(!miss_geo_query.check(&berlin_and_moscow));
     }
 
+    #[test]
+    fn test_geo_shape_matching() {
+        let zone = json!({
+            "exterior": {
+                "points": [
+                    { "lon": 13.0, "lat": 52.0 },
+                    { "lon": 14.0, "lat": 52.0 },
+                    { "lon": 14.0, "lat": 53.0 },
+                    { "lon": 13.0, "lat": 53.0 },
+                    { "lon": 13.0, "lat": 52.0 }
+                ]
+            }
+        });
+
+        let inside_point = GeoShape::Point(GeoPoint {
+            lon: 13.5,
+            lat: 52.5,
+        });
+        let outside_point = GeoShape::Point(GeoPoint {
+            lon: 20.0,
+            lat: 52.5,
+        });
+        let large_area = GeoShape::Polygon(crate::types::test_utils::build_polygon(vec![
+            (10.0, 50.0),
+            (20.0, 50.0),
+            (20.0, 60.0),
+            (10.0, 60.0),
+            (10.0, 50.0),
+        ]));
+
+        let condition = |relation, shape| GeoShapeCondition { relation, shape };
+
+        assert!(condition(GeoShapeRelation::Contains, inside_point.clone()).check(&zone));
+        assert!(!condition(GeoShapeRelation::Contains, outside_point.clone()).check(&zone));
+        assert!(condition(GeoShapeRelation::Intersects, inside_point).check(&zone));
+        assert!(!condition(GeoShapeRelation::Intersects, outside_point).check(&zone));
+        assert!(condition(GeoShapeRelation::Within, large_area.clone()).check(&zone));
+        assert!(!condition(GeoShapeRelation::Contains, large_area).check(&zone));
+
+        // Values which are not geo shapes never match
+        assert!(!condition(
+            GeoShapeRelation::Intersects,
+            GeoShape::Point(GeoPoint { lon: 1.0, lat: 1.0 })
+        )
+        .check(&json!("not a shape")));
+    }
+
     #[test]
     fn test_value_count() {
         let countries = json!([
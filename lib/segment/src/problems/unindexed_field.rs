@@ -193,6 +193,7 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
         geo_bounding_box,
         geo_radius,
         geo_polygon,
+        geo_shape,
         values_count,
     } = field_condition;
 
@@ -216,7 +217,7 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     }
     if let Some(range_interface) = range {
         match range_interface {
-            RangeInterface::DateTime(_) => {
+            RangeInterface::DateTime(_) | RangeInterface::RelativeDateTime(_) => {
                 inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime));
             }
             RangeInterface::Float(_) => {
@@ -228,6 +229,9 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
     if geo_bounding_box.is_some() || geo_radius.is_some() || geo_polygon.is_some() {
         inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::Geo));
     }
+    if geo_shape.is_some() {
+        inferred.push(PayloadFieldSchema::FieldType(PayloadSchemaType::GeoShape));
+    }
     if values_count.is_some() {
         // Any index will do, let user choose depending on their data type
         inferred.extend(all_indexes());
@@ -1,19 +1,23 @@
 use std::cmp::max;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
+use std::time::Instant;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitVec;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
 use io::file_operations::{atomic_save_json, read_json};
 use io::storage_version::{StorageVersion, VERSION_FILE};
-use itertools::Either;
+use itertools::{Either, Itertools};
 use memory::mmap_ops;
 use parking_lot::{Mutex, RwLock};
+use rand::rngs::ThreadRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use rocksdb::DB;
 use tar::Builder;
 use uuid::Uuid;
@@ -23,13 +27,26 @@ use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
-use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
+use crate::common::{
+    check_filter_resolved, check_named_vectors, check_query_vectors, check_stopped,
+    check_vector_name,
+};
+use crate::data_types::aggregations::{
+    aggregation_value_from_json, AggregationParams, DateHistogramBucketing, DateHistogramCounts,
+    NumericAggregation,
+};
+use crate::data_types::distinct::{CardinalitySketch, DistinctParams};
+use crate::data_types::explain::{segment_name, SearchTrace};
+use crate::data_types::facets::{FacetParams, FacetValue, FacetValueRef};
+use crate::data_types::formula::{FormulaInput, FormulaQuery};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
+use crate::data_types::principal_range::PrincipalRange;
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
+use crate::index::field_index::geo_index::geo_distance;
 use crate::index::field_index::numeric_index::StreamRange;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::struct_payload_index::StructPayloadIndex;
@@ -38,12 +55,14 @@ use crate::json_path::JsonPath;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    Filter, GeoPoint, Payload, PayloadContainer, PayloadFieldSchema, PayloadIndexInfo,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType, Range, RangeInterface,
+    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentState, SegmentType,
+    SeqNumberType, VectorDataInfo, WithPayload, WithVector,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
+use crate::utils::sampling::sampling_keys;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
@@ -83,6 +102,10 @@ pub struct Segment {
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     pub vector_data: HashMap<String, VectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Range of values of the principal field, used to skip the segment if the filter can't match.
+    /// It is computed when the segment is loaded and only extended on updates, so after deletions
+    /// it may be wider than the actual values.
+    pub principal_range: Option<PrincipalRange>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
@@ -400,6 +423,28 @@ impl Segment {
         BitVec::from(self.id_tracker.borrow().deleted_point_bitslice())
     }
 
+    /// Extend the principal range with the values of the updated point
+    fn extend_principal_range(&mut self, internal_id: PointOffsetType) {
+        let payload_index = self.payload_index.borrow();
+        let Some(point_range) = payload_index.point_principal_range(internal_id) else {
+            return;
+        };
+        self.principal_range = match &self.principal_range {
+            None => Some(point_range),
+            Some(range) => range
+                .union(&point_range)
+                .or_else(|| payload_index.principal_range()),
+        };
+    }
+
+    /// Check if the principal range shows that no point of the segment matches the filter
+    fn is_excluded_by_principal_range(&self, filter: Option<&Filter>) -> bool {
+        match (&self.principal_range, filter) {
+            (Some(range), Some(filter)) => !range.may_match(filter),
+            _ => false,
+        }
+    }
+
     fn lookup_internal_id(&self, point_id: PointIdType) -> OperationResult<PointOffsetType> {
         let internal_id_opt = self.id_tracker.borrow().internal_id(point_id);
         match internal_id_opt {
@@ -813,6 +858,226 @@ impl Segment {
         Ok(page)
     }
 
+    /// Read points in order of distance from `origin` to the geo values of `order_by.key`
+    pub fn filtered_read_by_geo_distance(
+        &self,
+        order_by: &OrderBy,
+        origin: &GeoPoint,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+
+        let geo_index = payload_index
+            .field_indexes
+            .get(&order_by.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_geo()))
+            .ok_or_else(|| OperationError::MissingGeoIndexForOrderBy {
+                key: order_by.key.to_string(),
+            })?;
+
+        let start_from = order_by.start_from();
+
+        let id_tracker = self.id_tracker.borrow();
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+
+        let values_ids_iterator = geo_index
+            .stream_by_distance(origin.clone())?
+            .filter(move |(_, internal_id)| {
+                filter_context
+                    .as_ref()
+                    .map_or(true, |filter_context| filter_context.check(*internal_id))
+            })
+            .filter_map(|(distance, internal_id)| {
+                id_tracker
+                    .external_id(internal_id)
+                    .map(|external_id| (OrderValue::Float(distance), external_id))
+            });
+
+        let page = match order_by.direction() {
+            // Points are streamed from the closest, so the stream can stop at the limit
+            Direction::Asc => values_ids_iterator
+                .skip_while(|(value, _)| value < &start_from)
+                .take(limit.unwrap_or(usize::MAX))
+                .collect(),
+            // Farthest first has to go through the whole stream, only keep the farthest ones
+            Direction::Desc => {
+                let values_ids_iterator =
+                    values_ids_iterator.filter(|(value, _)| value <= &start_from);
+                let mut page = match limit {
+                    Some(limit) => peek_top_largest_iterable(values_ids_iterator, limit),
+                    None => values_ids_iterator.collect(),
+                };
+                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a));
+                page
+            }
+        };
+
+        Ok(page)
+    }
+
+    /// Read points in order of a compound key, or of a single keyword key.
+    ///
+    /// Points are streamed by the first key, and the points with the same value of it are sorted
+    /// by the rest of the keys and the point id. The index doesn't order these ties, so all of them
+    /// are streamed, but only as many as the remaining limit are kept.
+    pub fn filtered_read_by_compound_key(
+        &self,
+        order_by: &OrderBy,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let indexes = order_by
+            .keys()
+            .map(|key| {
+                if key.geo_origin.is_some() && order_by.is_compound() {
+                    return Err(OperationError::ValidationError {
+                        description: "Can't order by geo distance along with other keys"
+                            .to_string(),
+                    });
+                }
+                payload_index
+                    .field_indexes
+                    .get(&key.key)
+                    .and_then(|indexes| indexes.iter().find_map(|index| index.as_ordering()))
+                    .ok_or_else(|| OperationError::MissingOrderingIndexForOrderBy {
+                        key: key.key.to_string(),
+                    })
+            })
+            .collect::<OperationResult<Vec<_>>>()?;
+
+        let filter_context = filter.map(|filter| payload_index.filter_context(filter));
+        let start_from = order_by.compound_start_from();
+
+        let sort_ties = |ties: &mut Vec<(OrderValue, PointIdType)>, count: usize| {
+            ties.sort_by(|(value_a, id_a), (value_b, id_b)| {
+                order_by.cmp_points((value_a, *id_a), (value_b, *id_b))
+            });
+            ties.truncate(count);
+        };
+
+        let mut seen = HashSet::new();
+        let mut reads: Vec<(OrderValue, PointIdType)> = Vec::new();
+        // Points with the current value of the first key
+        let mut ties: Vec<(OrderValue, PointIdType)> = Vec::new();
+        let mut ties_value: Option<OrderValue> = None;
+        let mut remaining = limit.unwrap_or(usize::MAX);
+
+        for (first_value, internal_id) in indexes[0].stream(order_by) {
+            if ties_value.as_ref() != Some(&first_value) {
+                sort_ties(&mut ties, remaining);
+                remaining -= ties.len();
+                reads.append(&mut ties);
+                if remaining == 0 {
+                    break;
+                }
+                ties_value = Some(first_value.clone());
+            }
+
+            if seen.contains(&internal_id)
+                || !filter_context
+                    .as_ref()
+                    .map_or(true, |context| context.check(internal_id))
+            {
+                continue;
+            }
+
+            let value = if order_by.is_compound() {
+                let rest_values =
+                    indexes[1..]
+                        .iter()
+                        .zip(order_by.then_by_keys())
+                        .map(|(index, key)| {
+                            index
+                                .get_ordering_value(internal_id, key.direction())
+                                .unwrap_or_else(|| key.missing_value())
+                        });
+                OrderValue::Compound(std::iter::once(first_value).chain(rest_values).collect())
+            } else {
+                first_value
+            };
+
+            if let Some(start_from) = &start_from {
+                if order_by.cmp_values(&value, start_from).is_lt() {
+                    continue;
+                }
+            }
+
+            let Some(external_id) = id_tracker.external_id(internal_id) else {
+                continue;
+            };
+
+            seen.insert(internal_id);
+            ties.push((value, external_id));
+
+            // Keep the ties bounded by the remaining limit
+            if ties.len() >= remaining.saturating_mul(2) {
+                sort_ties(&mut ties, remaining);
+            }
+        }
+
+        sort_ties(&mut ties, remaining);
+        reads.append(&mut ties);
+
+        Ok(reads)
+    }
+
+    /// Uniformly random sample of up to `limit` internal ids, which satisfy the filter,
+    /// along with the exact number of such points
+    fn read_random_internal_ids(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+    ) -> (Vec<PointOffsetType>, usize) {
+        let mut rng = rand::thread_rng();
+        let id_tracker = self.id_tracker.borrow();
+
+        let read_all = |ids: Vec<PointOffsetType>, rng: &mut ThreadRng| {
+            let total = ids.len();
+            let mut sample = ids.choose_multiple(rng, limit).copied().collect_vec();
+            sample.shuffle(rng);
+            (sample, total)
+        };
+
+        // The number of matching points weights this segment against the others, so it has to be
+        // exact. Counting filtered points takes a full pass anyway, so sample from that pass.
+        if let Some(condition) = filter {
+            let ids = self.payload_index.borrow().query_points(condition);
+            return read_all(ids, &mut rng);
+        }
+
+        // Random draws repeat a lot, if the sample is a large part of the segment
+        let available = id_tracker.available_point_count();
+        if limit.saturating_mul(2) >= available {
+            return read_all(id_tracker.iter_ids().collect(), &mut rng);
+        }
+
+        // Draw until the sample is full. Less than half of the points are needed, so a couple of
+        // draws per hit are expected, and the bound only guards against a sparse id space.
+        let total_ids = id_tracker.total_point_count() as PointOffsetType;
+        let max_draws = id_tracker.total_point_count().saturating_mul(2);
+        let mut seen = HashSet::with_capacity(limit);
+        let mut sample = Vec::with_capacity(limit);
+        for _ in 0..max_draws {
+            if sample.len() >= limit {
+                break;
+            }
+            // Sampled points aren't tied to a vector, so only point deletion is relevant here
+            let internal_id = rng.gen_range(0..total_ids);
+            if !id_tracker.is_deleted_point(internal_id) && seen.insert(internal_id) {
+                sample.push(internal_id);
+            }
+        }
+
+        if sample.len() < limit {
+            return read_all(id_tracker.iter_ids().collect(), &mut rng);
+        }
+        (sample, available)
+    }
+
     pub fn filtered_read_by_id_stream(
         &self,
         offset: Option<PointIdType>,
@@ -878,6 +1143,66 @@ impl Segment {
         Ok(reads)
     }
 
+    /// Count points per date histogram bucket, using the datetime index of the key.
+    ///
+    /// A point with several values in the same bucket is counted once.
+    fn date_histogram(
+        &self,
+        payload_index: &StructPayloadIndex,
+        request: &AggregationParams,
+        bucketing: &DateHistogramBucketing,
+    ) -> OperationResult<BTreeMap<i64, DateHistogramCounts>> {
+        let datetime_index = payload_index
+            .field_indexes
+            .get(&request.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_datetime()))
+            .ok_or_else(|| OperationError::MissingDatetimeIndexForHistogram {
+                key: request.key.to_string(),
+            })?;
+
+        let facet_index = bucketing
+            .facet
+            .as_ref()
+            .map(|key| {
+                payload_index
+                    .field_indexes
+                    .get(key)
+                    .and_then(|indexes| indexes.iter().find_map(|index| index.as_facet_index()))
+                    .ok_or_else(|| OperationError::MissingMapIndexForFacet {
+                        key: key.to_string(),
+                    })
+            })
+            .transpose()?;
+
+        let id_tracker = self.id_tracker.borrow();
+        let points = match &request.filter {
+            Some(filter) => Either::Left(payload_index.query_points(filter).into_iter()),
+            None => Either::Right(id_tracker.iter_ids()),
+        };
+
+        let mut histogram: BTreeMap<i64, DateHistogramCounts> = BTreeMap::new();
+        for internal_id in points {
+            let Some(values) = datetime_index.get_values(internal_id) else {
+                continue;
+            };
+            let buckets = values
+                .iter()
+                .map(|&timestamp| bucketing.bucket_of(timestamp))
+                .unique();
+            for bucket in buckets {
+                let counts = histogram.entry(bucket).or_default();
+                counts.count += 1;
+                if let Some(facet_index) = &facet_index {
+                    for value in facet_index.get_values(internal_id).unique() {
+                        *counts.facet.entry(value.to_owned()).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(histogram)
+    }
+
     /// Check consistency of the segment's data and repair it if possible.
     pub fn check_consistency_and_repair(&mut self) -> OperationResult<()> {
         let mut internal_ids_to_delete = HashSet::new();
@@ -1096,6 +1421,77 @@ impl Segment {
 
 /// This is a basic implementation of `SegmentEntry`,
 /// meaning that it implements the _actual_ operations with data and not any kind of proxy or wrapping
+/// Variables of a point, as seen by the segment
+struct SegmentFormulaInput<'a> {
+    point_id: PointIdType,
+    internal_id: PointOffsetType,
+    prefetches_scores: &'a [HashMap<PointIdType, ScoreType>],
+    payload_index: &'a StructPayloadIndex,
+}
+
+impl FormulaInput for SegmentFormulaInput<'_> {
+    fn score(&self, prefetch: usize) -> ScoreType {
+        self.prefetches_scores
+            .get(prefetch)
+            .and_then(|scores| scores.get(&self.point_id))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    fn payload_value(&self, key: &JsonPath) -> OperationResult<Option<f64>> {
+        let numeric_index = self
+            .payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()));
+
+        if let Some(numeric_index) = numeric_index {
+            return Ok(numeric_index
+                .get_ordering_values(self.internal_id)
+                .next()
+                .map(|value| value.as_f64()));
+        }
+
+        // No numeric index, read the value from payload
+        let payload = self.payload_index.payload(self.internal_id)?;
+        let value = payload
+            .get_value(key)
+            .into_iter()
+            .flat_map(|value| match value {
+                serde_json::Value::Array(values) => Either::Left(values.iter()),
+                value => Either::Right(std::iter::once(value)),
+            })
+            .find_map(aggregation_value_from_json);
+        Ok(value)
+    }
+
+    fn geo_distance(&self, key: &JsonPath, origin: &GeoPoint) -> OperationResult<Option<f64>> {
+        let geo_index = self
+            .payload_index
+            .field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_geo()));
+
+        if let Some(geo_index) = geo_index {
+            return Ok(geo_index.min_distance(self.internal_id, origin));
+        }
+
+        // No geo index, read the value from payload
+        let payload = self.payload_index.payload(self.internal_id)?;
+        let distance = payload
+            .get_value(key)
+            .into_iter()
+            .flat_map(|value| match value {
+                serde_json::Value::Array(values) => Either::Left(values.iter()),
+                value => Either::Right(std::iter::once(value)),
+            })
+            .filter_map(|value| serde_json::from_value::<GeoPoint>(value.clone()).ok())
+            .map(|point| geo_distance(origin, &point))
+            .min_by(|a, b| a.total_cmp(b));
+        Ok(distance)
+    }
+}
+
 impl SegmentEntry for Segment {
     fn version(&self) -> SeqNumberType {
         self.version.unwrap_or(0)
@@ -1120,8 +1516,17 @@ impl SegmentEntry for Segment {
         query_context: SegmentQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
+        check_filter_resolved(filter)?;
+        if self.is_excluded_by_principal_range(filter) {
+            return Ok(vec![vec![]; query_vectors.len()]);
+        }
         let vector_data = &self.vector_data[vector_name];
-        let vector_query_context = query_context.get_vector_context(vector_name);
+        let timer = Instant::now();
+        let trace = Mutex::new(SearchTrace::default());
+        let mut vector_query_context = query_context.get_vector_context(vector_name);
+        if query_context.is_explained() {
+            vector_query_context = vector_query_context.with_trace(&trace);
+        }
         let internal_results = vector_data.vector_index.borrow().search(
             query_vectors,
             filter,
@@ -1132,6 +1537,17 @@ impl SegmentEntry for Segment {
 
         check_stopped(&vector_query_context.is_stopped())?;
 
+        if vector_query_context.is_traced() {
+            let explanation = trace.into_inner().into_explanation(
+                segment_name(&self.current_path),
+                self.segment_type,
+                self.available_point_count(),
+                Some(vector_name.to_string()),
+                timer.elapsed(),
+            );
+            query_context.add_explanation(explanation);
+        }
+
         let res = internal_results
             .iter()
             .map(|internal_result| {
@@ -1258,6 +1674,7 @@ impl SegmentEntry for Segment {
                     .payload_index
                     .borrow_mut()
                     .assign_all(internal_id, full_payload)?;
+                segment.extend_principal_range(internal_id);
                 Ok((true, Some(internal_id)))
             }
             None => Err(OperationError::PointIdError {
@@ -1280,6 +1697,7 @@ impl SegmentEntry for Segment {
                     .payload_index
                     .borrow_mut()
                     .assign(internal_id, payload, key)?;
+                segment.extend_principal_range(internal_id);
                 Ok((true, Some(internal_id)))
             }
             None => Err(OperationError::PointIdError {
@@ -1361,8 +1779,13 @@ impl SegmentEntry for Segment {
         offset: Option<PointIdType>,
         limit: Option<usize>,
         filter: Option<&'a Filter>,
-    ) -> Vec<PointIdType> {
-        match filter {
+    ) -> OperationResult<Vec<PointIdType>> {
+        check_filter_resolved(filter)?;
+        if self.is_excluded_by_principal_range(filter) {
+            return Ok(vec![]);
+        }
+
+        let points = match filter {
             None => self.read_by_id_stream(offset, limit),
             Some(condition) => {
                 if self.should_pre_filter(condition, limit) {
@@ -1371,7 +1794,34 @@ impl SegmentEntry for Segment {
                     self.filtered_read_by_id_stream(offset, limit, condition)
                 }
             }
+        };
+        Ok(points)
+    }
+
+    fn read_random_filtered<'a>(
+        &'a self,
+        limit: usize,
+        filter: Option<&'a Filter>,
+    ) -> OperationResult<Vec<(ScoreType, PointIdType)>> {
+        check_filter_resolved(filter)?;
+        if self.is_excluded_by_principal_range(filter) {
+            return Ok(vec![]);
         }
+
+        let (internal_ids, total) = self.read_random_internal_ids(limit, filter);
+        let keys = sampling_keys(total, internal_ids.len(), &mut rand::thread_rng());
+
+        let id_tracker = self.id_tracker.borrow();
+        let points = keys
+            .into_iter()
+            .zip(internal_ids)
+            .filter_map(|(key, internal_id)| {
+                id_tracker
+                    .external_id(internal_id)
+                    .map(|external_id| (key, external_id))
+            })
+            .collect();
+        Ok(points)
     }
 
     fn read_ordered_filtered<'a>(
@@ -1380,6 +1830,30 @@ impl SegmentEntry for Segment {
         filter: Option<&'a Filter>,
         order_by: &'a OrderBy,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        check_filter_resolved(filter)?;
+        if self.is_excluded_by_principal_range(filter) {
+            return Ok(vec![]);
+        }
+
+        // Compound and keyword keys are read by the common ordering index
+        let is_keyword_key = || {
+            self.payload_index
+                .borrow()
+                .field_indexes
+                .get(&order_by.key)
+                .map_or(false, |indexes| {
+                    indexes.iter().all(|index| index.as_numeric().is_none())
+                        && indexes.iter().any(|index| index.as_ordering().is_some())
+                })
+        };
+        if order_by.is_compound() || (order_by.geo_origin.is_none() && is_keyword_key()) {
+            return self.filtered_read_by_compound_key(order_by, limit, filter);
+        }
+
+        if let Some(origin) = &order_by.geo_origin {
+            return self.filtered_read_by_geo_distance(order_by, origin, limit, filter);
+        }
+
         match filter {
             None => self.filtered_read_by_value_stream(order_by, limit, None),
             Some(filter) => {
@@ -1392,6 +1866,185 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn facet(&self, request: &FacetParams) -> OperationResult<HashMap<FacetValue, usize>> {
+        check_filter_resolved(request.filter.as_ref())?;
+        let payload_index = self.payload_index.borrow();
+
+        let facet_index = payload_index
+            .field_indexes
+            .get(&request.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_facet_index()))
+            .ok_or_else(|| OperationError::MissingMapIndexForFacet {
+                key: request.key.to_string(),
+            })?;
+
+        let Some(filter) = &request.filter else {
+            let counts = facet_index
+                .iter_counts_per_value()
+                .map(|(value, count)| (value.to_owned(), count))
+                .collect();
+            return Ok(counts);
+        };
+
+        let mut counts: HashMap<FacetValueRef, usize> = HashMap::new();
+
+        let cardinality = payload_index.estimate_cardinality(filter);
+        if cardinality.exp < facet_index.values_count() {
+            // Filter is restrictive: count values of each matching point
+            for internal_id in payload_index.query_points(filter) {
+                for value in facet_index.get_values(internal_id).unique() {
+                    *counts.entry(value).or_default() += 1;
+                }
+            }
+        } else if !request.exact {
+            // Filter is broad and counts may be approximate: instead of checking every point,
+            // scale the counts of the index by the estimated share of matching points
+            let matching_share =
+                cardinality.exp as f64 / self.available_point_count().max(1) as f64;
+            for (value, count) in facet_index.iter_counts_per_value() {
+                let estimated_count = (count as f64 * matching_share).round() as usize;
+                if estimated_count > 0 {
+                    *counts.entry(value).or_default() += estimated_count;
+                }
+            }
+        } else {
+            // Filter is broad: check the points of each value against the filter
+            let filter_context = payload_index.filter_context(filter);
+            for (value, points) in facet_index.iter_values_map() {
+                let count = points
+                    .filter(|internal_id| filter_context.check(*internal_id))
+                    .count();
+                *counts.entry(value).or_default() += count;
+            }
+        }
+
+        Ok(counts
+            .into_iter()
+            .map(|(value, count)| (value.to_owned(), count))
+            .collect())
+    }
+
+    fn aggregate(&self, request: &AggregationParams) -> OperationResult<NumericAggregation> {
+        check_filter_resolved(request.filter.as_ref())?;
+        let payload_index = self.payload_index.borrow();
+        let bucketing = request.histogram.as_ref();
+
+        let mut aggregation = NumericAggregation::default();
+
+        if let Some(date_histogram) = &request.date_histogram {
+            aggregation.date_histogram =
+                self.date_histogram(&payload_index, request, date_histogram)?;
+        }
+
+        let numeric_index = payload_index
+            .field_indexes
+            .get(&request.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_numeric()));
+
+        let Some(numeric_index) = numeric_index else {
+            // No numeric index, read values from payload
+            let id_tracker = self.id_tracker.borrow();
+            let points = match &request.filter {
+                Some(filter) => Either::Left(payload_index.query_points(filter).into_iter()),
+                None => Either::Right(id_tracker.iter_ids()),
+            };
+            for internal_id in points {
+                let payload = payload_index.payload(internal_id)?;
+                for value in payload.get_value(&request.key) {
+                    let values = match value {
+                        serde_json::Value::Array(values) => Either::Left(values.iter()),
+                        value => Either::Right(std::iter::once(value)),
+                    };
+                    for value in values.filter_map(aggregation_value_from_json) {
+                        aggregation.add(value, bucketing);
+                    }
+                }
+            }
+            return Ok(aggregation);
+        };
+
+        let all_values = RangeInterface::Float(Range::default());
+
+        match &request.filter {
+            None => {
+                for (value, _) in numeric_index.stream_range(&all_values) {
+                    aggregation.add(value.as_f64(), bucketing);
+                }
+            }
+            Some(filter) => {
+                let cardinality = payload_index.estimate_cardinality(filter);
+                if cardinality.exp < self.available_point_count() {
+                    // Filter is restrictive: read values of each matching point
+                    for internal_id in payload_index.query_points(filter) {
+                        for value in numeric_index.get_ordering_values(internal_id) {
+                            aggregation.add(value.as_f64(), bucketing);
+                        }
+                    }
+                } else {
+                    // Filter is broad: check the point of each value against the filter
+                    let filter_context = payload_index.filter_context(filter);
+                    for (value, internal_id) in numeric_index.stream_range(&all_values) {
+                        if filter_context.check(internal_id) {
+                            aggregation.add(value.as_f64(), bucketing);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(aggregation)
+    }
+
+    fn distinct(&self, request: &DistinctParams) -> OperationResult<CardinalitySketch> {
+        check_filter_resolved(request.filter.as_ref())?;
+        let mut sketch = CardinalitySketch::default();
+
+        let has_facet_index = self
+            .payload_index
+            .borrow()
+            .field_indexes
+            .get(&request.key)
+            .is_some_and(|indexes| indexes.iter().any(|index| index.as_facet_index().is_some()));
+
+        if has_facet_index {
+            // Values of the index are exact, only keep the ones of the matching points
+            let facet_params = FacetParams {
+                key: request.key.clone(),
+                limit: usize::MAX,
+                filter: request.filter.clone(),
+                exact: true,
+            };
+            for (value, count) in self.facet(&facet_params)? {
+                if count > 0 {
+                    sketch.insert_facet_value(value);
+                }
+            }
+            return Ok(sketch);
+        }
+
+        // No index, read values from payload
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let points = match &request.filter {
+            Some(filter) => Either::Left(payload_index.query_points(filter).into_iter()),
+            None => Either::Right(id_tracker.iter_ids()),
+        };
+        for internal_id in points {
+            let payload = payload_index.payload(internal_id)?;
+            for value in payload.get_value(&request.key) {
+                match value {
+                    serde_json::Value::Array(values) => {
+                        values.iter().for_each(|value| sketch.insert_value(value))
+                    }
+                    serde_json::Value::Null => {}
+                    value => sketch.insert_value(value),
+                }
+            }
+        }
+
+        Ok(sketch)
+    }
+
     fn read_range(&self, from: Option<PointIdType>, to: Option<PointIdType>) -> Vec<PointIdType> {
         let id_tracker = self.id_tracker.borrow();
         let iterator = id_tracker.iter_from(from).map(|x| x.0);
@@ -1421,8 +2074,16 @@ impl SegmentEntry for Segment {
             .available_size_in_bytes())
     }
 
-    fn estimate_point_count<'a>(&'a self, filter: Option<&'a Filter>) -> CardinalityEstimation {
-        match filter {
+    fn estimate_point_count<'a>(
+        &'a self,
+        filter: Option<&'a Filter>,
+    ) -> OperationResult<CardinalityEstimation> {
+        check_filter_resolved(filter)?;
+        if self.is_excluded_by_principal_range(filter) {
+            return Ok(CardinalityEstimation::exact(0));
+        }
+
+        let estimation = match filter {
             None => {
                 let available = self.available_point_count();
                 CardinalityEstimation {
@@ -1436,7 +2097,8 @@ impl SegmentEntry for Segment {
                 let payload_index = self.payload_index.borrow();
                 payload_index.estimate_cardinality(filter)
             }
-        }
+        };
+        Ok(estimation)
     }
 
     fn segment_type(&self) -> SegmentType {
@@ -1658,6 +2320,7 @@ impl SegmentEntry for Segment {
     fn delete_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
         self.handle_segment_version_and_failure(op_num, |segment| {
             segment.payload_index.borrow_mut().drop_index(key)?;
+            segment.principal_range = segment.payload_index.borrow().principal_range();
             Ok(true)
         })
     }
@@ -1674,6 +2337,7 @@ impl SegmentEntry for Segment {
                     .payload_index
                     .borrow_mut()
                     .set_indexed(key, schema.clone())?;
+                segment.principal_range = segment.payload_index.borrow().principal_range();
                 Ok(true)
             }
             None => match segment.infer_from_payload_data(key)? {
@@ -1685,6 +2349,7 @@ impl SegmentEntry for Segment {
                         .payload_index
                         .borrow_mut()
                         .set_indexed(key, schema_type)?;
+                    segment.principal_range = segment.payload_index.borrow().principal_range();
                     Ok(true)
                 }
             },
@@ -1705,7 +2370,7 @@ impl SegmentEntry for Segment {
         filter: &'a Filter,
     ) -> OperationResult<usize> {
         let mut deleted_points = 0;
-        for point_id in self.read_filtered(None, None, Some(filter)) {
+        for point_id in self.read_filtered(None, None, Some(filter))? {
             deleted_points += self.delete_point(op_num, point_id)? as usize;
         }
 
@@ -2054,6 +2719,86 @@ mod tests {
         assert!(results_with_invalid_filter.is_empty());
     }
 
+    #[test]
+    fn test_reject_unresolved_filter() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        segment
+            .upsert_point(0, 0.into(), only_default_vector(&[1.0, 1.0]))
+            .unwrap();
+
+        let filter: Filter = serde_json::from_str(
+            r#"{ "must": [{ "key": "created_at", "range": { "gte": "now-7d" } }] }"#,
+        )
+        .unwrap();
+
+        // Reads must fail instead of matching nothing
+        assert!(segment.read_filtered(None, None, Some(&filter)).is_err());
+        assert!(segment.read_random_filtered(10, Some(&filter)).is_err());
+        assert!(segment.estimate_point_count(Some(&filter)).is_err());
+        assert!(segment.read_filtered(None, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_read_random_filtered() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::Memory,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+        };
+
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        for i in 0..100 {
+            segment
+                .upsert_point(i, i.into(), only_default_vector(&[1.0, 1.0]))
+                .unwrap();
+        }
+        for i in 0..30 {
+            segment.delete_point(100 + i, i.into()).unwrap();
+        }
+
+        // Sample is always full, unique and doesn't contain deleted points
+        for _ in 0..10 {
+            let sample = segment.read_random_filtered(20, None).unwrap();
+            let ids: HashSet<_> = sample.iter().map(|(_, id)| *id).collect();
+            assert_eq!(ids.len(), 20);
+            assert!(ids.iter().all(|id| segment.has_point(*id)));
+            assert!(sample.windows(2).all(|pair| pair[0].0 >= pair[1].0));
+        }
+
+        let sample = segment.read_random_filtered(100, None).unwrap();
+        assert_eq!(sample.len(), 70);
+    }
+
     #[test]
     fn test_snapshot() {
         let data = r#"
@@ -6,7 +6,10 @@ use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
 use io::storage_version::StorageVersion;
+use itertools::Itertools;
+use smol_str::SmolStr;
 
 use super::{
     create_id_tracker, create_payload_storage, create_sparse_vector_index,
@@ -16,21 +19,43 @@ use super::{
 };
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
+use crate::data_types::order_by::OrderValue;
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::{IdTracker, IdTrackerEnum};
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndexOpenArgs;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::PayloadIndex;
+use crate::json_path::JsonPath;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::load_segment;
 use crate::types::{
-    PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
+    Payload, PayloadContainer, PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState,
+    SeqNumberType,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
+/// Point of a source segment, positioned in the order it is written into the new segment
+struct PositionedPoint {
+    segment_index: usize,
+    internal_id: PointOffsetType,
+    /// Values of the tenant fields, which define the order of points
+    tenant: Vec<Option<SmolStr>>,
+    /// Smallest value of the principal field, orders points within a tenant
+    principal: Option<OrderValue>,
+}
+
+/// Smallest keyword value of the tenant field in the payload
+fn tenant_value(payload: &Payload, key: &JsonPath) -> Option<SmolStr> {
+    payload
+        .get_value(key)
+        .iter()
+        .filter_map(|value| value.as_str().map(SmolStr::new))
+        .min()
+}
+
 /// Structure for constructing segment out of several other segments
 pub struct SegmentBuilder {
     version: SeqNumberType,
@@ -124,39 +149,160 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update_from(&mut self, other: &Segment, stopped: &AtomicBool) -> OperationResult<bool> {
-        self.version = cmp::max(self.version, other.version());
+        self.update(&[other], stopped)
+    }
 
-        let other_id_tracker = other.id_tracker.borrow();
-        let other_vector_storages: HashMap<_, _> = other
-            .vector_data
+    /// Update current segment builder with all (not deleted) vectors and payload from `segments`
+    ///
+    /// If any of the indexed fields is a tenant field, points of all segments are written
+    /// ordered by the value of this field, so that points of one tenant are stored together.
+    /// Points are then ordered by the principal field, if there is one.
+    ///
+    /// # Result
+    ///
+    /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
+    ///
+    pub fn update(&mut self, segments: &[&Segment], stopped: &AtomicBool) -> OperationResult<bool> {
+        for segment in segments {
+            self.version = cmp::max(self.version, segment.version());
+
+            let segment_vectors_count = segment.vector_data.len();
+            if self.vector_storages.len() != segment_vectors_count {
+                return Err(OperationError::service_error(
+                    format!("Self and other segments have different vector names count. Self count: {}, other count: {}", self.vector_storages.len(), segment_vectors_count),
+                ));
+            }
+
+            for (field, payload_schema) in segment.payload_index.borrow().indexed_fields() {
+                self.indexed_fields.insert(field, payload_schema);
+            }
+        }
+
+        let tenant_keys: Vec<_> = self
+            .indexed_fields
             .iter()
-            .map(|(vector_name, vector_data)| {
-                (vector_name.to_owned(), vector_data.vector_storage.borrow())
-            })
+            .filter(|(_, payload_schema)| payload_schema.is_tenant())
+            .map(|(field, _)| field.clone())
+            .sorted()
             .collect();
-        let other_payload_index = other.payload_index.borrow();
 
-        let id_tracker = &mut self.id_tracker;
+        let principal_key = self
+            .indexed_fields
+            .iter()
+            .filter(|(_, payload_schema)| payload_schema.is_principal())
+            .map(|(field, _)| field.clone())
+            .min();
+
+        let mut points = Vec::new();
+        for (segment_index, segment) in segments.iter().enumerate() {
+            let id_tracker = segment.id_tracker.borrow();
+            let payload_index = segment.payload_index.borrow();
+            let tenant_indexes: Vec<_> = tenant_keys
+                .iter()
+                .map(|key| {
+                    payload_index
+                        .field_indexes
+                        .get(key)?
+                        .iter()
+                        .find_map(|index| index.as_keyword())
+                })
+                .collect();
+            let principal_index = principal_key.as_ref().and_then(|key| {
+                payload_index
+                    .field_indexes
+                    .get(key)?
+                    .iter()
+                    .find_map(|index| index.as_numeric())
+            });
+            for internal_id in id_tracker.iter_ids() {
+                check_process_stopped(stopped)?;
+                // Tenant values are taken from the keyword index, payload is only read if
+                // the segment has no index for one of the tenant fields
+                let payload = if tenant_indexes.iter().any(Option::is_none) {
+                    Some(payload_index.payload(internal_id)?)
+                } else {
+                    None
+                };
+                let tenant = tenant_keys
+                    .iter()
+                    .zip(&tenant_indexes)
+                    .map(|(key, index)| match index {
+                        Some(index) => index
+                            .get_values(internal_id)
+                            .and_then(|values| values.iter().min().cloned()),
+                        None => payload
+                            .as_ref()
+                            .and_then(|payload| tenant_value(payload, key)),
+                    })
+                    .collect();
+                let principal = principal_index
+                    .as_ref()
+                    .and_then(|index| index.get_ordering_values(internal_id).min());
+                points.push(PositionedPoint {
+                    segment_index,
+                    internal_id,
+                    tenant,
+                    principal,
+                });
+            }
+        }
 
-        if self.vector_storages.len() != other_vector_storages.len() {
-            return Err(OperationError::service_error(
-                format!("Self and other segments have different vector names count. Self count: {}, other count: {}", self.vector_storages.len(), other_vector_storages.len()),
-            ));
+        // Stable sort keeps the original order of points with equal keys
+        if !tenant_keys.is_empty() || principal_key.is_some() {
+            points.sort_by(|a, b| (&a.tenant, &a.principal).cmp(&(&b.tenant, &b.principal)));
         }
 
+        self.update_from_points(segments, &points, stopped)?;
+
+        self.id_tracker.mapping_flusher()()?;
+        self.id_tracker.versions_flusher()()?;
+
+        Ok(true)
+    }
+
+    /// Copy given points of the `segments` into the builder, in the given order.
+    /// Points, which already exist in the builder, are replaced only by a newer version.
+    fn update_from_points(
+        &mut self,
+        segments: &[&Segment],
+        points: &[PositionedPoint],
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        let other_id_trackers: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.id_tracker.borrow())
+            .collect();
+        let other_payload_indexes: Vec<_> = segments
+            .iter()
+            .map(|segment| segment.payload_index.borrow())
+            .collect();
+
+        let id_tracker = &mut self.id_tracker;
+
         let mut new_internal_range = None;
         for (vector_name, vector_storage) in &mut self.vector_storages {
             check_process_stopped(stopped)?;
-            let other_vector_storage = other_vector_storages.get(vector_name).ok_or_else(|| {
-                OperationError::service_error(format!(
-                    "Cannot update from other segment because if missing vector name {vector_name}"
-                ))
-            })?;
-            let internal_range = vector_storage.update_from(
-                other_vector_storage,
-                &mut other_id_tracker.iter_ids(),
-                stopped,
-            )?;
+            let other_vector_storages = segments
+                .iter()
+                .map(|segment| {
+                    let vector_data = segment.vector_data.get(vector_name).ok_or_else(|| {
+                        OperationError::service_error(format!(
+                            "Cannot update from other segment because if missing vector name {vector_name}"
+                        ))
+                    })?;
+                    Ok(vector_data.vector_storage.borrow())
+                })
+                .collect::<OperationResult<Vec<_>>>()?;
+
+            // Copy vectors of all segments in a single pass
+            let mut other_vectors = points.iter().map(|point| {
+                let other_vector_storage = &other_vector_storages[point.segment_index];
+                (
+                    other_vector_storage.get_vector(point.internal_id),
+                    other_vector_storage.is_deleted_vector(point.internal_id),
+                )
+            });
+            let internal_range = vector_storage.update_from(&mut other_vectors, stopped)?;
             match new_internal_range.clone() {
                 Some(new_internal_range) => {
                     if new_internal_range != internal_range {
@@ -170,11 +316,15 @@ impl SegmentBuilder {
         }
 
         if let Some(new_internal_range) = new_internal_range {
-            let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
+            let internal_id_iter = new_internal_range.zip(points);
 
-            for (new_internal_id, old_internal_id) in internal_id_iter {
+            for (new_internal_id, point) in internal_id_iter {
                 check_process_stopped(stopped)?;
 
+                let old_internal_id = point.internal_id;
+                let other_id_tracker = &other_id_trackers[point.segment_index];
+                let other_payload_index = &other_payload_indexes[point.segment_index];
+
                 let external_id =
                     if let Some(external_id) = other_id_tracker.external_id(old_internal_id) {
                         external_id
@@ -237,14 +387,7 @@ impl SegmentBuilder {
             }
         }
 
-        for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
-            self.indexed_fields.insert(field, payload_schema);
-        }
-
-        id_tracker.mapping_flusher()()?;
-        id_tracker.versions_flusher()()?;
-
-        Ok(true)
+        Ok(())
     }
 
     pub fn build(self, permit: CpuPermit, stopped: &AtomicBool) -> Result<Segment, OperationError> {
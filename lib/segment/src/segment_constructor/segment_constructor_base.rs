@@ -528,6 +528,8 @@ fn create_segment(
         SegmentType::Plain
     };
 
+    let principal_range = payload_index.borrow().principal_range();
+
     Ok(Segment {
         version,
         persisted_version: Arc::new(Mutex::new(version)),
@@ -537,6 +539,7 @@ fn create_segment(
         segment_type,
         appendable_flag,
         payload_index,
+        principal_range,
         segment_config: config.clone(),
         error_status: None,
         database,
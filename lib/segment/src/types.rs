@@ -12,7 +12,7 @@ use std::sync::Arc;
 use common::types::ScoreType;
 use fnv::FnvBuildHasher;
 use geo::prelude::HaversineDistance;
-use geo::{Contains, Coord, LineString, Point, Polygon};
+use geo::{Contains, Coord, Geometry, Intersects, LineString, Point, Polygon};
 use indexmap::IndexSet;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
@@ -27,8 +27,8 @@ use validator::{Validate, ValidationError, ValidationErrors};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
 use crate::data_types::index::{
-    BoolIndexParams, DatetimeIndexParams, FloatIndexParams, GeoIndexParams, IntegerIndexParams,
-    KeywordIndexParams, TextIndexParams,
+    BoolIndexParams, DatetimeIndexParams, FloatIndexParams, GeoIndexParams, GeoShapeIndexParams,
+    IntegerIndexParams, KeywordIndexParams, TextIndexParams,
 };
 use crate::data_types::order_by::OrderValue;
 use crate::data_types::vectors::VectorStructInternal;
@@ -203,7 +203,7 @@ impl Distance {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Order {
     LargeBetter,
     SmallBetter,
@@ -1103,6 +1103,7 @@ pub enum PayloadSchemaType {
     Text,
     Bool,
     Datetime,
+    GeoShape,
 }
 
 impl PayloadSchemaType {
@@ -1120,6 +1121,7 @@ impl PayloadSchemaType {
             Self::Text => PayloadSchemaParams::Text(TextIndexParams::default()),
             Self::Bool => PayloadSchemaParams::Bool(BoolIndexParams::default()),
             Self::Datetime => PayloadSchemaParams::Datetime(DatetimeIndexParams::default()),
+            Self::GeoShape => PayloadSchemaParams::GeoShape(GeoShapeIndexParams::default()),
         }
     }
 }
@@ -1135,6 +1137,7 @@ pub enum PayloadSchemaParams {
     Text(TextIndexParams),
     Bool(BoolIndexParams),
     Datetime(DatetimeIndexParams),
+    GeoShape(GeoShapeIndexParams),
 }
 
 impl PayloadSchemaParams {
@@ -1152,6 +1155,35 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Text(_) => PayloadSchemaType::Text,
             PayloadSchemaParams::Bool(_) => PayloadSchemaType::Bool,
             PayloadSchemaParams::Datetime(_) => PayloadSchemaType::Datetime,
+            PayloadSchemaParams::GeoShape(_) => PayloadSchemaType::GeoShape,
+        }
+    }
+
+    /// Whether the field is used to group points of one tenant together
+    pub fn is_tenant(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Keyword(keyword) => keyword.is_tenant.unwrap_or_default(),
+            PayloadSchemaParams::Integer(_)
+            | PayloadSchemaParams::Float(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Text(_)
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::Datetime(_)
+            | PayloadSchemaParams::GeoShape(_) => false,
+        }
+    }
+
+    /// Whether the field defines the order of points within segments
+    pub fn is_principal(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Integer(integer) => integer.is_principal.unwrap_or_default(),
+            PayloadSchemaParams::Float(float) => float.is_principal.unwrap_or_default(),
+            PayloadSchemaParams::Datetime(datetime) => datetime.is_principal.unwrap_or_default(),
+            PayloadSchemaParams::Keyword(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Text(_)
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::GeoShape(_) => false,
         }
     }
 }
@@ -1178,6 +1210,20 @@ impl PayloadFieldSchema {
             PayloadFieldSchema::FieldParams(field_params) => field_params.name(),
         }
     }
+
+    pub fn is_tenant(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(params) => params.is_tenant(),
+        }
+    }
+
+    pub fn is_principal(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(params) => params.is_principal(),
+        }
+    }
 }
 
 impl From<PayloadSchemaType> for PayloadFieldSchema {
@@ -1225,6 +1271,9 @@ pub fn value_type(value: &Value) -> Option<PayloadSchemaType> {
             if let (Some(_), Some(_)) = (lon_op, lat_op) {
                 return Some(PayloadSchemaType::Geo);
             }
+            if obj.contains_key("exterior") && GeoPolygon::deserialize(value).is_ok() {
+                return Some(PayloadSchemaType::GeoShape);
+            }
             None
         }
     }
@@ -1438,11 +1487,26 @@ impl From<Vec<IntPayloadType>> for MatchExcept {
 pub enum RangeInterface {
     Float(Range<FloatPayloadType>),
     DateTime(Range<DateTimePayloadType>),
+    /// Datetime range with bounds relative to the time of the request, like `now-7d`
+    RelativeDateTime(Range<DateTimeExpression>),
+}
+
+impl RangeInterface {
+    /// Replace bounds relative to the time of the request with absolute ones
+    pub fn resolve_relative_datetime(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> OperationResult<()> {
+        if let RangeInterface::RelativeDateTime(range) = self {
+            *self = RangeInterface::DateTime(range.resolve(now)?);
+        }
+        Ok(())
+    }
 }
 
 /// Range filter request
 #[macro_rules_attribute::macro_rules_derive(crate::common::macros::schemars_rename_generics)]
-#[derive_args(< FloatPayloadType > => "Range", < DateTimePayloadType > => "DatetimeRange")]
+#[derive_args(< FloatPayloadType > => "Range", < DateTimePayloadType > => "DatetimeRange", < DateTimeExpression > => "RelativeDatetimeRange")]
 #[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Range<T> {
@@ -1488,6 +1552,252 @@ impl FromStr for DateTimePayloadType {
     }
 }
 
+/// Calendar unit used in relative datetime expressions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateMathUnit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateMathUnit {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'y' => Some(Self::Year),
+            'M' => Some(Self::Month),
+            'w' => Some(Self::Week),
+            'd' => Some(Self::Day),
+            'h' | 'H' => Some(Self::Hour),
+            'm' => Some(Self::Minute),
+            's' => Some(Self::Second),
+            _ => None,
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            Self::Year => 'y',
+            Self::Month => 'M',
+            Self::Week => 'w',
+            Self::Day => 'd',
+            Self::Hour => 'h',
+            Self::Minute => 'm',
+            Self::Second => 's',
+        }
+    }
+
+    /// Shift `datetime` by `amount` units, `None` on overflow
+    fn shift(
+        &self,
+        datetime: chrono::DateTime<chrono::Utc>,
+        amount: i64,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let shift_months = |months: i64| {
+            let delta = chrono::Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+            if months >= 0 {
+                datetime.checked_add_months(delta)
+            } else {
+                datetime.checked_sub_months(delta)
+            }
+        };
+        let duration = match self {
+            Self::Year => return shift_months(amount.checked_mul(12)?),
+            Self::Month => return shift_months(amount),
+            Self::Week => chrono::TimeDelta::try_weeks(amount)?,
+            Self::Day => chrono::TimeDelta::try_days(amount)?,
+            Self::Hour => chrono::TimeDelta::try_hours(amount)?,
+            Self::Minute => chrono::TimeDelta::try_minutes(amount)?,
+            Self::Second => chrono::TimeDelta::try_seconds(amount)?,
+        };
+        datetime.checked_add_signed(duration)
+    }
+
+    /// Round `datetime` down to the beginning of the unit, weeks start on Monday
+    fn round_down(
+        &self,
+        datetime: chrono::DateTime<chrono::Utc>,
+    ) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::{Datelike, Timelike};
+
+        let date = datetime.date_naive();
+        let rounded = match self {
+            Self::Year => chrono::NaiveDate::from_ymd_opt(date.year(), 1, 1)?.and_hms_opt(0, 0, 0),
+            Self::Month => {
+                chrono::NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?.and_hms_opt(0, 0, 0)
+            }
+            Self::Week => date
+                .checked_sub_days(chrono::Days::new(
+                    date.weekday().num_days_from_monday().into(),
+                ))?
+                .and_hms_opt(0, 0, 0),
+            Self::Day => date.and_hms_opt(0, 0, 0),
+            Self::Hour => date.and_hms_opt(datetime.hour(), 0, 0),
+            Self::Minute => date.and_hms_opt(datetime.hour(), datetime.minute(), 0),
+            Self::Second => date.and_hms_opt(datetime.hour(), datetime.minute(), datetime.second()),
+        }?;
+        Some(rounded.and_utc())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateMathOp {
+    Shift(i64, DateMathUnit),
+    Round(DateMathUnit),
+}
+
+/// Datetime expression relative to the moment a request is processed, e.g. `now-7d` or `now/d`.
+///
+/// Starts with `now`, followed by any number of shifts (`+1h`, `-7d`) and roundings down to
+/// the beginning of a unit (`/d`). Supported units: `y`, `M`, `w`, `d`, `h`, `m`, `s`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RelativeDateTime {
+    ops: Vec<DateMathOp>,
+}
+
+impl RelativeDateTime {
+    /// Compute the absolute datetime, given the moment the request is processed
+    pub fn resolve(&self, now: chrono::DateTime<chrono::Utc>) -> OperationResult<DateTimeWrapper> {
+        self.ops
+            .iter()
+            .try_fold(now, |datetime, op| match op {
+                DateMathOp::Shift(amount, unit) => unit.shift(datetime, *amount),
+                DateMathOp::Round(unit) => unit.round_down(datetime),
+            })
+            .map(DateTimeWrapper)
+            .ok_or_else(|| OperationError::ValidationError {
+                description: format!("datetime expression `{self}` is out of range"),
+            })
+    }
+}
+
+impl FromStr for RelativeDateTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("'{s}' is not a valid relative datetime expression, e.g. `now-7d/d`");
+
+        let mut rest = s.trim().strip_prefix("now").ok_or_else(invalid)?;
+        let mut ops = Vec::new();
+        while let Some(op) = rest.chars().next() {
+            rest = &rest[op.len_utf8()..];
+            match op {
+                '/' => {
+                    let mut chars = rest.chars();
+                    let unit = chars
+                        .next()
+                        .and_then(DateMathUnit::from_char)
+                        .ok_or_else(invalid)?;
+                    rest = chars.as_str();
+                    ops.push(DateMathOp::Round(unit));
+                }
+                '+' | '-' => {
+                    let digits_len = rest
+                        .find(|c: char| !c.is_ascii_digit())
+                        .ok_or_else(invalid)?;
+                    let amount: i64 = rest[..digits_len].parse().map_err(|_| invalid())?;
+                    rest = &rest[digits_len..];
+                    let mut chars = rest.chars();
+                    let unit = chars
+                        .next()
+                        .and_then(DateMathUnit::from_char)
+                        .ok_or_else(invalid)?;
+                    rest = chars.as_str();
+                    let amount = if op == '-' { -amount } else { amount };
+                    ops.push(DateMathOp::Shift(amount, unit));
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Self { ops })
+    }
+}
+
+impl TryFrom<String> for RelativeDateTime {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for RelativeDateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "now")?;
+        for op in &self.ops {
+            match op {
+                DateMathOp::Shift(amount, unit) => write!(f, "{amount:+}{}", unit.as_char())?,
+                DateMathOp::Round(unit) => write!(f, "/{}", unit.as_char())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<RelativeDateTime> for String {
+    fn from(value: RelativeDateTime) -> Self {
+        value.to_string()
+    }
+}
+
+impl JsonSchema for RelativeDateTime {
+    fn schema_name() -> String {
+        "RelativeDatetime".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.metadata().description = Some(
+            "Datetime relative to the time of the request, e.g. `now-7d` or `now/d`".to_string(),
+        );
+        schema.string().pattern = Some(r"^now([+-]\d+[yMwdhHms]|/[yMwdhHms])*$".to_string());
+        schema.into()
+    }
+}
+
+/// Datetime bound of a range: either an absolute value or one relative to the time of the request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum DateTimeExpression {
+    Absolute(DateTimePayloadType),
+    Relative(RelativeDateTime),
+}
+
+impl DateTimeExpression {
+    pub fn resolve(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> OperationResult<DateTimePayloadType> {
+        match self {
+            DateTimeExpression::Absolute(datetime) => Ok(*datetime),
+            DateTimeExpression::Relative(relative) => relative.resolve(now),
+        }
+    }
+}
+
+impl Range<DateTimeExpression> {
+    /// Convert all bounds into absolute datetimes, relative to `now`
+    pub fn resolve(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> OperationResult<Range<DateTimePayloadType>> {
+        let resolve = |bound: &Option<DateTimeExpression>| {
+            bound.as_ref().map(|expr| expr.resolve(now)).transpose()
+        };
+        Ok(Range {
+            lt: resolve(&self.lt)?,
+            gt: resolve(&self.gt)?,
+            gte: resolve(&self.gte)?,
+            lte: resolve(&self.lte)?,
+        })
+    }
+}
+
 impl<T: Copy> Range<T> {
     /// Convert range to a range of another type
     pub fn map<U, F: Fn(T) -> U>(&self, f: F) -> Range<U> {
@@ -1691,6 +2001,90 @@ impl TryFrom<GeoPolygonShadow> for GeoPolygon {
     }
 }
 
+/// Geo shape payload schema
+///
+/// Describes a spatial object stored in payload: either a single point or a polygon
+/// with optional holes, as in [`GeoPolygon`]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum GeoShape {
+    Point(GeoPoint),
+    Polygon(GeoPolygon),
+}
+
+impl GeoShape {
+    /// Convert shape into `geo` crate geometry, used for checking spatial relations
+    pub fn to_geometry(&self) -> Geometry {
+        match self {
+            GeoShape::Point(point) => Geometry::Point(Point::new(point.lon, point.lat)),
+            GeoShape::Polygon(polygon) => Geometry::Polygon(polygon.convert().polygon),
+        }
+    }
+
+    /// Try to read a shape from a single (non-array) payload value
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Object(_) => GeoShape::deserialize(value).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Spatial relation between a stored shape and the shape given in the condition:
+///
+/// * `intersects` - stored shape and condition shape have at least one common point
+/// * `within` - stored shape lies completely inside the condition shape
+/// * `contains` - stored shape completely covers the condition shape
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GeoShapeRelation {
+    Intersects,
+    Within,
+    Contains,
+}
+
+/// Geo filter request
+///
+/// Matches geo shapes which are in the given spatial `relation` with the `shape`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoShapeCondition {
+    /// How stored shapes should relate to the given shape
+    pub relation: GeoShapeRelation,
+    /// Shape to compare stored shapes with
+    pub shape: GeoShape,
+}
+
+impl GeoShapeCondition {
+    /// Prepare condition for repeated checks, converting the query shape only once
+    pub fn checker(&self) -> GeoShapeChecker {
+        GeoShapeChecker {
+            relation: self.relation,
+            geometry: self.shape.to_geometry(),
+        }
+    }
+}
+
+/// Condition shape, converted into geometry ready for relation checks
+pub struct GeoShapeChecker {
+    relation: GeoShapeRelation,
+    geometry: Geometry,
+}
+
+impl GeoShapeChecker {
+    pub fn check_shape(&self, shape: &GeoShape) -> bool {
+        self.check_geometry(&shape.to_geometry())
+    }
+
+    pub fn check_geometry(&self, stored: &Geometry) -> bool {
+        match self.relation {
+            GeoShapeRelation::Intersects => stored.intersects(&self.geometry),
+            GeoShapeRelation::Within => self.geometry.contains(stored),
+            GeoShapeRelation::Contains => stored.contains(&self.geometry),
+        }
+    }
+}
+
 /// All possible payload filtering conditions
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[validate(schema(function = "validate_field_condition"))]
@@ -1708,6 +2102,8 @@ pub struct FieldCondition {
     pub geo_radius: Option<GeoRadius>,
     /// Check if geo point is within a given polygon
     pub geo_polygon: Option<GeoPolygon>,
+    /// Check spatial relation of the stored geo shape with a given shape
+    pub geo_shape: Option<GeoShapeCondition>,
     /// Check number of values of the field
     pub values_count: Option<ValuesCount>,
 }
@@ -1721,6 +2117,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1733,6 +2130,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1745,6 +2143,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1757,6 +2156,7 @@ impl FieldCondition {
             geo_bounding_box: Some(geo_bounding_box),
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1769,6 +2169,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: Some(geo_radius),
             geo_polygon: None,
+            geo_shape: None,
             values_count: None,
         }
     }
@@ -1781,6 +2182,20 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: Some(geo_polygon),
+            geo_shape: None,
+            values_count: None,
+        }
+    }
+
+    pub fn new_geo_shape(key: JsonPath, geo_shape: GeoShapeCondition) -> Self {
+        Self {
+            key,
+            r#match: None,
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            geo_shape: Some(geo_shape),
             values_count: None,
         }
     }
@@ -1793,6 +2208,7 @@ impl FieldCondition {
             geo_bounding_box: None,
             geo_radius: None,
             geo_polygon: None,
+            geo_shape: None,
             values_count: Some(values_count),
         }
     }
@@ -1806,6 +2222,7 @@ impl FieldCondition {
                 geo_bounding_box: None,
                 geo_radius: None,
                 geo_polygon: None,
+                geo_shape: None,
                 values_count: None,
                 key: _,
             }
@@ -1951,6 +2368,39 @@ impl Condition {
     pub fn is_local_only(&self) -> bool {
         matches!(self, Condition::Resharding(_))
     }
+
+    pub fn resolve_relative_datetime(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> OperationResult<()> {
+        match self {
+            Condition::Field(FieldCondition {
+                range: Some(range), ..
+            }) => range.resolve_relative_datetime(now),
+            Condition::Nested(nested) => nested.nested.filter.resolve_relative_datetime(now),
+            Condition::Filter(filter) => filter.resolve_relative_datetime(now),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_)
+            | Condition::Resharding(_) => Ok(()),
+        }
+    }
+
+    pub fn has_relative_datetime(&self) -> bool {
+        match self {
+            Condition::Field(FieldCondition {
+                range: Some(range), ..
+            }) => matches!(range, RangeInterface::RelativeDateTime(_)),
+            Condition::Nested(nested) => nested.nested.filter.has_relative_datetime(),
+            Condition::Filter(filter) => filter.has_relative_datetime(),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_)
+            | Condition::Resharding(_) => false,
+        }
+    }
 }
 
 // The validator crate does not support deriving for enums.
@@ -2264,6 +2714,54 @@ impl Filter {
         }
     }
 
+    /// Replace datetime ranges relative to the time of the request with absolute ones.
+    ///
+    /// Should be called once per request on the node which received it, so that all shards and
+    /// replicas evaluate the filter against the same moment.
+    pub fn resolve_relative_datetime(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> OperationResult<()> {
+        let Filter {
+            should,
+            min_should,
+            must,
+            must_not,
+        } = self;
+        should
+            .iter_mut()
+            .flatten()
+            .chain(
+                min_should
+                    .iter_mut()
+                    .flat_map(|min_should| min_should.conditions.iter_mut()),
+            )
+            .chain(must.iter_mut().flatten())
+            .chain(must_not.iter_mut().flatten())
+            .try_for_each(|condition| condition.resolve_relative_datetime(now))
+    }
+
+    /// Whether the filter still has datetime ranges relative to the time of the request
+    pub fn has_relative_datetime(&self) -> bool {
+        let Filter {
+            should,
+            min_should,
+            must,
+            must_not,
+        } = self;
+        should
+            .iter()
+            .flatten()
+            .chain(
+                min_should
+                    .iter()
+                    .flat_map(|min_should| min_should.conditions.iter()),
+            )
+            .chain(must.iter().flatten())
+            .chain(must_not.iter().flatten())
+            .any(Condition::has_relative_datetime)
+    }
+
     pub fn merge_opts(this: Option<Self>, other: Option<Self>) -> Option<Self> {
         match (this, other) {
             (None, None) => None,
@@ -2381,6 +2879,94 @@ mod tests {
         assert_eq!(datetime.timestamp(), datetime_no_z.timestamp());
     }
 
+    #[rstest]
+    #[case::now("now", "2024-05-15T13:45:30.5Z")]
+    #[case::minus_days("now-7d", "2024-05-08T13:45:30.5Z")]
+    #[case::round_day("now/d", "2024-05-15T00:00:00Z")]
+    #[case::round_week("now/w", "2024-05-13T00:00:00Z")]
+    #[case::round_month("now/M", "2024-05-01T00:00:00Z")]
+    #[case::shift_then_round("now-1M/M", "2024-04-01T00:00:00Z")]
+    #[case::multiple_shifts("now+1h-30m/m", "2024-05-15T14:15:00Z")]
+    #[case::month_overflow("now+9M", "2025-02-15T13:45:30.5Z")]
+    #[case::years("now-1y/y", "2023-01-01T00:00:00Z")]
+    fn test_relative_datetime(#[case] expression: &str, #[case] expected: &str) {
+        let now = DateTimePayloadType::from_str("2024-05-15T13:45:30.5Z")
+            .unwrap()
+            .0;
+        let relative = RelativeDateTime::from_str(expression).unwrap();
+        let expected = DateTimePayloadType::from_str(expected).unwrap();
+        assert_eq!(relative.resolve(now).unwrap(), expected);
+
+        // Serialization round trip
+        let serialized = serde_json::to_string(&relative).unwrap();
+        let deserialized: RelativeDateTime = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(relative, deserialized);
+    }
+
+    #[rstest]
+    #[case("today")]
+    #[case("now-d")]
+    #[case("now-7")]
+    #[case("now/x")]
+    #[case("now-7d/")]
+    fn test_invalid_relative_datetime(#[case] expression: &str) {
+        assert!(RelativeDateTime::from_str(expression).is_err());
+    }
+
+    #[test]
+    fn test_resolve_relative_datetime_filter() {
+        let mut filter: Filter = serde_json::from_value(json!({
+            "must": [
+                { "key": "created_at", "range": { "gte": "now-7d/d", "lt": "2024-05-15T00:00:00Z" } },
+                { "nested": { "key": "items", "filter": {
+                    "must": { "key": "updated_at", "range": { "gt": "now-1h" } }
+                } } },
+                { "key": "price", "range": { "gt": 10.0 } },
+            ]
+        }))
+        .unwrap();
+
+        let now = DateTimePayloadType::from_str("2024-05-15T13:45:30Z")
+            .unwrap()
+            .0;
+        assert!(filter.has_relative_datetime());
+        filter.resolve_relative_datetime(now).unwrap();
+        assert!(!filter.has_relative_datetime());
+
+        let must = filter.must.unwrap();
+        let Condition::Field(created_at) = &must[0] else {
+            panic!("expected field condition");
+        };
+        assert_eq!(
+            created_at.range,
+            Some(RangeInterface::DateTime(Range {
+                gte: Some(DateTimePayloadType::from_str("2024-05-08T00:00:00Z").unwrap()),
+                lt: Some(DateTimePayloadType::from_str("2024-05-15T00:00:00Z").unwrap()),
+                gt: None,
+                lte: None,
+            }))
+        );
+        let Condition::Nested(nested) = &must[1] else {
+            panic!("expected nested condition");
+        };
+        let Condition::Field(updated_at) = &nested.filter().must.as_ref().unwrap()[0] else {
+            panic!("expected field condition");
+        };
+        assert_eq!(
+            updated_at.range,
+            Some(RangeInterface::DateTime(Range {
+                gt: Some(DateTimePayloadType::from_str("2024-05-15T12:45:30Z").unwrap()),
+                lt: None,
+                gte: None,
+                lte: None,
+            }))
+        );
+        let Condition::Field(price) = &must[2] else {
+            panic!("expected field condition");
+        };
+        assert!(matches!(price.range, Some(RangeInterface::Float(_))));
+    }
+
     #[test]
     fn test_timezone_ordering() {
         let datetimes = [
@@ -2548,6 +3134,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_geo_polygon_value_type() {
+        let polygon = json!({
+            "exterior": {
+                "points": [
+                    {"lon": 0.0, "lat": 0.0},
+                    {"lon": 1.0, "lat": 0.0},
+                    {"lon": 1.0, "lat": 1.0},
+                    {"lon": 0.0, "lat": 0.0}
+                ]
+            }
+        });
+        assert_eq!(value_type(&polygon), Some(PayloadSchemaType::GeoShape));
+
+        // Objects with an `exterior` key, which are not valid polygons
+        let not_closed = json!({
+            "exterior": {
+                "points": [
+                    {"lon": 0.0, "lat": 0.0},
+                    {"lon": 1.0, "lat": 0.0},
+                    {"lon": 1.0, "lat": 1.0},
+                    {"lon": 0.0, "lat": 1.0}
+                ]
+            }
+        });
+        assert_eq!(value_type(&not_closed), None);
+        assert_eq!(value_type(&json!({"exterior": "garage"})), None);
+    }
+
     #[test]
     fn test_serialize_query() {
         let filter = Filter {
@@ -2,5 +2,6 @@ pub mod fmt;
 pub mod fs;
 pub mod mem;
 pub mod path;
+pub mod sampling;
 pub mod scored_point_ties;
 pub mod tar;
@@ -0,0 +1,52 @@
+use common::types::ScoreType;
+use rand::Rng;
+
+/// Sampling keys for a uniform sample of `count` out of `total` items, in descending order.
+///
+/// Keys are distributed as the `count` largest of `total` independent random keys. This way
+/// samples of different sets can be merged by taking the largest keys, and the result is still a
+/// uniform sample of the union, with each set weighted by its size.
+///
+/// Keys are logarithms of uniform keys, so they don't lose precision on large sets.
+pub fn sampling_keys(total: usize, count: usize, rng: &mut impl Rng) -> Vec<ScoreType> {
+    let count = count.min(total);
+    let mut log_key = 0.0f64;
+    (0..count)
+        .map(|i| {
+            // Largest of the remaining keys is the previous one, times `U^(1/remaining)`
+            let uniform = 1.0 - rng.gen::<f64>();
+            log_key += uniform.ln() / (total - i) as f64;
+            log_key as ScoreType
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_sampling_keys() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let keys = sampling_keys(1_000_000, 100, &mut rng);
+        assert_eq!(keys.len(), 100);
+        assert!(keys.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(keys.iter().all(|key| *key <= 0.0));
+
+        // can't sample more than there is
+        assert_eq!(sampling_keys(3, 10, &mut rng).len(), 3);
+        assert!(sampling_keys(0, 10, &mut rng).is_empty());
+
+        // larger sets win proportionally more of the merged sample
+        let rounds = 10_000;
+        let large_wins = (0..rounds)
+            .filter(|_| sampling_keys(300, 1, &mut rng)[0] > sampling_keys(100, 1, &mut rng)[0])
+            .count();
+        let ratio = large_wins as f64 / rounds as f64;
+        assert!((ratio - 0.75).abs() < 0.03, "ratio: {ratio}");
+    }
+}
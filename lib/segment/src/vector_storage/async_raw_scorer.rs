@@ -4,7 +4,9 @@ use bitvec::prelude::BitSlice;
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
-use super::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
+use super::query::{
+    ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery, TransformInto,
+};
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::vectors::{DenseVector, QueryVector, Vector, VectorElementType};
@@ -303,6 +305,36 @@ impl<'a> AsyncRawScorerBuilder<'a> {
                     is_stopped.unwrap_or(&DEFAULT_STOPPED),
                 )))
             }
+            QueryVector::RecommendSumScores(reco_query) => {
+                let reco_query: RecoSumQuery<DenseVector> = reco_query.transform_into()?;
+                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
+                    reco_query, storage,
+                );
+                Ok(Box::new(AsyncRawScorerImpl::new(
+                    points_count,
+                    query_scorer,
+                    storage.get_mmap_vectors(),
+                    point_deleted,
+                    vec_deleted,
+                    is_stopped.unwrap_or(&DEFAULT_STOPPED),
+                )))
+            }
+            QueryVector::RecommendContrastive(contrastive_query) => {
+                let contrastive_query: ContrastiveQuery<DenseVector> =
+                    contrastive_query.transform_into()?;
+                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    storage,
+                );
+                Ok(Box::new(AsyncRawScorerImpl::new(
+                    points_count,
+                    query_scorer,
+                    storage.get_mmap_vectors(),
+                    point_deleted,
+                    vec_deleted,
+                    is_stopped.unwrap_or(&DEFAULT_STOPPED),
+                )))
+            }
             QueryVector::Discovery(discovery_query) => {
                 let discovery_query: DiscoveryQuery<DenseVector> =
                     discovery_query.transform_into()?;
@@ -173,10 +173,9 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
         panic!("Can't directly update vector in mmap storage")
     }
 
-    fn update_from(
+    fn update_from<'a>(
         &mut self,
-        other: &VectorStorageEnum,
-        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        other_vectors: &mut impl Iterator<Item = (CowVector<'a>, bool)>,
         stopped: &AtomicBool,
     ) -> OperationResult<Range<PointOffsetType>> {
         let dim = self.vector_dim();
@@ -192,18 +191,17 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
         // Extend vectors file, write other vectors into it
         let mut vectors_file = open_append(&self.vectors_path)?;
         let mut deleted_ids = vec![];
-        for id in other_ids {
+        for (other_vector, other_deleted) in other_vectors {
             check_process_stopped(stopped)?;
-            let other_vector = other.get_vector(id);
             let vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
             let raw_bites = mmap_ops::transmute_to_u8_slice(vector.as_ref());
             vectors_file.write_all(raw_bites)?;
-            end_index += 1;
 
             // Remember deleted IDs so we can propagate deletions later
-            if other.is_deleted_vector(id) {
-                deleted_ids.push((start_index + id) as PointOffsetType);
+            if other_deleted {
+                deleted_ids.push(end_index);
             }
+            end_index += 1;
         }
         vectors_file.flush()?;
         drop(vectors_file);
@@ -333,9 +331,8 @@ mod tests {
                     .insert_vector(2, points[2].as_slice().into())
                     .unwrap();
             }
-            storage
-                .update_from(&storage2, &mut Box::new(0..3), &Default::default())
-                .unwrap();
+            let mut iter = (0..3).map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+            storage.update_from(&mut iter, &Default::default()).unwrap();
         }
 
         assert_eq!(storage.total_vector_count(), 3);
@@ -366,9 +363,8 @@ mod tests {
                     .insert_vector(4, points[4].as_slice().into())
                     .unwrap();
             }
-            storage
-                .update_from(&storage2, &mut Box::new(0..2), &Default::default())
-                .unwrap();
+            let mut iter = (0..2).map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+            storage.update_from(&mut iter, &Default::default()).unwrap();
         }
 
         assert_eq!(storage.total_vector_count(), 5);
@@ -429,13 +425,9 @@ mod tests {
                         .unwrap();
                 });
             }
-            storage
-                .update_from(
-                    &storage2,
-                    &mut Box::new(0..points.len() as u32),
-                    &Default::default(),
-                )
-                .unwrap();
+            let mut iter = (0..points.len() as u32)
+                .map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+            storage.update_from(&mut iter, &Default::default()).unwrap();
         }
 
         assert_eq!(storage.total_vector_count(), 5);
@@ -551,13 +543,9 @@ mod tests {
                     }
                 });
             }
-            storage
-                .update_from(
-                    &storage2,
-                    &mut Box::new(0..points.len() as u32),
-                    &Default::default(),
-                )
-                .unwrap();
+            let mut iter = (0..points.len() as u32)
+                .map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+            storage.update_from(&mut iter, &Default::default()).unwrap();
         }
 
         assert_eq!(
@@ -624,13 +612,9 @@ mod tests {
                         .unwrap();
                 }
             }
-            storage
-                .update_from(
-                    &storage2,
-                    &mut Box::new(0..points.len() as PointOffsetType),
-                    &Default::default(),
-                )
-                .unwrap();
+            let mut iter = (0..points.len() as PointOffsetType)
+                .map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+            storage.update_from(&mut iter, &Default::default()).unwrap();
         }
 
         let vector = vec![-1.0, -1.0, -1.0, -1.0];
@@ -708,13 +692,9 @@ mod tests {
                         .unwrap();
                 }
             }
-            storage
-                .update_from(
-                    &storage2,
-                    &mut Box::new(0..points.len() as PointOffsetType),
-                    &Default::default(),
-                )
-                .unwrap();
+            let mut iter = (0..points.len() as PointOffsetType)
+                .map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+            storage.update_from(&mut iter, &Default::default()).unwrap();
         }
 
         let config: QuantizationConfig = ScalarQuantizationConfig {
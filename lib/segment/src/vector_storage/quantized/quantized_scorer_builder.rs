@@ -15,7 +15,9 @@ use crate::data_types::vectors::{
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
-use crate::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
+use crate::vector_storage::query::{
+    ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery, TransformInto,
+};
 use crate::vector_storage::{raw_scorer_from_query_scorer, RawScorer};
 
 pub(super) struct QuantizedScorerBuilder<'a> {
@@ -169,6 +171,25 @@ impl<'a> QuantizedScorerBuilder<'a> {
                 );
                 raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
             }
+            QueryVector::RecommendSumScores(reco_query) => {
+                let reco_query: RecoSumQuery<DenseVector> = reco_query.transform_into()?;
+                let query_scorer = QuantizedCustomQueryScorer::<TElement, TMetric, _, _, _>::new(
+                    reco_query,
+                    quantized_storage,
+                    quantization_config,
+                );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
+            QueryVector::RecommendContrastive(contrastive_query) => {
+                let contrastive_query: ContrastiveQuery<DenseVector> =
+                    contrastive_query.transform_into()?;
+                let query_scorer = QuantizedCustomQueryScorer::<TElement, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    quantized_storage,
+                    quantization_config,
+                );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
             QueryVector::Discovery(discovery_query) => {
                 let discovery_query: DiscoveryQuery<DenseVector> =
                     discovery_query.transform_into()?;
@@ -231,6 +252,28 @@ impl<'a> QuantizedScorerBuilder<'a> {
                     );
                 raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
             }
+            QueryVector::RecommendSumScores(reco_query) => {
+                let reco_query: RecoSumQuery<MultiDenseVectorInternal> =
+                    reco_query.transform_into()?;
+                let query_scorer =
+                    QuantizedCustomQueryScorer::<TElement, TMetric, _, _, _>::new_multi(
+                        reco_query,
+                        quantized_storage,
+                        quantization_config,
+                    );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
+            QueryVector::RecommendContrastive(contrastive_query) => {
+                let contrastive_query: ContrastiveQuery<MultiDenseVectorInternal> =
+                    contrastive_query.transform_into()?;
+                let query_scorer =
+                    QuantizedCustomQueryScorer::<TElement, TMetric, _, _, _>::new_multi(
+                        contrastive_query,
+                        quantized_storage,
+                        quantization_config,
+                    );
+                raw_scorer_from_query_scorer(query_scorer, point_deleted, vec_deleted, is_stopped)
+            }
             QueryVector::Discovery(discovery_query) => {
                 let discovery_query: DiscoveryQuery<MultiDenseVectorInternal> =
                     discovery_query.transform_into()?;
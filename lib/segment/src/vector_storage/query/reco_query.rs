@@ -76,6 +76,170 @@ impl From<RecoQuery<Vector>> for QueryVector {
     }
 }
 
+/// Sums the similarities to all positives, and subtracts the similarities to all negatives.
+///
+/// Unlike [`RecoQuery`], every example contributes to the score of every candidate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoSumQuery<T> {
+    pub positives: Vec<T>,
+    pub negatives: Vec<T>,
+}
+
+impl<T> RecoSumQuery<T> {
+    pub fn new(positives: Vec<T>, negatives: Vec<T>) -> Self {
+        Self {
+            positives,
+            negatives,
+        }
+    }
+
+    pub fn flat_iter(&self) -> impl Iterator<Item = &T> {
+        self.positives.iter().chain(self.negatives.iter())
+    }
+}
+
+impl<T, U> TransformInto<RecoSumQuery<U>, T, U> for RecoSumQuery<T> {
+    fn transform<F>(self, mut f: F) -> OperationResult<RecoSumQuery<U>>
+    where
+        F: FnMut(T) -> OperationResult<U>,
+    {
+        Ok(RecoSumQuery::new(
+            self.positives.into_iter().map(&mut f).try_collect()?,
+            self.negatives.into_iter().map(&mut f).try_collect()?,
+        ))
+    }
+}
+
+impl<T> Query<T> for RecoSumQuery<T> {
+    fn score_by(&self, similarity: impl Fn(&T) -> ScoreType) -> ScoreType {
+        let positive_sum: ScoreType = self.positives.iter().map(&similarity).sum();
+        let negative_sum: ScoreType = self.negatives.iter().map(&similarity).sum();
+        positive_sum - negative_sum
+    }
+}
+
+impl From<RecoSumQuery<Vector>> for QueryVector {
+    fn from(query: RecoSumQuery<Vector>) -> Self {
+        QueryVector::RecommendSumScores(query)
+    }
+}
+
+/// Contrasts weighted positives against weighted negatives.
+///
+/// Each side is folded with a weighted log-sum-exp, which is a smooth maximum of the similarities:
+/// the closest examples dominate, while the others still contribute according to their weights.
+///
+/// `score = ln(sum(w_pos * exp(sim_pos))) - ln(sum(w_neg * exp(sim_neg)))`,
+/// a side without examples contributes nothing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastiveQuery<T> {
+    /// Examples along with their weights
+    pub positives: Vec<(T, ScoreType)>,
+    pub negatives: Vec<(T, ScoreType)>,
+}
+
+impl<T> ContrastiveQuery<T> {
+    pub fn new(positives: Vec<(T, ScoreType)>, negatives: Vec<(T, ScoreType)>) -> Self {
+        Self {
+            positives,
+            negatives,
+        }
+    }
+
+    /// Pairs the examples with their weights, missing weights default to 1.0
+    pub fn with_weights(
+        positives: Vec<T>,
+        negatives: Vec<T>,
+        positive_weights: Option<Vec<ScoreType>>,
+        negative_weights: Option<Vec<ScoreType>>,
+    ) -> Self {
+        fn weighted<T>(examples: Vec<T>, weights: Option<Vec<ScoreType>>) -> Vec<(T, ScoreType)> {
+            let weights = weights.into_iter().flatten().chain(std::iter::repeat(1.0));
+            examples.into_iter().zip(weights).collect()
+        }
+
+        Self::new(
+            weighted(positives, positive_weights),
+            weighted(negatives, negative_weights),
+        )
+    }
+
+    pub fn flat_iter(&self) -> impl Iterator<Item = &T> {
+        self.positives
+            .iter()
+            .chain(self.negatives.iter())
+            .map(|(example, _weight)| example)
+    }
+}
+
+impl<T, U> TransformInto<ContrastiveQuery<U>, T, U> for ContrastiveQuery<T> {
+    fn transform<F>(self, mut f: F) -> OperationResult<ContrastiveQuery<U>>
+    where
+        F: FnMut(T) -> OperationResult<U>,
+    {
+        let mut transform_weighted =
+            |(example, weight): (T, ScoreType)| f(example).map(|example| (example, weight));
+        Ok(ContrastiveQuery::new(
+            self.positives
+                .into_iter()
+                .map(&mut transform_weighted)
+                .try_collect()?,
+            self.negatives
+                .into_iter()
+                .map(&mut transform_weighted)
+                .try_collect()?,
+        ))
+    }
+}
+
+impl<T> Query<T> for ContrastiveQuery<T> {
+    fn score_by(&self, similarity: impl Fn(&T) -> ScoreType) -> ScoreType {
+        let positive = weighted_log_sum_exp(
+            self.positives
+                .iter()
+                .map(|(example, weight)| (similarity(example), *weight)),
+        );
+        let negative = weighted_log_sum_exp(
+            self.negatives
+                .iter()
+                .map(|(example, weight)| (similarity(example), *weight)),
+        );
+        positive - negative
+    }
+}
+
+/// `ln(sum(weight * exp(similarity)))`, computed in a single pass without overflowing.
+///
+/// Examples without a positive weight are skipped, zero is returned if none is left.
+#[inline]
+fn weighted_log_sum_exp(weighted: impl Iterator<Item = (ScoreType, ScoreType)>) -> ScoreType {
+    let mut max = ScoreType::NEG_INFINITY;
+    let mut sum: ScoreType = 0.0;
+    for (similarity, weight) in weighted {
+        if weight <= 0.0 {
+            continue;
+        }
+        let value = similarity + weight.ln();
+        if value > max {
+            sum = sum * (max - value).exp() + 1.0;
+            max = value;
+        } else {
+            sum += (value - max).exp();
+        }
+    }
+
+    if sum == 0.0 {
+        return 0.0;
+    }
+    max + sum.ln()
+}
+
+impl From<ContrastiveQuery<Vector>> for QueryVector {
+    fn from(query: ContrastiveQuery<Vector>) -> Self {
+        QueryVector::RecommendContrastive(query)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use common::math::scaled_fast_sigmoid;
@@ -83,7 +247,7 @@ mod test {
     use proptest::prelude::*;
     use rstest::rstest;
 
-    use super::RecoQuery;
+    use super::{ContrastiveQuery, RecoQuery, RecoSumQuery};
     use crate::vector_storage::query::Query;
 
     enum Chosen {
@@ -125,7 +289,71 @@ mod test {
         }
     }
 
+    #[rstest]
+    #[case::only_positives(vec![1, 2, 3], vec![], 6.0)]
+    #[case::only_negatives(vec![], vec![1, 2], -3.0)]
+    #[case::both(vec![10, -2], vec![4, 5], -1.0)]
+    #[case::empty(vec![], vec![], 0.0)]
+    fn score_sum_query(
+        #[case] positives: Vec<isize>,
+        #[case] negatives: Vec<isize>,
+        #[case] expected: ScoreType,
+    ) {
+        let query = RecoSumQuery::new(positives, negatives);
+
+        let dummy_similarity = |x: &isize| *x as ScoreType;
+
+        assert_eq!(query.score_by(dummy_similarity), expected);
+    }
+
+    #[test]
+    fn score_contrastive_query() {
+        let dummy_similarity = |x: &f32| *x as ScoreType;
+        let assert_close = |actual: ScoreType, expected: ScoreType| {
+            assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+        };
+
+        // A single example with unit weight is scored by its similarity
+        let query = ContrastiveQuery::new(vec![(3.0, 1.0)], vec![(1.0, 1.0)]);
+        assert_close(query.score_by(dummy_similarity), 2.0);
+
+        // Doubling the weight is the same as adding its logarithm to the similarity
+        let query = ContrastiveQuery::new(vec![(3.0, 2.0)], vec![]);
+        assert_close(query.score_by(dummy_similarity), 3.0 + 2f32.ln());
+
+        // Same as two equal examples with unit weights
+        let query = ContrastiveQuery::new(vec![(3.0, 1.0), (3.0, 1.0)], vec![]);
+        assert_close(query.score_by(dummy_similarity), 3.0 + 2f32.ln());
+
+        // The closest example dominates, even for large similarities
+        let query = ContrastiveQuery::new(vec![(1000.0, 1.0), (0.0, 1.0)], vec![(-5.0, 1.0)]);
+        assert_close(query.score_by(dummy_similarity), 1005.0);
+
+        // Examples without a positive weight are ignored
+        let query = ContrastiveQuery::new(vec![(3.0, 0.0)], vec![(1.0, 1.0)]);
+        assert_close(query.score_by(dummy_similarity), -1.0);
+    }
+
     proptest! {
+        /// Checks that the contrastive score keeps the order of the candidates
+        /// by similarity to a positive, and inverts it for a negative
+        #[test]
+        fn contrastive_order(a in -100f32..=100f32, b in -100f32..=100f32) {
+            let dummy_similarity = |x: &f32| *x as ScoreType;
+
+            let ordering_before = a.total_cmp(&b);
+
+            let positive_a = ContrastiveQuery::new(vec![(a, 1.0)], vec![]);
+            let positive_b = ContrastiveQuery::new(vec![(b, 1.0)], vec![]);
+            let ordering_after = positive_a.score_by(dummy_similarity).total_cmp(&positive_b.score_by(dummy_similarity));
+            assert_eq!(ordering_before, ordering_after);
+
+            let negative_a = ContrastiveQuery::new(vec![], vec![(a, 1.0)]);
+            let negative_b = ContrastiveQuery::new(vec![], vec![(b, 1.0)]);
+            let ordering_after = negative_a.score_by(dummy_similarity).total_cmp(&negative_b.score_by(dummy_similarity));
+            assert_eq!(ordering_before.reverse(), ordering_after);
+        }
+
         /// Checks that the negative-chosen scores invert the order of the candidates
         #[test]
         fn correct_negative_order(a in -100f32..=100f32, b in -100f32..=100f32) {
@@ -4,15 +4,17 @@ use bitvec::prelude::BitSlice;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use sparse::common::sparse_vector::SparseVector;
 
-use super::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
+use super::query::{
+    ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery, TransformInto,
+};
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
 use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    DenseVector, MultiDenseVectorInternal, QueryVector, Vector, VectorElementType,
+    VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
 };
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
@@ -200,6 +202,25 @@ pub fn raw_sparse_scorer_impl<'a, TVectorStorage: SparseVectorStorage>(
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<SparseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                SparseCustomQueryScorer::<_, _>::new(reco_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<SparseVector> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                SparseCustomQueryScorer::<_, _>::new(contrastive_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<SparseVector> = discovery_query.transform_into()?;
             raw_scorer_from_query_scorer(
@@ -229,6 +250,88 @@ pub fn new_raw_scorer<'a>(
     new_stoppable_raw_scorer(vector, vector_storage, point_deleted, &DEFAULT_STOPPED)
 }
 
+/// Preprocess a query vector with the metric of `distance`, the same way as stored vectors are.
+///
+/// Used along with [`score_vectors`] to compare vectors, which are not in a vector storage.
+pub fn preprocess_vector(distance: Distance, vector: Vector) -> Vector {
+    let preprocess = |dense: DenseVector| match distance {
+        Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(dense),
+        Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::preprocess(dense),
+        Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::preprocess(dense),
+        Distance::Manhattan => <ManhattanMetric as Metric<VectorElementType>>::preprocess(dense),
+    };
+
+    match vector {
+        Vector::Dense(dense) => Vector::Dense(preprocess(dense)),
+        Vector::Sparse(mut sparse) => {
+            sparse.sort_by_indices();
+            Vector::Sparse(sparse)
+        }
+        Vector::MultiDense(mut multi_dense) => {
+            for dense in multi_dense.multi_vectors_mut() {
+                let preprocessed = preprocess(dense.to_vec());
+                dense.copy_from_slice(&preprocessed);
+            }
+            Vector::MultiDense(multi_dense)
+        }
+    }
+}
+
+/// Similarity of two preprocessed vectors, which are not in a vector storage.
+///
+/// Greater the value - closer the vectors, same as for the scores of [`RawScorer`].
+/// Multi-vectors are compared with max-sim.
+pub fn score_vectors(distance: Distance, a: VectorRef, b: VectorRef) -> OperationResult<ScoreType> {
+    let similarity = |a: &[VectorElementType], b: &[VectorElementType]| {
+        if a.len() != b.len() {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: a.len(),
+                received_dim: b.len(),
+            });
+        }
+        let score = match distance {
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::similarity(a, b),
+            Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::similarity(a, b),
+            Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::similarity(a, b),
+            Distance::Manhattan => <ManhattanMetric as Metric<VectorElementType>>::similarity(a, b),
+        };
+        Ok(score)
+    };
+
+    match (a, b) {
+        (VectorRef::Dense(a), VectorRef::Dense(b)) => similarity(a, b),
+        (VectorRef::Sparse(a), VectorRef::Sparse(b)) => {
+            let score = if a.is_sorted() && b.is_sorted() {
+                a.score(b)
+            } else {
+                let (mut a, mut b) = (a.clone(), b.clone());
+                a.sort_by_indices();
+                b.sort_by_indices();
+                a.score(&b)
+            };
+            // Sparse vectors without common indices have nothing in common
+            Ok(score.unwrap_or(0.0))
+        }
+        (VectorRef::MultiDense(a), VectorRef::MultiDense(b)) => {
+            let mut sum = 0.0;
+            for a in a.multi_vectors() {
+                let mut max_similarity = ScoreType::NEG_INFINITY;
+                for b in b.multi_vectors() {
+                    max_similarity = max_similarity.max(similarity(a, b)?);
+                }
+                if max_similarity.is_finite() {
+                    sum += max_similarity;
+                }
+            }
+            Ok(sum)
+        }
+        (VectorRef::Sparse(_), _) | (_, VectorRef::Sparse(_)) => Err(OperationError::WrongSparse),
+        (VectorRef::MultiDense(_), _) | (_, VectorRef::MultiDense(_)) => {
+            Err(OperationError::WrongMulti)
+        }
+    }
+}
+
 pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -296,6 +399,31 @@ fn new_scorer_with_metric<
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<DenseVector> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
             raw_scorer_from_query_scorer(
@@ -390,6 +518,31 @@ fn new_scorer_byte_with_metric<
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeByte, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<DenseVector> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeByte, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
             raw_scorer_from_query_scorer(
@@ -484,6 +637,31 @@ fn new_scorer_half_with_metric<
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeHalf, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<DenseVector> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeHalf, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
             raw_scorer_from_query_scorer(
@@ -597,6 +775,31 @@ fn new_multi_scorer_with_metric<
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<MultiDenseVectorInternal> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<MultiDenseVectorInternal> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<MultiDenseVectorInternal> =
                 discovery_query.transform_into()?;
@@ -693,6 +896,31 @@ fn new_multi_scorer_byte_with_metric<
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<MultiDenseVectorInternal> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeByte, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<MultiDenseVectorInternal> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeByte, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<MultiDenseVectorInternal> =
                 discovery_query.transform_into()?;
@@ -789,6 +1017,31 @@ fn new_multi_scorer_half_with_metric<
                 is_stopped,
             )
         }
+        QueryVector::RecommendSumScores(reco_query) => {
+            let reco_query: RecoSumQuery<MultiDenseVectorInternal> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeHalf, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::RecommendContrastive(contrastive_query) => {
+            let contrastive_query: ContrastiveQuery<MultiDenseVectorInternal> =
+                contrastive_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeHalf, TMetric, _, _, _>::new(
+                    contrastive_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
         QueryVector::Discovery(discovery_query) => {
             let discovery_query: DiscoveryQuery<MultiDenseVectorInternal> =
                 discovery_query.transform_into()?;
@@ -175,19 +175,16 @@ impl VectorStorage for SimpleSparseVectorStorage {
         Ok(())
     }
 
-    fn update_from(
+    fn update_from<'a>(
         &mut self,
-        other: &VectorStorageEnum,
-        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        other_vectors: &mut impl Iterator<Item = (CowVector<'a>, bool)>,
         stopped: &AtomicBool,
     ) -> OperationResult<Range<PointOffsetType>> {
         let start_index = self.total_vector_count as PointOffsetType;
-        for point_id in other_ids {
+        for (other_vector, other_deleted) in other_vectors {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
-            let other_vector = other.get_vector(point_id);
             let other_vector = other_vector.as_vec_ref().try_into()?;
-            let other_deleted = other.is_deleted_vector(point_id);
             let new_id = self.total_vector_count as PointOffsetType;
             self.total_vector_count += 1;
             self.set_deleted(new_id, other_deleted);
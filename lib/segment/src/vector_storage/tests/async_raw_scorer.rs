@@ -76,7 +76,13 @@ fn test_async_raw_scorer(
         insert_random_vectors(&mut rng, dim, &mut mutable_storage, points)?;
         delete_random_vectors(&mut rng, &mut mutable_storage, &mut id_tracker, delete)?;
 
-        storage.update_from(&mutable_storage, &mut (0..points as _), &Default::default())?;
+        let mut iter = (0..points as _).map(|i| {
+            (
+                mutable_storage.get_vector(i),
+                mutable_storage.is_deleted_vector(i),
+            )
+        });
+        storage.update_from(&mut iter, &Default::default())?;
     }
 
     for _ in 0..score {
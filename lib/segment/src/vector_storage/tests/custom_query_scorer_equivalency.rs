@@ -22,7 +22,9 @@ use crate::types::{
 use crate::vector_storage::dense::memmap_dense_vector_storage::open_memmap_vector_storage_with_async_io;
 use crate::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
+use crate::vector_storage::query::{
+    ContextPair, ContextQuery, ContrastiveQuery, DiscoveryQuery, RecoQuery, RecoSumQuery,
+};
 use crate::vector_storage::tests::utils::score;
 use crate::vector_storage::vector_storage_base::VectorStorage;
 use crate::vector_storage::{new_raw_scorer, VectorStorageEnum};
@@ -49,6 +51,8 @@ fn random_query<R: Rng + ?Sized>(
 ) -> QueryVector {
     match query_variant {
         QueryVariant::Recommend => random_reco_query(rnd, sampler),
+        QueryVariant::RecommendSumScores => random_reco_sum_query(rnd, sampler),
+        QueryVariant::RecommendContrastive => random_contrastive_query(rnd, sampler),
         QueryVariant::Discovery => random_discovery_query(rnd, sampler),
         QueryVariant::Context => random_context_query(rnd, sampler),
     }
@@ -72,6 +76,52 @@ fn random_reco_query<R: Rng + ?Sized>(
     RecoQuery::new(positives, negatives).into()
 }
 
+fn random_reco_sum_query<R: Rng + ?Sized>(
+    rnd: &mut R,
+    sampler: &mut impl Iterator<Item = f32>,
+) -> QueryVector {
+    let num_positives: usize = rnd.gen_range(0..MAX_EXAMPLES);
+    let num_negatives: usize = rnd.gen_range(1..MAX_EXAMPLES);
+
+    let positives = (0..num_positives)
+        .map(|_| sampler.take(DIMS).collect_vec().into())
+        .collect_vec();
+
+    let negatives = (0..num_negatives)
+        .map(|_| sampler.take(DIMS).collect_vec().into())
+        .collect_vec();
+
+    RecoSumQuery::new(positives, negatives).into()
+}
+
+fn random_contrastive_query<R: Rng + ?Sized>(
+    rnd: &mut R,
+    sampler: &mut impl Iterator<Item = f32>,
+) -> QueryVector {
+    let num_positives: usize = rnd.gen_range(0..MAX_EXAMPLES);
+    let num_negatives: usize = rnd.gen_range(1..MAX_EXAMPLES);
+
+    let positives = (0..num_positives)
+        .map(|_| {
+            (
+                sampler.take(DIMS).collect_vec().into(),
+                rnd.gen_range(0.1..2.0),
+            )
+        })
+        .collect_vec();
+
+    let negatives = (0..num_negatives)
+        .map(|_| {
+            (
+                sampler.take(DIMS).collect_vec().into(),
+                rnd.gen_range(0.1..2.0),
+            )
+        })
+        .collect_vec();
+
+    ContrastiveQuery::new(positives, negatives).into()
+}
+
 fn random_discovery_query<R: Rng + ?Sized>(
     rnd: &mut R,
     sampler: &mut impl Iterator<Item = f32>,
@@ -174,6 +224,8 @@ fn binary() -> Option<WithQuantization> {
 
 enum QueryVariant {
     Recommend,
+    RecommendSumScores,
+    RecommendContrastive,
     Discovery,
     Context,
 }
@@ -216,7 +268,9 @@ fn scoring_equivalency(
 
     let mut other_storage = other_storage(other_dir.path());
 
-    other_storage.update_from(&raw_storage, &mut (0..NUM_POINTS as _), &Default::default())?;
+    let mut iter =
+        (0..NUM_POINTS as _).map(|i| (raw_storage.get_vector(i), raw_storage.is_deleted_vector(i)));
+    other_storage.update_from(&mut iter, &Default::default())?;
 
     let quant_dir = tempfile::Builder::new().prefix("quant-storage").tempdir()?;
     let quantized_vectors = if let Some(config) = &quant_config {
@@ -316,6 +370,8 @@ fn scoring_equivalency(
 fn compare_scoring_equivalency(
     #[values(
         QueryVariant::Recommend,
+        QueryVariant::RecommendSumScores,
+        QueryVariant::RecommendContrastive,
         QueryVariant::Discovery,
         QueryVariant::Context
     )]
@@ -333,6 +389,8 @@ fn compare_scoring_equivalency(
 fn async_compare_scoring_equivalency(
     #[values(
         QueryVariant::Recommend,
+        QueryVariant::RecommendSumScores,
+        QueryVariant::RecommendContrastive,
         QueryVariant::Discovery,
         QueryVariant::Context
     )]
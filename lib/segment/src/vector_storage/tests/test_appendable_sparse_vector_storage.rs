@@ -135,13 +135,9 @@ fn do_test_update_from_delete_points(storage: &mut VectorStorageEnum) {
                 }
             });
         }
-        storage
-            .update_from(
-                &storage2,
-                &mut Box::new(0..points.len() as u32),
-                &Default::default(),
-            )
-            .unwrap();
+        let mut iter = (0..points.len() as u32)
+            .map(|i| (storage2.get_vector(i), storage2.is_deleted_vector(i)));
+        storage.update_from(&mut iter, &Default::default()).unwrap();
     }
 
     assert_eq!(
@@ -59,10 +59,12 @@ pub trait VectorStorage {
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()>;
 
-    fn update_from(
+    /// Append the given vectors, each paired with its deleted flag, to the end of this storage
+    ///
+    /// Returns the range of internal IDs assigned to the appended vectors.
+    fn update_from<'a>(
         &mut self,
-        other: &VectorStorageEnum,
-        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        other_vectors: &mut impl Iterator<Item = (CowVector<'a>, bool)>,
         stopped: &AtomicBool,
     ) -> OperationResult<Range<PointOffsetType>>;
 
@@ -371,38 +373,37 @@ impl VectorStorage for VectorStorageEnum {
         }
     }
 
-    fn update_from(
+    fn update_from<'a>(
         &mut self,
-        other: &VectorStorageEnum,
-        other_ids: &mut impl Iterator<Item = PointOffsetType>,
+        other_vectors: &mut impl Iterator<Item = (CowVector<'a>, bool)>,
         stopped: &AtomicBool,
     ) -> OperationResult<Range<PointOffsetType>> {
         match self {
-            VectorStorageEnum::DenseSimple(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::DenseSimpleByte(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::DenseSimpleHalf(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::DenseMemmap(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::DenseMemmapByte(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::DenseMemmapHalf(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::DenseAppendableMemmap(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::DenseSimple(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseSimpleByte(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseMemmap(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseMemmapByte(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.update_from(other_vectors, stopped),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => {
-                v.update_from(other, other_ids, stopped)
+                v.update_from(other_vectors, stopped)
             }
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
-                v.update_from(other, other_ids, stopped)
+                v.update_from(other_vectors, stopped)
             }
-            VectorStorageEnum::SparseSimple(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::MultiDenseSimpleByte(v) => v.update_from(other, other_ids, stopped),
-            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.update_from(other, other_ids, stopped),
+            VectorStorageEnum::SparseSimple(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.update_from(other_vectors, stopped),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => {
-                v.update_from(other, other_ids, stopped)
+                v.update_from(other_vectors, stopped)
             }
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => {
-                v.update_from(other, other_ids, stopped)
+                v.update_from(other_vectors, stopped)
             }
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
-                v.update_from(other, other_ids, stopped)
+                v.update_from(other_vectors, stopped)
             }
         }
     }
@@ -9,7 +9,12 @@ use indexmap::IndexSet;
 use itertools::Itertools;
 use rand::prelude::StdRng;
 use rand::{Rng, SeedableRng};
+use segment::data_types::aggregations::{AggregationParams, HistogramBucketing};
+use segment::data_types::distinct::DistinctParams;
+use segment::data_types::explain::SearchStrategy;
+use segment::data_types::facets::{FacetParams, FacetValue};
 use segment::data_types::index::{IntegerIndexParams, IntegerIndexType};
+use segment::data_types::query_context::QueryContext;
 use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::payload_context_fixture::FixtureIdTracker;
@@ -30,9 +35,9 @@ use segment::types::PayloadFieldSchema::{FieldParams, FieldType};
 use segment::types::PayloadSchemaType::{Integer, Keyword};
 use segment::types::{
     AnyVariants, Condition, Distance, FieldCondition, Filter, GeoBoundingBox, GeoLineString,
-    GeoPoint, GeoPolygon, GeoRadius, Indexes, IsEmptyCondition, Match, Payload, PayloadField,
-    PayloadSchemaParams, PayloadSchemaType, Range, SegmentConfig, VectorDataConfig,
-    VectorStorageType, WithPayload,
+    GeoPoint, GeoPolygon, GeoRadius, Indexes, IsEmptyCondition, IsNullCondition, Match, Payload,
+    PayloadContainer, PayloadField, PayloadSchemaParams, PayloadSchemaType, Range, SegmentConfig,
+    SeqNumberType, VectorDataConfig, VectorStorageType, WithPayload,
 };
 use segment::utils::scored_point_ties::ScoredPointTies;
 use serde_json::json;
@@ -110,6 +115,7 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: true,
                     range: false,
+                    is_principal: None,
                 },
             ))),
         )
@@ -123,6 +129,7 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
                     r#type: IntegerIndexType::Integer,
                     lookup: false,
                     range: true,
+                    is_principal: None,
                 },
             ))),
         )
@@ -401,6 +408,51 @@ fn test_is_empty_conditions() {
         (estimation_struct.exp as f64 - real_number as f64).abs()
             <= (estimation_plain.exp as f64 - real_number as f64).abs()
     );
+
+    // Null index of the field gives the exact number of empty points
+    assert_eq!(estimation_struct.min, real_number);
+    assert_eq!(estimation_struct.max, real_number);
+}
+
+#[test]
+fn test_is_null_conditions() {
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let (mut struct_segment, mut plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+    let null_payload: Payload = json!({ FLICKING_KEY: null }).into();
+    for (opnum, idx) in (0..50u64).step_by(5).enumerate() {
+        let opnum = 10_000 + opnum as SeqNumberType;
+        if !struct_segment.has_point(idx.into()) {
+            continue;
+        }
+        plain_segment
+            .set_payload(opnum, idx.into(), &null_payload, &None)
+            .unwrap();
+        struct_segment
+            .set_payload(opnum, idx.into(), &null_payload, &None)
+            .unwrap();
+    }
+
+    let filter = Filter::new_must(Condition::IsNull(IsNullCondition {
+        is_null: PayloadField {
+            key: JsonPath::new(FLICKING_KEY),
+        },
+    }));
+
+    let estimation_struct = struct_segment
+        .payload_index
+        .borrow()
+        .estimate_cardinality(&filter);
+
+    let plain_result = plain_segment.payload_index.borrow().query_points(&filter);
+    let struct_result = struct_segment.payload_index.borrow().query_points(&filter);
+
+    assert!(!plain_result.is_empty());
+    assert_eq!(plain_result, struct_result);
+    assert_eq!(estimation_struct.min, plain_result.len());
+    assert_eq!(estimation_struct.max, plain_result.len());
 }
 
 #[test]
@@ -417,7 +469,11 @@ fn test_integer_index_types() {
             .get(&JsonPath::new(INT_KEY))
             .unwrap()
             .as_slice(),
-        [FieldIndex::IntMapIndex(_), FieldIndex::IntIndex(_)]
+        [
+            FieldIndex::IntMapIndex(_),
+            FieldIndex::IntIndex(_),
+            FieldIndex::NullIndex(_)
+        ]
     ));
     assert!(matches!(
         indexes
@@ -425,7 +481,7 @@ fn test_integer_index_types() {
             .get(&JsonPath::new(INT_KEY_2))
             .unwrap()
             .as_slice(),
-        [FieldIndex::IntMapIndex(_)]
+        [FieldIndex::IntMapIndex(_), FieldIndex::NullIndex(_)]
     ));
     assert!(matches!(
         indexes
@@ -433,7 +489,7 @@ fn test_integer_index_types() {
             .get(&JsonPath::new(INT_KEY_3))
             .unwrap()
             .as_slice(),
-        [FieldIndex::IntIndex(_)]
+        [FieldIndex::IntIndex(_), FieldIndex::NullIndex(_)]
     ));
 }
 
@@ -930,3 +986,219 @@ fn test_any_matcher_cardinality_estimation() {
     assert!(exact <= estimation.max);
     assert!(exact >= estimation.min);
 }
+
+#[test]
+fn test_facet_counts() {
+    // Compare facet counts from the keyword index with counts over plain payload
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let mut rnd = rand::thread_rng();
+
+    let (struct_segment, plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+    let key = JsonPath::new(STR_KEY);
+
+    let filters = std::iter::once(None)
+        .chain((0..ATTEMPTS).map(|_| Some(random_filter(&mut rnd, 3))))
+        .collect_vec();
+
+    let all_counts = struct_segment
+        .facet(&FacetParams {
+            key: key.clone(),
+            limit: FacetParams::DEFAULT_LIMIT,
+            filter: None,
+            exact: true,
+        })
+        .unwrap();
+
+    for filter in filters {
+        let request = FacetParams {
+            key: key.clone(),
+            limit: FacetParams::DEFAULT_LIMIT,
+            filter: filter.clone(),
+            exact: true,
+        };
+        let facet_counts = struct_segment.facet(&request).unwrap();
+
+        let mut expected_counts: HashMap<FacetValue, usize> = HashMap::new();
+        for point_id in plain_segment
+            .read_filtered(None, None, filter.as_ref())
+            .unwrap()
+        {
+            let payload = plain_segment.payload(point_id).unwrap();
+            let keywords = payload
+                .get_value(&key)
+                .into_iter()
+                .flat_map(|value| match value {
+                    serde_json::Value::Array(values) => values.iter().collect_vec(),
+                    value => vec![value],
+                })
+                .filter_map(|value| value.as_str())
+                .unique();
+            for keyword in keywords {
+                *expected_counts
+                    .entry(FacetValue::Keyword(keyword.to_string()))
+                    .or_default() += 1;
+            }
+        }
+
+        let facet_counts: HashMap<_, _> = facet_counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        assert_eq!(facet_counts, expected_counts, "filter: {filter:#?}");
+
+        // Approximate counts are estimated for the values of the index only
+        let approximate_counts = struct_segment
+            .facet(&FacetParams {
+                exact: false,
+                ..request
+            })
+            .unwrap();
+        if filter.is_none() {
+            let approximate_counts: HashMap<_, _> = approximate_counts
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(value, count)| (value.clone(), *count))
+                .collect();
+            assert_eq!(approximate_counts, facet_counts);
+        }
+        for value in approximate_counts.keys() {
+            assert!(all_counts.contains_key(value), "value: {value:?}");
+        }
+    }
+}
+
+#[test]
+fn test_numeric_aggregation() {
+    // Compare aggregation over the numeric index with aggregation over plain payload
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let mut rnd = rand::thread_rng();
+
+    let (struct_segment, plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+    let filters = std::iter::once(None)
+        .chain((0..ATTEMPTS).map(|_| Some(random_filter(&mut rnd, 3))))
+        .collect_vec();
+
+    for filter in filters {
+        let request = AggregationParams {
+            key: JsonPath::new(INT_KEY),
+            filter: filter.clone(),
+            histogram: Some(HistogramBucketing {
+                interval: 7.0,
+                offset: 0.0,
+                buckets: None,
+            }),
+            date_histogram: None,
+        };
+
+        let indexed_aggregation = struct_segment.aggregate(&request).unwrap();
+        let plain_aggregation = plain_segment.aggregate(&request).unwrap();
+
+        assert_eq!(
+            indexed_aggregation, plain_aggregation,
+            "filter: {filter:#?}"
+        );
+    }
+}
+
+#[test]
+fn test_segment_search_explain() {
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let mut rnd = rand::thread_rng();
+
+    let (struct_segment, plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+    for _ in 0..ATTEMPTS {
+        let query = random_vector(&mut rnd, DIM).into();
+        let filter = random_filter(&mut rnd, 3);
+        let exact = plain_segment
+            .read_filtered(None, None, Some(&filter))
+            .unwrap()
+            .len();
+
+        for segment in [&struct_segment, &plain_segment] {
+            let query_context = QueryContext::default().with_explain(true);
+            segment
+                .search_batch(
+                    DEFAULT_VECTOR_NAME,
+                    &[&query],
+                    &WithPayload::default(),
+                    &false.into(),
+                    Some(&filter),
+                    5,
+                    None,
+                    query_context.get_segment_query_context(),
+                )
+                .unwrap();
+
+            let explanations = query_context.take_explanations();
+            assert_eq!(explanations.len(), 1);
+            let explanation = &explanations[0];
+            assert_eq!(explanation.vector.as_deref(), Some(DEFAULT_VECTOR_NAME));
+            assert_eq!(
+                explanation.actual_cardinality,
+                Some(exact),
+                "filter: {filter:#?}",
+            );
+
+            let estimation = explanation.estimated_cardinality.unwrap();
+            assert!(estimation.min <= exact, "filter: {filter:#?}");
+            assert!(exact <= estimation.max, "filter: {filter:#?}");
+
+            if explanation.indexes_used.is_empty() {
+                assert_eq!(explanation.strategy, SearchStrategy::FullScan);
+            } else {
+                assert_eq!(explanation.strategy, SearchStrategy::PayloadIndex);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_distinct_values_sketch() {
+    // Values from the keyword index must be sketched the same way as values from plain payload
+    let dir1 = Builder::new().prefix("segment1_dir").tempdir().unwrap();
+    let dir2 = Builder::new().prefix("segment2_dir").tempdir().unwrap();
+
+    let mut rnd = rand::thread_rng();
+
+    let (struct_segment, plain_segment) = build_test_segments(dir1.path(), dir2.path());
+
+    let key = JsonPath::new(STR_KEY);
+
+    let filters = std::iter::once(None)
+        .chain((0..ATTEMPTS).map(|_| Some(random_filter(&mut rnd, 3))))
+        .collect_vec();
+
+    for filter in filters {
+        let request = DistinctParams {
+            key: key.clone(),
+            filter: filter.clone(),
+        };
+
+        let indexed_sketch = struct_segment.distinct(&request).unwrap();
+        let plain_sketch = plain_segment.distinct(&request).unwrap();
+        assert_eq!(indexed_sketch, plain_sketch, "filter: {filter:#?}");
+
+        let facet_request = FacetParams {
+            key: key.clone(),
+            limit: usize::MAX,
+            filter: filter.clone(),
+            exact: true,
+        };
+        let distinct_values = struct_segment
+            .facet(&facet_request)
+            .unwrap()
+            .into_values()
+            .filter(|count| *count > 0)
+            .count();
+        assert_eq!(indexed_sketch.estimate(), distinct_values);
+    }
+}
@@ -6,13 +6,21 @@ use std::time::{Duration, Instant};
 use common::cpu::CpuPermit;
 use itertools::Itertools;
 use segment::common::operation_error::OperationError;
+use segment::data_types::index::{IntegerIndexParams, KeywordIndexParams};
 use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::order_by::OrderValue;
 use segment::data_types::vectors::{only_default_vector, VectorRef, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
+use segment::id_tracker::IdTracker;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::json_path::JsonPath;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::types::{
+    Condition, FieldCondition, Filter, Indexes, PayloadFieldSchema, PayloadSchemaParams,
+    PointIdType, Range, SegmentConfig, VectorDataConfig, VectorStorageType,
+};
+use serde_json::json;
 use sparse::common::sparse_vector::SparseVector;
 use tempfile::Builder;
 
@@ -143,6 +151,157 @@ fn test_building_new_sparse_segment() {
     assert_eq!(merged_segment.point_version(3.into()), Some(100));
 }
 
+#[test]
+fn test_building_segment_ordered_by_tenant() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let tenant_key = JsonPath::new("tenant");
+    let tenant_schema =
+        PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+            is_tenant: Some(true),
+            ..Default::default()
+        }));
+
+    let mut segment1 = empty_segment(dir.path());
+    let mut segment2 = empty_segment(dir.path());
+
+    for (idx, segment) in [&mut segment1, &mut segment2].into_iter().enumerate() {
+        for i in 0..20u64 {
+            let point_id = (idx as u64 * 100 + i).into();
+            let tenant = ["c", "a", "b"][i as usize % 3];
+            segment
+                .upsert_point(1, point_id, only_default_vector(&[i as f32, 0., 0., 0.]))
+                .unwrap();
+            segment
+                .set_payload(2, point_id, &json!({ "tenant": tenant }).into(), &None)
+                .unwrap();
+        }
+        segment
+            .create_field_index(3, &tenant_key, Some(&tenant_schema))
+            .unwrap();
+    }
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segment1.segment_config).unwrap();
+    builder.update(&[&segment1, &segment2], &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    assert_eq!(merged_segment.available_point_count(), 40);
+
+    let id_tracker = merged_segment.id_tracker.borrow();
+    let tenants = id_tracker
+        .iter_ids()
+        .map(|internal_id| {
+            let point_id = id_tracker.external_id(internal_id).unwrap();
+            let payload = merged_segment.payload(point_id).unwrap();
+            payload.0["tenant"].as_str().unwrap().to_owned()
+        })
+        .collect_vec();
+
+    // Points of each tenant are stored contiguously
+    assert!(tenants.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(tenants.iter().dedup().collect_vec(), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_building_segment_ordered_by_principal() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let principal_key = JsonPath::new("created_at");
+    let principal_schema =
+        PayloadFieldSchema::FieldParams(PayloadSchemaParams::Integer(IntegerIndexParams {
+            is_principal: Some(true),
+            ..Default::default()
+        }));
+
+    let mut segment = empty_segment(dir.path());
+    for i in 0..30u64 {
+        let created_at = (i * 7) % 30;
+        segment
+            .upsert_point(1, i.into(), only_default_vector(&[i as f32, 0., 0., 0.]))
+            .unwrap();
+        segment
+            .set_payload(
+                2,
+                i.into(),
+                &json!({ "created_at": created_at }).into(),
+                &None,
+            )
+            .unwrap();
+    }
+    segment
+        .create_field_index(3, &principal_key, Some(&principal_schema))
+        .unwrap();
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segment.segment_config).unwrap();
+    builder.update_from(&segment, &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+    let mut merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    let id_tracker = merged_segment.id_tracker.borrow();
+    let values = id_tracker
+        .iter_ids()
+        .map(|internal_id| {
+            let point_id = id_tracker.external_id(internal_id).unwrap();
+            let payload = merged_segment.payload(point_id).unwrap();
+            payload.0["created_at"].as_i64().unwrap()
+        })
+        .collect_vec();
+    assert_eq!(values, (0..30).collect_vec());
+    drop(id_tracker);
+
+    let range = merged_segment.principal_range.clone().unwrap();
+    assert_eq!(range.key, principal_key);
+    assert_eq!(range.min, OrderValue::Int(0));
+    assert_eq!(range.max, OrderValue::Int(29));
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_range(
+        principal_key.clone(),
+        Range {
+            lt: None,
+            gt: None,
+            gte: Some(100.0),
+            lte: None,
+        },
+    )));
+    assert!(merged_segment
+        .read_filtered(None, None, Some(&filter))
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        merged_segment
+            .estimate_point_count(Some(&filter))
+            .unwrap()
+            .max,
+        0
+    );
+
+    // Updated values extend the range, so the segment is not skipped
+    merged_segment
+        .set_payload(4, 0.into(), &json!({ "created_at": 150 }).into(), &None)
+        .unwrap();
+    let range = merged_segment.principal_range.clone().unwrap();
+    assert_eq!(range.max, OrderValue::Int(150));
+    assert_eq!(
+        merged_segment
+            .read_filtered(None, None, Some(&filter))
+            .unwrap(),
+        vec![PointIdType::from(0)]
+    );
+}
+
 fn estimate_build_time(segment: &Segment, stop_delay_millis: Option<u64>) -> (u64, bool) {
     let stopped = Arc::new(AtomicBool::new(false));
 
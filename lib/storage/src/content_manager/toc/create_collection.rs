@@ -177,7 +177,7 @@ impl TableOfContent {
 
         let storage_config = self
             .storage_config
-            .to_shared_storage_config(self.is_distributed())
+            .to_shared_storage_config(self.is_distributed(), self.embeddings.clone())
             .into();
 
         let collection_config = CollectionConfig {
@@ -243,6 +243,21 @@ impl TableOfContent {
         Ok(true)
     }
 
+    /// Vectors can only refer to the embedding providers, configured on this node
+    pub fn check_embedding_providers(&self, vectors: &VectorsConfig) -> Result<(), StorageError> {
+        for (vector_name, params) in vectors.params_iter() {
+            let Some(provider_name) = &params.embedding else {
+                continue;
+            };
+            if !self.embeddings.has_provider(provider_name) {
+                return Err(StorageError::bad_input(format!(
+                    "Embedding provider {provider_name} of vector {vector_name} is not configured",
+                )));
+            }
+        }
+        Ok(())
+    }
+
     async fn check_collections_compatibility(
         &self,
         vectors: &VectorsConfig,
@@ -19,6 +19,7 @@ use std::sync::Arc;
 
 use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{default_replication_factor, CollectionConfig};
+use collection::embeddings::EmbeddingProviders;
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::replica_set;
@@ -74,6 +75,8 @@ pub struct TableOfContent {
     collection_create_lock: Mutex<()>,
     /// Dispatcher for shard transfer to access consensus.
     shard_transfer_dispatcher: parking_lot::Mutex<Option<ShardTransferDispatcher>>,
+    /// Embedding providers, shared by all collections along with their cache
+    embeddings: Arc<EmbeddingProviders>,
 }
 
 impl TableOfContent {
@@ -101,6 +104,7 @@ impl TableOfContent {
             read_dir(&collections_path).expect("Can't read Collections directory");
         let mut collections: HashMap<String, Collection> = Default::default();
         let is_distributed = consensus_proposal_sender.is_some();
+        let embeddings = Arc::new(storage_config.embedding.build_providers());
         for entry in collection_paths {
             let collection_path = entry
                 .expect("Can't access of one of the collection files")
@@ -132,7 +136,7 @@ impl TableOfContent {
                 &collection_path,
                 &collection_snapshots_path,
                 storage_config
-                    .to_shared_storage_config(is_distributed)
+                    .to_shared_storage_config(is_distributed, embeddings.clone())
                     .into(),
                 channel_service.clone(),
                 Self::change_peer_state_callback(
@@ -194,6 +198,7 @@ impl TableOfContent {
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
+            embeddings,
         }
     }
 
@@ -12,6 +12,8 @@ use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
+use segment::data_types::explain::ShardExplanation;
+use segment::data_types::profile::QueryProfile;
 use segment::types::{ScoredPoint, ShardKey};
 
 use super::TableOfContent;
@@ -129,6 +131,39 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Search a single page of points, continuing after the given page token
+    ///
+    /// Also returns the token of the next page, if there can be more results.
+    /// If `explain` is set, also returns how the segments of each shard replica have executed the
+    /// search of this page.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn search_page(
+        &self,
+        collection_name: &str,
+        mut request: CoreSearchRequest,
+        page_token: Option<&str>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> Result<(Vec<ScoredPoint>, Option<String>, Vec<ShardExplanation>), StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .search_page(
+                request,
+                page_token,
+                read_consistency,
+                shard_selection,
+                timeout,
+                explain,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Count points in the collection.
     ///
     /// # Arguments
@@ -158,6 +193,95 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count points per value of a payload key.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we count
+    /// * `request` - [`FacetRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Most frequent values of the payload key with the number of points having them.
+    ///
+    pub async fn facet(
+        &self,
+        collection_name: &str,
+        mut request: FacetRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> Result<FacetResult, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .facet(request, read_consistency, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Sample points and find the nearest neighbours of each of them among the sampled points.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we sample
+    /// * `request` - [`SearchMatrixRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    /// * `timeout` - how long to wait for the response
+    ///
+    /// # Result
+    ///
+    /// Ids of the sampled points, with the nearest neighbours of each of them.
+    ///
+    pub async fn search_points_matrix(
+        &self,
+        collection_name: &str,
+        mut request: SearchMatrixRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> Result<SearchMatrixResult, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .search_points_matrix(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Aggregate values of a payload key.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we aggregate
+    /// * `request` - [`AggregateRequestInternal`]
+    /// * `shard_selection` - which local shard to use
+    ///
+    /// # Result
+    ///
+    /// Statistics and histogram of the values of the payload key.
+    ///
+    pub async fn aggregate(
+        &self,
+        collection_name: &str,
+        mut request: AggregateRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> Result<AggregateResult, StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .aggregate(request, read_consistency, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -323,6 +447,72 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Query a single page of results, continuing after the given page token
+    ///
+    /// Also returns the token of the next page, if there can be more results.
+    /// If `explain` is set, also returns how the segments of each shard replica have executed the
+    /// query of this page.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_page(
+        &self,
+        collection_name: &str,
+        mut request: CollectionQueryRequest,
+        page_token: Option<&str>,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> Result<(Vec<ScoredPoint>, Option<String>, Vec<ShardExplanation>), StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .query_page(
+                request,
+                page_token,
+                shard_selection,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+                timeout,
+                explain,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Query a single request, measuring the time spent on each stage of the query.
+    ///
+    /// If `explain` is set, the profile also holds how each segment has executed the query.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_profiled(
+        &self,
+        collection_name: &str,
+        mut request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> Result<(Vec<ScoredPoint>, QueryProfile), StorageError> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .query_profiled(
+                request,
+                shard_selection,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+                timeout,
+                explain,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// # Cancel safety
     ///
     /// This method is cancel safe.
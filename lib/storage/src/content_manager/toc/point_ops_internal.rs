@@ -4,6 +4,8 @@ use std::time::Duration;
 
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use segment::data_types::aggregations::{AggregationParams, NumericAggregation};
+use segment::data_types::profile::QueryProfile;
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
@@ -15,11 +17,28 @@ impl TableOfContent {
         requests: Vec<ShardQueryRequest>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> Result<Vec<ShardQueryResponse>, StorageError> {
+        profile: bool,
+        explain: bool,
+    ) -> Result<(Vec<ShardQueryResponse>, Option<QueryProfile>), StorageError> {
         let collection = self.get_collection_unchecked(collection_name).await?;
 
         let res = collection
-            .query_batch_internal(requests, &shard_selection, timeout)
+            .query_batch_internal(requests, &shard_selection, timeout, profile, explain)
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn aggregate_internal(
+        &self,
+        collection_name: &str,
+        params: AggregationParams,
+        shard_selection: ShardSelectorInternal,
+    ) -> Result<NumericAggregation, StorageError> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection
+            .aggregate_internal(params, &shard_selection)
             .await?;
 
         Ok(res)
@@ -56,6 +56,11 @@ impl Dispatcher {
     ) -> Result<bool, StorageError> {
         access.check_collection_meta_operation(&operation)?;
 
+        if let CollectionMetaOperations::CreateCollection(op) = &operation {
+            self.toc
+                .check_embedding_providers(&op.create_collection.vectors)?;
+        }
+
         // if distributed deployment is enabled
         if let Some(state) = self.consensus_state.as_ref() {
             let start = Instant::now();
@@ -0,0 +1,229 @@
+//! Configuration of the embedding providers, and the provider for OpenAI-compatible HTTP APIs
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use collection::embeddings::{
+    EmbeddingInput, EmbeddingProvider, EmbeddingProviders, StubEmbeddingProvider,
+};
+use collection::operations::types::{CollectionError, CollectionResult};
+use segment::data_types::vectors::DenseVector;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_EMBEDDING_CACHE_SIZE_MB: usize = 32;
+const DEFAULT_EMBEDDING_BATCH_SIZE: usize = 32;
+const DEFAULT_EMBEDDING_TIMEOUT_SEC: u64 = 30;
+
+/// Embedding providers of the node.
+/// Vectors of collections refer to them by name, to have their raw documents and images embedded.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EmbeddingConfig {
+    /// Providers by their names
+    #[serde(default)]
+    pub providers: HashMap<String, EmbeddingProviderConfig>,
+    /// Max size of the recently computed embeddings to reuse, in megabytes
+    #[serde(default = "default_embedding_cache_size_mb")]
+    pub cache_size_mb: usize,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            providers: HashMap::new(),
+            cache_size_mb: DEFAULT_EMBEDDING_CACHE_SIZE_MB,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EmbeddingProviderConfig {
+    /// OpenAI-compatible `/embeddings` endpoint
+    Http(HttpEmbeddingConfig),
+    /// Deterministic embeddings without any model, for testing
+    Stub { dimension: usize },
+}
+
+#[derive(Deserialize, Clone)]
+pub struct HttpEmbeddingConfig {
+    /// Base URL of the API, `/embeddings` is appended to it
+    pub url: String,
+    pub model: String,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Dimension of the embeddings, for the models which support shortening them
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    #[serde(default = "default_embedding_batch_size")]
+    pub batch_size: usize,
+    #[serde(default = "default_embedding_timeout_sec")]
+    pub timeout_sec: u64,
+}
+
+impl fmt::Debug for HttpEmbeddingConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            url,
+            model,
+            api_key,
+            dimensions,
+            batch_size,
+            timeout_sec,
+        } = self;
+        f.debug_struct("HttpEmbeddingConfig")
+            .field("url", url)
+            .field("model", model)
+            .field("api_key", &api_key.as_ref().map(|_| "<redacted>"))
+            .field("dimensions", dimensions)
+            .field("batch_size", batch_size)
+            .field("timeout_sec", timeout_sec)
+            .finish()
+    }
+}
+
+const fn default_embedding_cache_size_mb() -> usize {
+    DEFAULT_EMBEDDING_CACHE_SIZE_MB
+}
+
+const fn default_embedding_batch_size() -> usize {
+    DEFAULT_EMBEDDING_BATCH_SIZE
+}
+
+const fn default_embedding_timeout_sec() -> u64 {
+    DEFAULT_EMBEDDING_TIMEOUT_SEC
+}
+
+impl EmbeddingConfig {
+    pub fn build_providers(&self) -> EmbeddingProviders {
+        let providers = self
+            .providers
+            .iter()
+            .map(|(name, config)| {
+                let provider: Arc<dyn EmbeddingProvider> = match config {
+                    EmbeddingProviderConfig::Http(config) => {
+                        Arc::new(HttpEmbeddingProvider::new(config.clone()))
+                    }
+                    EmbeddingProviderConfig::Stub { dimension } => {
+                        Arc::new(StubEmbeddingProvider {
+                            dimension: *dimension,
+                        })
+                    }
+                };
+                (name.clone(), provider)
+            })
+            .collect();
+
+        EmbeddingProviders::new(providers, self.cache_size_mb * 1024 * 1024)
+    }
+}
+
+/// Provider, which calls an OpenAI-compatible `/embeddings` endpoint.
+///
+/// Images are sent as input strings as well, which is what multimodal servers of this API expect.
+pub struct HttpEmbeddingProvider {
+    config: HttpEmbeddingConfig,
+    client: reqwest::Client,
+}
+
+impl fmt::Debug for HttpEmbeddingProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpEmbeddingProvider")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dimensions: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: DenseVector,
+}
+
+impl HttpEmbeddingProvider {
+    pub fn new(config: HttpEmbeddingConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for HttpEmbeddingProvider {
+    fn batch_size(&self) -> usize {
+        self.config.batch_size
+    }
+
+    async fn embed(&self, inputs: &[EmbeddingInput]) -> CollectionResult<Vec<DenseVector>> {
+        let request = EmbeddingsRequest {
+            model: &self.config.model,
+            input: inputs
+                .iter()
+                .map(|input| match input {
+                    EmbeddingInput::Text(text) => text.as_str(),
+                    EmbeddingInput::Image(image) => image.as_str(),
+                })
+                .collect(),
+            dimensions: self.config.dimensions,
+        };
+        let body = serde_json::to_vec(&request).map_err(|err| {
+            CollectionError::service_error(format!("Can't serialize embedding request: {err}"))
+        })?;
+
+        let url = format!("{}/embeddings", self.config.url.trim_end_matches('/'));
+        let mut request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .timeout(Duration::from_secs(self.config.timeout_sec))
+            .body(body);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|err| {
+            CollectionError::service_error(format!("Embedding request failed: {err}"))
+        })?;
+        let status = response.status();
+        let bytes = response.bytes().await.map_err(|err| {
+            CollectionError::service_error(format!("Can't read embedding response: {err}"))
+        })?;
+        if !status.is_success() {
+            // The body may echo the request or credentials, so it is only logged
+            log::warn!(
+                "Embedding provider {} responded with {status}: {}",
+                self.config.url,
+                String::from_utf8_lossy(&bytes),
+            );
+            return Err(CollectionError::service_error(format!(
+                "Embedding provider responded with {status}",
+            )));
+        }
+
+        let response: EmbeddingsResponse = serde_json::from_slice(&bytes).map_err(|err| {
+            CollectionError::service_error(format!("Can't parse embedding response: {err}"))
+        })?;
+        let mut data = response.data;
+        data.sort_unstable_by_key(|embedding| embedding.index);
+        Ok(data
+            .into_iter()
+            .map(|embedding| embedding.embedding)
+            .collect())
+    }
+}
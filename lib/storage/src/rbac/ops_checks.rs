@@ -8,8 +8,9 @@ use collection::lookup::WithLookup;
 use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
 use collection::operations::point_ops::{PointIdsList, PointOperations};
 use collection::operations::types::{
-    ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
-    PointRequestInternal, RecommendExample, RecommendRequestInternal, ScrollRequestInternal,
+    AggregateRequestInternal, ContextExamplePair, CoreSearchRequest, CountRequestInternal,
+    DiscoverRequestInternal, FacetRequestInternal, PointRequestInternal, RecommendExample,
+    RecommendRequestInternal, ScrollRequestInternal, SearchMatrixRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
@@ -136,6 +137,16 @@ impl<'a> CollectionAccessView<'a> {
                     self.check_vector_input(vector_input)?
                 }
             }
+            VectorQuery::RecommendSumScores(reco) => {
+                for vector_input in reco.flat_iter() {
+                    self.check_vector_input(vector_input)?
+                }
+            }
+            VectorQuery::RecommendContrastive(contrastive) => {
+                for vector_input in contrastive.flat_iter() {
+                    self.check_vector_input(vector_input)?
+                }
+            }
             VectorQuery::Discover(discover) => {
                 for vector_input in discover.flat_iter() {
                     self.check_vector_input(vector_input)?
@@ -153,7 +164,7 @@ impl<'a> CollectionAccessView<'a> {
 
     fn check_vector_input(&self, vector_input: &VectorInput) -> Result<(), StorageError> {
         match vector_input {
-            VectorInput::Vector(_) => Ok(()),
+            VectorInput::Vector(_) | VectorInput::Document(_) | VectorInput::Image(_) => Ok(()),
             VectorInput::Id(_) => self.check_whole_access(),
         }
     }
@@ -241,6 +252,63 @@ impl CheckableCollectionOperation for CountRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for FacetRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for SearchMatrixRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
+impl CheckableCollectionOperation for AggregateRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for GroupRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -708,6 +776,8 @@ mod tests_ops {
             positive: vec![RecommendExample::Dense(vec![0.0, 1.0, 2.0])],
             negative: vec![RecommendExample::Sparse(vec![(0, 0.0)].try_into().unwrap())],
             strategy: Some(RecommendStrategy::AverageVector),
+            positive_weights: None,
+            negative_weights: None,
             filter: None,
             params: Some(SearchParams::default()),
             limit: 100,
@@ -841,6 +911,100 @@ mod tests_ops {
         let op = CountRequestInternal {
             filter: None,
             exact: false,
+            group_by: None,
+            group_by_limit: None,
+            distinct: None,
+            explain: None,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_facet_request_internal() {
+        let op = FacetRequestInternal {
+            key: "field".parse().unwrap(),
+            limit: None,
+            filter: None,
+            exact: false,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_search_matrix_request_internal() {
+        let op = SearchMatrixRequestInternal {
+            filter: None,
+            sample: Some(10),
+            limit: Some(3),
+            using: None,
+        };
+
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
+        assert_allowed(&op, &Access::Global(GlobalAccessMode::Read));
+
+        assert_allowed(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, true)
+                .into(),
+        );
+
+        assert_allowed_rewrite(
+            &op,
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .into(),
+            |op| {
+                op.filter = Some(PayloadConstraint::new_test("col").to_filter());
+            },
+        );
+    }
+
+    #[test]
+    fn test_aggregate_request_internal() {
+        let op = AggregateRequestInternal {
+            key: "field".parse().unwrap(),
+            filter: None,
+            histogram: None,
+            date_histogram: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -879,6 +1043,9 @@ mod tests_ops {
                 score_threshold: Some(42.0),
             }),
             group_by: "path".parse().unwrap(),
+            then_group_by: vec![],
+            group_scoring: Default::default(),
+            group_order_by: None,
             group_size: 100,
             limit: 100,
             with_lookup: Some(WithLookup {
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use collection::common::snapshots_manager::SnapShotsConfig;
 use collection::config::WalConfig;
+use collection::embeddings::EmbeddingProviders;
 use collection::operations::config_diff::OptimizersConfigDiff;
 use collection::operations::shared_storage_config::{
     SharedStorageConfig, DEFAULT_IO_SHARD_TRANSFER_LIMIT, DEFAULT_SNAPSHOTS_PATH,
@@ -21,6 +23,8 @@ use serde::{Deserialize, Serialize};
 use tonic::transport::Uri;
 use validator::Validate;
 
+use crate::embeddings::EmbeddingConfig;
+
 pub type PeerAddressById = HashMap<PeerId, Uri>;
 pub type PeerMetadataById = HashMap<PeerId, PeerMetadata>;
 
@@ -97,10 +101,17 @@ pub struct StorageConfig {
     /// Default values for collections.
     #[serde(default)]
     pub collection: Option<CollectionConfigDefaults>,
+    /// Providers, which embed raw documents and images of the named vectors
+    #[serde(default)]
+    pub embedding: EmbeddingConfig,
 }
 
 impl StorageConfig {
-    pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
+    pub fn to_shared_storage_config(
+        &self,
+        is_distributed: bool,
+        embeddings: Arc<EmbeddingProviders>,
+    ) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
             self.node_type,
@@ -116,6 +127,7 @@ impl StorageConfig {
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            embeddings,
         )
     }
 }
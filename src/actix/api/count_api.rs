@@ -8,7 +8,7 @@ use storage::dispatcher::Dispatcher;
 use super::CollectionPath;
 use crate::actix::api::read_params::ReadParams;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::process_response_with_explain;
 use crate::common::points::do_count_points;
 
 #[post("/collections/{name}/points/count")]
@@ -31,6 +31,8 @@ async fn count_points(
         Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
     };
 
+    let explain = count_request.explain.unwrap_or_default();
+
     let response = do_count_points(
         dispatcher.toc(&access),
         &collection.name,
@@ -40,7 +42,11 @@ async fn count_points(
         access,
         // ToDo: use timeout from params
     )
-    .await;
+    .await
+    .map(|mut count| {
+        let explanation = explain.then(|| std::mem::take(&mut count.explanations));
+        (count, explanation)
+    });
 
-    process_response(response, timing)
+    process_response_with_explain(response, timing)
 }
@@ -1,7 +1,10 @@
+use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use api::rest::{QueryGroupsRequest, QueryRequest, QueryRequestBatch, QueryResponse};
+use collection::grouping::group_by::GroupRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CollectionError;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use itertools::Itertools;
 use storage::content_manager::errors::StorageError;
@@ -11,7 +14,7 @@ use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers;
-use crate::common::points::do_query_point_groups;
+use crate::common::points::{do_query_point_groups, do_query_points};
 
 #[post("/collections/{name}/points/query")]
 async fn query_points(
@@ -21,10 +24,15 @@ async fn query_points(
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    helpers::time(async move {
+    let timing = Instant::now();
+
+    let response = async move {
         let QueryRequest {
             internal: query_request,
             shard_key,
+            explain,
+            profile,
+            page_token,
         } = request.into_inner();
 
         let shard_selection = match shard_key {
@@ -32,27 +40,36 @@ async fn query_points(
             Some(shard_keys) => shard_keys.into(),
         };
 
-        let points = dispatcher
-            .toc(&access)
-            .query_batch(
-                &collection.name,
-                vec![(query_request.into(), shard_selection)],
-                params.consistency,
-                access,
-                params.timeout(),
-            )
-            .await?
-            .pop()
-            .ok_or_else(|| {
-                StorageError::service_error("Expected at least one response for one query")
-            })?
+        let query_request = CollectionQueryRequest::try_from(query_request)?;
+
+        let (points, profile, next_page_token, explanation) = do_query_points(
+            dispatcher.toc(&access),
+            &collection.name,
+            query_request,
+            page_token.as_deref(),
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            profile.unwrap_or_default(),
+            explain.unwrap_or_default(),
+        )
+        .await?;
+
+        let points = points
             .into_iter()
             .map(api::rest::ScoredPoint::from)
             .collect_vec();
 
-        Ok(QueryResponse { points })
-    })
-    .await
+        Ok::<_, StorageError>((
+            QueryResponse { points, profile },
+            next_page_token,
+            explanation,
+        ))
+    }
+    .await;
+
+    helpers::process_page_response_with_explain(response, timing)
 }
 
 #[post("/collections/{name}/points/query/batch")]
@@ -66,23 +83,35 @@ async fn query_points_batch(
     helpers::time(async move {
         let QueryRequestBatch { searches } = request.into_inner();
 
+        if searches
+            .iter()
+            .any(|search| search.explain.unwrap_or_default())
+        {
+            return Err(StorageError::bad_input(
+                "Explain is not supported in batch requests",
+            ));
+        }
+
         let batch = searches
             .into_iter()
             .map(|request| {
                 let QueryRequest {
                     internal,
                     shard_key,
+                    explain: _,
+                    profile: _,
+                    page_token: _,
                 } = request;
 
-                let request = CollectionQueryRequest::from(internal);
+                let request = CollectionQueryRequest::try_from(internal)?;
                 let shard_selection = match shard_key {
                     None => ShardSelectorInternal::All,
                     Some(shard_keys) => shard_keys.into(),
                 };
 
-                (request, shard_selection)
+                Ok((request, shard_selection))
             })
-            .collect();
+            .collect::<Result<_, CollectionError>>()?;
 
         let res = dispatcher
             .toc(&access)
@@ -100,6 +129,7 @@ async fn query_points_batch(
                     .into_iter()
                     .map(api::rest::ScoredPoint::from)
                     .collect_vec(),
+                profile: None,
             })
             .collect_vec();
 
@@ -127,10 +157,12 @@ async fn query_points_groups(
             Some(shard_keys) => shard_keys.into(),
         };
 
+        let group_request = GroupRequest::try_from(search_group_request)?;
+
         do_query_point_groups(
             dispatcher.toc(&access),
             &collection.name,
-            search_group_request,
+            group_request,
             params.consistency,
             shard_selection,
             access,
@@ -3,17 +3,21 @@ use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    CoreSearchRequest, SearchGroupsRequest, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixRequest, SearchRequest, SearchRequestBatch,
 };
 use itertools::Itertools;
+use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{
+    process_page_response_with_explain, process_response, process_response_error,
+};
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_core_search_points, do_search_batch_points, do_search_point_groups, do_search_points_matrix,
 };
 
 #[post("/collections/{name}/points/search")]
@@ -29,6 +33,8 @@ async fn search_points(
     let SearchRequest {
         search_request,
         shard_key,
+        explain,
+        page_token,
     } = request.into_inner();
 
     let shard_selection = match shard_key {
@@ -36,24 +42,32 @@ async fn search_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = do_core_search_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        search_request.into(),
-        params.consistency,
-        shard_selection,
-        access,
-        params.timeout(),
-    )
-    .await
-    .map(|scored_points| {
-        scored_points
+    let search_request: CoreSearchRequest = search_request.into();
+
+    let response = async {
+        let (points, next_page_token, explanation) = do_core_search_points(
+            dispatcher.toc(&access),
+            &collection.name,
+            search_request,
+            page_token.as_deref(),
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+            explain.unwrap_or_default(),
+        )
+        .await?;
+
+        let points = points
             .into_iter()
             .map(api::rest::ScoredPoint::from)
-            .collect_vec()
-    });
+            .collect_vec();
 
-    process_response(response, timing)
+        Ok::<_, StorageError>((points, next_page_token, explanation))
+    }
+    .await;
+
+    process_page_response_with_explain(response, timing)
 }
 
 #[post("/collections/{name}/points/search/batch")]
@@ -67,6 +81,16 @@ async fn batch_search_points(
     let timing = Instant::now();
 
     let request = request.into_inner();
+
+    if request
+        .searches
+        .iter()
+        .any(|search| search.explain.unwrap_or_default())
+    {
+        let error = StorageError::bad_input("Explain is not supported in batch requests");
+        return process_response_error(error, timing);
+    }
+
     let requests = request
         .searches
         .into_iter()
@@ -74,6 +98,8 @@ async fn batch_search_points(
             let SearchRequest {
                 search_request,
                 shard_key,
+                explain: _,
+                page_token: _,
             } = req;
             let shard_selection = match shard_key {
                 None => ShardSelectorInternal::All,
@@ -143,9 +169,81 @@ async fn search_point_groups(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/search/matrix/offsets")]
+async fn search_points_matrix_offsets(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_matrix_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_search_points_matrix(
+        dispatcher.toc(&access),
+        &collection.name,
+        search_matrix_request,
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+    )
+    .await
+    .map(SearchMatrixOffsetsResponse::from);
+
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/points/search/matrix/pairs")]
+async fn search_points_matrix_pairs(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchMatrixRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let SearchMatrixRequest {
+        search_matrix_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = do_search_points_matrix(
+        dispatcher.toc(&access),
+        &collection.name,
+        search_matrix_request,
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+    )
+    .await
+    .map(SearchMatrixPairsResponse::from);
+
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
-        .service(search_point_groups);
+        .service(search_point_groups)
+        .service(search_points_matrix_offsets)
+        .service(search_points_matrix_pairs);
 }
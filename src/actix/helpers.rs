@@ -5,6 +5,7 @@ use actix_web::rt::time::Instant;
 use actix_web::{http, HttpResponse, ResponseError};
 use api::grpc::models::{ApiResponse, ApiStatus};
 use collection::operations::types::CollectionError;
+use segment::data_types::explain::ShardExplanation;
 use serde::Serialize;
 use storage::content_manager::errors::StorageError;
 use tokio::task::JoinHandle;
@@ -14,18 +15,44 @@ pub fn accepted_response(timing: Instant) -> HttpResponse {
         result: None,
         status: ApiStatus::Accepted,
         time: timing.elapsed().as_secs_f64(),
+        explain: None,
+        next_page_token: None,
     })
 }
 
 pub fn process_response<D>(response: Result<D, StorageError>, timing: Instant) -> HttpResponse
+where
+    D: Serialize,
+{
+    process_response_with_explain(response.map(|res| (res, None)), timing)
+}
+
+/// Same as [`process_response`], but also attaches the explanation of the request execution
+pub fn process_response_with_explain<D>(
+    response: Result<(D, Option<Vec<ShardExplanation>>), StorageError>,
+    timing: Instant,
+) -> HttpResponse
+where
+    D: Serialize,
+{
+    process_page_response_with_explain(response.map(|(res, explain)| (res, None, explain)), timing)
+}
+
+/// Same as [`process_response_with_explain`], but also attaches the token of the next page
+pub fn process_page_response_with_explain<D>(
+    response: Result<(D, Option<String>, Option<Vec<ShardExplanation>>), StorageError>,
+    timing: Instant,
+) -> HttpResponse
 where
     D: Serialize,
 {
     match response {
-        Ok(res) => HttpResponse::Ok().json(ApiResponse {
+        Ok((res, next_page_token, explain)) => HttpResponse::Ok().json(ApiResponse {
             result: Some(res),
             status: ApiStatus::Ok,
             time: timing.elapsed().as_secs_f64(),
+            explain,
+            next_page_token,
         }),
         Err(err) => process_response_error(err, timing),
     }
@@ -49,6 +76,8 @@ pub fn process_response_error(err: StorageError, timing: Instant) -> HttpRespons
         result: None,
         status: ApiStatus::Error(error.to_string()),
         time: timing.elapsed().as_secs_f64(),
+        explain: None,
+        next_page_token: None,
     })
 }
 
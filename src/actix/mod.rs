@@ -21,11 +21,13 @@ use collection::operations::validation;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 
+use crate::actix::api::aggregate_api::aggregate;
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discovery_api::config_discovery_api;
+use crate::actix::api::facet_api::facet;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
@@ -152,6 +154,8 @@ pub fn init(
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
                 .service(count_points)
+                .service(facet)
+                .service(aggregate)
                 .service(get_point)
                 .service(get_points);
 
@@ -231,6 +235,8 @@ fn validation_error_handler(
         result: None,
         status: ApiStatus::Error(msg),
         time: 0.0,
+        explain: None,
+        next_page_token: None,
     });
     error::InternalError::from_response(err, response).into()
 }
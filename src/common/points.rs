@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use api::rest::{QueryGroupsRequestInternal, SearchGroupsRequestInternal, ShardKeySelector};
+use api::rest::{SearchGroupsRequestInternal, ShardKeySelector};
 use collection::common::batching::batch_requests;
 use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
@@ -14,9 +14,11 @@ use collection::operations::point_ops::{
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, GroupsResult, PointRequestInternal, RecommendGroupsRequestInternal,
-    Record, ScrollRequestInternal, ScrollResult, UpdateResult,
+    AggregateRequestInternal, AggregateResult, CoreSearchRequest, CoreSearchRequestBatch,
+    CountRequestInternal, CountResult, DiscoverRequestBatch, FacetRequestInternal, FacetResult,
+    GroupsResult, PointRequestInternal, RecommendGroupsRequestInternal, Record,
+    ScrollRequestInternal, ScrollResult, SearchMatrixRequestInternal, SearchMatrixResult,
+    UpdateResult,
 };
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::vector_ops::{
@@ -27,6 +29,8 @@ use collection::operations::{
 };
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
+use segment::data_types::explain::ShardExplanation;
+use segment::data_types::profile::QueryProfile;
 use segment::json_path::JsonPath;
 use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
 use serde::{Deserialize, Serialize};
@@ -749,31 +753,42 @@ pub async fn do_delete_index(
     .await
 }
 
+/// Search a single page of points. Also returns the token of the next page, if there can be more.
+///
+/// If `explain` is set, also returns how the segments of each shard replica have executed the
+/// search of this page.
+#[allow(clippy::too_many_arguments)]
 pub async fn do_core_search_points(
     toc: &TableOfContent,
     collection_name: &str,
     request: CoreSearchRequest,
+    page_token: Option<&str>,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<ScoredPoint>, StorageError> {
-    let batch_res = do_core_search_batch_points(
-        toc,
-        collection_name,
-        CoreSearchRequestBatch {
-            searches: vec![request],
-        },
-        read_consistency,
-        shard_selection,
-        access,
-        timeout,
-    )
-    .await?;
-    batch_res
-        .into_iter()
-        .next()
-        .ok_or_else(|| StorageError::service_error("Empty search result"))
+    explain: bool,
+) -> Result<
+    (
+        Vec<ScoredPoint>,
+        Option<String>,
+        Option<Vec<ShardExplanation>>,
+    ),
+    StorageError,
+> {
+    let (points, next_page_token, explanations) = toc
+        .search_page(
+            collection_name,
+            request,
+            page_token,
+            read_consistency,
+            shard_selection,
+            access,
+            timeout,
+            explain,
+        )
+        .await?;
+    Ok((points, next_page_token, explain.then_some(explanations)))
 }
 
 pub async fn do_search_batch_points(
@@ -926,6 +941,62 @@ pub async fn do_count_points(
     .await
 }
 
+pub async fn do_facet(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: FacetRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<FacetResult, StorageError> {
+    toc.facet(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+    )
+    .await
+}
+
+pub async fn do_aggregate(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: AggregateRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<AggregateResult, StorageError> {
+    toc.aggregate(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+    )
+    .await
+}
+
+pub async fn do_search_points_matrix(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchMatrixRequestInternal,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<SearchMatrixResult, StorageError> {
+    toc.search_points_matrix(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+    )
+    .await
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -962,23 +1033,76 @@ pub async fn do_scroll_points(
     .await
 }
 
+/// Query a single page of points, continuing after the page token.
+///
+/// Returns the token of the next page, if there can be more results.
+/// If `profile` is set, returns the time spent on each stage instead, and doesn't support tokens.
+/// If `explain` is set, also returns how the segments of each shard replica have executed the query.
+#[allow(clippy::too_many_arguments)]
 pub async fn do_query_points(
     toc: &TableOfContent,
     collection_name: &str,
     request: CollectionQueryRequest,
+    page_token: Option<&str>,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
     timeout: Option<Duration>,
-) -> Result<Vec<ScoredPoint>, StorageError> {
-    let requests = vec![(request, shard_selection)];
-    let batch_res = toc
-        .query_batch(collection_name, requests, read_consistency, access, timeout)
+    profile: bool,
+    explain: bool,
+) -> Result<
+    (
+        Vec<ScoredPoint>,
+        Option<QueryProfile>,
+        Option<String>,
+        Option<Vec<ShardExplanation>>,
+    ),
+    StorageError,
+> {
+    if profile {
+        if page_token.is_some() {
+            return Err(StorageError::bad_input(
+                "Page token can't be used together with profile",
+            ));
+        }
+        let (points, mut query_profile) = toc
+            .query_profiled(
+                collection_name,
+                request,
+                shard_selection,
+                read_consistency,
+                access,
+                timeout,
+                explain,
+            )
+            .await?;
+        let explanations = query_profile.take_explanations();
+        return Ok((
+            points,
+            Some(query_profile),
+            None,
+            explain.then_some(explanations),
+        ));
+    }
+
+    let (points, next_page_token, explanations) = toc
+        .query_page(
+            collection_name,
+            request,
+            page_token,
+            shard_selection,
+            read_consistency,
+            access,
+            timeout,
+            explain,
+        )
         .await?;
-    batch_res
-        .into_iter()
-        .next()
-        .ok_or_else(|| StorageError::service_error("Empty query result"))
+    Ok((
+        points,
+        None,
+        next_page_token,
+        explain.then_some(explanations),
+    ))
 }
 
 pub async fn do_query_batch_points(
@@ -996,7 +1120,7 @@ pub async fn do_query_batch_points(
 pub async fn do_query_point_groups(
     toc: &TableOfContent,
     collection_name: &str,
-    request: QueryGroupsRequestInternal,
+    request: GroupRequest,
     read_consistency: Option<ReadConsistency>,
     shard_selection: ShardSelectorInternal,
     access: Access,
@@ -1004,7 +1128,7 @@ pub async fn do_query_point_groups(
 ) -> Result<GroupsResult, StorageError> {
     toc.group(
         collection_name,
-        GroupRequest::from(request),
+        request,
         read_consistency,
         shard_selection,
         access,
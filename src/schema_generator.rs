@@ -10,15 +10,17 @@ use collection::operations::snapshot_ops::{
     ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
-    CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    AggregateRequest, AggregateResult, AliasDescription, CollectionClusterInfo,
+    CollectionExistence, CollectionInfo, CollectionsAliasesResponse, CountRequest, CountResult,
+    DiscoverRequest, DiscoverRequestBatch, FacetRequest, FacetResult, GroupsResult, PointGroup,
+    PointRequest, RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, ScrollRequest,
+    ScrollResult, SearchGroupsRequest, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
+    SearchMatrixRequest, SearchRequest, SearchRequestBatch, UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
+use segment::data_types::explain::ShardExplanation;
 use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
@@ -87,6 +89,14 @@ struct AllDefinitions {
     bf: QueryRequestBatch,
     bg: QueryResponse,
     bh: QueryGroupsRequest,
+    bi: FacetRequest,
+    bj: FacetResult,
+    bk: AggregateRequest,
+    bl: AggregateResult,
+    bm: SearchMatrixRequest,
+    bn: SearchMatrixOffsetsResponse,
+    bo: SearchMatrixPairsResponse,
+    bp: ShardExplanation,
 }
 
 fn save_schema<T: JsonSchema>() {
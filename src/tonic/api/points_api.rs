@@ -3,29 +3,31 @@ use std::time::Duration;
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
-    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, QueryBatchPoints, QueryBatchResponse, QueryPoints,
+    AggregatePoints, AggregateResponse, ClearPayloadPoints, CountPoints, CountResponse,
+    CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
+    DeletePointVectors, DeletePoints, DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints,
+    DiscoverResponse, FacetCounts, FacetResponse, GetPoints, GetResponse, PointsOperationResponse,
+    QueryBatchPoints, QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints,
     QueryResponse, RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse,
     RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints,
-    SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsetsResponse,
+    SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse,
+    SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
 use storage::dispatcher::Dispatcher;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, query, query_batch, recommend_groups, search_groups,
-    update_batch, update_vectors,
+    delete_vectors, discover, discover_batch, query, query_batch, query_groups, recommend_groups,
+    search_groups, update_batch, update_vectors,
 };
 use super::validate;
 use crate::tonic::api::points_common::{
-    clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
-    delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
-    scroll, search, set_payload, upsert,
+    aggregate, clear_payload, convert_shard_selector_for_read, core_search_batch, count,
+    create_field_index, delete, delete_field_index, delete_payload, facet, get, overwrite_payload,
+    recommend, recommend_batch, scroll, search, search_points_matrix_offsets,
+    search_points_matrix_pairs, set_payload, upsert,
 };
 use crate::tonic::auth::extract_access;
 
@@ -298,6 +300,11 @@ impl Points for PointsService {
         let mut requests = Vec::new();
 
         for mut search_point in search_points {
+            if search_point.explain.unwrap_or_default() {
+                return Err(Status::invalid_argument(
+                    "Explain is not supported in batch requests",
+                ));
+            }
             let shard_key = search_point.shard_key_selector.take();
 
             let shard_selector = convert_shard_selector_for_read(None, shard_key);
@@ -446,6 +453,63 @@ impl Points for PointsService {
         .await
     }
 
+    async fn facet(
+        &self,
+        mut request: Request<FacetCounts>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        facet(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
+
+    async fn aggregate(
+        &self,
+        mut request: Request<AggregatePoints>,
+    ) -> Result<Response<AggregateResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        aggregate(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
+
+    async fn search_matrix_pairs(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        search_points_matrix_pairs(self.dispatcher.toc(&access), request.into_inner(), access).await
+    }
+
+    async fn search_matrix_offsets(
+        &self,
+        mut request: Request<SearchMatrixPoints>,
+    ) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        search_points_matrix_offsets(self.dispatcher.toc(&access), request.into_inner(), access)
+            .await
+    }
+
     async fn query(
         &self,
         mut request: Request<QueryPoints>,
@@ -485,4 +549,19 @@ impl Points for PointsService {
         )
         .await
     }
+
+    async fn query_groups(
+        &self,
+        mut request: Request<QueryPointGroups>,
+    ) -> Result<Response<QueryGroupsResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        query_groups(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
 }
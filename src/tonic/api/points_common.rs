@@ -5,18 +5,21 @@ use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
+    points_update_operation, AggregatePoints, AggregateResponse, BatchResult, ClearPayloadPoints,
+    CoreSearchPoints, CountPoints, CountResponse, CountResponseInternal,
+    CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
     DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, QueryBatchResponse, QueryPoints, QueryResponse,
-    ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
-    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
-    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
-    SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
-    UpsertPoints,
+    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
+    PointsOperationResponseInternal, PointsSelector, QueryBatchResponse, QueryGroupsResponse,
+    QueryPointGroups, QueryPoints, QueryResponse, ReadConsistency as ReadConsistencyGrpc,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
+    SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints,
+    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
     try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
@@ -28,8 +31,9 @@ use collection::operations::point_ops::{
 use collection::operations::query_enum::QueryEnum;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    default_exact_count, CoreSearchRequest, CoreSearchRequestBatch, PointRequestInternal,
-    RecommendExample, Record, ScrollRequestInternal,
+    default_exact_count, AggregateRequestInternal, CoreSearchRequest, CoreSearchRequestBatch,
+    CountResult, FacetRequestInternal, PointRequestInternal, RecommendExample, Record,
+    ScrollRequestInternal, SearchMatrixRequestInternal, SearchMatrixResult,
 };
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::vector_ops::{DeleteVectors, PointVectors, UpdateVectors};
@@ -37,7 +41,6 @@ use collection::operations::{ClockTag, CollectionUpdateOperations, OperationWith
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
 use segment::data_types::order_by::OrderBy;
-use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
     ExtendedPointId, Filter, PayloadFieldSchema, PayloadSchemaParams, PayloadSchemaType,
 };
@@ -48,11 +51,12 @@ use storage::rbac::Access;
 use tonic::{Response, Status};
 
 use crate::common::points::{
-    do_clear_payload, do_core_search_points, do_count_points, do_create_index,
+    do_aggregate, do_clear_payload, do_core_search_points, do_count_points, do_create_index,
     do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload,
-    do_query_batch_points, do_query_points, do_scroll_points, do_search_batch_points,
-    do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_delete_points, do_delete_vectors, do_facet, do_get_points, do_overwrite_payload,
+    do_query_batch_points, do_query_point_groups, do_query_points, do_scroll_points,
+    do_search_batch_points, do_search_points_matrix, do_set_payload, do_update_vectors,
+    do_upsert_points, CreateFieldIndex,
 };
 
 fn extract_points_selector(
@@ -252,14 +256,11 @@ pub async fn update_vectors(
             Some(id) => id.try_into()?,
             None => return Err(Status::invalid_argument("id is expected")),
         };
-        let vector: VectorStructInternal = match point.vectors {
+        let vector: api::rest::VectorStruct = match point.vectors {
             Some(vectors) => vectors.try_into()?,
             None => return Err(Status::invalid_argument("vectors is expected")),
         };
-        op_points.push(PointVectors {
-            id,
-            vector: api::rest::VectorStruct::from(vector),
-        });
+        op_points.push(PointVectors { id, vector });
     }
 
     let operation = UpdateVectors {
@@ -781,6 +782,7 @@ fn convert_field_type(
             FieldType::Text => Some(PayloadSchemaType::Text.into()),
             FieldType::Bool => Some(PayloadSchemaType::Bool.into()),
             FieldType::Datetime => Some(PayloadSchemaType::Datetime.into()),
+            FieldType::GeoShape => Some(PayloadSchemaType::GeoShape.into()),
         },
         // Parameterized index with mismatching types
         (
@@ -965,6 +967,8 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        explain,
+        page_token,
     } = search_points;
 
     let vector_struct =
@@ -990,14 +994,16 @@ pub async fn search(
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
     let timing = Instant::now();
-    let scored_points = do_core_search_points(
+    let (scored_points, next_page_token, explanation) = do_core_search_points(
         toc,
         &collection_name,
         search_request,
+        page_token.as_deref(),
         read_consistency,
         shard_selector,
         access,
         timeout.map(Duration::from_secs),
+        explain.unwrap_or_default(),
     )
     .await
     .map_err(error_to_status)?;
@@ -1008,6 +1014,8 @@ pub async fn search(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        explain: explanation.into_iter().flatten().map(From::from).collect(),
+        next_page_token,
     };
 
     Ok(Response::new(response))
@@ -1170,6 +1178,8 @@ pub async fn recommend(
         read_consistency,
         timeout,
         shard_key_selector,
+        positive_weights,
+        negative_weights,
     } = recommend_points;
 
     let timeout = timeout.map(Duration::from_secs);
@@ -1194,10 +1204,16 @@ pub async fn recommend(
         .collect();
     let negative = [negative_ids, negative_vectors].concat();
 
+    // Repeated fields can't be unset, no weights are the same as default weights
+    let positive_weights = Some(positive_weights).filter(|weights| !weights.is_empty());
+    let negative_weights = Some(negative_weights).filter(|weights| !weights.is_empty());
+
     let request = collection::operations::types::RecommendRequestInternal {
         positive,
         negative,
         strategy: strategy.map(|s| s.try_into()).transpose()?,
+        positive_weights,
+        negative_weights,
         filter: filter.map(|f| f.try_into()).transpose()?,
         params: params.map(|p| p.into()),
         limit: limit as usize,
@@ -1466,23 +1482,32 @@ pub async fn scroll(
     Ok(Response::new(response))
 }
 
-pub async fn count(
+/// Count points and return the result together with the time spent
+async fn count_points(
     toc: &TableOfContent,
     count_points: CountPoints,
     shard_selection: Option<ShardId>,
     access: Access,
-) -> Result<Response<CountResponse>, Status> {
+) -> Result<(CountResult, f64), Status> {
     let CountPoints {
         collection_name,
         filter,
         exact,
         read_consistency,
         shard_key_selector,
+        explain,
+        group_by,
+        distinct,
+        group_by_limit,
     } = count_points;
 
     let count_request = collection::operations::types::CountRequestInternal {
         filter: filter.map(|f| f.try_into()).transpose()?,
         exact: exact.unwrap_or_else(default_exact_count),
+        group_by: group_by.as_deref().map(json_path_from_proto).transpose()?,
+        group_by_limit: group_by_limit.map(|limit| limit as usize),
+        distinct: distinct.as_deref().map(json_path_from_proto).transpose()?,
+        explain,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1501,8 +1526,217 @@ pub async fn count(
     .await
     .map_err(error_to_status)?;
 
+    Ok((count_result, timing.elapsed().as_secs_f64()))
+}
+
+pub async fn count(
+    toc: &TableOfContent,
+    count_points_request: CountPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<CountResponse>, Status> {
+    let (mut count_result, time) =
+        count_points(toc, count_points_request, shard_selection, access).await?;
+
+    let explanations = std::mem::take(&mut count_result.explanations);
+
     let response = CountResponse {
         result: Some(count_result.into()),
+        time,
+        explain: explanations.into_iter().map(From::from).collect(),
+    };
+
+    Ok(Response::new(response))
+}
+
+/// Count points of a single shard, keeping the sketch of distinct values to merge results of shards
+pub async fn count_internal(
+    toc: &TableOfContent,
+    count_points_request: CountPoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<CountResponseInternal>, Status> {
+    let (mut count_result, time) =
+        count_points(toc, count_points_request, shard_selection, access).await?;
+
+    let explanations = std::mem::take(&mut count_result.explanations);
+
+    let response = CountResponseInternal {
+        result: Some(count_result.into()),
+        time,
+        explain: explanations.into_iter().map(From::from).collect(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn facet(
+    toc: &TableOfContent,
+    facet_counts: FacetCounts,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<FacetResponse>, Status> {
+    let FacetCounts {
+        collection_name,
+        key,
+        filter,
+        limit,
+        exact,
+        read_consistency,
+        shard_key_selector,
+    } = facet_counts;
+
+    let facet_request = FacetRequestInternal {
+        key: json_path_from_proto(&key)?,
+        limit: limit.map(|limit| limit as usize),
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        exact: exact.unwrap_or_default(),
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timing = Instant::now();
+    let facet_result = do_facet(
+        toc,
+        &collection_name,
+        facet_request,
+        read_consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = FacetResponse {
+        hits: facet_result.hits.into_iter().map(From::from).collect(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn aggregate(
+    toc: &TableOfContent,
+    aggregate_points: AggregatePoints,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<AggregateResponse>, Status> {
+    let AggregatePoints {
+        collection_name,
+        key,
+        filter,
+        histogram,
+        read_consistency,
+        shard_key_selector,
+        date_histogram,
+    } = aggregate_points;
+
+    let aggregate_request = AggregateRequestInternal {
+        key: json_path_from_proto(&key)?,
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        histogram: histogram.map(|h| h.try_into()).transpose()?,
+        date_histogram: date_histogram.map(|d| d.try_into()).transpose()?,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timing = Instant::now();
+    let aggregate_result = do_aggregate(
+        toc,
+        &collection_name,
+        aggregate_request,
+        read_consistency,
+        shard_selector,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = AggregateResponse {
+        result: Some(aggregate_result.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+/// Convert the gRPC request, and compute the distance matrix of the sampled points
+async fn search_points_matrix(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    access: Access,
+) -> Result<SearchMatrixResult, Status> {
+    let SearchMatrixPoints {
+        collection_name,
+        filter,
+        sample,
+        limit,
+        using,
+        timeout,
+        read_consistency,
+        shard_key_selector,
+    } = search_matrix_points;
+
+    let search_matrix_request = SearchMatrixRequestInternal {
+        filter: filter.map(|f| f.try_into()).transpose()?,
+        sample: sample.map(|sample| sample as usize),
+        limit: limit.map(|limit| limit as usize),
+        using,
+    };
+
+    let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
+
+    let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
+
+    do_search_points_matrix(
+        toc,
+        &collection_name,
+        search_matrix_request,
+        read_consistency,
+        shard_selector,
+        access,
+        timeout.map(Duration::from_secs),
+    )
+    .await
+    .map_err(error_to_status)
+}
+
+pub async fn search_points_matrix_pairs(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixPairsResponse>, Status> {
+    let timing = Instant::now();
+    let search_matrix_result = search_points_matrix(toc, search_matrix_points, access).await?;
+
+    let pairs =
+        collection::operations::types::SearchMatrixPairsResponse::from(search_matrix_result);
+
+    let response = SearchMatrixPairsResponse {
+        result: Some(pairs.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
+pub async fn search_points_matrix_offsets(
+    toc: &TableOfContent,
+    search_matrix_points: SearchMatrixPoints,
+    access: Access,
+) -> Result<Response<SearchMatrixOffsetsResponse>, Status> {
+    let timing = Instant::now();
+    let search_matrix_result = search_points_matrix(toc, search_matrix_points, access).await?;
+
+    let offsets =
+        collection::operations::types::SearchMatrixOffsetsResponse::from(search_matrix_result);
+
+    let response = SearchMatrixOffsetsResponse {
+        result: Some(offsets.into()),
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -1575,16 +1809,22 @@ pub async fn query(
         .transpose()?;
     let timeout = query_points.timeout.map(Duration::from_secs);
     let collection_name = query_points.collection_name.clone();
+    let explain = query_points.explain;
+    let profile = query_points.profile.unwrap_or_default();
+    let page_token = query_points.page_token.clone();
     let request = CollectionQueryRequest::try_from(query_points)?;
     let timing = Instant::now();
-    let scored_points = do_query_points(
+    let (scored_points, profile, next_page_token, explanation) = do_query_points(
         toc,
         &collection_name,
         request,
+        page_token.as_deref(),
         read_consistency,
         shard_selector,
         access,
         timeout,
+        profile,
+        explain.unwrap_or_default(),
     )
     .await
     .map_err(error_to_status)?;
@@ -1595,6 +1835,9 @@ pub async fn query(
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        explain: explanation.into_iter().flatten().map(From::from).collect(),
+        profile: profile.map(From::from),
+        next_page_token,
     };
 
     Ok(Response::new(response))
@@ -1611,6 +1854,11 @@ pub async fn query_batch(
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
     let mut requests = Vec::with_capacity(points.len());
     for query_points in points {
+        if query_points.explain.unwrap_or_default() {
+            return Err(Status::invalid_argument(
+                "Explain is not supported in batch requests",
+            ));
+        }
         let shard_key_selector = query_points.shard_key_selector.clone();
         let shard_selector = convert_shard_selector_for_read(None, shard_key_selector);
         let request = CollectionQueryRequest::try_from(query_points)?;
@@ -1640,3 +1888,40 @@ pub async fn query_batch(
 
     Ok(Response::new(response))
 }
+
+pub async fn query_groups(
+    toc: &TableOfContent,
+    query_point_groups: QueryPointGroups,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<QueryGroupsResponse>, Status> {
+    let shard_key_selector = query_point_groups.shard_key_selector.clone();
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+    let read_consistency = query_point_groups
+        .read_consistency
+        .clone()
+        .map(TryFrom::try_from)
+        .transpose()?;
+    let timeout = query_point_groups.timeout.map(Duration::from_secs);
+    let collection_name = query_point_groups.collection_name.clone();
+    let request = GroupRequest::try_from(query_point_groups)?;
+    let timing = Instant::now();
+    let groups_result = do_query_point_groups(
+        toc,
+        &collection_name,
+        request,
+        read_consistency,
+        shard_selector,
+        access,
+        timeout,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = QueryGroupsResponse {
+        result: Some(groups_result.into()),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
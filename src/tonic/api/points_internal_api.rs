@@ -1,20 +1,24 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use api::grpc::conversions::json_path_from_proto;
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
-    ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
+    AggregatePointsInternal, AggregateResponseInternal, ClearPayloadPointsInternal,
+    CoreSearchBatchPointsInternal, CountPointsInternal, CountResponseInternal,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryBatchPointsInternal,
-    QueryBatchResponseInternal, QueryResultInternal, QueryShardPoints, RecommendPointsInternal,
-    RecommendResponse, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
-    SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    FacetResponse, GetPointsInternal, GetResponse, IntermediateResult,
+    PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
+    QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
+    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
+    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
+use segment::data_types::aggregations::AggregationParams;
 use storage::content_manager::conversions::error_to_status;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
@@ -23,9 +27,9 @@ use tonic::{Request, Response, Status};
 use super::points_common::core_search_list;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    clear_payload, count_internal, create_field_index_internal, delete,
+    delete_field_index_internal, delete_payload, delete_vectors, facet, get, overwrite_payload,
+    recommend, scroll, set_payload, sync, update_vectors, upsert,
 };
 
 const FULL_ACCESS: Access = Access::full("Internal API");
@@ -47,6 +51,8 @@ pub async fn query_batch_internal(
     query_points: Vec<QueryShardPoints>,
     shard_selection: Option<ShardId>,
     timeout: Option<Duration>,
+    profile: bool,
+    explain: bool,
 ) -> Result<Response<QueryBatchResponseInternal>, Status> {
     let batch_requests: Vec<_> = query_points
         .into_iter()
@@ -65,8 +71,15 @@ pub async fn query_batch_internal(
         Some(shard_id) => ShardSelectorInternal::ShardId(shard_id),
     };
 
-    let batch_response = toc
-        .query_batch_internal(&collection_name, batch_requests, shard_selection, timeout)
+    let (batch_response, profile) = toc
+        .query_batch_internal(
+            &collection_name,
+            batch_requests,
+            shard_selection,
+            timeout,
+            profile,
+            explain,
+        )
         .await
         .map_err(error_to_status)?;
 
@@ -83,6 +96,7 @@ pub async fn query_batch_internal(
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        profile: profile.map(From::from),
     };
 
     Ok(Response::new(response))
@@ -438,7 +452,7 @@ impl PointsInternal for PointsInternalService {
     async fn count(
         &self,
         request: Request<CountPointsInternal>,
-    ) -> Result<Response<CountResponse>, Status> {
+    ) -> Result<Response<CountResponseInternal>, Status> {
         validate_and_log(request.get_ref());
 
         let CountPointsInternal {
@@ -448,7 +462,7 @@ impl PointsInternal for PointsInternalService {
 
         let count_points =
             count_points.ok_or_else(|| Status::invalid_argument("CountPoints is missing"))?;
-        count(
+        count_internal(
             self.toc.as_ref(),
             count_points,
             shard_id,
@@ -457,6 +471,76 @@ impl PointsInternal for PointsInternalService {
         .await
     }
 
+    async fn facet(
+        &self,
+        request: Request<FacetCountsInternal>,
+    ) -> Result<Response<FacetResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let FacetCountsInternal {
+            facet_counts,
+            shard_id,
+        } = request.into_inner();
+
+        let facet_counts =
+            facet_counts.ok_or_else(|| Status::invalid_argument("FacetCounts is missing"))?;
+        facet(
+            self.toc.as_ref(),
+            facet_counts,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn aggregate(
+        &self,
+        request: Request<AggregatePointsInternal>,
+    ) -> Result<Response<AggregateResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let AggregatePointsInternal {
+            collection_name,
+            key,
+            filter,
+            histogram,
+            shard_id,
+            date_histogram,
+        } = request.into_inner();
+
+        let params = AggregationParams {
+            key: json_path_from_proto(&key)?,
+            filter: filter.map(|f| f.try_into()).transpose()?,
+            histogram: histogram.map(From::from),
+            date_histogram: date_histogram.map(|d| d.try_into()).transpose()?,
+        };
+
+        let timing = Instant::now();
+
+        // As this function is handling an internal request,
+        // we can assume that shard_key is already resolved
+        let shard_selection = match shard_id {
+            None => {
+                debug_assert!(false, "Shard selection is expected for internal request");
+                ShardSelectorInternal::All
+            }
+            Some(shard_id) => ShardSelectorInternal::ShardId(shard_id),
+        };
+
+        let aggregation = self
+            .toc
+            .aggregate_internal(&collection_name, params, shard_selection)
+            .await
+            .map_err(error_to_status)?;
+
+        let response = AggregateResponseInternal {
+            result: Some(aggregation.into()),
+            time: timing.elapsed().as_secs_f64(),
+        };
+
+        Ok(Response::new(response))
+    }
+
     async fn sync(
         &self,
         request: Request<SyncPointsInternal>,
@@ -492,6 +576,8 @@ impl PointsInternal for PointsInternalService {
             shard_id,
             query_points,
             timeout,
+            profile,
+            explain,
         } = request.into_inner();
 
         let timeout = timeout.map(Duration::from_secs);
@@ -502,6 +588,8 @@ impl PointsInternal for PointsInternalService {
             query_points,
             shard_id,
             timeout,
+            profile.unwrap_or_default(),
+            explain.unwrap_or_default(),
         )
         .await
     }